@@ -0,0 +1,192 @@
+use tp1_bomberman::bomberman::prelude::*;
+
+use crate::toml::{self, Clave, Valor};
+
+/// Claves que acepta un archivo de reglas: `preset` y una por cada campo de `Reglas`, mas `max_cadena`, que es
+/// `Limites::cadena`.
+const CLAVES: &[&str] = &[
+    "preset",
+    "enemigos_bloquean",
+    "golpes_por_onda",
+    "alcance_maximo",
+    "vida_maxima",
+    "ladrillos",
+    "mejoras",
+    "toroidal",
+    "traspaso_atraviesa_paredes",
+    "desvios_consumen_alcance",
+    "un_golpe_por_bomba",
+    "max_cadena",
+];
+
+/// Lee un archivo de reglas (`--rules`), para simular con las de otra consigna sin pasar cada opcion por la linea
+/// de comandos. Es un documento del subconjunto de TOML de `toml::parsear`, sin tablas, con un `preset` opcional
+/// (ver `Reglas::preset`) que se aplica primero y despues las claves que cambian, con el nombre de su campo en
+/// `Reglas`. Las que no estan quedan como estaban, asi las opciones anteriores a `--rules` siguen valiendo.
+///
+/// # Ejemplos
+///
+/// ```
+/// let mut reglas = Reglas::default();
+/// aplicar("preset = \"arcade\"\ndesvios_consumen_alcance = false\nmax_cadena = 20\n", &mut reglas, &mut limites)?;
+/// assert!(reglas.enemigos_bloquean && !reglas.desvios_consumen_alcance);
+/// ```
+///
+/// # Argumentos
+///
+/// * texto: El contenido del archivo.
+/// * reglas, limites: Las que se estan armando con la linea de comandos; se cambian solo las claves del archivo.
+///
+/// # Devuelve
+///
+/// Un Ok(()) o un Err(String) con la linea y la causa del primer error: una linea que no se entiende, una tabla, una
+/// clave desconocida, repetida o del tipo equivocado, o un preset que no existe. Si hay un error no se cambia nada.
+pub fn aplicar(texto: &str, reglas: &mut Reglas, limites: &mut Limites) -> Result<(), String> {
+    let tablas = toml::parsear(texto)?;
+    if let Some(tabla) = tablas.get(1) {
+        return Err(format!(
+            "Error: linea {}: un archivo de reglas no tiene tablas, y esta {}",
+            tabla.linea, tabla.encabezado
+        ));
+    }
+    let claves = &tablas[0].claves;
+    let mut nuevas = reglas.clone();
+    let mut cadena = limites.cadena;
+    if let Some(Clave { valor, linea, .. }) = tablas[0].clave("preset") {
+        nuevas = match valor {
+            Valor::Texto(nombre) => Reglas::preset(nombre).ok_or_else(|| {
+                format!(
+                    "Error: linea {}: preset desconocido {} (se aceptan {})",
+                    linea,
+                    nombre,
+                    Reglas::PRESETS.join(", ")
+                )
+            })?,
+            _ => {
+                return Err(format!(
+                    "Error: linea {}: preset tiene que ser un texto entre comillas",
+                    linea
+                ))
+            }
+        };
+    }
+    for Clave {
+        nombre,
+        valor,
+        linea,
+    } in claves
+    {
+        if !CLAVES.contains(&nombre.as_str()) {
+            return Err(format!(
+                "Error: linea {}: clave desconocida {} (se aceptan {})",
+                linea,
+                nombre,
+                CLAVES.join(", ")
+            ));
+        }
+        let error =
+            |tipo: &str| format!("Error: linea {}: {} tiene que ser {}", linea, nombre, tipo);
+        match (nombre.as_str(), valor) {
+            ("preset", _) => {}
+            ("enemigos_bloquean", Valor::Booleano(valor)) => nuevas.enemigos_bloquean = *valor,
+            ("ladrillos", Valor::Booleano(valor)) => nuevas.ladrillos = *valor,
+            ("mejoras", Valor::Booleano(valor)) => nuevas.mejoras = *valor,
+            ("toroidal", Valor::Booleano(valor)) => nuevas.toroidal = *valor,
+            ("traspaso_atraviesa_paredes", Valor::Booleano(valor)) => {
+                nuevas.traspaso_atraviesa_paredes = *valor
+            }
+            ("desvios_consumen_alcance", Valor::Booleano(valor)) => {
+                nuevas.desvios_consumen_alcance = *valor
+            }
+            ("un_golpe_por_bomba", Valor::Booleano(valor)) => nuevas.un_golpe_por_bomba = *valor,
+            ("golpes_por_onda", Valor::Numero(valor)) => nuevas.golpes_por_onda = Some(*valor),
+            ("alcance_maximo", Valor::Numero(valor)) => nuevas.alcance_maximo = Some(*valor),
+            ("vida_maxima", Valor::Numero(valor)) => nuevas.vida_maxima = *valor,
+            ("max_cadena", Valor::Numero(valor)) => cadena = Some(*valor),
+            ("golpes_por_onda" | "alcance_maximo" | "vida_maxima" | "max_cadena", _) => {
+                return Err(error("un numero natural"))
+            }
+            _ => return Err(error("true o false")),
+        }
+    }
+    *reglas = nuevas;
+    limites.cadena = cadena;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::aplicar;
+    use tp1_bomberman::bomberman::prelude::*;
+
+    #[test]
+    fn test01_aplica_el_preset_y_despues_las_claves() {
+        let mut reglas = Reglas {
+            ladrillos: true,
+            ..Reglas::default()
+        };
+        let mut limites = Limites::default();
+        let texto = "# reglas de otra comision\ntraspaso_atraviesa_paredes = true\nvida_maxima = 5\nmax_cadena = 20\n";
+        aplicar(texto, &mut reglas, &mut limites).unwrap();
+        assert_eq!(
+            reglas,
+            Reglas {
+                ladrillos: true,
+                traspaso_atraviesa_paredes: true,
+                vida_maxima: 5,
+                ..Reglas::default()
+            }
+        );
+        assert_eq!(limites.cadena, Some(20));
+
+        aplicar(
+            "un_golpe_por_bomba = true\npreset = \"arcade\"",
+            &mut reglas,
+            &mut limites,
+        )
+        .unwrap();
+        assert_eq!(
+            reglas,
+            Reglas {
+                enemigos_bloquean: true,
+                un_golpe_por_bomba: true,
+                ..Reglas::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test02_errores_con_su_linea() {
+        for (texto, error) in [
+            (
+                "toroidal = 1",
+                "Error: linea 1: toroidal tiene que ser true o false",
+            ),
+            (
+                "\nvida_maxima = true",
+                "Error: linea 2: vida_maxima tiene que ser un numero natural",
+            ),
+            (
+                "preset = \"moderno\"",
+                "Error: linea 1: preset desconocido moderno (se aceptan clasico, arcade)",
+            ),
+            (
+                "[reglas]\nmejoras = true",
+                "Error: linea 1: un archivo de reglas no tiene tablas, y esta [reglas]",
+            ),
+        ] {
+            let mut reglas = Reglas::default();
+            let mut limites = Limites::default();
+            assert_eq!(
+                aplicar(texto, &mut reglas, &mut limites),
+                Err(error.to_string()),
+                "{}",
+                texto
+            );
+            assert_eq!(reglas, Reglas::default());
+        }
+        let mut reglas = Reglas::default();
+        let error = aplicar("paredes = true", &mut reglas, &mut Limites::default()).unwrap_err();
+        assert!(error.starts_with("Error: linea 1: clave desconocida paredes (se aceptan preset, "));
+    }
+}
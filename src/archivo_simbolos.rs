@@ -0,0 +1,87 @@
+use tp1_bomberman::bomberman::simbolos::{Simbolos, TIPOS};
+
+use crate::toml::{self, Clave, Valor};
+
+/// Lee un archivo de simbolos (`--simbolos`): un documento del subconjunto de TOML de `toml::parsear`, sin tablas,
+/// con el simbolo de cada tipo de casilla que cambia, con el nombre del tipo en `simbolos::TIPOS`. Los que no estan
+/// quedan como siempre.
+///
+/// # Ejemplos
+///
+/// ```
+/// let simbolos = parsear("# los mapas del editor\npared = \"#\"\nenemigo = \"E\"\n")?;
+/// assert_eq!(simbolos.leer("E3"), "F3");
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con los Simbolos o un Err(String) con la linea y la causa del primer error: una linea que no se entiende,
+/// una tabla, un tipo desconocido o repetido, un valor que no es texto, o un simbolo que `Simbolos::new` no acepta.
+pub fn parsear(texto: &str) -> Result<Simbolos, String> {
+    let tablas = toml::parsear(texto)?;
+    if let Some(tabla) = tablas.get(1) {
+        return Err(format!(
+            "Error: linea {}: un archivo de simbolos no tiene tablas, y esta {}",
+            tabla.linea, tabla.encabezado
+        ));
+    }
+    let mut cambios = Vec::new();
+    for Clave {
+        nombre,
+        valor,
+        linea,
+    } in &tablas[0].claves
+    {
+        if !TIPOS.iter().any(|(tipo, _)| tipo == nombre) {
+            let tipos: Vec<&str> = TIPOS.iter().map(|(tipo, _)| *tipo).collect();
+            return Err(format!(
+                "Error: linea {}: tipo de casilla desconocido {} (se aceptan {})",
+                linea,
+                nombre,
+                tipos.join(", ")
+            ));
+        }
+        match valor {
+            Valor::Texto(simbolo) => cambios.push((nombre.as_str(), simbolo.as_str())),
+            _ => {
+                return Err(format!(
+                    "Error: linea {}: {} tiene que ser un texto entre comillas",
+                    linea, nombre
+                ))
+            }
+        }
+    }
+    Simbolos::new(&cambios)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parsear;
+
+    #[test]
+    fn test01_lee_los_simbolos_y_sus_errores() {
+        let simbolos = parsear("pared = \"#\"\nenemigo = \"E\"").unwrap();
+        assert_eq!(simbolos.escribir("F2"), "E2");
+        assert_eq!(simbolos.leer("#"), "W");
+        assert!(parsear("").unwrap().es_identidad());
+        for (texto, error) in [
+            (
+                "pared = 1",
+                "Error: linea 1: pared tiene que ser un texto entre comillas",
+            ),
+            (
+                "[simbolos]",
+                "Error: linea 1: un archivo de simbolos no tiene tablas, y esta [simbolos]",
+            ),
+            (
+                "roca = \"W\"",
+                "Error: el simbolo de pared (W) es el comienzo del de roca (W)",
+            ),
+        ] {
+            assert_eq!(parsear(texto), Err(error.to_string()), "{}", texto);
+        }
+        assert!(parsear("\nmuro = \"#\"")
+            .unwrap_err()
+            .starts_with("Error: linea 2: tipo de casilla desconocido muro (se aceptan vacio, "));
+    }
+}
@@ -0,0 +1,208 @@
+use tp1_bomberman::bomberman::comparacion;
+use tp1_bomberman::bomberman::json::Json;
+
+/// Compara dos resultados de `--format json` de la misma jugada, por ejemplo de dos versiones del programa, y describe
+/// en que cambiaron: el error, los numeros del resultado, lo que hizo cada bomba, las casillas del tablero final y las
+/// advertencias. La version y los metadatos de la entrada no cuentan como diferencias.
+///
+/// # Ejemplos
+///
+/// ```
+/// for linea in diferencias(&antes, &despues)? {
+///     println!("{}", linea); // resultado.enemigos_eliminados: 1 -> 2
+/// }
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con una linea por diferencia, vacio si los resultados son equivalentes, o un Err(String) si alguno no es
+/// un resultado o si sus id dicen que son de jugadas distintas.
+pub fn diferencias(antes: &Json, despues: &Json) -> Result<Vec<String>, String> {
+    for resultado in [antes, despues] {
+        if !matches!(resultado, Json::Objeto(_)) {
+            return Err("Error: un resultado de --format json tiene que ser un objeto".to_string());
+        }
+    }
+    if let (Some(Json::Texto(a)), Some(Json::Texto(b))) = (antes.campo("id"), despues.campo("id")) {
+        if a != b {
+            return Err(format!(
+                "Error: los resultados son de jugadas distintas ({} y {})",
+                a, b
+            ));
+        }
+    }
+    let mut lineas = Vec::new();
+    for seccion in ["error", "resultado"] {
+        campos(
+            seccion,
+            antes.campo(seccion),
+            despues.campo(seccion),
+            &mut lineas,
+        );
+    }
+    bombas(antes, despues, &mut lineas);
+    let (filas_antes, filas_despues) = (filas(antes), filas(despues));
+    for diferencia in comparacion::diferencias(&filas_antes, &filas_despues) {
+        lineas.push(format!("tablero {}", diferencia));
+    }
+    let (advertencias_antes, advertencias_despues) = (
+        textos(antes.campo("advertencias")),
+        textos(despues.campo("advertencias")),
+    );
+    for advertencia in &advertencias_antes {
+        if !advertencias_despues.contains(advertencia) {
+            lineas.push(format!("advertencia que ya no esta: {}", advertencia));
+        }
+    }
+    for advertencia in &advertencias_despues {
+        if !advertencias_antes.contains(advertencia) {
+            lineas.push(format!("advertencia nueva: {}", advertencia));
+        }
+    }
+    Ok(lineas)
+}
+
+/// Compara dos valores; si son objetos, clave por clave.
+fn campos(nombre: &str, antes: Option<&Json>, despues: Option<&Json>, lineas: &mut Vec<String>) {
+    match (antes, despues) {
+        (Some(Json::Objeto(a)), Some(Json::Objeto(b))) => {
+            let mut claves: Vec<&String> = a.iter().map(|(clave, _)| clave).collect();
+            for (clave, _) in b {
+                if !claves.contains(&clave) {
+                    claves.push(clave);
+                }
+            }
+            for clave in claves {
+                campos(
+                    &format!("{}.{}", nombre, clave),
+                    valor(a, clave),
+                    valor(b, clave),
+                    lineas,
+                );
+            }
+        }
+        _ if antes != despues => {
+            let texto = |valor: Option<&Json>| valor.map_or("(nada)".to_string(), Json::to_string);
+            lineas.push(format!(
+                "{}: {} -> {}",
+                nombre,
+                texto(antes),
+                texto(despues)
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// El valor de una clave entre los campos de un objeto.
+fn valor<'a>(campos: &'a [(String, Json)], clave: &str) -> Option<&'a Json> {
+    campos.iter().find(|(c, _)| c == clave).map(|(_, v)| v)
+}
+
+/// Compara lo que hizo cada bomba, buscandola por sus coordenadas porque el orden de las detonaciones tambien puede
+/// cambiar.
+fn bombas(antes: &Json, despues: &Json, lineas: &mut Vec<String>) {
+    let por_punto = |resultado: &Json| -> Vec<(String, Json)> {
+        let Some(Json::Lista(bombas)) = resultado.campo("bombas") else {
+            return Vec::new();
+        };
+        bombas
+            .iter()
+            .map(|bomba| {
+                let punto = format!(
+                    "bomba ({}, {})",
+                    bomba.campo("x").map_or(String::new(), Json::to_string),
+                    bomba.campo("y").map_or(String::new(), Json::to_string)
+                );
+                (punto, bomba.clone())
+            })
+            .collect()
+    };
+    let (a, b) = (por_punto(antes), por_punto(despues));
+    for (punto, bomba) in &a {
+        match b.iter().find(|(otro, _)| otro == punto) {
+            Some((_, otra)) => campos(punto, Some(bomba), Some(otra), lineas),
+            None => lineas.push(format!("{}: ya no explota", punto)),
+        }
+    }
+    for (punto, _) in b
+        .iter()
+        .filter(|(punto, _)| !a.iter().any(|(otro, _)| otro == punto))
+    {
+        lineas.push(format!("{}: ahora explota", punto));
+    }
+}
+
+/// Las filas del tablero final, o ninguna si el resultado es un error.
+fn filas(resultado: &Json) -> Vec<Vec<String>> {
+    match resultado.campo("tablero") {
+        Some(Json::Lista(filas)) => filas.iter().map(|fila| textos(Some(fila))).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Los textos de una lista JSON.
+fn textos(lista: Option<&Json>) -> Vec<String> {
+    match lista {
+        Some(Json::Lista(valores)) => valores
+            .iter()
+            .map(|valor| match valor {
+                Json::Texto(texto) => texto.clone(),
+                otro => otro.to_string(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diferencias;
+    use tp1_bomberman::bomberman::json::parsear;
+
+    #[test]
+    fn test01_diferencias_entre_versiones() {
+        let antes = parsear(
+            "{\"id\":\"a\",\"version\":\"0.1.0\",\"tablero\":[[\"_\",\"F1\"]],\
+             \"resultado\":{\"enemigos_eliminados\":0,\"gano\":false},\
+             \"bombas\":[{\"x\":0,\"y\":0,\"rafagas\":{\"R\":0}}],\"advertencias\":[\"vieja\"]}",
+        )
+        .unwrap();
+        let despues = parsear(
+            "{\"id\":\"a\",\"version\":\"0.2.0\",\"tablero\":[[\"_\",\"_\"]],\
+             \"resultado\":{\"enemigos_eliminados\":1,\"gano\":true},\
+             \"bombas\":[{\"x\":0,\"y\":0,\"rafagas\":{\"R\":1}},{\"x\":1,\"y\":0}],\"advertencias\":[]}",
+        )
+        .unwrap();
+        assert_eq!(
+            diferencias(&antes, &despues).unwrap(),
+            [
+                "resultado.enemigos_eliminados: 0 -> 1",
+                "resultado.gano: false -> true",
+                "bomba (0, 0).rafagas.R: 0 -> 1",
+                "bomba (1, 0): ahora explota",
+                "tablero (1, 0): F1 -> _",
+                "advertencia que ya no esta: vieja",
+            ]
+        );
+        assert_eq!(diferencias(&antes, &antes), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test02_jugadas_distintas_y_errores() {
+        let a = parsear("{\"id\":\"a\",\"error\":\"Error: coordenadas invalidas\"}").unwrap();
+        let b = parsear("{\"id\":\"b\",\"tablero\":[]}").unwrap();
+        assert_eq!(
+            diferencias(&a, &b),
+            Err("Error: los resultados son de jugadas distintas (a y b)".to_string())
+        );
+        let c = parsear("{\"tablero\":[[\"_\"]]}").unwrap();
+        assert_eq!(
+            diferencias(&a, &c).unwrap(),
+            [
+                "error: \"Error: coordenadas invalidas\" -> (nada)",
+                "tablero (0, 0): (nada) -> _",
+            ]
+        );
+    }
+}
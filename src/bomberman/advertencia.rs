@@ -0,0 +1,52 @@
+use super::direccion::Direccion;
+use super::punto::Punto;
+
+/// Una situacion no fatal que se encontro (y se resolvio) durante una simulacion.
+/// Se informan para que el usuario sepa que el resultado depende de como se manejo cada caso.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Advertencia {
+    /// El alcance de la bomba en `punto` era mayor que el limite y se recorto.
+    AlcanceRecortado {
+        punto: Punto,
+        alcance: usize,
+        limite: usize,
+    },
+    /// La bomba en `punto` fue alcanzada por otra rafaga cuando ya estaba esperando para explotar, asi que
+    /// explota una sola vez.
+    BombaRepetida { punto: Punto },
+    /// Una rafaga paso por el desvio en `punto`, que la manda fuera del tablero.
+    DesvioFueraDelTablero { punto: Punto, direccion: Direccion },
+    /// La casilla en `punto` no era valida y se reemplazo por `_` al leer el tablero con `--recuperar`.
+    CasillaReemplazada { punto: Punto, casilla: String },
+}
+
+impl std::fmt::Display for Advertencia {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Advertencia::AlcanceRecortado {
+                punto,
+                alcance,
+                limite,
+            } => write!(
+                f,
+                "Advertencia: el alcance {} de la bomba en ({}, {}) se recorto a {}",
+                alcance, punto.x, punto.y, limite
+            ),
+            Advertencia::BombaRepetida { punto } => write!(
+                f,
+                "Advertencia: la bomba en ({}, {}) fue alcanzada mas de una vez y explota una sola vez",
+                punto.x, punto.y
+            ),
+            Advertencia::DesvioFueraDelTablero { punto, direccion } => write!(
+                f,
+                "Advertencia: el desvio D{} en ({}, {}) apunta fuera del tablero",
+                direccion, punto.x, punto.y
+            ),
+            Advertencia::CasillaReemplazada { punto, casilla } => write!(
+                f,
+                "Advertencia: la casilla invalida {} en ({}, {}) se reemplazo por _",
+                casilla, punto.x, punto.y
+            ),
+        }
+    }
+}
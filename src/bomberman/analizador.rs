@@ -0,0 +1,607 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+use super::bloqueos::Bloqueos;
+use super::celda::Celda;
+use super::danio::{DanioUnitario, ModeloDanio};
+use super::direccion::Direccion;
+use super::error::BombermanError;
+use super::motor::SalidaSimulacion;
+use super::punto::Punto;
+use super::reglas::{Limites, Reglas};
+use super::simulacion::SimulacionBuilder;
+use super::tablero::Tablero;
+
+/// Una vista de solo lectura de un tablero con sus reglas, para hacer analisis sin tocar el Bomberman original.
+///
+/// Clonarla es barato (el tablero se comparte con un Arc) y se puede mandar a otros hilos, asi que varios
+/// analisis pueden correr a la vez sobre el mismo tablero. Cada analisis simula sobre su propia copia.
+#[derive(Clone)]
+pub struct Analizador {
+    tablero: Arc<Tablero>,
+    bloqueos: Arc<Bloqueos>,
+    reglas: Reglas,
+    limites: Limites,
+    modelo_danio: Arc<dyn ModeloDanio>,
+}
+
+/// Una bomba que, detonada sola, elimina a todos los enemigos del tablero (ver `Analizador::soluciones`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solucion {
+    pub bomba: Punto,
+    /// Las bombas que explotaron, en el orden en que lo hicieron, empezando por la detonada.
+    pub cadena: Vec<Punto>,
+}
+
+/// Un ModeloDanio no tiene por que implementar Debug, asi que no se muestra.
+impl std::fmt::Debug for Analizador {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Analizador")
+            .field("tablero", &self.tablero)
+            .field("reglas", &self.reglas)
+            .field("limites", &self.limites)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Analizador {
+    /// Crea un Analizador sobre el tablero dado. Normalmente se obtiene con `Bomberman::analizador`.
+    pub fn new(tablero: Tablero, reglas: Reglas, limites: Limites) -> Analizador {
+        Analizador {
+            bloqueos: Arc::new(Bloqueos::new(&tablero)),
+            tablero: Arc::new(tablero),
+            reglas,
+            limites,
+            modelo_danio: Arc::new(DanioUnitario),
+        }
+    }
+
+    /// Usa el ModeloDanio dado en lugar de `DanioUnitario` en las simulaciones del analisis.
+    pub fn con_modelo_danio(mut self, modelo: Arc<dyn ModeloDanio>) -> Analizador {
+        self.modelo_danio = modelo;
+        self
+    }
+
+    /// El tablero que se analiza.
+    pub fn tablero(&self) -> &Tablero {
+        &self.tablero
+    }
+
+    /// Las paredes, rocas y desvios del tablero, que se calculan una sola vez al crear el Analizador y se comparten
+    /// entre sus copias.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let tramo = analizador.bloqueos().tramo(Punto { x: 0, y: 0 }, Direccion::Derecha, 3, false);
+    /// ```
+    pub fn bloqueos(&self) -> &Bloqueos {
+        &self.bloqueos
+    }
+
+    /// Calcula que casillas cambiarian si se detonara la bomba en el punto dado, contando toda la reaccion en cadena.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let afectadas = bomberman.analizador().afectadas(Punto { x: 0, y: 0 })?;
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las casillas afectadas o un Err(BombermanError) si en ese punto no hay una bomba o la simulacion falla.
+    pub fn afectadas(&self, punto: Punto) -> Result<HashSet<Punto>, BombermanError> {
+        Ok(self
+            .simular(punto)?
+            .cambios
+            .iter()
+            .flatten()
+            .map(|cambio| cambio.punto)
+            .collect())
+    }
+
+    /// Prueba detonar cada bomba del tablero, de a una y cada una sobre su propia copia, y devuelve las que eliminan
+    /// a todos los enemigos con su reaccion en cadena. En un tablero sin enemigos, cualquier bomba es una solucion.
+    /// Las bombas cuyas rafagas no pueden llegar a nada (ver `puede_alcanzar_algo`) no se simulan.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// for solucion in bomberman.analizador().soluciones()? {
+    ///     println!("{:?}: {:?}", solucion.bomba, solucion.cadena);
+    /// }
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las soluciones, leyendo las bombas por filas (vacio si no hay ninguna), o un Err(BombermanError)
+    /// si alguna simulacion falla.
+    pub fn soluciones(&self) -> Result<Vec<Solucion>, BombermanError> {
+        let hay_enemigos = hay_enemigos(self.tablero());
+        let mut soluciones = Vec::new();
+        for bomba in self.bombas() {
+            if hay_enemigos && !self.puede_alcanzar_algo(self.tablero(), bomba) {
+                continue;
+            }
+            let salida = self.simular(bomba)?;
+            if salida.resultado(self.tablero()).gano() {
+                soluciones.push(Solucion {
+                    bomba,
+                    cadena: salida.estadisticas.iter().map(|e| e.punto).collect(),
+                });
+            }
+        }
+        Ok(soluciones)
+    }
+
+    /// Busca la menor cantidad de detonaciones, una despues de otra y cada una sobre el tablero que dejo la anterior,
+    /// que elimina a todos los enemigos, probando todas las combinaciones de hasta `maximo` detonaciones. Para no
+    /// repetir trabajo no vuelve a revisar un tablero al que ya llego con las mismas detonaciones por delante, y
+    /// descarta las detonaciones que solo hacen explotar bombas, porque nada mas cambia y quedan menos bombas; las que
+    /// no pueden llegar a nada (ver `puede_alcanzar_algo`) las descarta sin simularlas.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let orden = bomberman.analizador().solucion_minima(3)?; // Some([Punto { x: 0, y: 0 }, Punto { x: 2, y: 2 }])
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las bombas a detonar en orden (ninguna si el tablero no tiene enemigos), None si no alcanza con
+    /// `maximo` detonaciones, o un Err(BombermanError) si alguna simulacion falla.
+    pub fn solucion_minima(&self, maximo: usize) -> Result<Option<Vec<Punto>>, BombermanError> {
+        for detonaciones in 0..=maximo {
+            let mut orden = Vec::new();
+            let mut vistos = HashSet::new();
+            if self.buscar(
+                Tablero::clone(&self.tablero),
+                detonaciones,
+                &mut orden,
+                &mut vistos,
+            )? {
+                return Ok(Some(orden));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Busca en profundidad, a partir del tablero dado, hasta `restantes` detonaciones que eliminen a todos los
+    /// enemigos, y las deja en `orden` detras de las que llevaron hasta ese tablero.
+    fn buscar(
+        &self,
+        tablero: Tablero,
+        restantes: usize,
+        orden: &mut Vec<Punto>,
+        vistos: &mut HashSet<(String, usize)>,
+    ) -> Result<bool, BombermanError> {
+        if !hay_enemigos(&tablero) {
+            return Ok(true);
+        }
+        if restantes == 0 || !vistos.insert((tablero.to_texto(), restantes)) {
+            return Ok(false);
+        }
+        // Los bloqueos son los del tablero original, y una detonacion anterior pudo haber roto algun ladrillo.
+        let ladrillos_intactos = !self.reglas.ladrillos;
+        for bomba in bombas_de(&tablero) {
+            if ladrillos_intactos && !self.puede_alcanzar_algo(&tablero, bomba) {
+                continue;
+            }
+            let salida = self.simular_en(tablero.clone(), bomba)?;
+            let solo_bombas = salida
+                .cambios
+                .iter()
+                .flatten()
+                .all(|cambio| matches!(tablero.get(cambio.punto), Some(Celda::Bomba(_))));
+            if solo_bombas {
+                continue;
+            }
+            orden.push(bomba);
+            if self.buscar(salida.tablero, restantes - 1, orden, vistos)? {
+                return Ok(true);
+            }
+            orden.pop();
+        }
+        Ok(false)
+    }
+
+    /// Indica si las rafagas de la bomba en el punto dado pueden llegar a algo que siga la reaccion en cadena o que
+    /// cambie al morir un enemigo, buscando sus tramos en los bloqueos (ver `Bloqueos::tramo`) en lugar de simular.
+    /// Un tramo llega a algo si pasa por una casilla que no es vacia, pared, roca ni ladrillo, o al lado de una mina,
+    /// o si termina en un desvio o un portal que lo lleva mas lejos; una mina al lado de la bomba tambien cuenta. Las rafagas reales pueden llegar a menos casillas,
+    /// por ejemplo porque las recorta un limite o las frena un enemigo, pero nunca a mas, asi que un false es seguro.
+    /// Los tramos siguen la consigna, asi que con `Reglas::toroidal` o `Reglas::traspaso_atraviesa_paredes` siempre
+    /// devuelve true.
+    ///
+    /// # Argumentos
+    ///
+    /// * tablero: El tablero en el que se detonaria, con las mismas paredes, rocas, ladrillos, desvios y portales que
+    ///   el del Analizador.
+    /// * punto: La casilla de la bomba.
+    fn puede_alcanzar_algo(&self, tablero: &Tablero, punto: Punto) -> bool {
+        if self.reglas.toroidal || self.reglas.traspaso_atraviesa_paredes {
+            return true;
+        }
+        let Some(Celda::Bomba(bomba)) = tablero.get(punto) else {
+            return true;
+        };
+        let hay_mina_al_lado = |casilla: Punto| {
+            Direccion::TODAS.iter().any(|direccion| {
+                tablero
+                    .avanzar(casilla, *direccion, false)
+                    .is_some_and(|vecina| matches!(tablero.get(vecina), Some(Celda::Mina { .. })))
+            })
+        };
+        hay_mina_al_lado(punto)
+            || Direccion::TODAS
+                .iter()
+                .zip(bomba.alcances)
+                .any(|(direccion, alcance)| {
+                    let tramo = self
+                        .bloqueos
+                        .tramo(punto, *direccion, alcance, bomba.traspaso);
+                    let mut casilla = punto;
+                    (0..tramo.casillas).any(|_| {
+                        let Some(siguiente) = tablero.avanzar(casilla, *direccion, false) else {
+                            return false;
+                        };
+                        casilla = siguiente;
+                        !matches!(
+                            tablero.get(casilla),
+                            Some(Celda::Vacio | Celda::Pared | Celda::Roca | Celda::Ladrillo)
+                        ) || hay_mina_al_lado(casilla)
+                    })
+                })
+    }
+
+    /// Detona la bomba en el punto dado sobre una copia del tablero, con las reglas, limites y modelo de danio del
+    /// Analizador.
+    fn simular(&self, punto: Punto) -> Result<SalidaSimulacion, BombermanError> {
+        self.simular_en(Tablero::clone(&self.tablero), punto)
+    }
+
+    /// Como `simular`, pero sobre otro tablero.
+    fn simular_en(
+        &self,
+        tablero: Tablero,
+        punto: Punto,
+    ) -> Result<SalidaSimulacion, BombermanError> {
+        SimulacionBuilder::new(tablero)
+            .reglas(self.reglas.clone())
+            .limites(self.limites.clone())
+            .modelo_danio(Arc::clone(&self.modelo_danio))
+            .construir()
+            .simular(punto.columna(), punto.fila())
+    }
+
+    /// Arma un mapa de peligro: para cada casilla, cuantas de las bombas del tablero la afectarian si se las detonara.
+    /// Las bombas se reparten en tramos seguidos entre tantos hilos como nucleos haya (ver
+    /// `thread::available_parallelism`), asi que un tablero con miles de bombas no abre miles de hilos. Si el sistema
+    /// no deja abrir alguno, su tramo se analiza en el hilo actual.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// for fila in bomberman.analizador().mapa_de_peligro()? {
+    ///     println!("{:?}", fila); // [1, 0, 2]
+    /// }
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con una matriz del mismo tamanio que el tablero, o un Err(BombermanError) si alguna simulacion falla.
+    pub fn mapa_de_peligro(&self) -> Result<Vec<Vec<usize>>, BombermanError> {
+        let bombas = self.bombas();
+        let hilos = thread::available_parallelism()
+            .map_or(1, |nucleos| nucleos.get())
+            .clamp(1, bombas.len().max(1));
+        let tramo = bombas.len().div_ceil(hilos).max(1);
+        let analizar = |tramo: &[Punto]| -> Vec<Result<HashSet<Punto>, BombermanError>> {
+            tramo.iter().map(|bomba| self.afectadas(*bomba)).collect()
+        };
+        let resultados: Vec<Result<HashSet<Punto>, BombermanError>> = thread::scope(|alcance| {
+            let trabajos: Vec<_> = bombas
+                .chunks(tramo)
+                .map(|tramo| {
+                    let hilo =
+                        thread::Builder::new().spawn_scoped(alcance, move || analizar(tramo));
+                    (tramo, hilo)
+                })
+                .collect();
+            trabajos
+                .into_iter()
+                .flat_map(|(tramo, hilo)| match hilo {
+                    Ok(hilo) => hilo.join().unwrap_or_else(|_| {
+                        tramo
+                            .iter()
+                            .map(|_| Err(BombermanError::HiloDeAnalisis))
+                            .collect()
+                    }),
+                    Err(_) => analizar(tramo),
+                })
+                .collect()
+        });
+        self.sumar_afectadas(resultados)
+    }
+
+    /// Hace lo mismo que `mapa_de_peligro`, pero analiza las bombas una despues de otra en el hilo actual. Sirve para
+    /// comprobar que el analisis en paralelo da exactamente el mismo resultado (feature `determinismo`).
+    #[cfg(any(test, feature = "determinismo"))]
+    pub fn mapa_de_peligro_secuencial(&self) -> Result<Vec<Vec<usize>>, BombermanError> {
+        let resultados = self
+            .bombas()
+            .into_iter()
+            .map(|bomba| self.afectadas(bomba))
+            .collect();
+        self.sumar_afectadas(resultados)
+    }
+
+    /// Las bombas del tablero, leyendo por filas.
+    fn bombas(&self) -> Vec<Punto> {
+        bombas_de(self.tablero())
+    }
+
+    /// Arma el mapa de peligro con las casillas afectadas por cada bomba, en el orden de `bombas`.
+    fn sumar_afectadas(
+        &self,
+        resultados: Vec<Result<HashSet<Punto>, BombermanError>>,
+    ) -> Result<Vec<Vec<usize>>, BombermanError> {
+        let mut mapa: Vec<Vec<usize>> = self
+            .tablero()
+            .filas()
+            .iter()
+            .map(|fila| vec![0; fila.len()])
+            .collect();
+        for afectadas in resultados {
+            for punto in afectadas? {
+                mapa[punto.y][punto.x] += 1;
+            }
+        }
+        Ok(mapa)
+    }
+
+    /// Puntua la dificultad del tablero entre 0 y 1: la proporcion de enemigos a los que no alcanza la detonacion
+    /// de ninguna de las bombas. Un tablero sin enemigos tiene dificultad 0.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let dificultad = bomberman.analizador().dificultad()?; // 0.5: la mitad de los enemigos esta a salvo
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con la dificultad o un Err(BombermanError) si alguna simulacion falla.
+    pub fn dificultad(&self) -> Result<f64, BombermanError> {
+        let mapa = self.mapa_de_peligro()?;
+        let mut enemigos = 0;
+        let mut a_salvo = 0;
+        for (punto, celda) in self.tablero().celdas() {
+            if let Celda::Enemigo { .. } = celda {
+                enemigos += 1;
+                if mapa[punto.y][punto.x] == 0 {
+                    a_salvo += 1;
+                }
+            }
+        }
+        if enemigos == 0 {
+            return Ok(0.0);
+        }
+        Ok(a_salvo as f64 / enemigos as f64)
+    }
+}
+
+/// Las bombas de un tablero, leyendo por filas.
+/// Indica si queda algun enemigo en el tablero.
+fn hay_enemigos(tablero: &Tablero) -> bool {
+    tablero
+        .celdas()
+        .any(|(_, celda)| matches!(celda, Celda::Enemigo { .. }))
+}
+
+fn bombas_de(tablero: &Tablero) -> Vec<Punto> {
+    tablero
+        .celdas()
+        .filter(|(_, celda)| matches!(celda, Celda::Bomba(_)))
+        .map(|(punto, _)| punto)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::{Analizador, Solucion};
+    use crate::bomberman::celda::Celda;
+    use crate::bomberman::optimizador::Aleatorio;
+    use crate::bomberman::punto::Punto;
+    use crate::bomberman::reglas::{Limites, Reglas};
+    use crate::bomberman::tablero::Tablero;
+
+    fn analizador() -> Analizador {
+        let filas = ["B1 F1 _", "_ W _", "_ F2 B1"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect();
+        Analizador::new(
+            Tablero::desde_filas(filas).unwrap(),
+            Reglas::default(),
+            Limites::default(),
+        )
+    }
+
+    #[test]
+    fn test01_mapa_de_peligro_cuenta_las_bombas_que_afectan_cada_casilla() {
+        let mapa = analizador().mapa_de_peligro().unwrap();
+        assert_eq!(mapa, vec![vec![1, 1, 0], vec![0, 0, 0], vec![0, 1, 1]]);
+    }
+
+    #[test]
+    fn test02_se_puede_usar_desde_varios_hilos() {
+        let analizador = analizador();
+        let original = analizador.tablero().clone();
+        thread::scope(|alcance| {
+            for punto in [Punto { x: 0, y: 0 }, Punto { x: 2, y: 2 }] {
+                let copia = analizador.clone();
+                alcance.spawn(move || assert_eq!(copia.afectadas(punto).unwrap().len(), 2));
+            }
+        });
+        assert_eq!(*analizador.tablero(), original);
+        assert!(analizador.afectadas(Punto { x: 1, y: 1 }).is_err());
+    }
+
+    #[test]
+    fn test03_dificultad_cuenta_los_enemigos_a_salvo() {
+        assert_eq!(analizador().dificultad().unwrap(), 0.0);
+        let filas = ["B1 _ F1", "_ W _", "_ F2 B1"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect();
+        let analizador = Analizador::new(
+            Tablero::desde_filas(filas).unwrap(),
+            Reglas::default(),
+            Limites::default(),
+        );
+        assert_eq!(analizador.dificultad().unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test04_en_paralelo_da_lo_mismo_que_en_un_hilo() {
+        const CASILLAS: [&str; 12] = [
+            "_", "_", "W", "R", "DU", "DL", "F1", "F3", "B1", "B3", "S2", "B[U2,R4]",
+        ];
+        // Con la feature `determinismo` se prueban muchos mas tableros.
+        let tableros = if cfg!(feature = "determinismo") {
+            2000
+        } else {
+            100
+        };
+        let mut aleatorio = Aleatorio::new(1514);
+        for _ in 0..tableros {
+            let ancho = 1 + aleatorio.siguiente_hasta(6);
+            let filas: Vec<Vec<String>> = (0..1 + aleatorio.siguiente_hasta(6))
+                .map(|_| {
+                    (0..ancho)
+                        .map(|_| CASILLAS[aleatorio.siguiente_hasta(CASILLAS.len())].to_string())
+                        .collect()
+                })
+                .collect();
+            let reglas = Reglas {
+                enemigos_bloquean: aleatorio.siguiente_hasta(2) == 0,
+                ..Reglas::default()
+            };
+            let analizador = Analizador::new(
+                Tablero::desde_filas(filas).unwrap(),
+                reglas,
+                Limites::default(),
+            );
+            // Se comparan los textos, para que cualquier diferencia en la salida haga fallar la prueba.
+            let secuencial = format!("{:?}", analizador.mapa_de_peligro_secuencial());
+            for _ in 0..3 {
+                assert_eq!(
+                    format!("{:?}", analizador.mapa_de_peligro()),
+                    secuencial,
+                    "{}",
+                    analizador.tablero().to_texto()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test05_soluciones_son_las_bombas_que_eliminan_a_todos_los_enemigos() {
+        // La de la izquierda llega al enemigo de la derecha a traves de la otra bomba, que no llega al de abajo.
+        let tablero = Tablero::desde_texto("B2 _ B1 F1\nF1 _ _ _").unwrap();
+        let analizador = Analizador::new(tablero, Reglas::default(), Limites::default());
+        assert_eq!(
+            analizador.soluciones().unwrap(),
+            vec![Solucion {
+                bomba: Punto { x: 0, y: 0 },
+                cadena: vec![Punto { x: 0, y: 0 }, Punto { x: 2, y: 0 }],
+            }]
+        );
+        let tablero = Tablero::desde_texto("B1 _ F1").unwrap();
+        let analizador = Analizador::new(tablero, Reglas::default(), Limites::default());
+        assert!(analizador.soluciones().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test06_solucion_minima_busca_la_menor_cantidad_de_detonaciones() {
+        // La primera bomba no alcanza a ningun enemigo, asi que se descarta.
+        let tablero = Tablero::desde_texto("B1 _ B1 F1\n_ W _ _\n_ F1 B1 _").unwrap();
+        let analizador = Analizador::new(tablero, Reglas::default(), Limites::default());
+        assert_eq!(analizador.solucion_minima(1).unwrap(), None);
+        assert_eq!(
+            analizador.solucion_minima(3).unwrap(),
+            Some(vec![Punto { x: 2, y: 0 }, Punto { x: 2, y: 2 }])
+        );
+        let tablero = Tablero::desde_texto("B1 W").unwrap();
+        let analizador = Analizador::new(tablero, Reglas::default(), Limites::default());
+        assert_eq!(analizador.solucion_minima(0).unwrap(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test07_muchas_bombas_se_reparten_entre_pocos_hilos() {
+        // Mas bombas que hilos, separadas por paredes, asi que cada una solo se afecta a si misma.
+        let bombas = "B1 W ".repeat(20).trim_end().to_string();
+        let paredes = vec!["W"; 40].join(" ");
+        let texto = vec![format!("{}\n{}", bombas, paredes); 20].join("\n");
+        let analizador = Analizador::new(
+            Tablero::desde_texto(&texto).unwrap(),
+            Reglas::default(),
+            Limites::default(),
+        );
+        let mapa = analizador.mapa_de_peligro().unwrap();
+        for (punto, celda) in analizador.tablero().celdas() {
+            let esperado = matches!(celda, Celda::Bomba(_)) as usize;
+            assert_eq!(mapa[punto.y][punto.x], esperado);
+        }
+    }
+
+    #[test]
+    fn test08_las_bombas_que_no_alcanzan_nada_solo_se_cambian_a_si_mismas() {
+        const CASILLAS: [&str; 13] = [
+            "_", "_", "_", "W", "R", "X", "DD", "F1", "G2", "M1", "B1", "S2", "B[U2,R4]",
+        ];
+        let reglas = Reglas {
+            ladrillos: true,
+            ..Reglas::default()
+        };
+        let mut aleatorio = Aleatorio::new(1526);
+        let mut descartadas = 0;
+        for _ in 0..300 {
+            let ancho = 1 + aleatorio.siguiente_hasta(6);
+            let mut texto: Vec<String> = (0..1 + aleatorio.siguiente_hasta(6))
+                .map(|_| {
+                    (0..ancho)
+                        .map(|_| CASILLAS[aleatorio.siguiente_hasta(CASILLAS.len())])
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            // Los portales van de a dos, asi que se agregan al principio de dos filas mas.
+            let fila_con_portal = format!("P1{}", " _".repeat(ancho - 1));
+            texto.extend([fila_con_portal.clone(), fila_con_portal]);
+            let tablero = Tablero::desde_texto(&texto.join("\n")).unwrap();
+            let analizador = Analizador::new(tablero.clone(), reglas.clone(), Limites::default());
+            for bomba in super::bombas_de(&tablero) {
+                if analizador.puede_alcanzar_algo(&tablero, bomba) {
+                    continue;
+                }
+                descartadas += 1;
+                let afectadas = analizador.afectadas(bomba).unwrap();
+                assert!(
+                    afectadas
+                        .iter()
+                        .all(|punto| *punto == bomba
+                            || tablero.get(*punto) == Some(&Celda::Ladrillo)),
+                    "{:?} en\n{}",
+                    bomba,
+                    tablero.to_texto()
+                );
+            }
+        }
+        assert!(descartadas > 0);
+    }
+}
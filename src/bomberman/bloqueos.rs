@@ -0,0 +1,289 @@
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// Hasta donde llega una rafaga en linea recta, segun `Bloqueos::tramo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tramo {
+    /// Las casillas que recorre la rafaga despues de salir de la bomba, incluida la que la frena o la desvia.
+    pub casillas: usize,
+    /// La pared, ladrillo, roca, desvio o portal en el que termina el tramo, o None si la rafaga se quedo sin alcance o llego al borde.
+    pub fin: Option<Punto>,
+}
+
+/// Las paredes, ladrillos, rocas, desvios y portales de un tablero, ordenados por fila y por columna, para saber hasta donde llega una
+/// rafaga en linea recta con una busqueda binaria en lugar de recorrer las casillas una por una. Sirve para analizar
+/// miles de detonaciones hipoteticas sobre el mismo tablero, como hace un solver.
+///
+/// El motor nunca cambia las paredes, las rocas, los desvios ni los portales, asi que esos bloqueos de un tablero siguen valiendo
+/// durante toda su simulacion. Las bombas, las minas y los enemigos si cambian, y no se tienen en cuenta. Los ladrillos frenan
+/// a todas las rafagas pero se rompen al hacerlo, asi que solo valen para el tablero con el que se armaron. Los tramos
+/// siguen la consigna: terminan en el borde, como sin `Reglas::toroidal`, y las paredes frenan a todas las rafagas,
+/// como sin `Reglas::traspaso_atraviesa_paredes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bloqueos {
+    ancho: usize,
+    alto: usize,
+    /// Las columnas de las paredes, los ladrillos, los desvios y los portales de cada fila, de izquierda a derecha.
+    fijos_por_fila: Vec<Vec<usize>>,
+    /// Las filas de las paredes, los ladrillos, los desvios y los portales de cada columna, de arriba hacia abajo.
+    fijos_por_columna: Vec<Vec<usize>>,
+    /// Las columnas de las rocas de cada fila, que solo frenan a las bombas sin traspaso.
+    rocas_por_fila: Vec<Vec<usize>>,
+    /// Las filas de las rocas de cada columna.
+    rocas_por_columna: Vec<Vec<usize>>,
+}
+
+impl Bloqueos {
+    /// Recorre el tablero una vez y anota sus bloqueos. Como `Tablero::celdas` va por filas, cada lista queda ordenada.
+    pub fn new(tablero: &Tablero) -> Bloqueos {
+        let (ancho, alto) = (tablero.ancho(), tablero.alto());
+        let mut bloqueos = Bloqueos {
+            ancho,
+            alto,
+            fijos_por_fila: vec![Vec::new(); alto],
+            fijos_por_columna: vec![Vec::new(); ancho],
+            rocas_por_fila: vec![Vec::new(); alto],
+            rocas_por_columna: vec![Vec::new(); ancho],
+        };
+        for (punto, celda) in tablero.celdas() {
+            let (por_fila, por_columna) = match celda {
+                Celda::Pared | Celda::Ladrillo | Celda::Desvio(_) | Celda::Portal { .. } => (
+                    &mut bloqueos.fijos_por_fila,
+                    &mut bloqueos.fijos_por_columna,
+                ),
+                Celda::Roca => (
+                    &mut bloqueos.rocas_por_fila,
+                    &mut bloqueos.rocas_por_columna,
+                ),
+                _ => continue,
+            };
+            por_fila[punto.y].push(punto.x);
+            por_columna[punto.x].push(punto.y);
+        }
+        bloqueos
+    }
+
+    /// Calcula hasta donde llega en linea recta una rafaga que sale de `origen` hacia `direccion`, en O(log n): la
+    /// frena la primera pared o ladrillo (o roca, si la bomba no es de traspaso), la dobla el primer desvio o la hace saltar el primer portal, y si no hay ninguno,
+    /// sigue hasta quedarse sin alcance o llegar al borde del tablero.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::bloqueos::{Bloqueos, Tramo};
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let bloqueos = Bloqueos::new(&Tablero::desde_texto("B3 R _ W")?);
+    /// let tramo = bloqueos.tramo(Punto { x: 0, y: 0 }, Direccion::Derecha, 3, true);
+    /// assert_eq!(tramo, Tramo { casillas: 3, fin: Some(Punto { x: 3, y: 0 }) });
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * origen: La casilla de la bomba, que no cuenta.
+    /// * direccion: Hacia donde sale la rafaga.
+    /// * alcance: La cantidad de casillas que puede recorrer.
+    /// * traspaso: true si la bomba atraviesa las rocas.
+    ///
+    /// # Devuelve
+    ///
+    /// El Tramo recorrido, que no tiene casillas si el origen esta fuera del tablero.
+    pub fn tramo(
+        &self,
+        origen: Punto,
+        direccion: Direccion,
+        alcance: usize,
+        traspaso: bool,
+    ) -> Tramo {
+        if origen.x >= self.ancho || origen.y >= self.alto {
+            return Tramo {
+                casillas: 0,
+                fin: None,
+            };
+        }
+        let horizontal = matches!(direccion, Direccion::Izquierda | Direccion::Derecha);
+        let (linea, posicion, largo, fijos, rocas) = match horizontal {
+            true => (
+                origen.y,
+                origen.x,
+                self.ancho,
+                &self.fijos_por_fila,
+                &self.rocas_por_fila,
+            ),
+            false => (
+                origen.x,
+                origen.y,
+                self.alto,
+                &self.fijos_por_columna,
+                &self.rocas_por_columna,
+            ),
+        };
+        let adelante = matches!(direccion, Direccion::Derecha | Direccion::Abajo);
+        let mut bloqueo = siguiente(&fijos[linea], posicion, adelante);
+        if !traspaso {
+            bloqueo = match (bloqueo, siguiente(&rocas[linea], posicion, adelante)) {
+                (Some(a), Some(b)) if adelante => Some(a.min(b)),
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            };
+        }
+        let hasta_el_borde = if adelante {
+            largo - 1 - posicion
+        } else {
+            posicion
+        };
+        let libres = alcance.min(hasta_el_borde);
+        match bloqueo {
+            Some(bloqueo) if bloqueo.abs_diff(posicion) <= libres => Tramo {
+                casillas: bloqueo.abs_diff(posicion),
+                fin: Some(match horizontal {
+                    true => Punto {
+                        x: bloqueo,
+                        y: linea,
+                    },
+                    false => Punto {
+                        x: linea,
+                        y: bloqueo,
+                    },
+                }),
+            },
+            _ => Tramo {
+                casillas: libres,
+                fin: None,
+            },
+        }
+    }
+}
+
+/// El primer valor de una lista ordenada despues de `posicion` (o antes, si no es hacia adelante).
+fn siguiente(lista: &[usize], posicion: usize, adelante: bool) -> Option<usize> {
+    if adelante {
+        lista
+            .get(lista.partition_point(|&valor| valor <= posicion))
+            .copied()
+    } else {
+        lista
+            .partition_point(|&valor| valor < posicion)
+            .checked_sub(1)
+            .map(|indice| lista[indice])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bloqueos, Tramo};
+    use crate::bomberman::celda::Celda;
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::optimizador::Aleatorio;
+    use crate::bomberman::punto::Punto;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_tramos_de_una_fila_y_una_columna() {
+        let tablero = Tablero::desde_texto("W _ B3 R _ DD\n_ _ _ _ _ _\n_ _ W _ _ _").unwrap();
+        let bloqueos = Bloqueos::new(&tablero);
+        let origen = Punto { x: 2, y: 0 };
+        let tramo =
+            |direccion, alcance, traspaso| bloqueos.tramo(origen, direccion, alcance, traspaso);
+        assert_eq!(
+            tramo(Direccion::Derecha, 3, false),
+            Tramo {
+                casillas: 1,
+                fin: Some(Punto { x: 3, y: 0 })
+            }
+        );
+        assert_eq!(
+            tramo(Direccion::Derecha, 3, true),
+            Tramo {
+                casillas: 3,
+                fin: Some(Punto { x: 5, y: 0 })
+            }
+        );
+        assert_eq!(
+            tramo(Direccion::Izquierda, 1, false),
+            Tramo {
+                casillas: 1,
+                fin: None
+            }
+        );
+        assert_eq!(
+            tramo(Direccion::Arriba, 3, false),
+            Tramo {
+                casillas: 0,
+                fin: None
+            }
+        );
+        assert_eq!(
+            tramo(Direccion::Abajo, 9, false),
+            Tramo {
+                casillas: 2,
+                fin: Some(Punto { x: 2, y: 2 })
+            }
+        );
+        assert_eq!(
+            bloqueos.tramo(Punto { x: 6, y: 0 }, Direccion::Izquierda, 3, false),
+            Tramo {
+                casillas: 0,
+                fin: None
+            }
+        );
+    }
+
+    #[test]
+    fn test02_coincide_con_recorrer_las_casillas() {
+        let mut aleatorio = Aleatorio::new(1526);
+        for _ in 0..20 {
+            let (ancho, alto) = (
+                1 + aleatorio.siguiente_hasta(8),
+                1 + aleatorio.siguiente_hasta(8),
+            );
+            let filas: Vec<Vec<String>> = (0..alto)
+                .map(|_| {
+                    (0..ancho)
+                        .map(|_| {
+                            ["_", "_", "W", "R", "X", "DL", "F1"][aleatorio.siguiente_hasta(7)]
+                                .to_string()
+                        })
+                        .collect()
+                })
+                .collect();
+            let tablero = Tablero::desde_filas(filas).unwrap();
+            let bloqueos = Bloqueos::new(&tablero);
+            for (origen, _) in tablero.celdas() {
+                for direccion in Direccion::todas() {
+                    for traspaso in [false, true] {
+                        let mut esperado = Tramo {
+                            casillas: 0,
+                            fin: None,
+                        };
+                        let mut punto = direccion.aplicar(origen);
+                        while let Some(celda) = punto
+                            .filter(|_| esperado.casillas < 5)
+                            .and_then(|p| tablero.get(p))
+                        {
+                            esperado.casillas += 1;
+                            if matches!(celda, Celda::Pared | Celda::Ladrillo | Celda::Desvio(_))
+                                || (!traspaso && *celda == Celda::Roca)
+                            {
+                                esperado.fin = punto;
+                                break;
+                            }
+                            punto = punto.and_then(|p| direccion.aplicar(p));
+                        }
+                        assert_eq!(
+                            bloqueos.tramo(origen, direccion, 5, traspaso),
+                            esperado,
+                            "{:?} {:?} {}\n{}",
+                            origen,
+                            direccion,
+                            traspaso,
+                            tablero.to_texto()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
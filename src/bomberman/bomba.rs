@@ -0,0 +1,207 @@
+use super::celda;
+use super::direccion::Direccion;
+
+/// Una casilla de bomba ya interpretada: si es de traspaso y el alcance de la rafaga en cada direccion.
+///
+/// Se escribe como `B3`, `S2` o `B12` (mismo alcance en las 4 direcciones) o con un alcance por direccion como
+/// `B[U2,R5,D0,L1]`, donde las direcciones que no aparecen tienen alcance 0. El tope del alcance no depende del
+/// formato sino de `Reglas::alcance_maximo`.
+///
+/// Una bomba temporizada se escribe con `T` y su mecha delante de la bomba, como `T3B2` o `T1S[R4]`; `T3` sola es
+/// `T3B1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bomba {
+    /// true para una bomba de traspaso ('S'), cuyas rafagas atraviesan las rocas, o false para una normal ('B').
+    pub traspaso: bool,
+    /// Alcance en cada direccion, en el orden de `Direccion::TODAS`.
+    pub alcances: [usize; 4],
+    /// Para una bomba temporizada, cuantos tics tarda en explotar desde que la alcanza una rafaga (ver
+    /// `Evento::MechaEncendida`). None para las bombas que explotan en cuanto las alcanzan.
+    pub mecha: Option<usize>,
+}
+
+impl Bomba {
+    /// Interpreta el texto de una casilla como una bomba.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let bomba = Bomba::parsear("B[U2,R5]").unwrap();
+    /// assert_eq!(bomba.alcance(Direccion::Derecha), 5);
+    /// assert_eq!(bomba.alcance(Direccion::Izquierda), 0);
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * casilla: El texto de la casilla.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con la Bomba, o None si la casilla no es una bomba valida. El dato opaco de la casilla se ignora.
+    pub fn parsear(casilla: &str) -> Option<Bomba> {
+        let (casilla, _) = celda::separar_dato(casilla);
+        if let Some(resto) = casilla.strip_prefix('T') {
+            return Self::parsear_temporizada(resto);
+        }
+        let tipo = casilla.chars().next().filter(|t| *t == 'B' || *t == 'S')?;
+        let resto = &casilla[1..];
+        if let Some(lista) = resto.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            return Self::parsear_por_direccion(tipo == 'S', lista);
+        }
+        // El alcance es el numero que sigue al tipo, que no puede ser 0 ni tener ceros a la izquierda.
+        if resto.starts_with('0') || !resto.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let alcance = resto.parse().ok()?;
+        Some(Bomba {
+            traspaso: tipo == 'S',
+            alcances: [alcance; 4],
+            mecha: None,
+        })
+    }
+
+    /// Lee lo que sigue a la `T` de una bomba temporizada: la mecha, que como el alcance no puede ser 0 ni tener ceros
+    /// a la izquierda, y la bomba, si no es `B1`.
+    fn parsear_temporizada(resto: &str) -> Option<Bomba> {
+        let digitos = resto.bytes().take_while(u8::is_ascii_digit).count();
+        let (mecha, bomba) = resto.split_at(digitos);
+        if mecha.starts_with('0') {
+            return None;
+        }
+        let mut bomba = match bomba {
+            "" => Bomba {
+                traspaso: false,
+                alcances: [1; 4],
+                mecha: None,
+            },
+            bomba => Self::parsear(bomba).filter(|bomba| bomba.mecha.is_none())?,
+        };
+        bomba.mecha = Some(mecha.parse().ok()?);
+        Some(bomba)
+    }
+
+    fn parsear_por_direccion(traspaso: bool, lista: &str) -> Option<Bomba> {
+        let mut alcances: [Option<usize>; 4] = [None; 4];
+        for parte in lista.split(',') {
+            let mut chars = parte.trim().chars();
+            let indice = Direccion::desde_letra(chars.next()?)?.indice();
+            let alcance = chars.as_str().parse().ok()?;
+            if alcances[indice].replace(alcance).is_some() {
+                return None;
+            }
+        }
+        Some(Bomba {
+            traspaso,
+            alcances: alcances.map(|a| a.unwrap_or(0)),
+            mecha: None,
+        })
+    }
+
+    /// La letra del tipo de bomba: 'S' si es de traspaso o 'B' si es normal.
+    pub fn tipo(&self) -> char {
+        if self.traspaso {
+            'S'
+        } else {
+            'B'
+        }
+    }
+
+    /// Devuelve el alcance de la rafaga en la direccion dada.
+    pub fn alcance(&self, direccion: Direccion) -> usize {
+        self.alcances[direccion.indice()]
+    }
+
+    /// El mayor alcance de la bomba entre todas las direcciones.
+    pub fn alcance_maximo(&self) -> usize {
+        self.alcances.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Escribe la bomba como `B3` si tiene el mismo alcance (distinto de 0) en las 4 direcciones, o como `B[L1,U2,R5,D0]`,
+/// con la mecha delante si es temporizada (`T3S2`, o `T3` para `T3B1`).
+impl std::fmt::Display for Bomba {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(mecha) = self.mecha {
+            write!(f, "T{}", mecha)?;
+            if !self.traspaso && self.alcances == [1; 4] {
+                return Ok(());
+            }
+        }
+        let alcance = self.alcances[0];
+        if self.alcances.iter().all(|a| *a == alcance) && alcance > 0 {
+            return write!(f, "{}{}", self.tipo(), alcance);
+        }
+        let partes: Vec<String> = Direccion::todas()
+            .zip(self.alcances)
+            .map(|(direccion, alcance)| format!("{}{}", direccion, alcance))
+            .collect();
+        write!(f, "{}[{}]", self.tipo(), partes.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bomba;
+    use crate::bomberman::direccion::Direccion;
+
+    #[test]
+    fn test01_bomba_con_el_mismo_alcance_en_todas_las_direcciones() {
+        let bomba = Bomba::parsear("S3").unwrap();
+        assert!(bomba.traspaso);
+        assert_eq!(bomba.alcances, [3; 4]);
+        assert_eq!(Bomba::parsear("B0"), None);
+        assert_eq!(Bomba::parsear("B07"), None);
+        assert_eq!(Bomba::parsear("F1"), None);
+        let grande = Bomba::parsear("B12").unwrap();
+        assert_eq!(grande.alcances, [12; 4]);
+        assert_eq!(grande.to_string(), "B12");
+    }
+
+    #[test]
+    fn test02_bomba_con_alcance_por_direccion() {
+        let bomba = Bomba::parsear("B[U2,R5,D0,L1]").unwrap();
+        assert_eq!(bomba.alcance(Direccion::Arriba), 2);
+        assert_eq!(bomba.alcance(Direccion::Derecha), 5);
+        assert_eq!(bomba.alcance(Direccion::Abajo), 0);
+        assert_eq!(bomba.alcance(Direccion::Izquierda), 1);
+        assert_eq!(bomba.alcance_maximo(), 5);
+        assert_eq!(Bomba::parsear("S[R12]").unwrap().alcances, [0, 0, 12, 0]);
+    }
+
+    #[test]
+    fn test03_alcances_por_direccion_invalidos() {
+        assert_eq!(Bomba::parsear("B[U2,U3]"), None);
+        assert_eq!(Bomba::parsear("B[X2]"), None);
+        assert_eq!(Bomba::parsear("B[U]"), None);
+        assert_eq!(Bomba::parsear("B[U2"), None);
+    }
+
+    #[test]
+    fn test05_bombas_temporizadas() {
+        let bomba = Bomba::parsear("T3").unwrap();
+        assert_eq!(bomba.mecha, Some(3));
+        assert_eq!((bomba.traspaso, bomba.alcances), (false, [1; 4]));
+        assert_eq!(Bomba::parsear("T3B1").unwrap(), bomba);
+        assert_eq!(bomba.to_string(), "T3");
+        let traspaso = Bomba::parsear("T12S[R4]{id=1}").unwrap();
+        assert_eq!(traspaso.mecha, Some(12));
+        assert_eq!(traspaso.alcances, [0, 0, 4, 0]);
+        assert_eq!(traspaso.to_string(), "T12S[L0,U0,R4,D0]");
+        assert_eq!(Bomba::parsear("T1B2").unwrap().to_string(), "T1B2");
+        for invalida in ["T", "T0", "T03", "TB2", "T2T3", "T2F1", "T2B0"] {
+            assert_eq!(Bomba::parsear(invalida), None, "{}", invalida);
+        }
+    }
+
+    #[test]
+    fn test06_alcances_de_varios_digitos() {
+        assert_eq!(Bomba::parsear("B10").unwrap().alcances, [10; 4]);
+        assert_eq!(Bomba::parsear("B25").unwrap().alcance_maximo(), 25);
+        let traspaso = Bomba::parsear("S100{id=1}").unwrap();
+        assert!(traspaso.traspaso);
+        assert_eq!(traspaso.alcances, [100; 4]);
+        assert_eq!(Bomba::parsear("B1x"), None);
+        assert_eq!(Bomba::parsear("B-1"), None);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use super::evento::{Evento, Observador};
+
+/// Que hace un EmisorEventos cuando la cola esta llena porque el receptor no llega a procesar los eventos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contrapresion {
+    /// Descarta el evento mas viejo de la cola para hacerle lugar al nuevo. La simulacion nunca espera, pero el
+    /// receptor se puede perder eventos (ver `ReceptorEventos::descartados`).
+    DescartarViejos,
+    /// Frena la simulacion hasta que el receptor saque un evento de la cola. No se pierde ningun evento.
+    Bloquear,
+}
+
+/// Lo que comparten el emisor y el receptor de un canal.
+struct Estado {
+    cola: VecDeque<Evento>,
+    descartados: usize,
+    emisor_vivo: bool,
+    receptor_vivo: bool,
+}
+
+/// La cola con su Condvar, que avisa tanto cuando entra un evento como cuando se libera lugar.
+struct Compartido {
+    estado: Mutex<Estado>,
+    cambio: Condvar,
+}
+
+impl Compartido {
+    fn estado(&self) -> MutexGuard<'_, Estado> {
+        // Ninguna operacion puede entrar en panic con la cola a medio modificar, asi que el estado sigue siendo valido.
+        self.estado
+            .lock()
+            .unwrap_or_else(|envenenado| envenenado.into_inner())
+    }
+}
+
+/// El extremo de la simulacion de un canal acotado: es un Observador que encola cada Evento para otro hilo.
+pub struct EmisorEventos {
+    compartido: Arc<Compartido>,
+    capacidad: usize,
+    politica: Contrapresion,
+}
+
+/// El extremo del consumidor de un canal acotado, por ejemplo el hilo que dibuja una interfaz o manda los eventos
+/// por la red. Como iterador devuelve los eventos en orden hasta que la simulacion termina.
+pub struct ReceptorEventos {
+    compartido: Arc<Compartido>,
+}
+
+/// Crea un canal de eventos que nunca guarda mas de `capacidad` eventos sin procesar, para que un consumidor lento
+/// no haga crecer la memoria sin limite durante una reaccion en cadena enorme.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let (emisor, receptor) = canal_acotado(1024, Contrapresion::Bloquear);
+/// let consumidor = thread::spawn(move || receptor.count());
+/// SimulacionBuilder::new(tablero).observador(Box::new(emisor)).construir().simular(Columna(x), Fila(y))?;
+/// println!("{} eventos", consumidor.join().unwrap());
+/// ```
+///
+/// # Argumentos
+///
+/// * capacidad: La cantidad maxima de eventos en la cola; si es 0 se usa 1.
+/// * politica: Que hacer cuando la cola esta llena (ver `Contrapresion`).
+///
+/// # Devuelve
+///
+/// El emisor, para agregarlo como observador, y el receptor, para leer los eventos desde otro hilo.
+pub fn canal_acotado(
+    capacidad: usize,
+    politica: Contrapresion,
+) -> (EmisorEventos, ReceptorEventos) {
+    let compartido = Arc::new(Compartido {
+        estado: Mutex::new(Estado {
+            cola: VecDeque::new(),
+            descartados: 0,
+            emisor_vivo: true,
+            receptor_vivo: true,
+        }),
+        cambio: Condvar::new(),
+    });
+    let emisor = EmisorEventos {
+        compartido: Arc::clone(&compartido),
+        capacidad: capacidad.max(1),
+        politica,
+    };
+    (emisor, ReceptorEventos { compartido })
+}
+
+impl Observador for EmisorEventos {
+    fn notificar(&mut self, evento: &Evento) {
+        let mut estado = self.compartido.estado();
+        if !estado.receptor_vivo {
+            return;
+        }
+        while estado.cola.len() >= self.capacidad {
+            match self.politica {
+                Contrapresion::DescartarViejos => {
+                    estado.cola.pop_front();
+                    estado.descartados += 1;
+                }
+                Contrapresion::Bloquear => {
+                    estado = self
+                        .compartido
+                        .cambio
+                        .wait(estado)
+                        .unwrap_or_else(|envenenado| envenenado.into_inner());
+                    if !estado.receptor_vivo {
+                        return;
+                    }
+                }
+            }
+        }
+        estado.cola.push_back(evento.clone());
+        self.compartido.cambio.notify_all();
+    }
+}
+
+impl Drop for EmisorEventos {
+    fn drop(&mut self) {
+        self.compartido.estado().emisor_vivo = false;
+        self.compartido.cambio.notify_all();
+    }
+}
+
+impl ReceptorEventos {
+    /// Espera el proximo evento.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con el evento, o None si la simulacion termino (se libero el emisor) y ya no quedan eventos.
+    pub fn recibir(&self) -> Option<Evento> {
+        let mut estado = self.compartido.estado();
+        loop {
+            if let Some(evento) = estado.cola.pop_front() {
+                self.compartido.cambio.notify_all();
+                return Some(evento);
+            }
+            if !estado.emisor_vivo {
+                return None;
+            }
+            estado = self
+                .compartido
+                .cambio
+                .wait(estado)
+                .unwrap_or_else(|envenenado| envenenado.into_inner());
+        }
+    }
+
+    /// Cuantos eventos se descartaron hasta ahora con `Contrapresion::DescartarViejos` porque la cola estaba llena.
+    pub fn descartados(&self) -> usize {
+        self.compartido.estado().descartados
+    }
+}
+
+impl Iterator for ReceptorEventos {
+    type Item = Evento;
+
+    fn next(&mut self) -> Option<Evento> {
+        self.recibir()
+    }
+}
+
+/// Si el receptor deja de leer, el emisor descarta los eventos en lugar de bloquear la simulacion para siempre.
+impl Drop for ReceptorEventos {
+    fn drop(&mut self) {
+        let mut estado = self.compartido.estado();
+        estado.receptor_vivo = false;
+        estado.cola.clear();
+        self.compartido.cambio.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::{canal_acotado, Contrapresion};
+    use crate::bomberman::evento::{Evento, Observador};
+    use crate::bomberman::punto::Punto;
+
+    fn evento(x: usize) -> Evento {
+        Evento::BombaAlcanzada {
+            punto: Punto { x, y: 0 },
+        }
+    }
+
+    #[test]
+    fn test01_descartar_viejos_guarda_los_ultimos() {
+        let (mut emisor, receptor) = canal_acotado(2, Contrapresion::DescartarViejos);
+        for x in 0..5 {
+            emisor.notificar(&evento(x));
+        }
+        drop(emisor);
+        assert_eq!(receptor.descartados(), 3);
+        assert_eq!(receptor.collect::<Vec<_>>(), vec![evento(3), evento(4)]);
+    }
+
+    #[test]
+    fn test02_bloquear_no_pierde_eventos() {
+        let (mut emisor, receptor) = canal_acotado(1, Contrapresion::Bloquear);
+        let consumidor = thread::spawn(move || receptor.collect::<Vec<_>>());
+        for x in 0..100 {
+            emisor.notificar(&evento(x));
+        }
+        drop(emisor);
+        assert_eq!(
+            consumidor.join().unwrap(),
+            (0..100).map(evento).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test03_sin_receptor_no_bloquea() {
+        let (mut emisor, receptor) = canal_acotado(1, Contrapresion::Bloquear);
+        drop(receptor);
+        for x in 0..3 {
+            emisor.notificar(&evento(x));
+        }
+    }
+}
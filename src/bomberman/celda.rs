@@ -0,0 +1,269 @@
+use super::bomba::Bomba;
+use super::direccion::Direccion;
+use super::mejora::Mejora;
+
+/// El contenido de una casilla del tablero, ya interpretado a partir de su texto.
+///
+/// El texto de una casilla puede terminar con un dato opaco entre llaves (`B2{color=rojo}`) que el motor no
+/// interpreta: `parsear` lo ignora y el Tablero lo guarda por posicion, asi que cuando el motor cambia la casilla el
+/// dato queda en la casilla nueva (`F1{id=3}` pasa a `_{id=3}`) y las herramientas externas recuperan sus anotaciones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Celda {
+    /// `_`
+    Vacio,
+    /// `W`: frena todas las rafagas.
+    Pared,
+    /// `R`: frena las rafagas de las bombas normales.
+    Roca,
+    /// `X`: un ladrillo, que cualquier rafaga rompe (queda `_`) pero que igual la frena. Solo es valido con
+    /// `Reglas::ladrillos`.
+    Ladrillo,
+    /// `DU`, `DD`, `DL` o `DR`: cambia la direccion de la rafaga. `DUL`, `DUR`, `DDL` o `DDR` la mandan en diagonal.
+    Desvio(Direccion),
+    /// `F1`, `F2`, ...: un enemigo con la vida indicada, o `G1`, `G2`, ... si es blindado: solo le quitan vida las
+    /// rafagas de las bombas de traspaso.
+    Enemigo { vida: usize, blindado: bool },
+    /// `B3`, `S2`, `B[U2,R5]`, `T3`, ... (ver `Bomba`).
+    Bomba(Bomba),
+    /// `M1`, `M2`, ...: una mina, que explota como una bomba normal con el alcance indicado cuando una rafaga la
+    /// alcanza o pasa por alguna de las 4 casillas de al lado.
+    Mina { alcance: usize },
+    /// `+R`: una mejora que dejo un enemigo al morir (ver `Mejora`). Solo es valida con `Reglas::mejoras`.
+    Mejora(Mejora),
+    /// `P1`, `P2`, ...: un portal. Cada numero aparece exactamente dos veces en el tablero (lo controla
+    /// `Tablero::desde_filas`) y la rafaga que entra a uno sale por el otro.
+    Portal { numero: usize },
+    /// `P`: el jugador de un `Juego`. Las rafagas no lo frenan, pero lo matan (la casilla queda `_`).
+    Jugador,
+}
+
+impl Celda {
+    /// Interpreta el texto de una casilla.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// assert_eq!(Celda::parsear("G2"), Some(Celda::Enemigo { vida: 2, blindado: true }));
+    /// assert_eq!(Celda::parsear("DX"), None);
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con la Celda, o None si el texto no es una casilla valida.
+    pub fn parsear(texto: &str) -> Option<Celda> {
+        let (texto, _) = separar_dato(texto);
+        match texto {
+            "_" => Some(Celda::Vacio),
+            "W" => Some(Celda::Pared),
+            "R" => Some(Celda::Roca),
+            "X" => Some(Celda::Ladrillo),
+            "DU" => Some(Celda::Desvio(Direccion::Arriba)),
+            "DD" => Some(Celda::Desvio(Direccion::Abajo)),
+            "DL" => Some(Celda::Desvio(Direccion::Izquierda)),
+            "DR" => Some(Celda::Desvio(Direccion::Derecha)),
+            "DUL" => Some(Celda::Desvio(Direccion::ArribaIzquierda)),
+            "DUR" => Some(Celda::Desvio(Direccion::ArribaDerecha)),
+            "DDL" => Some(Celda::Desvio(Direccion::AbajoIzquierda)),
+            "DDR" => Some(Celda::Desvio(Direccion::AbajoDerecha)),
+            "P" => Some(Celda::Jugador),
+            _ => match texto.split_at_checked(1) {
+                // La vida no puede ser 0 ni tener ceros a la izquierda, asi cada enemigo tiene un solo texto. El tope
+                // (`Reglas::vida_maxima`) lo controla `io::es_casilla_valida`. Lo mismo vale para el alcance de las
+                // minas y el numero de los portales.
+                Some((letra @ ("F" | "G"), vida)) => {
+                    numero_sin_ceros(vida).map(|vida| Celda::Enemigo {
+                        vida,
+                        blindado: letra == "G",
+                    })
+                }
+                Some(("+", letra)) if letra.len() == 1 => letra
+                    .chars()
+                    .next()
+                    .and_then(Mejora::desde_letra)
+                    .map(Celda::Mejora),
+                Some(("M", alcance)) => {
+                    numero_sin_ceros(alcance).map(|alcance| Celda::Mina { alcance })
+                }
+                Some(("P", numero)) => {
+                    numero_sin_ceros(numero).map(|numero| Celda::Portal { numero })
+                }
+                _ => Bomba::parsear(texto).map(Celda::Bomba),
+            },
+        }
+    }
+}
+
+/// Lee un numero positivo escrito sin ceros a la izquierda.
+fn numero_sin_ceros(texto: &str) -> Option<usize> {
+    if texto.starts_with('0') || !texto.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    texto.parse().ok()
+}
+
+/// Separa el texto de una casilla de su dato opaco, si lo tiene. El dato no puede tener llaves ni espacios adentro,
+/// para que la casilla se siga leyendo igual al escribir el tablero (ver `Tablero::to_texto`).
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::celda::separar_dato;
+/// assert_eq!(separar_dato("B2{color=rojo}"), ("B2", Some("color=rojo")));
+/// assert_eq!(separar_dato("B2"), ("B2", None));
+/// ```
+pub fn separar_dato(texto: &str) -> (&str, Option<&str>) {
+    let partes = texto
+        .strip_suffix('}')
+        .and_then(|resto| resto.split_once('{'))
+        .filter(|(_, dato)| !dato.contains(['{', '}']) && !dato.contains(char::is_whitespace));
+    match partes {
+        Some((casilla, dato)) => (casilla, Some(dato)),
+        None => (texto, None),
+    }
+}
+
+/// Escribe la celda con el mismo texto que se usa en los archivos de tablero.
+impl std::fmt::Display for Celda {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Celda::Vacio => write!(f, "_"),
+            Celda::Pared => write!(f, "W"),
+            Celda::Roca => write!(f, "R"),
+            Celda::Ladrillo => write!(f, "X"),
+            Celda::Desvio(direccion) => write!(f, "D{}", direccion),
+            Celda::Enemigo {
+                vida,
+                blindado: false,
+            } => write!(f, "F{}", vida),
+            Celda::Enemigo {
+                vida,
+                blindado: true,
+            } => write!(f, "G{}", vida),
+            Celda::Bomba(bomba) => write!(f, "{}", bomba),
+            Celda::Mina { alcance } => write!(f, "M{}", alcance),
+            Celda::Mejora(mejora) => write!(f, "{}", mejora),
+            Celda::Portal { numero } => write!(f, "P{}", numero),
+            Celda::Jugador => write!(f, "P"),
+        }
+    }
+}
+
+/// Con la feature `serde`, una Celda se guarda como el texto de su casilla (`"B[U2,R5,D0,L1]"`, `"F3"`), igual que en
+/// los archivos de tablero, en lugar de como la estructura del enum.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Celda {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Celda {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Celda, D::Error> {
+        let texto = String::deserialize(deserializer)?;
+        Celda::parsear(&texto)
+            .ok_or_else(|| serde::de::Error::custom(format!("casilla invalida {}", texto)))
+    }
+}
+
+/// Con la feature `arbitrary`, una casilla valida con las reglas de siempre: enemigos con vida entre 1 y
+/// `VIDA_MAXIMA` y bombas y minas con alcance hasta `ALCANCE_MAXIMO`; las bombas a veces tienen alcance distinto en
+/// cada direccion y a veces son temporizadas.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Celda {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Celda> {
+        use super::generador::{ALCANCE_MAXIMO, VIDA_MAXIMA};
+        Ok(match u.int_in_range(0..=6u8)? {
+            0 => Celda::Vacio,
+            1 => Celda::Pared,
+            2 => Celda::Roca,
+            3 => Celda::Desvio(*u.choose(&Direccion::TODAS)?),
+            4 => Celda::Enemigo {
+                vida: u.int_in_range(1..=VIDA_MAXIMA)?,
+                blindado: u.ratio(1, 4)?,
+            },
+            5 => Celda::Mina {
+                alcance: u.int_in_range(1..=ALCANCE_MAXIMO)?,
+            },
+            _ => {
+                let traspaso = u.arbitrary()?;
+                let mut alcances = [u.int_in_range(1..=ALCANCE_MAXIMO)?; 4];
+                if u.ratio(1, 4)? {
+                    for alcance in &mut alcances[1..] {
+                        *alcance = u.int_in_range(0..=ALCANCE_MAXIMO)?;
+                    }
+                }
+                let mecha = match u.ratio(1, 8)? {
+                    true => Some(u.int_in_range(1..=3)?),
+                    false => None,
+                };
+                Celda::Bomba(Bomba {
+                    traspaso,
+                    alcances,
+                    mecha,
+                })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{separar_dato, Celda};
+
+    #[test]
+    fn test01_texto_ida_y_vuelta() {
+        for texto in [
+            "_",
+            "W",
+            "X",
+            "R",
+            "DU",
+            "DL",
+            "DUL",
+            "DDR",
+            "F3",
+            "F12",
+            "B1",
+            "S9",
+            "B[L1,U2,R5,D0]",
+            "T3",
+            "T1S2",
+            "M2",
+            "G3",
+            "+R",
+            "P1",
+            "P12",
+            "P",
+        ] {
+            match Celda::parsear(texto) {
+                Some(celda) => assert_eq!(celda.to_string(), texto),
+                None => panic!("{} deberia ser una celda valida", texto),
+            }
+        }
+    }
+
+    #[test]
+    fn test02_textos_invalidos() {
+        for texto in [
+            "", "Q", "D", "DX", "DLU", "DUU", "F0", "F01", "F+1", "F", "FF", "B0", "WW", "B2{",
+            "B2{a{b}", "B2{a b}", "Q{a}", "M", "M0", "M03", "G0", "G", "+", "+Q", "+RR", "P0",
+            "P01", "PP",
+        ] {
+            assert_eq!(Celda::parsear(texto), None);
+        }
+    }
+
+    #[test]
+    fn test03_el_dato_opaco_se_ignora_y_se_conserva() {
+        assert_eq!(separar_dato("B[U2,R5]{c=1}"), ("B[U2,R5]", Some("c=1")));
+        assert_eq!(
+            Celda::parsear("F2{id=3}"),
+            Some(Celda::Enemigo {
+                vida: 2,
+                blindado: false
+            })
+        );
+    }
+}
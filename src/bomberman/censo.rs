@@ -0,0 +1,220 @@
+use std::collections::BTreeSet;
+
+use super::celda::Celda;
+use super::tablero::Tablero;
+
+/// Un tipo de casilla o una forma de escribirla que puede aparecer en un tablero, para saber que cubre una bateria
+/// de pruebas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Caracteristica {
+    Vacio,
+    Pared,
+    Roca,
+    Ladrillo,
+    Desvio,
+    /// Un desvio que dobla la rafaga en diagonal, como `DUL`.
+    DesvioDiagonal,
+    Enemigo,
+    /// Un enemigo que solo pierde vida con las bombas de traspaso, como `G2`.
+    EnemigoBlindado,
+    /// Un enemigo con 10 o mas de vida.
+    VidaDeVariosDigitos,
+    BombaNormal,
+    BombaDeTraspaso,
+    /// Una bomba con mecha, como `T3`.
+    BombaTemporizada,
+    Mina,
+    Mejora,
+    Portal,
+    Jugador,
+    /// Una bomba con distinto alcance en alguna direccion, como `B[U2,R5]`.
+    AlcancePorDireccion,
+    /// Una bomba con alcance 10 o mas en alguna direccion.
+    AlcanceDeVariosDigitos,
+    /// Una casilla con dato opaco, como `F1{id=3}`.
+    DatoOpaco,
+    /// Un tablero con distinta cantidad de filas que de columnas.
+    TableroNoCuadrado,
+}
+
+impl Caracteristica {
+    /// Todas las caracteristicas, en el orden en que se informan.
+    pub const TODAS: [Caracteristica; 20] = [
+        Caracteristica::Vacio,
+        Caracteristica::Pared,
+        Caracteristica::Roca,
+        Caracteristica::Ladrillo,
+        Caracteristica::Desvio,
+        Caracteristica::DesvioDiagonal,
+        Caracteristica::Enemigo,
+        Caracteristica::EnemigoBlindado,
+        Caracteristica::VidaDeVariosDigitos,
+        Caracteristica::BombaNormal,
+        Caracteristica::BombaDeTraspaso,
+        Caracteristica::BombaTemporizada,
+        Caracteristica::Mina,
+        Caracteristica::Mejora,
+        Caracteristica::Portal,
+        Caracteristica::Jugador,
+        Caracteristica::AlcancePorDireccion,
+        Caracteristica::AlcanceDeVariosDigitos,
+        Caracteristica::DatoOpaco,
+        Caracteristica::TableroNoCuadrado,
+    ];
+}
+
+impl std::fmt::Display for Caracteristica {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nombre = match self {
+            Caracteristica::Vacio => "vacio",
+            Caracteristica::Pared => "pared",
+            Caracteristica::Roca => "roca",
+            Caracteristica::Ladrillo => "ladrillo",
+            Caracteristica::Desvio => "desvio",
+            Caracteristica::DesvioDiagonal => "desvio_diagonal",
+            Caracteristica::Enemigo => "enemigo",
+            Caracteristica::EnemigoBlindado => "enemigo_blindado",
+            Caracteristica::VidaDeVariosDigitos => "vida_de_varios_digitos",
+            Caracteristica::BombaNormal => "bomba_normal",
+            Caracteristica::BombaDeTraspaso => "bomba_de_traspaso",
+            Caracteristica::BombaTemporizada => "bomba_temporizada",
+            Caracteristica::Mina => "mina",
+            Caracteristica::Mejora => "mejora",
+            Caracteristica::Portal => "portal",
+            Caracteristica::Jugador => "jugador",
+            Caracteristica::AlcancePorDireccion => "alcance_por_direccion",
+            Caracteristica::AlcanceDeVariosDigitos => "alcance_de_varios_digitos",
+            Caracteristica::DatoOpaco => "dato_opaco",
+            Caracteristica::TableroNoCuadrado => "tablero_no_cuadrado",
+        };
+        write!(f, "{}", nombre)
+    }
+}
+
+/// Devuelve las caracteristicas que usa un tablero.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::censo::{caracteristicas, Caracteristica};
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let tablero = Tablero::desde_texto("S[U2,R12] F1{id=1}")?;
+/// assert!(caracteristicas(&tablero).contains(&Caracteristica::AlcanceDeVariosDigitos));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn caracteristicas(tablero: &Tablero) -> BTreeSet<Caracteristica> {
+    let mut usadas = BTreeSet::new();
+    if tablero.ancho() != tablero.alto() {
+        usadas.insert(Caracteristica::TableroNoCuadrado);
+    }
+    for (punto, celda) in tablero.celdas() {
+        if tablero.dato(punto).is_some() {
+            usadas.insert(Caracteristica::DatoOpaco);
+        }
+        match celda {
+            Celda::Vacio => {
+                usadas.insert(Caracteristica::Vacio);
+            }
+            Celda::Pared => {
+                usadas.insert(Caracteristica::Pared);
+            }
+            Celda::Roca => {
+                usadas.insert(Caracteristica::Roca);
+            }
+            Celda::Ladrillo => {
+                usadas.insert(Caracteristica::Ladrillo);
+            }
+            Celda::Desvio(direccion) => {
+                usadas.insert(match direccion.es_diagonal() {
+                    true => Caracteristica::DesvioDiagonal,
+                    false => Caracteristica::Desvio,
+                });
+            }
+            Celda::Enemigo { vida, blindado } => {
+                usadas.insert(match blindado {
+                    true => Caracteristica::EnemigoBlindado,
+                    false => Caracteristica::Enemigo,
+                });
+                if *vida >= 10 {
+                    usadas.insert(Caracteristica::VidaDeVariosDigitos);
+                }
+            }
+            Celda::Bomba(bomba) => {
+                usadas.insert(if bomba.traspaso {
+                    Caracteristica::BombaDeTraspaso
+                } else {
+                    Caracteristica::BombaNormal
+                });
+                if bomba.mecha.is_some() {
+                    usadas.insert(Caracteristica::BombaTemporizada);
+                }
+                if bomba
+                    .alcances
+                    .iter()
+                    .any(|alcance| *alcance != bomba.alcances[0])
+                {
+                    usadas.insert(Caracteristica::AlcancePorDireccion);
+                }
+                if bomba.alcance_maximo() >= 10 {
+                    usadas.insert(Caracteristica::AlcanceDeVariosDigitos);
+                }
+            }
+            Celda::Mejora(_) => {
+                usadas.insert(Caracteristica::Mejora);
+            }
+            Celda::Portal { .. } => {
+                usadas.insert(Caracteristica::Portal);
+            }
+            Celda::Jugador => {
+                usadas.insert(Caracteristica::Jugador);
+            }
+            Celda::Mina { alcance } => {
+                usadas.insert(Caracteristica::Mina);
+                if *alcance >= 10 {
+                    usadas.insert(Caracteristica::AlcanceDeVariosDigitos);
+                }
+            }
+        }
+    }
+    usadas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{caracteristicas, Caracteristica};
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_caracteristicas_de_un_tablero() {
+        let tablero = Tablero::desde_texto("B1 S[U2,R12] _\nF10{id=1} DU W").unwrap();
+        let nombres: Vec<String> = caracteristicas(&tablero)
+            .iter()
+            .map(Caracteristica::to_string)
+            .collect();
+        assert_eq!(
+            nombres,
+            [
+                "vacio",
+                "pared",
+                "desvio",
+                "enemigo",
+                "vida_de_varios_digitos",
+                "bomba_normal",
+                "bomba_de_traspaso",
+                "alcance_por_direccion",
+                "alcance_de_varios_digitos",
+                "dato_opaco",
+                "tablero_no_cuadrado"
+            ]
+        );
+        let cuadrado = Tablero::desde_texto("B[U2,D2,L2,R2] R\n_ _").unwrap();
+        assert_eq!(
+            caracteristicas(&cuadrado).into_iter().collect::<Vec<_>>(),
+            [
+                Caracteristica::Vacio,
+                Caracteristica::Roca,
+                Caracteristica::BombaNormal
+            ]
+        );
+    }
+}
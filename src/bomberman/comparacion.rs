@@ -0,0 +1,103 @@
+use super::punto::Punto;
+
+/// Una casilla cuyo contenido difiere entre dos tableros. Si los tableros tienen distinto tamanio,
+/// la casilla que no existe en alguno de ellos se representa con None.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diferencia {
+    pub punto: Punto,
+    pub antes: Option<String>,
+    pub despues: Option<String>,
+}
+
+impl std::fmt::Display for Diferencia {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {}): {} -> {}",
+            self.punto.x,
+            self.punto.y,
+            self.antes.as_deref().unwrap_or("(nada)"),
+            self.despues.as_deref().unwrap_or("(nada)")
+        )
+    }
+}
+
+/// Toma dos tableros y devuelve todas las casillas en las que difieren, recorriendolas por filas.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// for diferencia in diferencias(&tablero_a, &tablero_b) {
+///     println!("{}", diferencia); // (4, 0): F1 -> _
+/// }
+/// ```
+///
+/// # Argumentos
+///
+/// * antes: El primer tablero a comparar.
+/// * despues: El segundo tablero a comparar.
+///
+/// # Devuelve
+///
+/// Un vector con las Diferencias encontradas, vacio si los tableros son iguales.
+pub fn diferencias(antes: &[Vec<String>], despues: &[Vec<String>]) -> Vec<Diferencia> {
+    let mut encontradas = Vec::new();
+    for y in 0..antes.len().max(despues.len()) {
+        let fila_antes = antes.get(y).map(Vec::as_slice).unwrap_or_default();
+        let fila_despues = despues.get(y).map(Vec::as_slice).unwrap_or_default();
+        for x in 0..fila_antes.len().max(fila_despues.len()) {
+            let casilla_antes = fila_antes.get(x);
+            let casilla_despues = fila_despues.get(x);
+            if casilla_antes != casilla_despues {
+                encontradas.push(Diferencia {
+                    punto: Punto { x, y },
+                    antes: casilla_antes.cloned(),
+                    despues: casilla_despues.cloned(),
+                });
+            }
+        }
+    }
+    encontradas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diferencias, Diferencia};
+    use crate::bomberman::punto::Punto;
+
+    fn tablero(filas: &[&str]) -> Vec<Vec<String>> {
+        filas
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test01_tableros_iguales_no_tienen_diferencias() {
+        let tab = tablero(&["B1 _", "R W"]);
+        assert!(diferencias(&tab, &tab).is_empty());
+    }
+
+    #[test]
+    fn test02_se_informan_las_casillas_distintas_con_coordenadas() {
+        let antes = tablero(&["B1 F1", "R W"]);
+        let despues = tablero(&["_ _", "R W"]);
+        let encontradas = diferencias(&antes, &despues);
+        assert_eq!(encontradas.len(), 2);
+        assert_eq!(encontradas[1].to_string(), "(1, 0): F1 -> _");
+    }
+
+    #[test]
+    fn test03_tableros_de_distinto_tamanio() {
+        let antes = tablero(&["_ _"]);
+        let despues = tablero(&["_ _", "W"]);
+        assert_eq!(
+            diferencias(&antes, &despues),
+            vec![Diferencia {
+                punto: Punto { x: 0, y: 1 },
+                antes: None,
+                despues: Some("W".to_string()),
+            }]
+        );
+    }
+}
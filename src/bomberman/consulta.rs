@@ -0,0 +1,258 @@
+use super::celda;
+use super::punto::Punto;
+
+/// Una consulta sobre un tablero, escrita en el mini lenguaje de `consultar`:
+///
+/// * `count F*`: cuenta las casillas que coinciden con el patron.
+/// * `list B where alcance>2`: lista las coordenadas de las casillas que coinciden, opcionalmente filtradas.
+/// * `at 3,4`: muestra el contenido de una casilla.
+///
+/// Un patron es el texto exacto de la casilla (`F2`, `DU`, `_`) o un prefijo terminado en `*` (`D*`).
+/// Un patron de una sola letra de bomba o enemigo (`B`, `S`, `F`) equivale a `B*`, `S*` o `F*`.
+/// El dato opaco de una casilla (`F2{id=3}`) no se tiene en cuenta al comparar, pero si se muestra.
+#[derive(Debug, PartialEq)]
+pub enum Consulta {
+    Contar(String),
+    Listar(String, Option<Condicion>),
+    En(Punto),
+}
+
+/// Filtro numerico de `list ... where`, por ejemplo `alcance>2` o `vida=1`.
+#[derive(Debug, PartialEq)]
+pub struct Condicion {
+    pub propiedad: Propiedad,
+    pub operador: Operador,
+    pub valor: usize,
+}
+
+/// Valor numerico de una casilla que se puede usar en una Condicion.
+#[derive(Debug, PartialEq)]
+pub enum Propiedad {
+    /// El alcance de una bomba (`B3`, `S2`).
+    Alcance,
+    /// La vida de un enemigo (`F2`).
+    Vida,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Operador {
+    Menor,
+    MenorIgual,
+    Igual,
+    Distinto,
+    MayorIgual,
+    Mayor,
+}
+
+impl Operador {
+    fn compara(&self, izq: usize, der: usize) -> bool {
+        match self {
+            Operador::Menor => izq < der,
+            Operador::MenorIgual => izq <= der,
+            Operador::Igual => izq == der,
+            Operador::Distinto => izq != der,
+            Operador::MayorIgual => izq >= der,
+            Operador::Mayor => izq > der,
+        }
+    }
+}
+
+/// Toma el texto de una consulta y lo convierte en una Consulta.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::consulta::parsear;
+/// let consulta = parsear("list B where alcance>2")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Argumentos
+///
+/// * texto: La consulta escrita en el mini lenguaje.
+///
+/// # Devuelve
+///
+/// Un Result con la Consulta o un Err(String) con la descripcion de lo que no se pudo entender.
+pub fn parsear(texto: &str) -> Result<Consulta, String> {
+    let palabras: Vec<&str> = texto.split_whitespace().collect();
+    match palabras.as_slice() {
+        ["count", patron] => Ok(Consulta::Contar(patron.to_string())),
+        ["list", patron] => Ok(Consulta::Listar(patron.to_string(), None)),
+        ["list", patron, "where", condicion] => Ok(Consulta::Listar(
+            patron.to_string(),
+            Some(parsear_condicion(condicion)?),
+        )),
+        ["at", coordenadas] => {
+            let (x, y) = coordenadas.split_once(',').ok_or_else(|| {
+                format!(
+                    "Error: coordenadas invalidas en la consulta: {}",
+                    coordenadas
+                )
+            })?;
+            match (x.trim().parse(), y.trim().parse()) {
+                (Ok(x), Ok(y)) => Ok(Consulta::En(Punto { x, y })),
+                _ => Err(format!(
+                    "Error: coordenadas invalidas en la consulta: {}",
+                    coordenadas
+                )),
+            }
+        }
+        _ => Err(format!("Error: consulta invalida: {}", texto)),
+    }
+}
+
+fn parsear_condicion(texto: &str) -> Result<Condicion, String> {
+    // Los operadores de dos caracteres van primero para que `>=` no se lea como `>`.
+    let operadores = [
+        ("<=", Operador::MenorIgual),
+        (">=", Operador::MayorIgual),
+        ("!=", Operador::Distinto),
+        ("<", Operador::Menor),
+        (">", Operador::Mayor),
+        ("=", Operador::Igual),
+    ];
+    for (simbolo, operador) in operadores {
+        if let Some((propiedad, valor)) = texto.split_once(simbolo) {
+            let propiedad = match propiedad {
+                "alcance" => Propiedad::Alcance,
+                "vida" => Propiedad::Vida,
+                _ => return Err(format!("Error: propiedad desconocida: {}", propiedad)),
+            };
+            let valor = valor
+                .parse()
+                .map_err(|_| format!("Error: valor invalido en la condicion: {}", valor))?;
+            return Ok(Condicion {
+                propiedad,
+                operador,
+                valor,
+            });
+        }
+    }
+    Err(format!("Error: condicion invalida: {}", texto))
+}
+
+/// Ejecuta una Consulta sobre un tablero y devuelve su resultado como lineas de texto.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// for linea in ejecutar(&Consulta::Contar("F*".to_string()), &tablero) {
+///     println!("{}", linea); // 3
+/// }
+/// ```
+///
+/// # Argumentos
+///
+/// * consulta: La Consulta a ejecutar.
+/// * tablero: El tablero sobre el que se consulta.
+///
+/// # Devuelve
+///
+/// Un Result con las lineas del resultado o un Err(String) si la consulta pide una casilla fuera del tablero.
+pub fn ejecutar(consulta: &Consulta, tablero: &[Vec<String>]) -> Result<Vec<String>, String> {
+    match consulta {
+        Consulta::Contar(patron) => Ok(vec![casillas_que_coinciden(tablero, patron, None)
+            .count()
+            .to_string()]),
+        Consulta::Listar(patron, condicion) => {
+            Ok(casillas_que_coinciden(tablero, patron, condicion.as_ref())
+                .map(|(punto, casilla)| format!("({}, {}): {}", punto.x, punto.y, casilla))
+                .collect())
+        }
+        Consulta::En(punto) => match tablero.get(punto.y).and_then(|fila| fila.get(punto.x)) {
+            Some(casilla) => Ok(vec![casilla.clone()]),
+            None => Err("Error: coordenadas invalidas".to_string()),
+        },
+    }
+}
+
+fn casillas_que_coinciden<'a>(
+    tablero: &'a [Vec<String>],
+    patron: &'a str,
+    condicion: Option<&'a Condicion>,
+) -> impl Iterator<Item = (Punto, &'a String)> + 'a {
+    tablero
+        .iter()
+        .enumerate()
+        .flat_map(|(y, fila)| {
+            fila.iter()
+                .enumerate()
+                .map(move |(x, casilla)| (Punto { x, y }, casilla))
+        })
+        .filter(move |(_, casilla)| coincide(casilla, patron))
+        .filter(move |(_, casilla)| condicion.is_none_or(|c| cumple(casilla, c)))
+}
+
+fn coincide(casilla: &str, patron: &str) -> bool {
+    let (casilla, _) = celda::separar_dato(casilla);
+    match patron {
+        "B" | "S" | "F" => casilla.starts_with(patron),
+        _ => match patron.strip_suffix('*') {
+            Some(prefijo) => casilla.starts_with(prefijo),
+            None => casilla == patron,
+        },
+    }
+}
+
+/// Una casilla cumple la condicion solo si tiene la propiedad pedida (las paredes no tienen alcance).
+fn cumple(casilla: &str, condicion: &Condicion) -> bool {
+    let (casilla, _) = celda::separar_dato(casilla);
+    let tipos: &[char] = match condicion.propiedad {
+        Propiedad::Alcance => &['B', 'S'],
+        Propiedad::Vida => &['F'],
+    };
+    if !casilla.starts_with(tipos) {
+        return false;
+    }
+    match casilla[1..].parse() {
+        Ok(valor) => condicion.operador.compara(valor, condicion.valor),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ejecutar, parsear};
+
+    fn tablero() -> Vec<Vec<String>> {
+        ["B1 F2 _", "S3 W F1", "DU B4 R"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect()
+    }
+
+    fn consultar(texto: &str) -> Vec<String> {
+        ejecutar(&parsear(texto).unwrap(), &tablero()).unwrap()
+    }
+
+    #[test]
+    fn test01_count_cuenta_por_prefijo() {
+        assert_eq!(consultar("count F*"), vec!["2"]);
+        assert_eq!(consultar("count D*"), vec!["1"]);
+        assert_eq!(consultar("count F2"), vec!["1"]);
+    }
+
+    #[test]
+    fn test02_list_filtra_por_propiedad() {
+        assert_eq!(consultar("list B where alcance>2"), vec!["(1, 2): B4"]);
+        assert_eq!(consultar("list S where alcance>=3"), vec!["(0, 1): S3"]);
+        assert_eq!(
+            consultar("list F where vida!=3"),
+            vec!["(1, 0): F2", "(2, 1): F1"]
+        );
+    }
+
+    #[test]
+    fn test03_at_devuelve_la_casilla() {
+        assert_eq!(consultar("at 0,2"), vec!["DU"]);
+        assert!(ejecutar(&parsear("at 5,5").unwrap(), &tablero()).is_err());
+    }
+
+    #[test]
+    fn test04_consultas_invalidas() {
+        assert!(parsear("count").is_err());
+        assert!(parsear("list B where color>2").is_err());
+        assert!(parsear("at x,1").is_err());
+    }
+}
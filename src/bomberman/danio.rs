@@ -0,0 +1,98 @@
+use super::punto::Punto;
+
+/// Un golpe de una rafaga a un enemigo, con lo que un ModeloDanio puede usar para decidir cuanta vida le quita.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Golpe<'a> {
+    /// Donde esta el enemigo.
+    pub punto: Punto,
+    /// La vida del enemigo antes del golpe.
+    pub vida: usize,
+    /// La bomba de la que salio la rafaga.
+    pub origen: Punto,
+    /// true si la rafaga es de una bomba de traspaso ('S').
+    pub traspaso: bool,
+    /// true si el enemigo es blindado (`G`). El motor no le pide el danio al modelo cuando lo golpea una bomba
+    /// normal, porque el blindaje lo protege, asi que en un Golpe a un blindado `traspaso` es siempre true.
+    pub blindado: bool,
+    /// El dato opaco de la casilla del enemigo, por ejemplo `tipo=blindado` en `F3{tipo=blindado}`.
+    pub dato: Option<&'a str>,
+}
+
+/// Decide cuanta vida le quita cada golpe a un enemigo, para probar variantes (golpes criticos, resistencias segun
+/// el tipo de enemigo) sin tocar el motor. Sigue valiendo que una bomba golpea una sola vez a cada enemigo y el tope
+/// de `Reglas::golpes_por_onda`, que cuenta golpes y no vidas.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// struct Blindados;
+///
+/// impl ModeloDanio for Blindados {
+///     fn danio(&self, golpe: &Golpe) -> usize {
+///         match golpe.dato {
+///             Some("tipo=blindado") if !golpe.traspaso => 0,
+///             _ => 1,
+///         }
+///     }
+/// }
+///
+/// let bomberman = SimulacionBuilder::new(tablero).modelo_danio(Arc::new(Blindados)).construir();
+/// ```
+pub trait ModeloDanio: Send + Sync {
+    /// Devuelve cuanta vida le quita el golpe al enemigo. Con 0 el enemigo no pierde vida, y si es mayor o igual a
+    /// su vida el enemigo muere.
+    fn danio(&self, golpe: &Golpe) -> usize;
+}
+
+/// El modelo de la consigna, que se usa por defecto: cada golpe quita una vida.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DanioUnitario;
+
+impl ModeloDanio for DanioUnitario {
+    fn danio(&self, _golpe: &Golpe) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Golpe, ModeloDanio};
+    use crate::bomberman::evento::Evento;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::simulacion::SimulacionBuilder;
+    use crate::bomberman::tablero::Tablero;
+
+    /// Las rafagas de traspaso quitan dos vidas y los enemigos blindados resisten las bombas normales.
+    struct Experimental;
+
+    impl ModeloDanio for Experimental {
+        fn danio(&self, golpe: &Golpe) -> usize {
+            match (golpe.dato, golpe.traspaso) {
+                (Some("tipo=blindado"), false) => 0,
+                (_, true) => 2,
+                _ => 1,
+            }
+        }
+    }
+
+    #[test]
+    fn test01_un_modelo_propio_cambia_el_danio() {
+        let tablero =
+            Tablero::desde_texto("S2 F3 B1\nF3{tipo=blindado} _ F1{tipo=blindado}").unwrap();
+        let salida = SimulacionBuilder::new(tablero)
+            .modelo_danio(Arc::new(Experimental))
+            .construir()
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(
+            salida.tablero.to_texto(),
+            "_ _ _\nF1{tipo=blindado} _ F1{tipo=blindado}"
+        );
+        assert!(salida.eventos.contains(&Evento::EnemigoDaniado {
+            punto: Punto { x: 2, y: 1 },
+            vida: 1
+        }));
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use super::celda::Celda;
+use super::reglas::Reglas;
+use super::ubicacion::{self, Ubicacion};
+
+/// Lo que tiene de malo una casilla o una fila de un tablero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problema {
+    /// El texto no es ninguna Celda.
+    CasillaDesconocida,
+    /// Un enemigo con mas vida que `Reglas::vida_maxima`.
+    VidaExcesiva { vida: usize, maxima: usize },
+    /// Una bomba o mina con mas alcance que `Reglas::alcance_maximo`.
+    AlcanceExcesivo { alcance: usize, maximo: usize },
+    /// Un ladrillo sin `Reglas::ladrillos`.
+    LadrilloSinRegla,
+    /// Una mejora sin `Reglas::mejoras`.
+    MejoraSinRegla,
+    /// Un portal cuyo numero no aparece exactamente dos veces en el tablero.
+    PortalSinPareja { apariciones: usize },
+    /// La fila no tiene tantas casillas como la primera.
+    FilaIrregular { casillas: usize, esperadas: usize },
+}
+
+/// Un problema de un tablero, con su posicion entre las casillas (contando desde 1, sin las lineas vacias ni de
+/// comentario, como en `BombermanError::ArchivoInvalido`) y el texto de la casilla. Para una fila irregular, la
+/// columna es 1 y el texto es el de toda la fila.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostico {
+    pub linea: usize,
+    pub columna: usize,
+    pub texto: String,
+    pub problema: Problema,
+    /// Donde esta en el texto, si el tablero se leyo de uno.
+    pub ubicacion: Option<Ubicacion>,
+}
+
+/// Se escribe como `linea 2, columna 3 (en el texto 4:5): F7 tiene 7 de vida y la maxima es 3`.
+impl std::fmt::Display for Diagnostico {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "linea {}, columna {}", self.linea, self.columna)?;
+        if let Some(ubicacion) = &self.ubicacion {
+            write!(f, " (en el texto {})", ubicacion)?;
+        }
+        match &self.problema {
+            Problema::CasillaDesconocida => write!(f, ": {} no es una casilla", self.texto),
+            Problema::VidaExcesiva { vida, maxima } => write!(
+                f,
+                ": {} tiene {} de vida y la maxima es {}",
+                self.texto, vida, maxima
+            ),
+            Problema::AlcanceExcesivo { alcance, maximo } => write!(
+                f,
+                ": {} tiene alcance {} y el maximo es {}",
+                self.texto, alcance, maximo
+            ),
+            Problema::LadrilloSinRegla => write!(
+                f,
+                ": {} es un ladrillo y la regla de ladrillos no esta activada",
+                self.texto
+            ),
+            Problema::MejoraSinRegla => write!(
+                f,
+                ": {} es una mejora y la regla de mejoras no esta activada",
+                self.texto
+            ),
+            Problema::PortalSinPareja { apariciones } => write!(
+                f,
+                ": {} aparece {} {} y cada portal tiene que aparecer 2",
+                self.texto,
+                apariciones,
+                if *apariciones == 1 { "vez" } else { "veces" }
+            ),
+            Problema::FilaIrregular {
+                casillas,
+                esperadas,
+            } => write!(
+                f,
+                ": la fila tiene {} casillas y la primera tiene {}",
+                casillas, esperadas
+            ),
+        }
+    }
+}
+
+/// Revisa todas las casillas de un tablero con las reglas dadas y junta todos sus problemas, en lugar de frenar en
+/// el primero como `tablero_desde_archivo_con_reglas`, para poder corregirlos de una vez.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::diagnostico::diagnosticar;
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let problemas = diagnosticar("B1 Q\nF300 _", &Reglas::default());
+/// assert_eq!(problemas[0].to_string(), "linea 1, columna 2 (en el texto 1:4): Q no es una casilla");
+/// assert_eq!(problemas[1].to_string(), "linea 2, columna 1 (en el texto 2:1): F300 tiene 300 de vida y la maxima es 255");
+/// ```
+///
+/// # Argumentos
+///
+/// * texto: El contenido de un archivo de tablero.
+/// * reglas: Las reglas con los topes de alcance y vida.
+///
+/// # Devuelve
+///
+/// Los problemas en el orden del texto, con su Ubicacion; si no hay ninguno, el tablero es valido.
+pub fn diagnosticar(texto: &str, reglas: &Reglas) -> Vec<Diagnostico> {
+    let casillas = ubicacion::casillas_con_ubicacion(texto);
+    let filas: Vec<Vec<String>> = casillas
+        .iter()
+        .map(|fila| {
+            fila.iter()
+                .map(|(casilla, _)| casilla.to_string())
+                .collect()
+        })
+        .collect();
+    let mut problemas = diagnosticar_filas(&filas, reglas);
+    for problema in &mut problemas {
+        problema.ubicacion = Some(casillas[problema.linea - 1][problema.columna - 1].1);
+    }
+    problemas
+}
+
+/// Como `diagnosticar`, pero con las filas ya separadas en casillas, por ejemplo las de un tablero JSON. Los
+/// Diagnosticos no tienen Ubicacion.
+pub fn diagnosticar_filas(filas: &[Vec<String>], reglas: &Reglas) -> Vec<Diagnostico> {
+    let esperadas = filas.first().map_or(0, Vec::len);
+    let mut portales: HashMap<usize, usize> = HashMap::new();
+    for casilla in filas.iter().flatten() {
+        if let Some(Celda::Portal { numero }) = Celda::parsear(casilla) {
+            *portales.entry(numero).or_default() += 1;
+        }
+    }
+    let mut problemas = Vec::new();
+    for (y, fila) in filas.iter().enumerate() {
+        if fila.len() != esperadas {
+            problemas.push(Diagnostico {
+                linea: y + 1,
+                columna: 1,
+                texto: fila.join(" "),
+                problema: Problema::FilaIrregular {
+                    casillas: fila.len(),
+                    esperadas,
+                },
+                ubicacion: None,
+            });
+        }
+        for (x, casilla) in fila.iter().enumerate() {
+            let sin_pareja = match Celda::parsear(casilla) {
+                Some(Celda::Portal { numero }) if portales[&numero] != 2 => {
+                    Some(Problema::PortalSinPareja {
+                        apariciones: portales[&numero],
+                    })
+                }
+                _ => None,
+            };
+            if let Some(problema) = problema_de(casilla, reglas).or(sin_pareja) {
+                problemas.push(Diagnostico {
+                    linea: y + 1,
+                    columna: x + 1,
+                    texto: casilla.clone(),
+                    problema,
+                    ubicacion: None,
+                });
+            }
+        }
+    }
+    problemas
+}
+
+/// El problema de una casilla con las reglas dadas, o None si es valida (lo mismo que `io::es_casilla_valida`). Los
+/// portales sin pareja se revisan aparte, porque dependen de las demas casillas.
+fn problema_de(casilla: &str, reglas: &Reglas) -> Option<Problema> {
+    match Celda::parsear(casilla) {
+        Some(Celda::Enemigo { vida, .. }) if vida > reglas.vida_maxima => {
+            Some(Problema::VidaExcesiva {
+                vida,
+                maxima: reglas.vida_maxima,
+            })
+        }
+        Some(Celda::Bomba(bomba)) => reglas
+            .alcance_maximo
+            .filter(|maximo| bomba.alcance_maximo() > *maximo)
+            .map(|maximo| Problema::AlcanceExcesivo {
+                alcance: bomba.alcance_maximo(),
+                maximo,
+            }),
+        Some(Celda::Mina { alcance }) => reglas
+            .alcance_maximo
+            .filter(|maximo| alcance > *maximo)
+            .map(|maximo| Problema::AlcanceExcesivo { alcance, maximo }),
+        Some(Celda::Ladrillo) if !reglas.ladrillos => Some(Problema::LadrilloSinRegla),
+        Some(Celda::Mejora(_)) if !reglas.mejoras => Some(Problema::MejoraSinRegla),
+        Some(_) => None,
+        None => Some(Problema::CasillaDesconocida),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diagnosticar, diagnosticar_filas, Problema};
+    use crate::bomberman::io::es_casilla_valida;
+    use crate::bomberman::reglas::Reglas;
+
+    #[test]
+    fn test01_junta_todos_los_problemas() {
+        let reglas = Reglas {
+            alcance_maximo: Some(3),
+            vida_maxima: 3,
+            ..Reglas::default()
+        };
+        let texto = "# nivel\nB1 Q F4\nB9{id=1} _\n_ X ?";
+        let lineas: Vec<String> = diagnosticar(texto, &reglas)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lineas,
+            [
+                "linea 1, columna 2 (en el texto 2:4): Q no es una casilla",
+                "linea 1, columna 3 (en el texto 2:6): F4 tiene 4 de vida y la maxima es 3",
+                "linea 2, columna 1 (en el texto 3:1): la fila tiene 2 casillas y la primera tiene 3",
+                "linea 2, columna 1 (en el texto 3:1): B9{id=1} tiene alcance 9 y el maximo es 3",
+                "linea 3, columna 2 (en el texto 4:3): X es un ladrillo y la regla de ladrillos no esta activada",
+                "linea 3, columna 3 (en el texto 4:5): ? no es una casilla",
+            ]
+        );
+        assert!(diagnosticar("B1 _\nW F3", &reglas).is_empty());
+        let con_ladrillos = Reglas {
+            ladrillos: true,
+            ..reglas
+        };
+        assert!(diagnosticar("B1 X\nW F3", &con_ladrillos).is_empty());
+        let portales: Vec<String> = diagnosticar("P1 P2 P1\nP3 P3 P3", &reglas)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            portales,
+            [
+                "linea 1, columna 2 (en el texto 1:4): P2 aparece 1 vez y cada portal tiene que aparecer 2",
+                "linea 2, columna 1 (en el texto 2:1): P3 aparece 3 veces y cada portal tiene que aparecer 2",
+                "linea 2, columna 2 (en el texto 2:4): P3 aparece 3 veces y cada portal tiene que aparecer 2",
+                "linea 2, columna 3 (en el texto 2:7): P3 aparece 3 veces y cada portal tiene que aparecer 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test02_coincide_con_es_casilla_valida() {
+        let reglas = Reglas {
+            alcance_maximo: Some(2),
+            vida_maxima: 2,
+            ..Reglas::default()
+        };
+        let casillas = [
+            "_", "W", "R", "X", "DU", "DX", "F2", "F3", "B2", "S3", "B[U1,R3]", "B0", "M2", "M3",
+            "+R",
+        ];
+        let filas = vec![casillas.iter().map(ToString::to_string).collect()];
+        let invalidas: Vec<usize> = diagnosticar_filas(&filas, &reglas)
+            .iter()
+            .map(|diagnostico| diagnostico.columna - 1)
+            .collect();
+        let esperadas: Vec<usize> = (0..casillas.len())
+            .filter(|&i| !es_casilla_valida(casillas[i], &reglas))
+            .collect();
+        assert_eq!(invalidas, esperadas);
+        let problemas: Vec<Problema> = diagnosticar_filas(&filas, &reglas)
+            .into_iter()
+            .map(|diagnostico| diagnostico.problema)
+            .collect();
+        assert_eq!(
+            problemas,
+            [
+                Problema::LadrilloSinRegla,
+                Problema::CasillaDesconocida,
+                Problema::VidaExcesiva { vida: 3, maxima: 2 },
+                Problema::AlcanceExcesivo {
+                    alcance: 3,
+                    maximo: 2
+                },
+                Problema::AlcanceExcesivo {
+                    alcance: 3,
+                    maximo: 2
+                },
+                Problema::CasillaDesconocida,
+                Problema::AlcanceExcesivo {
+                    alcance: 3,
+                    maximo: 2
+                },
+                Problema::MejoraSinRegla,
+            ]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use super::punto::Punto;
+
+/// Una de las direcciones en las que avanza una rafaga. Se escribe con su letra: 'L', 'U', 'R' o 'D', o con dos
+/// para las diagonales: `UL`, `UR`, `DL` o `DR`. Las bombas solo lanzan rafagas en las 4 de `TODAS`; una rafaga
+/// solo avanza en diagonal despues de pasar por un desvio diagonal. En un tablero hexagonal se usan las 6 de
+/// `hexagonal::DIRECCIONES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direccion {
+    Izquierda,
+    Arriba,
+    Derecha,
+    Abajo,
+    ArribaIzquierda,
+    ArribaDerecha,
+    AbajoIzquierda,
+    AbajoDerecha,
+}
+
+impl Direccion {
+    /// Las 4 direcciones en el orden en que una bomba lanza sus rafagas.
+    pub const TODAS: [Direccion; 4] = [
+        Direccion::Izquierda,
+        Direccion::Arriba,
+        Direccion::Derecha,
+        Direccion::Abajo,
+    ];
+
+    /// Las 4 diagonales, en las que puede seguir una rafaga despues de un desvio diagonal.
+    pub const DIAGONALES: [Direccion; 4] = [
+        Direccion::ArribaIzquierda,
+        Direccion::ArribaDerecha,
+        Direccion::AbajoIzquierda,
+        Direccion::AbajoDerecha,
+    ];
+
+    /// Recorre las 4 direcciones en el orden de `TODAS`.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// for direccion in Direccion::todas() {
+    ///     println!("{}", direccion); // L, U, R, D
+    /// }
+    /// ```
+    pub fn todas() -> impl Iterator<Item = Direccion> {
+        Self::TODAS.into_iter()
+    }
+
+    /// Interpreta la letra de una de las direcciones de `TODAS` ('L', 'U', 'R' o 'D'), o devuelve None si no es
+    /// ninguna.
+    pub fn desde_letra(letra: char) -> Option<Direccion> {
+        match letra {
+            'L' => Some(Direccion::Izquierda),
+            'U' => Some(Direccion::Arriba),
+            'R' => Some(Direccion::Derecha),
+            'D' => Some(Direccion::Abajo),
+            _ => None,
+        }
+    }
+
+    /// Las letras con las que se escribe la direccion en los tableros: una para las de `TODAS` y dos para las
+    /// diagonales.
+    pub fn letras(self) -> &'static str {
+        match self {
+            Direccion::Izquierda => "L",
+            Direccion::Arriba => "U",
+            Direccion::Derecha => "R",
+            Direccion::Abajo => "D",
+            Direccion::ArribaIzquierda => "UL",
+            Direccion::ArribaDerecha => "UR",
+            Direccion::AbajoIzquierda => "DL",
+            Direccion::AbajoDerecha => "DR",
+        }
+    }
+
+    /// Cuanto cambian la columna y la fila con un paso en esta direccion, cada una entre -1 y 1.
+    pub fn componentes(self) -> (isize, isize) {
+        match self {
+            Direccion::Izquierda => (-1, 0),
+            Direccion::Arriba => (0, -1),
+            Direccion::Derecha => (1, 0),
+            Direccion::Abajo => (0, 1),
+            Direccion::ArribaIzquierda => (-1, -1),
+            Direccion::ArribaDerecha => (1, -1),
+            Direccion::AbajoIzquierda => (-1, 1),
+            Direccion::AbajoDerecha => (1, 1),
+        }
+    }
+
+    /// La direccion con los componentes dados (ver `componentes`), o None si son (0, 0) o alguno no esta entre -1 y 1.
+    pub fn desde_componentes(dx: isize, dy: isize) -> Option<Direccion> {
+        Self::TODAS
+            .into_iter()
+            .chain(Self::DIAGONALES)
+            .find(|direccion| direccion.componentes() == (dx, dy))
+    }
+
+    /// true para las 4 diagonales.
+    pub fn es_diagonal(self) -> bool {
+        Self::DIAGONALES.contains(&self)
+    }
+
+    /// La direccion contraria.
+    pub fn opuesta(self) -> Direccion {
+        let (dx, dy) = self.componentes();
+        Self::desde_componentes(-dx, -dy).expect("la opuesta de una direccion es otra direccion")
+    }
+
+    /// La posicion de la direccion en `TODAS`, para indexar arreglos con un valor por direccion. Las diagonales no
+    /// estan en `TODAS` y dan un indice de 4 en adelante.
+    pub fn indice(self) -> usize {
+        self as usize
+    }
+
+    /// Calcula la casilla vecina a un punto en esta direccion, sin salirse de los limites de `usize`. En diagonal la
+    /// vecina es la de la esquina, que cambia la columna y la fila a la vez.
+    ///
+    /// Los movimientos hacia arriba desde la fila 0 o hacia la izquierda desde la columna 0 devuelven None en lugar de
+    /// dar la vuelta a un valor enorme, asi que quien lo use no depende de que ese valor falle el chequeo de rango.
+    /// Los movimientos que pasan el borde inferior o derecho si devuelven un punto, que el tablero tiene que rechazar.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// Direccion::Izquierda.aplicar(Punto { x: 0, y: 3 }); // None
+    /// Direccion::Derecha.aplicar(Punto { x: 0, y: 3 }); // Some(Punto { x: 1, y: 3 })
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con el punto vecino, o None si no hay casilla posible en esa direccion.
+    pub fn aplicar(self, punto: Punto) -> Option<Punto> {
+        let (dx, dy) = self.componentes();
+        Some(Punto {
+            x: punto.x.checked_add_signed(dx)?,
+            y: punto.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl std::fmt::Display for Direccion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.letras())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direccion;
+    use crate::bomberman::punto::Punto;
+
+    #[test]
+    fn test01_vecinos_en_las_cuatro_direcciones() {
+        let punto = Punto { x: 2, y: 2 };
+        assert_eq!(Direccion::Arriba.aplicar(punto), Some(Punto { x: 2, y: 1 }));
+        assert_eq!(Direccion::Abajo.aplicar(punto), Some(Punto { x: 2, y: 3 }));
+        assert_eq!(
+            Direccion::Izquierda.aplicar(punto),
+            Some(Punto { x: 1, y: 2 })
+        );
+        assert_eq!(
+            Direccion::Derecha.aplicar(punto),
+            Some(Punto { x: 3, y: 2 })
+        );
+    }
+
+    #[test]
+    fn test02_no_hay_vecino_antes_del_origen() {
+        let origen = Punto { x: 0, y: 0 };
+        assert_eq!(Direccion::Arriba.aplicar(origen), None);
+        assert_eq!(Direccion::Izquierda.aplicar(origen), None);
+        let borde = Punto {
+            x: usize::MAX,
+            y: 0,
+        };
+        assert_eq!(Direccion::Derecha.aplicar(borde), None);
+    }
+
+    #[test]
+    fn test03_letras_opuestas_e_indices() {
+        for (indice, direccion) in Direccion::todas().enumerate() {
+            let letra = direccion.letras().chars().next().unwrap();
+            assert_eq!(Direccion::desde_letra(letra), Some(direccion));
+            assert_eq!(direccion.opuesta().opuesta(), direccion);
+            assert_ne!(direccion.opuesta(), direccion);
+            assert_eq!(direccion.indice(), indice);
+        }
+        assert_eq!(Direccion::desde_letra('X'), None);
+    }
+
+    #[test]
+    fn test04_diagonales() {
+        let punto = Punto { x: 2, y: 2 };
+        assert_eq!(
+            Direccion::ArribaDerecha.aplicar(punto),
+            Some(Punto { x: 3, y: 1 })
+        );
+        assert_eq!(
+            Direccion::AbajoIzquierda.aplicar(punto),
+            Some(Punto { x: 1, y: 3 })
+        );
+        assert_eq!(
+            Direccion::ArribaIzquierda.aplicar(Punto { x: 3, y: 0 }),
+            None
+        );
+        for direccion in Direccion::DIAGONALES {
+            assert!(direccion.es_diagonal());
+            assert_eq!(direccion.opuesta().opuesta(), direccion);
+            assert_eq!(direccion.letras().len(), 2);
+        }
+        assert_eq!(
+            Direccion::ArribaIzquierda.opuesta(),
+            Direccion::AbajoDerecha
+        );
+        assert_eq!(Direccion::desde_componentes(0, 0), None);
+    }
+}
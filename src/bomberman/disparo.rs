@@ -0,0 +1,183 @@
+use super::direccion::Direccion;
+use super::punto::Punto;
+
+/// Una bomba que hizo explotar a otra: la rafaga que salio de `origen` llego a `destino` yendo en `direccion`
+/// despues de recorrer `distancia` casillas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disparo {
+    pub origen: Punto,
+    pub destino: Punto,
+    /// La direccion de la rafaga al llegar, que puede no ser la inicial si paso por desvios.
+    pub direccion: Direccion,
+    pub distancia: usize,
+}
+
+/// Un nodo del arbol de disparos: la bomba inicial es la raiz y los hijos de cada bomba son las que hizo explotar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodoDisparo {
+    pub punto: Punto,
+    /// Direccion y distancia de la rafaga que la hizo explotar; None para la raiz.
+    pub llegada: Option<(Direccion, usize)>,
+    pub hijos: Vec<NodoDisparo>,
+}
+
+impl NodoDisparo {
+    /// Arma el arbol de disparos de una reaccion en cadena.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let salida = Bomberman::new(tablero).simular(Columna(0), Fila(0))?;
+    /// let arbol = NodoDisparo::arbol(Punto { x: 0, y: 0 }, &salida.disparos);
+    /// println!("{}", arbol.profundidad());
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * raiz: La bomba que se detono primero.
+    /// * disparos: Los disparos de la simulacion, en el orden en que ocurrieron.
+    pub fn arbol(raiz: Punto, disparos: &[Disparo]) -> NodoDisparo {
+        Self::nodo(raiz, None, disparos)
+    }
+
+    fn nodo(
+        punto: Punto,
+        llegada: Option<(Direccion, usize)>,
+        disparos: &[Disparo],
+    ) -> NodoDisparo {
+        NodoDisparo {
+            punto,
+            llegada,
+            hijos: disparos
+                .iter()
+                .filter(|disparo| disparo.origen == punto)
+                .map(|disparo| {
+                    Self::nodo(
+                        disparo.destino,
+                        Some((disparo.direccion, disparo.distancia)),
+                        disparos,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// La cantidad de niveles del arbol: 1 si la bomba inicial no hizo explotar a ninguna otra.
+    pub fn profundidad(&self) -> usize {
+        1 + self
+            .hijos
+            .iter()
+            .map(NodoDisparo::profundidad)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Escribe el arbol como un objeto JSON, por ejemplo
+    /// `{"x":0,"y":0,"hijos":[{"x":0,"y":2,"direccion":"D","distancia":2,"hijos":[]}]}`.
+    pub fn a_json(&self) -> String {
+        let llegada = match self.llegada {
+            Some((direccion, distancia)) => {
+                format!(
+                    ",\"direccion\":\"{}\",\"distancia\":{}",
+                    direccion, distancia
+                )
+            }
+            None => String::new(),
+        };
+        let hijos: Vec<String> = self.hijos.iter().map(NodoDisparo::a_json).collect();
+        format!(
+            "{{\"x\":{},\"y\":{}{},\"hijos\":[{}]}}",
+            self.punto.x,
+            self.punto.y,
+            llegada,
+            hijos.join(",")
+        )
+    }
+
+    /// Escribe el arbol como un grafo de Graphviz, con un nodo por bomba y una flecha desde cada bomba hacia las que
+    /// hizo explotar, con la direccion y la distancia de la rafaga. La bomba inicial se dibuja con doble borde. Se
+    /// puede dibujar con `dot -Tsvg cadena.dot -o cadena.svg`.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```text
+    /// digraph cadena {
+    ///   "0,0" [label="(0, 0)", peripheries=2];
+    ///   "2,0" [label="(2, 0)"];
+    ///   "0,0" -> "2,0" [label="R 2"];
+    /// }
+    /// ```
+    pub fn a_dot(&self) -> String {
+        let mut nodos = Vec::new();
+        let mut flechas = Vec::new();
+        self.lineas_dot(true, &mut nodos, &mut flechas);
+        format!(
+            "digraph cadena {{\n{}\n{}}}\n",
+            nodos.join("\n"),
+            flechas
+                .iter()
+                .map(|flecha| format!("{}\n", flecha))
+                .collect::<String>()
+        )
+    }
+
+    /// Agrega la linea de este nodo y las de la flecha hacia cada hijo, y recorre los hijos.
+    fn lineas_dot(&self, raiz: bool, nodos: &mut Vec<String>, flechas: &mut Vec<String>) {
+        let id = |punto: Punto| format!("\"{},{}\"", punto.x, punto.y);
+        nodos.push(format!(
+            "  {} [label=\"({}, {})\"{}];",
+            id(self.punto),
+            self.punto.x,
+            self.punto.y,
+            if raiz { ", peripheries=2" } else { "" }
+        ));
+        for hijo in &self.hijos {
+            if let Some((direccion, distancia)) = hijo.llegada {
+                flechas.push(format!(
+                    "  {} -> {} [label=\"{} {}\"];",
+                    id(self.punto),
+                    id(hijo.punto),
+                    direccion,
+                    distancia
+                ));
+            }
+            hijo.lineas_dot(false, nodos, flechas);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodoDisparo;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_arbol_de_una_cadena() {
+        let filas = ["B2 _ B1", "_ _ _", "B1 _ _"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect();
+        let salida = match Bomberman::new(Tablero::desde_filas(filas).unwrap())
+            .simular(Columna(0), Fila(0))
+        {
+            Ok(salida) => salida,
+            Err(e) => panic!("{}", e),
+        };
+        let arbol = NodoDisparo::arbol(Punto { x: 0, y: 0 }, &salida.disparos);
+        assert_eq!(arbol.profundidad(), 2);
+        assert_eq!(
+            arbol.a_json(),
+            "{\"x\":0,\"y\":0,\"hijos\":[\
+             {\"x\":2,\"y\":0,\"direccion\":\"R\",\"distancia\":2,\"hijos\":[]},\
+             {\"x\":0,\"y\":2,\"direccion\":\"D\",\"distancia\":2,\"hijos\":[]}]}"
+        );
+        assert_eq!(
+            arbol.a_dot(),
+            "digraph cadena {\n  \"0,0\" [label=\"(0, 0)\", peripheries=2];\n  \"2,0\" [label=\"(2, 0)\"];\n  \
+             \"0,2\" [label=\"(0, 2)\"];\n  \"0,0\" -> \"2,0\" [label=\"R 2\"];\n  \
+             \"0,0\" -> \"0,2\" [label=\"D 2\"];\n}\n"
+        );
+    }
+}
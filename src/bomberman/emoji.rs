@@ -0,0 +1,128 @@
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// Dibuja el tablero para mostrarlo en una terminal, con un recuadro de lineas y un emoji por tipo de casilla: 🧱
+/// pared, 🪨 roca, 🟫 ladrillo, 💣 bomba, 💥 bomba de traspaso, 🕒 mecha, 👾 enemigo, 🤖 enemigo blindado, 🧨 mina,
+/// 🌀 portal, 🎁 mejora, 🧑 jugador, una flecha por desvio y `·` las vacias. Los numeros (la vida, el alcance, la
+/// mecha) siguen al emoji, y los datos opacos no se muestran. No se puede volver a leer como tablero: para eso esta
+/// `Tablero::escribir`.
+///
+/// # Ejemplos
+///
+/// ```text
+/// print!("{}", emoji::dibujar(&Tablero::desde_texto("B2 W F1\n_ DR R")?));
+/// ┌─────┬────┬─────┐
+/// │ 💣2 │ 🧱 │ 👾1 │
+/// ├─────┼────┼─────┤
+/// │ ·   │ →  │ 🪨  │
+/// └─────┴────┴─────┘
+/// ```
+pub fn dibujar(tablero: &Tablero) -> String {
+    let filas: Vec<Vec<String>> = (0..tablero.alto())
+        .map(|y| {
+            (0..tablero.ancho())
+                .map(|x| tablero.get(Punto { x, y }).map_or(String::new(), casilla))
+                .collect()
+        })
+        .collect();
+    let anchos: Vec<usize> = (0..tablero.ancho())
+        .map(|x| filas.iter().map(|fila| ancho(&fila[x])).max().unwrap_or(0))
+        .collect();
+    let linea = |izquierda: &str, medio: &str, derecha: &str| {
+        let tramos: Vec<String> = anchos.iter().map(|a| "─".repeat(a + 2)).collect();
+        format!("{}{}{}", izquierda, tramos.join(medio), derecha)
+    };
+    let mut lineas = vec![linea("┌", "┬", "┐")];
+    for (y, fila) in filas.iter().enumerate() {
+        if y > 0 {
+            lineas.push(linea("├", "┼", "┤"));
+        }
+        let casillas: Vec<String> = fila
+            .iter()
+            .zip(&anchos)
+            .map(|(casilla, a)| format!(" {}{} ", casilla, " ".repeat(a - ancho(casilla))))
+            .collect();
+        lineas.push(format!("│{}│", casillas.join("│")));
+    }
+    lineas.push(linea("└", "┴", "┘"));
+    lineas.join("\n")
+}
+
+/// El dibujo de una casilla.
+fn casilla(celda: &Celda) -> String {
+    match celda {
+        Celda::Vacio => "·".to_string(),
+        Celda::Pared => "🧱".to_string(),
+        Celda::Roca => "🪨".to_string(),
+        Celda::Ladrillo => "🟫".to_string(),
+        Celda::Desvio(direccion) => flecha(*direccion).to_string(),
+        Celda::Enemigo {
+            vida,
+            blindado: false,
+        } => format!("👾{}", vida),
+        Celda::Enemigo {
+            vida,
+            blindado: true,
+        } => format!("🤖{}", vida),
+        // `T3`, `B2`, `S1`, `B[L0,U2,R5,D0]`, `T3S2`: las letras de los alcances por direccion no son T, B ni S.
+        Celda::Bomba(bomba) => bomba
+            .to_string()
+            .replacen('T', "🕒", 1)
+            .replacen('B', "💣", 1)
+            .replacen('S', "💥", 1),
+        Celda::Mina { alcance } => format!("🧨{}", alcance),
+        Celda::Portal { numero } => format!("🌀{}", numero),
+        Celda::Jugador => "🧑".to_string(),
+        Celda::Mejora(_) => format!("🎁{}", celda.to_string().trim_start_matches('+')),
+    }
+}
+
+/// La flecha que apunta hacia la direccion de un desvio.
+fn flecha(direccion: Direccion) -> char {
+    match direccion {
+        Direccion::Izquierda => '←',
+        Direccion::Arriba => '↑',
+        Direccion::Derecha => '→',
+        Direccion::Abajo => '↓',
+        Direccion::ArribaIzquierda => '↖',
+        Direccion::ArribaDerecha => '↗',
+        Direccion::AbajoIzquierda => '↙',
+        Direccion::AbajoDerecha => '↘',
+    }
+}
+
+/// Cuantas columnas de la terminal ocupa el texto de una casilla: 2 los emoji y 1 los demas caracteres, que son
+/// ASCII, flechas o `·`.
+fn ancho(texto: &str) -> usize {
+    texto
+        .chars()
+        .map(|c| if u32::from(c) >= 0x1f000 { 2 } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dibujar;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_dibuja_cada_casilla_con_su_emoji() {
+        let tablero = Tablero::desde_texto("B2 W F1{id=a}\n_ DR R\nS1 B[U2,R5] G3").unwrap();
+        assert_eq!(
+            dibujar(&tablero),
+            "┌─────┬─────────────────┬─────┐\n\
+             │ 💣2 │ 🧱              │ 👾1 │\n\
+             ├─────┼─────────────────┼─────┤\n\
+             │ ·   │ →               │ 🪨  │\n\
+             ├─────┼─────────────────┼─────┤\n\
+             │ 💥1 │ 💣[L0,U2,R5,D0] │ 🤖3 │\n\
+             └─────┴─────────────────┴─────┘"
+        );
+        assert_eq!(
+            dibujar(&Tablero::desde_texto("T3 M2 P1 P1").unwrap()),
+            "┌─────┬─────┬─────┬─────┐\n│ 🕒3 │ 🧨2 │ 🌀1 │ 🌀1 │\n└─────┴─────┴─────┴─────┘"
+        );
+    }
+}
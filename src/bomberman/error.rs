@@ -0,0 +1,301 @@
+use std::io;
+
+use super::idioma::Idioma;
+use super::ubicacion::Ubicacion;
+
+/// Un error que impide terminar una lectura o una simulacion.
+///
+/// El texto de cada variante (su `Display`) es el que se escribe en el archivo de salida, asi que tiene que
+/// mantenerse igual aunque cambien los datos que lleva. Es el texto en castellano: para otro idioma esta
+/// `mensaje`.
+#[derive(Debug)]
+pub enum BombermanError {
+    /// La casilla en la linea y columna dadas (contando desde 1, sin las lineas vacias ni de comentario) no es valida.
+    /// Si se leyo de un texto, la Ubicacion dice donde esta la casilla en el.
+    ArchivoInvalido {
+        linea: usize,
+        columna: usize,
+        ubicacion: Option<Ubicacion>,
+    },
+    /// La fila de la linea dada (contando como en ArchivoInvalido) tiene una cantidad de casillas distinta a la de la
+    /// primera fila: el tablero puede no ser cuadrado, pero tiene que ser rectangular. Si se leyo de un texto, la
+    /// Ubicacion es la de la primera casilla de la fila.
+    FilaIrregular {
+        linea: usize,
+        casillas: usize,
+        esperadas: usize,
+        ubicacion: Option<Ubicacion>,
+    },
+    /// El portal de la linea y columna dadas (contando como en ArchivoInvalido) no aparece exactamente dos veces en
+    /// el tablero; la casilla es la primera de ese portal. Si se leyo de un texto, la Ubicacion dice donde esta.
+    PortalSinPareja {
+        linea: usize,
+        columna: usize,
+        apariciones: usize,
+        ubicacion: Option<Ubicacion>,
+    },
+    /// En las coordenadas dadas no hay una bomba, o estan fuera del tablero.
+    CoordenadasInvalidas { x: usize, y: usize },
+    /// No se pudo abrir el archivo de entrada.
+    Apertura(io::Error),
+    /// No se pudo leer el archivo de entrada.
+    Io(io::Error),
+    /// Se supero `Limites::cadena`.
+    LimiteCadena,
+    /// Se supero `Limites::pasos`.
+    LimitePasos,
+    /// Se supero `Limites::tiempo`.
+    LimiteTiempo,
+    /// El tablero tiene mas casillas que `Limites::casillas`.
+    LimiteTablero { casillas: usize, maximo: usize },
+    /// La entrada de `--format json` no es un documento JSON, o no tiene la forma de un tablero. Si es un problema
+    /// de sintaxis, la posicion es el byte del texto en el que se encontro.
+    JsonInvalido {
+        posicion: Option<usize>,
+        causa: String,
+    },
+    /// Uno de los hilos del `Analizador` entro en panico.
+    HiloDeAnalisis,
+}
+
+impl BombermanError {
+    /// El texto del error en el idioma dado. En castellano es el mismo de `Display`.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let error = BombermanError::CoordenadasInvalidas { x: 9, y: 9 };
+    /// assert_eq!(error.mensaje(Idioma::En), "Error: invalid coordinates");
+    /// ```
+    pub fn mensaje(&self, idioma: Idioma) -> &'static str {
+        idioma.texto(match self {
+            BombermanError::ArchivoInvalido { .. } => "archivo_invalido",
+            BombermanError::FilaIrregular { .. } => "fila_irregular",
+            BombermanError::PortalSinPareja { .. } => "portal_sin_pareja",
+            BombermanError::CoordenadasInvalidas { .. } => "coordenadas_invalidas",
+            BombermanError::Apertura(_) => "apertura",
+            BombermanError::Io(_) => "io",
+            BombermanError::LimiteCadena => "limite_cadena",
+            BombermanError::LimitePasos => "limite_pasos",
+            BombermanError::LimiteTiempo => "limite_tiempo",
+            BombermanError::LimiteTablero { .. } => "limite_tablero",
+            BombermanError::JsonInvalido { .. } => "json_invalido",
+            BombermanError::HiloDeAnalisis => "hilo_de_analisis",
+        })
+    }
+
+    /// Los datos del error que no entran en su texto, para mostrarlos aparte (por ejemplo en stderr).
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let error = BombermanError::ArchivoInvalido { linea: 2, columna: 5, ubicacion: None };
+    /// assert_eq!(error.detalle(), Some("linea 2, columna 5".to_string()));
+    /// ```
+    pub fn detalle(&self) -> Option<String> {
+        self.detalle_en(Idioma::Es)
+    }
+
+    /// Como `detalle`, con las palabras en el idioma dado. Las causas de los errores de lectura y de JSON vienen de
+    /// afuera y quedan como estan.
+    pub fn detalle_en(&self, idioma: Idioma) -> Option<String> {
+        let t = |clave| idioma.texto(clave);
+        let en_el_texto = |ubicacion: &Option<Ubicacion>| match ubicacion {
+            Some(ubicacion) => format!(
+                " ({} {}, byte {})",
+                t("en_el_texto"),
+                ubicacion,
+                ubicacion.inicio
+            ),
+            None => String::new(),
+        };
+        match self {
+            BombermanError::ArchivoInvalido {
+                linea,
+                columna,
+                ubicacion,
+            } => Some(format!(
+                "{} {}, {} {}{}",
+                t("linea"),
+                linea,
+                t("columna"),
+                columna,
+                en_el_texto(ubicacion)
+            )),
+            BombermanError::FilaIrregular {
+                linea,
+                casillas,
+                esperadas,
+                ubicacion,
+            } => Some(format!(
+                "{} {}: {} {} {} {}{}",
+                t("linea"),
+                linea,
+                casillas,
+                t("casillas"),
+                t("en_lugar_de"),
+                esperadas,
+                en_el_texto(ubicacion)
+            )),
+            BombermanError::PortalSinPareja {
+                linea,
+                columna,
+                apariciones,
+                ubicacion,
+            } => Some(format!(
+                "{} {}, {} {}: {} {} {}{}",
+                t("linea"),
+                linea,
+                t("columna"),
+                columna,
+                t("aparece"),
+                apariciones,
+                t(if *apariciones == 1 { "vez" } else { "veces" }),
+                en_el_texto(ubicacion)
+            )),
+            BombermanError::CoordenadasInvalidas { x, y } => Some(format!("x {}, y {}", x, y)),
+            BombermanError::Apertura(error) | BombermanError::Io(error) => Some(error.to_string()),
+            BombermanError::LimiteTablero { casillas, maximo } => Some(format!(
+                "{} {}, {} {}",
+                casillas,
+                t("casillas"),
+                t("maximo"),
+                maximo
+            )),
+            BombermanError::JsonInvalido {
+                posicion: Some(posicion),
+                causa,
+            } => Some(format!("byte {}: {}", posicion, causa)),
+            BombermanError::JsonInvalido {
+                posicion: None,
+                causa,
+            } => Some(causa.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl BombermanError {
+    /// Completa la Ubicacion en el texto de un ArchivoInvalido, FilaIrregular o PortalSinPareja a partir de las ubicaciones de las
+    /// casillas del tablero leido (ver `ubicacion::casillas_con_ubicacion`). Los demas errores quedan igual.
+    pub fn ubicar(self, ubicaciones: &[Vec<Ubicacion>]) -> BombermanError {
+        let buscar = |linea: usize, columna: usize| {
+            ubicaciones
+                .get(linea.checked_sub(1)?)?
+                .get(columna.checked_sub(1)?)
+                .copied()
+        };
+        match self {
+            BombermanError::ArchivoInvalido { linea, columna, .. } => {
+                BombermanError::ArchivoInvalido {
+                    linea,
+                    columna,
+                    ubicacion: buscar(linea, columna),
+                }
+            }
+            BombermanError::FilaIrregular {
+                linea,
+                casillas,
+                esperadas,
+                ..
+            } => BombermanError::FilaIrregular {
+                linea,
+                casillas,
+                esperadas,
+                ubicacion: buscar(linea, 1),
+            },
+            BombermanError::PortalSinPareja {
+                linea,
+                columna,
+                apariciones,
+                ..
+            } => BombermanError::PortalSinPareja {
+                linea,
+                columna,
+                apariciones,
+                ubicacion: buscar(linea, columna),
+            },
+            otro => otro,
+        }
+    }
+}
+
+impl std::fmt::Display for BombermanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mensaje(Idioma::Es))
+    }
+}
+
+impl std::error::Error for BombermanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BombermanError::Apertura(error) | BombermanError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BombermanError {
+    fn from(error: io::Error) -> BombermanError {
+        BombermanError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::io;
+
+    use super::BombermanError;
+    use crate::bomberman::idioma::Idioma;
+
+    #[test]
+    fn test01_el_texto_no_depende_de_los_datos() {
+        let error = BombermanError::ArchivoInvalido {
+            linea: 2,
+            columna: 5,
+            ubicacion: None,
+        };
+        assert_eq!(error.to_string(), "Error: archivo de entrada invalido");
+        assert_eq!(error.detalle(), Some("linea 2, columna 5".to_string()));
+        let error = BombermanError::CoordenadasInvalidas { x: 9, y: 9 };
+        assert_eq!(error.to_string(), "Error: coordenadas invalidas");
+        assert_eq!(error.detalle(), Some("x 9, y 9".to_string()));
+        assert_eq!(BombermanError::LimitePasos.detalle(), None);
+    }
+
+    #[test]
+    fn test02_los_errores_de_io_conservan_su_causa() {
+        let error = BombermanError::from(io::Error::other("disco lleno"));
+        assert_eq!(
+            error.to_string(),
+            "Error: no se pudo leer correctamente el archivo"
+        );
+        assert_eq!(error.source().unwrap().to_string(), "disco lleno");
+    }
+
+    #[test]
+    fn test03_el_idioma_cambia_el_texto_y_no_los_datos() {
+        let error = BombermanError::PortalSinPareja {
+            linea: 1,
+            columna: 3,
+            apariciones: 1,
+            ubicacion: None,
+        };
+        assert_eq!(
+            error.mensaje(Idioma::En),
+            "Error: each portal has to appear exactly twice"
+        );
+        assert_eq!(
+            error.detalle_en(Idioma::En),
+            Some("line 1, column 3: appears 1 time".to_string())
+        );
+        assert_eq!(error.mensaje(Idioma::Es), error.to_string());
+        assert_eq!(
+            error.detalle(),
+            Some("linea 1, columna 3: aparece 1 vez".to_string())
+        );
+    }
+}
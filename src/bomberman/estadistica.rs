@@ -0,0 +1,108 @@
+use super::direccion::Direccion;
+use super::punto::Punto;
+
+/// Lo que hizo una de las bombas de una reaccion en cadena, para ver cual hizo el trabajo de verdad.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstadisticaBomba {
+    pub punto: Punto,
+    /// 'B' o 'S', como en `Evento::Detonacion`.
+    pub tipo: char,
+    /// La cantidad de casillas distintas por las que pasaron sus rafagas, sin contar la bomba.
+    pub casillas_afectadas: usize,
+    /// Los golpes que dieron sus rafagas a algun enemigo, incluidos los que lo mataron.
+    pub enemigos_daniados: usize,
+    /// Las bombas que hizo explotar.
+    pub bombas_disparadas: usize,
+    /// Cuantas casillas recorrio la rafaga de cada direccion, incluida la casilla que la freno, en el orden en que
+    /// las lanzo: el de `Direccion::TODAS`, o el de `hexagonal::DIRECCIONES` en un tablero hexagonal.
+    pub rafagas: Vec<(Direccion, usize)>,
+    /// Las casillas por las que paso cada una de esas rafagas, en orden y con la que la freno: las que dobla en los
+    /// desvios y, si entra a un portal, el portal y su pareja. Ver `svg::simulacion`.
+    pub recorridos: Vec<Vec<Punto>>,
+}
+
+impl EstadisticaBomba {
+    /// Escribe la estadistica como un objeto JSON, por ejemplo
+    /// `{"x":0,"y":0,"tipo":"B","casillas_afectadas":3,"enemigos_daniados":1,"bombas_disparadas":0,"rafagas":{"L":0,"U":0,"R":2,"D":1}}`.
+    pub fn a_json(&self) -> String {
+        let rafagas: Vec<String> = self
+            .rafagas
+            .iter()
+            .map(|(direccion, largo)| format!("\"{}\":{}", direccion, largo))
+            .collect();
+        format!(
+            "{{\"x\":{},\"y\":{},\"tipo\":\"{}\",\"casillas_afectadas\":{},\"enemigos_daniados\":{},\
+             \"bombas_disparadas\":{},\"rafagas\":{{{}}}}}",
+            self.punto.x,
+            self.punto.y,
+            self.tipo,
+            self.casillas_afectadas,
+            self.enemigos_daniados,
+            self.bombas_disparadas,
+            rafagas.join(",")
+        )
+    }
+}
+
+/// Los numeros de una simulacion completa, para ver lo que paso sin comparar los tableros (ver
+/// `SalidaSimulacion::resumen`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumenSimulacion {
+    /// Las bombas que explotaron, incluida la primera.
+    pub bombas_detonadas: usize,
+    pub enemigos_eliminados: usize,
+    /// Los enemigos distintos que recibieron algun golpe, incluidos los que murieron.
+    pub enemigos_daniados: usize,
+    /// Las veces que una rafaga de traspaso atraveso una roca.
+    pub rocas_atravesadas: usize,
+    /// Las casillas distintas por las que paso alguna rafaga, incluidas las bombas detonadas.
+    pub casillas_alcanzadas: usize,
+    /// Los niveles del arbol de disparos (ver `NodoDisparo::profundidad`), 0 si no exploto ninguna bomba.
+    pub profundidad_cadena: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResumenSimulacion;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_una_fila_por_bomba_de_la_cadena() {
+        let tablero = Tablero::desde_texto("B2 F1 B1\n_ W _\nF2 _ R").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let filas: Vec<String> = salida.estadisticas.iter().map(|e| e.a_json()).collect();
+        assert_eq!(
+            filas,
+            [
+                "{\"x\":0,\"y\":0,\"tipo\":\"B\",\"casillas_afectadas\":4,\"enemigos_daniados\":2,\
+                 \"bombas_disparadas\":1,\"rafagas\":{\"L\":0,\"U\":0,\"R\":2,\"D\":2}}",
+                "{\"x\":2,\"y\":0,\"tipo\":\"B\",\"casillas_afectadas\":2,\"enemigos_daniados\":0,\
+                 \"bombas_disparadas\":0,\"rafagas\":{\"L\":1,\"U\":0,\"R\":0,\"D\":1}}"
+            ]
+        );
+        assert_eq!(salida.estadisticas[0].punto, Punto { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn test02_resumen_de_la_simulacion() {
+        let tablero = Tablero::desde_texto("S2 R F2 B1\n_ _ _ F1\nB1 F1 _ _").unwrap();
+        let salida = Bomberman::new(tablero.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(
+            salida.resumen(&tablero),
+            ResumenSimulacion {
+                bombas_detonadas: 2,
+                enemigos_eliminados: 1,
+                enemigos_daniados: 2,
+                rocas_atravesadas: 1,
+                casillas_alcanzadas: 6,
+                profundidad_cadena: 2,
+            }
+        );
+    }
+}
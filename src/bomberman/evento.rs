@@ -0,0 +1,174 @@
+use super::direccion::Direccion;
+use super::mejora::Mejora;
+use super::punto::Punto;
+
+/// Algo que ocurrio durante una simulacion, en el orden en que se fue resolviendo la explosion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evento {
+    /// Exploto la bomba en `punto`, de tipo 'B' o 'S' (o 'M' si es una mina), lanzando rafagas con el alcance dado.
+    Detonacion {
+        punto: Punto,
+        tipo: char,
+        alcance: usize,
+    },
+    /// Una rafaga alcanzo la bomba en `punto`, que va a explotar despues.
+    BombaAlcanzada { punto: Punto },
+    /// Una rafaga alcanzo la bomba temporizada en `punto`, que en lugar de explotar empieza a consumir su mecha y va a
+    /// explotar dentro de `mecha` tics.
+    MechaEncendida { punto: Punto, mecha: usize },
+    /// Una rafaga alcanzo la mina en `punto` o paso por su lado, y la mina va a explotar despues.
+    MinaActivada { punto: Punto },
+    /// En el tic dado se termino la mecha de la bomba temporizada en `punto`, que explota a continuacion.
+    MechaConsumida { punto: Punto, tic: usize },
+    /// Una rafaga de traspaso atraveso la roca en `punto`.
+    RocaAtravesada { punto: Punto },
+    /// Una rafaga de traspaso atraveso la pared en `punto`, con `Reglas::traspaso_atraviesa_paredes`.
+    ParedAtravesada { punto: Punto },
+    /// La rafaga se detuvo en `punto` por la casilla que habia ahi.
+    RafagaFrenada { punto: Punto, casilla: String },
+    /// La rafaga rompio el ladrillo en `punto`, que quedo vacio, y se detuvo ahi.
+    LadrilloRoto { punto: Punto },
+    /// El desvio en `punto` cambio la direccion de la rafaga.
+    Desvio { punto: Punto, direccion: Direccion },
+    /// La rafaga volvio al desvio en `punto`, que ya la habia mandado hacia `direccion`, y se corta ahi porque solo
+    /// repetiria las mismas casillas (por ejemplo, entre `DR DL`).
+    CicloDeDesvios { punto: Punto, direccion: Direccion },
+    /// La rafaga entro a un portal y sigue desde su pareja.
+    Teletransporte { entrada: Punto, salida: Punto },
+    /// El enemigo en `punto` recibio un golpe y le queda la vida indicada (0 si murio).
+    EnemigoDaniado { punto: Punto, vida: usize },
+    /// La rafaga paso por el enemigo en `punto`, que ya habia sido danado por la misma bomba.
+    EnemigoYaAfectado { punto: Punto },
+    /// El enemigo que murio en `punto` dejo la mejora dada, con `Reglas::mejoras`.
+    MejoraSoltada { punto: Punto, mejora: Mejora },
+    /// La rafaga de una bomba normal paso por el enemigo blindado en `punto`, que no perdio vida.
+    BlindajeResiste { punto: Punto },
+    /// La rafaga paso por el enemigo en `punto`, que ya perdio en esta onda todas las vidas que permite
+    /// `Reglas::golpes_por_onda`.
+    GolpesAgotados { punto: Punto },
+    /// Una rafaga alcanzo al jugador en `punto`, que murio.
+    JugadorMuerto { punto: Punto },
+}
+
+impl std::fmt::Display for Evento {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Evento::Detonacion {
+                punto,
+                tipo,
+                alcance,
+            } => write!(
+                f,
+                "explota {}{} en ({}, {})",
+                tipo, alcance, punto.x, punto.y
+            ),
+            Evento::BombaAlcanzada { punto } => {
+                write!(f, "la bomba en ({}, {}) es alcanzada", punto.x, punto.y)
+            }
+            Evento::MechaEncendida { punto, mecha } => write!(
+                f,
+                "se enciende la mecha de la bomba en ({}, {}), que explota en {} tics",
+                punto.x, punto.y, mecha
+            ),
+            Evento::MinaActivada { punto } => {
+                write!(f, "se activa la mina en ({}, {})", punto.x, punto.y)
+            }
+            Evento::MechaConsumida { punto, tic } => write!(
+                f,
+                "en el tic {} se consume la mecha de la bomba en ({}, {})",
+                tic, punto.x, punto.y
+            ),
+            Evento::ParedAtravesada { punto } => {
+                write!(
+                    f,
+                    "la rafaga atraviesa la pared en ({}, {})",
+                    punto.x, punto.y
+                )
+            }
+            Evento::RocaAtravesada { punto } => {
+                write!(
+                    f,
+                    "la rafaga atraviesa la roca en ({}, {})",
+                    punto.x, punto.y
+                )
+            }
+            Evento::RafagaFrenada { punto, casilla } => write!(
+                f,
+                "la rafaga se frena en ({}, {}) por {}",
+                punto.x, punto.y, casilla
+            ),
+            Evento::LadrilloRoto { punto } => {
+                write!(
+                    f,
+                    "la rafaga rompe el ladrillo en ({}, {})",
+                    punto.x, punto.y
+                )
+            }
+            Evento::Desvio { punto, direccion } => write!(
+                f,
+                "el desvio en ({}, {}) manda la rafaga hacia {}",
+                punto.x, punto.y, direccion
+            ),
+            Evento::CicloDeDesvios { punto, direccion } => write!(
+                f,
+                "la rafaga vuelve al desvio en ({}, {}) hacia {} y se corta",
+                punto.x, punto.y, direccion
+            ),
+            Evento::Teletransporte { entrada, salida } => write!(
+                f,
+                "la rafaga entra al portal en ({}, {}) y sale por ({}, {})",
+                entrada.x, entrada.y, salida.x, salida.y
+            ),
+            Evento::EnemigoDaniado { punto, vida: 0 } => {
+                write!(f, "muere el enemigo en ({}, {})", punto.x, punto.y)
+            }
+            Evento::EnemigoDaniado { punto, vida } => write!(
+                f,
+                "el enemigo en ({}, {}) queda con {} de vida",
+                punto.x, punto.y, vida
+            ),
+            Evento::EnemigoYaAfectado { punto } => write!(
+                f,
+                "el enemigo en ({}, {}) ya fue afectado por esta bomba",
+                punto.x, punto.y
+            ),
+            Evento::MejoraSoltada { punto, mejora } => write!(
+                f,
+                "el enemigo en ({}, {}) deja la mejora {}",
+                punto.x, punto.y, mejora
+            ),
+            Evento::BlindajeResiste { punto } => write!(
+                f,
+                "el blindaje del enemigo en ({}, {}) resiste la rafaga",
+                punto.x, punto.y
+            ),
+            Evento::GolpesAgotados { punto } => write!(
+                f,
+                "el enemigo en ({}, {}) ya recibio todos los golpes permitidos en esta onda",
+                punto.x, punto.y
+            ),
+            Evento::JugadorMuerto { punto } => {
+                write!(f, "muere el jugador en ({}, {})", punto.x, punto.y)
+            }
+        }
+    }
+}
+
+/// Recibe los eventos de una simulacion a medida que ocurren, por ejemplo para mostrarlos o guardarlos.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// struct Impresor;
+///
+/// impl Observador for Impresor {
+///     fn notificar(&mut self, evento: &Evento) {
+///         println!("{:?}", evento);
+///     }
+/// }
+///
+/// let bomberman = SimulacionBuilder::new(tablero).observador(Box::new(Impresor)).construir();
+/// ```
+pub trait Observador {
+    fn notificar(&mut self, evento: &Evento);
+}
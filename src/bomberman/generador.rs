@@ -0,0 +1,172 @@
+use super::bomba::Bomba;
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::optimizador::Aleatorio;
+use super::tablero::Tablero;
+
+/// El alcance mas grande de las bombas de `tablero_aleatorio`.
+pub const ALCANCE_MAXIMO: usize = 3;
+/// La vida mas grande de los enemigos de `tablero_aleatorio`, la de la consigna.
+pub const VIDA_MAXIMA: usize = 3;
+
+/// Que proporcion de las casillas de un tablero aleatorio ocupa cada tipo de celda, entre 0 y 1. Las casillas que
+/// sobran quedan vacias, asi que la suma tiene que ser como mucho 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Densidades {
+    pub paredes: f64,
+    pub rocas: f64,
+    pub bombas: f64,
+    pub desvios: f64,
+    pub enemigos: f64,
+}
+
+/// Un tablero parecido a los de la consigna: algunas paredes y rocas, pocas bombas y desvios y algunos enemigos.
+impl Default for Densidades {
+    fn default() -> Densidades {
+        Densidades {
+            paredes: 0.15,
+            rocas: 0.1,
+            bombas: 0.08,
+            desvios: 0.02,
+            enemigos: 0.1,
+        }
+    }
+}
+
+/// Arma un tablero al azar con las densidades pedidas, para pruebas de carga o para practicar niveles nuevos. Cada
+/// casilla se elige por separado, asi que las proporciones son aproximadas. Las bombas tienen alcance entre 1 y
+/// `ALCANCE_MAXIMO` y una de cada cuatro es de traspaso; los enemigos tienen vida entre 1 y `VIDA_MAXIMA`, asi que el
+/// tablero es valido con las reglas de siempre.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::generador::{tablero_aleatorio, Densidades};
+/// let tablero = tablero_aleatorio(7, 7, &Densidades::default(), 42)?;
+/// assert_eq!(tablero, tablero_aleatorio(7, 7, &Densidades::default(), 42)?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Argumentos
+///
+/// * ancho, alto: El tamanio del tablero.
+/// * densidades: La proporcion de cada tipo de celda.
+/// * semilla: La misma semilla da siempre el mismo tablero, en cualquier maquina.
+///
+/// # Devuelve
+///
+/// Un Result con el Tablero, o un Err(String) si alguna densidad es negativa o entre todas suman mas de 1.
+pub fn tablero_aleatorio(
+    ancho: usize,
+    alto: usize,
+    densidades: &Densidades,
+    semilla: u64,
+) -> Result<Tablero, String> {
+    let proporciones = [
+        densidades.paredes,
+        densidades.rocas,
+        densidades.bombas,
+        densidades.desvios,
+        densidades.enemigos,
+    ];
+    if proporciones.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err("Error: cada densidad tiene que estar entre 0 y 1".to_string());
+    }
+    let total: f64 = proporciones.iter().sum();
+    // Un margen para que, por ejemplo, 0.7 + 0.2 + 0.1 no quede afuera por el redondeo.
+    if total > 1.0 + 1e-9 {
+        return Err(format!(
+            "Error: las densidades suman {}, tienen que sumar como mucho 1",
+            total
+        ));
+    }
+    let mut aleatorio = Aleatorio::new(semilla);
+    let celdas = (0..alto)
+        .map(|_| {
+            (0..ancho)
+                .map(|_| celda_al_azar(&proporciones, &mut aleatorio))
+                .collect()
+        })
+        .collect();
+    Ok(Tablero::new(celdas))
+}
+
+/// Elige el tipo de una casilla segun las proporciones, en el orden de `Densidades`, y despues sus datos.
+fn celda_al_azar(proporciones: &[f64; 5], aleatorio: &mut Aleatorio) -> Celda {
+    let mut sorteo = aleatorio.siguiente_f64();
+    let tipo = proporciones.iter().position(|proporcion| {
+        sorteo -= proporcion;
+        sorteo < 0.0
+    });
+    match tipo {
+        Some(0) => Celda::Pared,
+        Some(1) => Celda::Roca,
+        Some(2) => {
+            let alcance = 1 + aleatorio.siguiente_hasta(ALCANCE_MAXIMO);
+            Celda::Bomba(Bomba {
+                traspaso: aleatorio.siguiente_hasta(4) == 0,
+                alcances: [alcance; 4],
+                mecha: None,
+            })
+        }
+        Some(3) => Celda::Desvio(Direccion::TODAS[aleatorio.siguiente_hasta(4)]),
+        Some(4) => Celda::Enemigo {
+            vida: 1 + aleatorio.siguiente_hasta(VIDA_MAXIMA),
+            blindado: false,
+        },
+        _ => Celda::Vacio,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tablero_aleatorio, Densidades};
+    use crate::bomberman::celda::Celda;
+    use crate::bomberman::io::tablero_desde_filas_con_reglas;
+    use crate::bomberman::reglas::Reglas;
+
+    #[test]
+    fn test01_la_misma_semilla_da_el_mismo_tablero_valido() {
+        let densidades = Densidades::default();
+        let tablero = tablero_aleatorio(9, 4, &densidades, 1527).unwrap();
+        assert_eq!((tablero.ancho(), tablero.alto()), (9, 4));
+        assert_eq!(tablero, tablero_aleatorio(9, 4, &densidades, 1527).unwrap());
+        assert_ne!(tablero, tablero_aleatorio(9, 4, &densidades, 1528).unwrap());
+        let leido = tablero_desde_filas_con_reglas(tablero.filas(), &Reglas::default()).unwrap();
+        assert_eq!(leido, tablero);
+    }
+
+    #[test]
+    fn test02_respeta_las_densidades() {
+        let solo_paredes = Densidades {
+            paredes: 1.0,
+            rocas: 0.0,
+            bombas: 0.0,
+            desvios: 0.0,
+            enemigos: 0.0,
+        };
+        let tablero = tablero_aleatorio(5, 5, &solo_paredes, 3).unwrap();
+        assert!(tablero.celdas().all(|(_, celda)| *celda == Celda::Pared));
+        let densidades = Densidades {
+            bombas: 0.5,
+            ..Densidades::default()
+        };
+        let tablero = tablero_aleatorio(100, 100, &densidades, 3).unwrap();
+        let bombas = tablero
+            .celdas()
+            .filter(|(_, celda)| matches!(celda, Celda::Bomba(_)))
+            .count();
+        assert!((4500..5500).contains(&bombas), "{}", bombas);
+
+        let demasiadas = Densidades {
+            paredes: 0.9,
+            ..Densidades::default()
+        };
+        assert!(tablero_aleatorio(5, 5, &demasiadas, 1).is_err());
+        let negativa = Densidades {
+            rocas: -0.1,
+            ..Densidades::default()
+        };
+        assert!(tablero_aleatorio(5, 5, &negativa, 1).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// Las 6 direcciones de una grilla hexagonal, en el orden en que una bomba lanza sus rafagas. Son las de siempre
+/// menos `Arriba` y `Abajo`, asi que un desvio hexagonal se escribe igual que uno cuadrado: `DL`, `DUL`, `DUR`, `DR`,
+/// `DDR` o `DDL`.
+pub const DIRECCIONES: [Direccion; 6] = [
+    Direccion::Izquierda,
+    Direccion::ArribaIzquierda,
+    Direccion::ArribaDerecha,
+    Direccion::Derecha,
+    Direccion::AbajoDerecha,
+    Direccion::AbajoIzquierda,
+];
+
+/// Cuanto cambian las coordenadas axiales (la columna y la fila del archivo) al dar un paso en la direccion dada, o
+/// None si no es una de `DIRECCIONES`. A diferencia de `Direccion::componentes`, `UL` y `DR` no cambian la columna.
+pub fn componentes(direccion: Direccion) -> Option<(isize, isize)> {
+    match direccion {
+        Direccion::Izquierda => Some((-1, 0)),
+        Direccion::Derecha => Some((1, 0)),
+        Direccion::ArribaIzquierda => Some((0, -1)),
+        Direccion::ArribaDerecha => Some((1, -1)),
+        Direccion::AbajoIzquierda => Some((-1, 1)),
+        Direccion::AbajoDerecha => Some((0, 1)),
+        Direccion::Arriba | Direccion::Abajo => None,
+    }
+}
+
+/// Un tablero de casillas hexagonales con coordenadas axiales: la columna del archivo es `q` y la fila es `r`. Se
+/// escribe como un Tablero comun, y se dibuja corriendo cada fila media casilla a la derecha de la anterior, asi que
+/// queda con forma de rombo:
+///
+/// ```text
+/// B2 _ F1
+///   _ W _
+///     DUR _ _
+/// ```
+///
+/// Las celdas son las mismas y hacen lo mismo, con dos condiciones que revisa `TableroHex::new`: los desvios apuntan a
+/// una de las 6 `DIRECCIONES` y las bombas tienen el mismo alcance en todas las direcciones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableroHex {
+    tablero: Tablero,
+}
+
+impl TableroHex {
+    /// Interpreta un tablero como hexagonal.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::hexagonal::TableroHex;
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let hex = TableroHex::new(Tablero::desde_texto("B1 DUR\n_ F1")?)?;
+    /// assert_eq!(hex.avanzar(Punto { x: 0, y: 1 }, Direccion::ArribaDerecha, false), Some(Punto { x: 1, y: 0 }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el TableroHex, o un Err(String) con la primera casilla que no tiene sentido en una grilla
+    /// hexagonal: un desvio hacia arriba o hacia abajo, o una bomba con alcances distintos por direccion.
+    pub fn new(tablero: Tablero) -> Result<TableroHex, String> {
+        for (punto, celda) in tablero.celdas() {
+            let problema = match celda {
+                Celda::Desvio(direccion) if componentes(*direccion).is_none() => {
+                    "los desvios de un tablero hexagonal apuntan a L, UL, UR, R, DR o DL"
+                }
+                Celda::Bomba(bomba) if bomba.alcances.iter().any(|a| *a != bomba.alcances[0]) => {
+                    "las bombas de un tablero hexagonal tienen el mismo alcance en todas las direcciones"
+                }
+                _ => continue,
+            };
+            return Err(format!(
+                "Error: {} en ({}, {}): {}",
+                celda, punto.x, punto.y, problema
+            ));
+        }
+        Ok(TableroHex { tablero })
+    }
+
+    /// Las casillas, con las coordenadas axiales como Punto.
+    pub fn tablero(&self) -> &Tablero {
+        &self.tablero
+    }
+
+    /// Devuelve el Tablero, por ejemplo para escribirlo.
+    pub fn into_tablero(self) -> Tablero {
+        self.tablero
+    }
+
+    /// Como `Tablero::avanzar`, con los pasos de `componentes`: la casilla vecina en una de las 6 direcciones, o
+    /// None si sale del tablero sin ser toroidal o si la direccion no es hexagonal. Con `toroidal`, la fila y la
+    /// columna dan la vuelta por separado, como en un tablero cuadrado.
+    pub fn avanzar(&self, punto: Punto, direccion: Direccion, toroidal: bool) -> Option<Punto> {
+        avanzar(&self.tablero, punto, direccion, toroidal)
+    }
+}
+
+/// `TableroHex::avanzar` sobre un Tablero cualquiera, para el motor, que guarda el tablero sin envolver.
+pub(super) fn avanzar(
+    tablero: &Tablero,
+    punto: Punto,
+    direccion: Direccion,
+    toroidal: bool,
+) -> Option<Punto> {
+    let (dx, dy) = componentes(direccion)?;
+    if !tablero.en_rango(punto) {
+        return None;
+    }
+    let (ancho, alto) = (tablero.ancho(), tablero.alto());
+    if toroidal {
+        return Some(Punto {
+            x: (punto.x + ancho).wrapping_add_signed(dx) % ancho,
+            y: (punto.y + alto).wrapping_add_signed(dy) % alto,
+        });
+    }
+    let vecina = Punto {
+        x: punto.x.checked_add_signed(dx)?,
+        y: punto.y.checked_add_signed(dy)?,
+    };
+    tablero.en_rango(vecina).then_some(vecina)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TableroHex, DIRECCIONES};
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_vecinas_y_casillas_invalidas() {
+        let hex = TableroHex::new(Tablero::desde_texto("_ _ _\n_ _ _\n_ _ _").unwrap()).unwrap();
+        let centro = Punto { x: 1, y: 1 };
+        let vecinas: Vec<Option<Punto>> = DIRECCIONES
+            .iter()
+            .map(|direccion| hex.avanzar(centro, *direccion, false))
+            .collect();
+        let esperadas = [(0, 1), (1, 0), (2, 0), (2, 1), (1, 2), (0, 2)];
+        let esperadas: Vec<Option<Punto>> = esperadas
+            .iter()
+            .map(|&(x, y)| Some(Punto { x, y }))
+            .collect();
+        assert_eq!(vecinas, esperadas);
+        for direccion in DIRECCIONES {
+            let vecina = hex.avanzar(centro, direccion, false).unwrap();
+            assert_eq!(
+                hex.avanzar(vecina, direccion.opuesta(), false),
+                Some(centro)
+            );
+        }
+        assert_eq!(hex.avanzar(centro, Direccion::Arriba, false), None);
+        assert_eq!(
+            hex.avanzar(Punto { x: 2, y: 0 }, Direccion::ArribaDerecha, false),
+            None
+        );
+        assert_eq!(
+            hex.avanzar(Punto { x: 2, y: 0 }, Direccion::ArribaDerecha, true),
+            Some(Punto { x: 0, y: 2 })
+        );
+
+        assert_eq!(
+            TableroHex::new(Tablero::desde_texto("B1 DU").unwrap()),
+            Err("Error: DU en (1, 0): los desvios de un tablero hexagonal apuntan a L, UL, UR, R, DR o DL".to_string())
+        );
+        assert!(TableroHex::new(Tablero::desde_texto("B[R2,L1]").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test02_las_bombas_lanzan_seis_rafagas() {
+        let texto = "F1 F1 _ F1\nF1 B1 F1 _\nF1 F1 F1 _";
+        let hex = TableroHex::new(Tablero::desde_texto(texto).unwrap()).unwrap();
+        let salida = Bomberman::hexagonal(hex)
+            .simular(Columna(1), Fila(1))
+            .unwrap();
+        // (0, 2) y (1, 2) son vecinas de (1, 1); (0, 0) y (2, 2) no.
+        assert_eq!(salida.tablero.to_texto(), "F1 _ _ F1\n_ _ _ _\n_ _ F1 _");
+        assert_eq!(salida.estadisticas[0].rafagas.len(), 6);
+
+        // Despues del desvio la rafaga baja por la columna 1, y la bomba que alcanza llega a (2, 2).
+        let texto = "B3 DDR _ _\n_ _ _ _\n_ B1 F1 _\n_ _ _ _";
+        let hex = TableroHex::new(Tablero::desde_texto(texto).unwrap()).unwrap();
+        let salida = Bomberman::hexagonal(hex)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(
+            salida.tablero.to_texto(),
+            "_ DDR _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _"
+        );
+    }
+}
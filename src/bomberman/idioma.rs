@@ -0,0 +1,298 @@
+use std::fmt::Display;
+
+/// El idioma en el que se muestran los errores (ver `BombermanError::mensaje`). Los textos de cada idioma estan en
+/// una tabla, asi que agregar uno es agregar una columna, sin tocar los errores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Idioma {
+    /// El de siempre, con el que se escriben los archivos de salida si no se pide otro.
+    #[default]
+    Es,
+    En,
+}
+
+/// Cada texto con su clave, en castellano y en ingles.
+const MENSAJES: &[(&str, &str, &str)] = &[
+    (
+        "archivo_invalido",
+        "Error: archivo de entrada invalido",
+        "Error: invalid input file",
+    ),
+    (
+        "fila_irregular",
+        "Error: las filas del tablero no tienen todas el mismo largo",
+        "Error: the rows of the board do not all have the same length",
+    ),
+    (
+        "portal_sin_pareja",
+        "Error: cada portal tiene que aparecer exactamente dos veces",
+        "Error: each portal has to appear exactly twice",
+    ),
+    (
+        "coordenadas_invalidas",
+        "Error: coordenadas invalidas",
+        "Error: invalid coordinates",
+    ),
+    (
+        "apertura",
+        "Error: no se pudo abrir correctamente el archivo",
+        "Error: the file could not be opened",
+    ),
+    (
+        "io",
+        "Error: no se pudo leer correctamente el archivo",
+        "Error: the file could not be read",
+    ),
+    (
+        "limite_cadena",
+        "Error: se supero la cantidad maxima de bombas en cadena",
+        "Error: the maximum number of chained bombs was exceeded",
+    ),
+    (
+        "limite_pasos",
+        "Error: se supero la cantidad maxima de pasos",
+        "Error: the maximum number of steps was exceeded",
+    ),
+    (
+        "limite_tiempo",
+        "Error: se supero el tiempo maximo de simulacion",
+        "Error: the maximum simulation time was exceeded",
+    ),
+    (
+        "limite_tablero",
+        "Error: el tablero supera la cantidad maxima de casillas",
+        "Error: the board exceeds the maximum number of cells",
+    ),
+    (
+        "json_invalido",
+        "Error: JSON de entrada invalido",
+        "Error: invalid input JSON",
+    ),
+    (
+        "hilo_de_analisis",
+        "Error: fallo un hilo de analisis",
+        "Error: an analysis thread failed",
+    ),
+    (
+        "ruta_de_salida_invalida",
+        "Error: ruta de salida invalida ({})",
+        "Error: invalid output path ({})",
+    ),
+    (
+        "cursor_hexagonal",
+        "Error: con --format hex la bomba no se puede elegir con el cursor",
+        "Error: with --format hex the bomb cannot be chosen with the cursor",
+    ),
+    (
+        "cursor_incompleto",
+        "Error: coordenadas invalidas (? va en las dos coordenadas o en ninguna)",
+        "Error: invalid coordinates (? goes in both coordinates or in neither)",
+    ),
+    (
+        "ninguna_bomba_elegida",
+        "Error: no se eligio ninguna bomba",
+        "Error: no bomb was chosen",
+    ),
+    (
+        "fuera_del_tablero",
+        "Error: coordenadas invalidas (({}, {}) esta fuera del tablero de {}x{})",
+        "Error: invalid coordinates (({}, {}) is outside the {}x{} board)",
+    ),
+    (
+        "detonacion_fuera_del_tablero",
+        "Error: coordenadas invalidas (la detonacion {} en ({}, {}) esta fuera del tablero de {}x{})",
+        "Error: invalid coordinates (detonation {} at ({}, {}) is outside the {}x{} board)",
+    ),
+    (
+        "escenario_invalido",
+        "Error: escenario invalido",
+        "Error: invalid scenario",
+    ),
+    (
+        "escenario_sin_texto",
+        "Error: un escenario solo se puede escribir como texto",
+        "Error: a scenario can only be written as text",
+    ),
+    (
+        "escritura_salida",
+        "Error: no se pudo escribir el archivo de salida",
+        "Error: the output file could not be written",
+    ),
+    (
+        "escritura_repeticion",
+        "Error: no se pudo escribir la repeticion en {}",
+        "Error: the replay could not be written to {}",
+    ),
+    (
+        "escritura_svg",
+        "Error: no se pudo escribir el SVG en {}",
+        "Error: the SVG could not be written to {}",
+    ),
+    (
+        "escritura_cuadros",
+        "Error: no se pudieron escribir los cuadros en {}",
+        "Error: the frames could not be written to {}",
+    ),
+    (
+        "escritura_diferencia",
+        "Error: no se pudo escribir la diferencia en {}",
+        "Error: the diff could not be written to {}",
+    ),
+    (
+        "lectura_reglas",
+        "Error: no se pudo leer el archivo de reglas {}",
+        "Error: the rules file {} could not be read",
+    ),
+    (
+        "lectura_simbolos",
+        "Error: no se pudo leer el archivo de simbolos {}",
+        "Error: the symbols file {} could not be read",
+    ),
+    (
+        "json_con_bloques",
+        "Error: --format json y --json-bloques no se pueden usar juntos",
+        "Error: --format json and --json-bloques cannot be used together",
+    ),
+    (
+        "hexagonal_sin_analisis",
+        "Error: --mapa-peligro y --grabar-replay todavia no se pueden usar con --format hex",
+        "Error: --mapa-peligro and --grabar-replay cannot be used with --format hex yet",
+    ),
+    (
+        "simbolos_sin_texto",
+        "Error: --simbolos solo se puede usar con la salida de texto",
+        "Error: --simbolos can only be used with the text output",
+    ),
+    (
+        "emoji_sin_texto",
+        "Error: --style emoji solo se puede usar con la salida de texto y sin --simbolos",
+        "Error: --style emoji can only be used with the text output and without --simbolos",
+    ),
+    (
+        "repeticion_con_tambien",
+        "Error: una repeticion tiene una sola detonacion, asi que --grabar-replay no se puede usar con --tambien",
+        "Error: a replay has a single detonation, so --grabar-replay cannot be used with --tambien",
+    ),
+    (
+        "al_terminar_fallo",
+        "Error: el comando de --al-terminar termino con {}",
+        "Error: the --al-terminar command ended with {}",
+    ),
+    (
+        "al_terminar_no_ejecutado",
+        "Error: no se pudo ejecutar el comando de --al-terminar",
+        "Error: the --al-terminar command could not be run",
+    ),
+    (
+        "pedido_demasiado_largo",
+        "Error: el pedido supera los {} bytes",
+        "Error: the request exceeds {} bytes",
+    ),
+    (
+        "pedido_no_utf8",
+        "Error: el pedido no es texto UTF-8",
+        "Error: the request is not UTF-8 text",
+    ),
+    (
+        "pedido_sin_coordenada",
+        "Error: el pedido necesita un numero natural en {}",
+        "Error: the request needs a natural number in {}",
+    ),
+    ("linea", "linea", "line"),
+    ("columna", "columna", "column"),
+    ("casillas", "casillas", "cells"),
+    ("en_lugar_de", "en lugar de", "instead of"),
+    ("aparece", "aparece", "appears"),
+    ("vez", "vez", "time"),
+    ("veces", "veces", "times"),
+    ("maximo", "maximo", "maximum"),
+    ("en_el_texto", "en el texto", "in the text"),
+];
+
+impl Idioma {
+    pub const TODOS: [Idioma; 2] = [Idioma::Es, Idioma::En];
+
+    /// Lee el codigo de un idioma, como en `--lang`: `es` o `en`.
+    pub fn parsear(texto: &str) -> Option<Idioma> {
+        Idioma::TODOS
+            .into_iter()
+            .find(|idioma| idioma.codigo() == texto)
+    }
+
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            Idioma::Es => "es",
+            Idioma::En => "en",
+        }
+    }
+
+    /// El texto de la clave en este idioma, o la clave misma si no esta en la tabla.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// assert_eq!(Idioma::En.texto("coordenadas_invalidas"), "Error: invalid coordinates");
+    /// ```
+    pub fn texto(&self, clave: &'static str) -> &'static str {
+        MENSAJES.iter().find(|(otra, _, _)| *otra == clave).map_or(
+            clave,
+            |(_, es, en)| match self {
+                Idioma::Es => es,
+                Idioma::En => en,
+            },
+        )
+    }
+
+    /// Como `texto`, pero cambia cada `{}` del texto, en orden, por uno de los argumentos.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// assert_eq!(
+    ///     Idioma::En.formatear("escritura_svg", &[&"mapa.svg"]),
+    ///     "Error: the SVG could not be written to mapa.svg"
+    /// );
+    /// ```
+    pub fn formatear(&self, clave: &'static str, argumentos: &[&dyn Display]) -> String {
+        let mut partes = self.texto(clave).split("{}");
+        let mut texto = partes.next().unwrap_or_default().to_string();
+        for (parte, argumento) in partes.zip(argumentos) {
+            texto.push_str(&argumento.to_string());
+            texto.push_str(parte);
+        }
+        texto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Idioma, MENSAJES};
+
+    #[test]
+    fn test01_cada_clave_tiene_un_texto_por_idioma() {
+        for (clave, es, en) in MENSAJES {
+            assert!(!es.is_empty() && !en.is_empty(), "{}", clave);
+            assert_eq!(
+                MENSAJES.iter().filter(|(otra, _, _)| otra == clave).count(),
+                1
+            );
+        }
+        assert_eq!(Idioma::parsear("en"), Some(Idioma::En));
+        assert_eq!(Idioma::parsear("fr"), None);
+        assert_eq!(Idioma::En.texto("vez"), "time");
+        assert_eq!(Idioma::Es.texto("no_existe"), "no_existe");
+        for (clave, es, en) in MENSAJES {
+            assert_eq!(
+                es.matches("{}").count(),
+                en.matches("{}").count(),
+                "{}",
+                clave
+            );
+        }
+        assert_eq!(
+            Idioma::Es.formatear("fuera_del_tablero", &[&3, &0, &2, &2]),
+            "Error: coordenadas invalidas ((3, 0) esta fuera del tablero de 2x2)"
+        );
+    }
+}
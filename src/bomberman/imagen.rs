@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use super::celda::Celda;
+use super::motor::SalidaSimulacion;
+use super::tablero::Tablero;
+
+/// Los colores de las imagenes, en RGB: el de la rejilla que separa las casillas y despues el de cada tipo de casilla,
+/// en el orden de `simbolos::TIPOS`, y al final el del jugador, que comparte la letra de los portales.
+pub const PALETA: [[u8; 3]; 14] = [
+    [0x40, 0x40, 0x40],
+    [0xe8, 0xe8, 0xd0],
+    [0x50, 0x50, 0x60],
+    [0x8b, 0x6b, 0x4a],
+    [0xc0, 0x60, 0x30],
+    [0x40, 0x80, 0xe0],
+    [0xe0, 0x30, 0x30],
+    [0x90, 0x10, 0x20],
+    [0x20, 0x20, 0x20],
+    [0x90, 0x40, 0xc0],
+    [0xe0, 0xc0, 0x20],
+    [0x20, 0xc0, 0xc0],
+    [0x40, 0xb0, 0x40],
+    [0xf0, 0x80, 0xf0],
+];
+
+/// Las casillas de `escala` pixeles de lado a partir de la que se dibuja la rejilla entre ellas.
+const ESCALA_CON_REJILLA: usize = 4;
+
+/// Una imagen con un indice de `PALETA` por pixel, fila por fila.
+struct Imagen {
+    ancho: usize,
+    alto: usize,
+    pixeles: Vec<u8>,
+}
+
+impl Tablero {
+    /// Con la feature `images`, guarda el tablero como una imagen PNG para informes o diapositivas: cada casilla es un
+    /// cuadrado de `escala` pixeles de lado con el color de su tipo en `PALETA`, y desde `ESCALA_CON_REJILLA` con una
+    /// linea oscura a la derecha y abajo. Una escala de 0 se toma como 1.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```no_run
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// Tablero::desde_texto("B2 R F1\n_ W _")?.render_png("tablero.png", 16)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un io::Result vacio, o el error de escribir el archivo.
+    pub fn render_png(&self, ruta: impl AsRef<Path>, escala: usize) -> io::Result<()> {
+        fs::write(ruta, png(&self.filas(), escala)?)
+    }
+}
+
+impl SalidaSimulacion {
+    /// Con la feature `images`, guarda la simulacion como un GIF animado que se repite: el tablero inicial y uno mas
+    /// por cada detonacion (ver `filas_en`), dibujados como en `Tablero::render_png`, con `demora` entre cuadros (el
+    /// formato la guarda en centesimas de segundo).
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let salida = Bomberman::new(tablero).simular(Columna(0), Fila(0))?;
+    /// salida.render_gif("cadena.gif", 16, Duration::from_millis(500))?;
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un io::Result vacio, o el error de escribir el archivo o un InvalidInput si la imagen mide mas de 65535 pixeles
+    /// de lado, que es lo maximo que admite el formato.
+    pub fn render_gif(
+        &self,
+        ruta: impl AsRef<Path>,
+        escala: usize,
+        demora: Duration,
+    ) -> io::Result<()> {
+        let cuadros: Vec<Vec<Vec<String>>> = (0..=self.cambios.len())
+            .map(|paso| self.filas_en(paso))
+            .collect();
+        fs::write(ruta, gif(&cuadros, escala, demora)?)
+    }
+}
+
+/// La posicion en `PALETA` del color de una casilla.
+fn color(casilla: &str) -> u8 {
+    match Celda::parsear(casilla) {
+        Some(Celda::Vacio) | None => 1,
+        Some(Celda::Pared) => 2,
+        Some(Celda::Roca) => 3,
+        Some(Celda::Ladrillo) => 4,
+        Some(Celda::Desvio(_)) => 5,
+        Some(Celda::Enemigo {
+            blindado: false, ..
+        }) => 6,
+        Some(Celda::Enemigo { blindado: true, .. }) => 7,
+        Some(Celda::Bomba(bomba)) if !bomba.traspaso => 8,
+        Some(Celda::Bomba(_)) => 9,
+        Some(Celda::Mina { .. }) => 10,
+        Some(Celda::Portal { .. }) => 11,
+        Some(Celda::Mejora(_)) => 12,
+        Some(Celda::Jugador) => 13,
+    }
+}
+
+/// Dibuja las filas de un tablero con `escala` pixeles por casilla.
+fn dibujar(filas: &[Vec<String>], escala: usize) -> Imagen {
+    let escala = escala.max(1);
+    let columnas = filas.iter().map(Vec::len).max().unwrap_or(0);
+    let (ancho, alto) = (columnas * escala, filas.len() * escala);
+    let mut pixeles = vec![1; ancho * alto];
+    for (y, fila) in filas.iter().enumerate() {
+        for (x, casilla) in fila.iter().enumerate() {
+            let color = color(casilla);
+            for dy in 0..escala {
+                for dx in 0..escala {
+                    let rejilla =
+                        escala >= ESCALA_CON_REJILLA && (dx == escala - 1 || dy == escala - 1);
+                    pixeles[(y * escala + dy) * ancho + x * escala + dx] =
+                        if rejilla { 0 } else { color };
+                }
+            }
+        }
+    }
+    Imagen {
+        ancho,
+        alto,
+        pixeles,
+    }
+}
+
+/// Codifica el tablero como PNG de color indexado, con los datos sin comprimir (bloques "stored" de deflate), que
+/// para imagenes de pocas casillas alcanza y no necesita un compresor.
+fn png(filas: &[Vec<String>], escala: usize) -> io::Result<Vec<u8>> {
+    let imagen = dibujar(filas, escala);
+    let (Ok(ancho), Ok(alto)) = (u32::try_from(imagen.ancho), u32::try_from(imagen.alto)) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "la imagen es demasiado grande para un PNG",
+        ));
+    };
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    let mut encabezado = Vec::new();
+    encabezado.extend(ancho.to_be_bytes());
+    encabezado.extend(alto.to_be_bytes());
+    // 8 bits por pixel, color indexado, compresion deflate, filtro adaptativo y sin entrelazado.
+    encabezado.extend([8, 3, 0, 0, 0]);
+    agregar_bloque_png(&mut png, b"IHDR", &encabezado);
+    agregar_bloque_png(&mut png, b"PLTE", &PALETA.concat());
+
+    // Cada fila empieza con el filtro que usa, 0 (ninguno).
+    let mut datos = Vec::with_capacity((imagen.ancho + 1) * imagen.alto);
+    for fila in imagen.pixeles.chunks(imagen.ancho.max(1)) {
+        datos.push(0);
+        datos.extend(fila);
+    }
+    let mut zlib = vec![0x78, 0x01];
+    let mut bloques = datos.chunks(u16::MAX as usize).peekable();
+    if bloques.peek().is_none() {
+        zlib.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(bloque) = bloques.next() {
+        let largo = bloque.len() as u16;
+        zlib.push(u8::from(bloques.peek().is_none()));
+        zlib.extend(largo.to_le_bytes());
+        zlib.extend((!largo).to_le_bytes());
+        zlib.extend(bloque);
+    }
+    zlib.extend(adler32(&datos).to_be_bytes());
+    agregar_bloque_png(&mut png, b"IDAT", &zlib);
+    agregar_bloque_png(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+/// Agrega un bloque al PNG: el largo, el tipo, los datos y el CRC del tipo y los datos.
+fn agregar_bloque_png(png: &mut Vec<u8>, tipo: &[u8; 4], datos: &[u8]) {
+    png.extend((datos.len() as u32).to_be_bytes());
+    let inicio = png.len();
+    png.extend(tipo);
+    png.extend(datos);
+    let crc = crc32(&png[inicio..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// El CRC-32 de ISO 3309 que usan los bloques de PNG.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// La suma Adler-32 con la que termina un flujo zlib.
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in bytes {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Codifica los cuadros como un GIF animado que se repite sin fin, con la paleta global de 16 colores (los de
+/// `PALETA` y negro en los que sobran).
+fn gif(cuadros: &[Vec<Vec<String>>], escala: usize, demora: Duration) -> io::Result<Vec<u8>> {
+    let imagenes: Vec<Imagen> = cuadros.iter().map(|filas| dibujar(filas, escala)).collect();
+    let (ancho, alto) = imagenes.first().map_or((0, 0), |i| (i.ancho, i.alto));
+    let (Ok(ancho), Ok(alto)) = (u16::try_from(ancho), u16::try_from(alto)) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "la imagen es demasiado grande para un GIF",
+        ));
+    };
+    let centesimas = u16::try_from(demora.as_millis() / 10).unwrap_or(u16::MAX);
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(ancho.to_le_bytes());
+    gif.extend(alto.to_le_bytes());
+    // Con paleta global de 2^(3 + 1) colores y 8 bits por color; el fondo es el color 0.
+    gif.extend([0xf3, 0, 0]);
+    for color in 0..16 {
+        gif.extend(PALETA.get(color).unwrap_or(&[0, 0, 0]));
+    }
+    // La extension NETSCAPE2.0, que hace que la animacion se repita (0 veces es sin fin).
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+    for imagen in &imagenes {
+        gif.extend([0x21, 0xf9, 4, 0]);
+        gif.extend(centesimas.to_le_bytes());
+        gif.extend([0, 0]);
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(ancho.to_le_bytes());
+        gif.extend(alto.to_le_bytes());
+        gif.push(0);
+        gif.push(BITS_GIF);
+        for bloque in lzw(&imagen.pixeles).chunks(255) {
+            gif.push(bloque.len() as u8);
+            gif.extend(bloque);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+/// Los bits de un color de la paleta del GIF, que es el tamanio minimo de los codigos LZW.
+const BITS_GIF: u8 = 4;
+
+/// Comprime los pixeles con la variante de LZW de GIF: codigos de largo variable, de `BITS_GIF + 1` a 12 bits,
+/// escritos desde el bit menos significativo, y un codigo de limpieza cuando se llena el diccionario.
+fn lzw(pixeles: &[u8]) -> Vec<u8> {
+    let limpiar: u16 = 1 << BITS_GIF;
+    let fin = limpiar + 1;
+    let mut salida = Escritor::default();
+    let mut diccionario: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut siguiente = fin + 1;
+    let mut bits = u32::from(BITS_GIF) + 1;
+    salida.escribir(limpiar, bits);
+    let mut actual: Option<u16> = None;
+    for &pixel in pixeles {
+        let Some(prefijo) = actual else {
+            actual = Some(u16::from(pixel));
+            continue;
+        };
+        if let Some(&codigo) = diccionario.get(&(prefijo, pixel)) {
+            actual = Some(codigo);
+            continue;
+        }
+        salida.escribir(prefijo, bits);
+        if siguiente < 4095 {
+            diccionario.insert((prefijo, pixel), siguiente);
+            siguiente += 1;
+            if u32::from(siguiente) > 1 << bits {
+                bits += 1;
+            }
+        } else {
+            salida.escribir(limpiar, bits);
+            diccionario.clear();
+            siguiente = fin + 1;
+            bits = u32::from(BITS_GIF) + 1;
+        }
+        actual = Some(u16::from(pixel));
+    }
+    if let Some(prefijo) = actual {
+        salida.escribir(prefijo, bits);
+    }
+    salida.escribir(fin, bits);
+    salida.terminar()
+}
+
+/// Junta codigos de distinto largo en bytes, empezando por el bit menos significativo.
+#[derive(Default)]
+struct Escritor {
+    bytes: Vec<u8>,
+    acumulado: u32,
+    bits: u32,
+}
+
+impl Escritor {
+    fn escribir(&mut self, codigo: u16, bits: u32) {
+        self.acumulado |= u32::from(codigo) << self.bits;
+        self.bits += bits;
+        while self.bits >= 8 {
+            self.bytes.push(self.acumulado as u8);
+            self.acumulado >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn terminar(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.acumulado as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::{crc32, lzw, BITS_GIF};
+    use crate::bomberman::punto::{Columna, Fila};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    /// Descomprime un flujo LZW de GIF, para comprobar que `lzw` escribe lo que lee cualquier visor.
+    fn descomprimir(bytes: &[u8]) -> Vec<u8> {
+        let limpiar = 1usize << BITS_GIF;
+        let (mut acumulado, mut disponibles, mut posicion) = (0u32, 0u32, 0);
+        let mut bits = u32::from(BITS_GIF) + 1;
+        let mut tabla: Vec<Vec<u8>> = Vec::new();
+        let mut anterior: Option<Vec<u8>> = None;
+        let mut pixeles = Vec::new();
+        loop {
+            while disponibles < bits {
+                acumulado |= u32::from(bytes[posicion]) << disponibles;
+                posicion += 1;
+                disponibles += 8;
+            }
+            let codigo = (acumulado & ((1 << bits) - 1)) as usize;
+            acumulado >>= bits;
+            disponibles -= bits;
+            if codigo == limpiar {
+                tabla = (0..limpiar as u8).map(|c| vec![c]).collect();
+                tabla.extend([Vec::new(), Vec::new()]);
+                bits = u32::from(BITS_GIF) + 1;
+                anterior = None;
+                continue;
+            }
+            if codigo == limpiar + 1 {
+                return pixeles;
+            }
+            let entrada = match (tabla.get(codigo), &anterior) {
+                (Some(entrada), _) => entrada.clone(),
+                (None, Some(anterior)) => [anterior.clone(), vec![anterior[0]]].concat(),
+                (None, None) => panic!("codigo {} invalido", codigo),
+            };
+            if let Some(anterior) = anterior {
+                tabla.push([anterior, vec![entrada[0]]].concat());
+                if tabla.len() == 1 << bits && bits < 12 {
+                    bits += 1;
+                }
+            }
+            pixeles.extend(&entrada);
+            anterior = Some(entrada);
+        }
+    }
+
+    #[test]
+    fn test01_el_png_tiene_el_color_de_cada_casilla() {
+        let ruta = std::env::temp_dir().join("tp1_bomberman_imagen_test01.png");
+        let tablero = Tablero::desde_texto("B2 W F1\n_ R DU").unwrap();
+        tablero.render_png(&ruta, 2).unwrap();
+        let png = std::fs::read(&ruta).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDR: 3x2 casillas de 2 pixeles.
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], [0, 0, 0, 6, 0, 0, 0, 4]);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        let mut bloques: HashMap<[u8; 4], &[u8]> = HashMap::new();
+        let mut posicion = 8;
+        while posicion < png.len() {
+            let largo =
+                u32::from_be_bytes(png[posicion..posicion + 4].try_into().unwrap()) as usize;
+            let tipo = png[posicion + 4..posicion + 8].try_into().unwrap();
+            bloques.insert(tipo, &png[posicion + 8..posicion + 8 + largo]);
+            posicion += 12 + largo;
+        }
+        // Un solo bloque sin comprimir, despues de los 2 bytes de zlib y los 5 de deflate.
+        let datos = &bloques[b"IDAT"][7..7 + 4 * 7];
+        let filas: Vec<&[u8]> = datos.chunks(7).collect();
+        assert_eq!(filas[0], [0, 8, 8, 2, 2, 6, 6]);
+        assert_eq!(filas[1], filas[0]);
+        assert_eq!(filas[3], [0, 1, 1, 3, 3, 5, 5]);
+        assert_eq!(bloques[b"PLTE"].len(), 3 * 14);
+    }
+
+    #[test]
+    fn test02_el_gif_tiene_un_cuadro_por_detonacion() {
+        let pixeles: Vec<u8> = (0..20_000u32).map(|i| ((i * i) % 13) as u8).collect();
+        assert_eq!(descomprimir(&lzw(&pixeles)), pixeles);
+        assert_eq!(descomprimir(&lzw(&[3; 5000])), [3; 5000]);
+
+        let ruta = std::env::temp_dir().join("tp1_bomberman_imagen_test02.gif");
+        let salida = Bomberman::new(Tablero::desde_texto("B1 B1 F1\n_ W _").unwrap())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        salida
+            .render_gif(&ruta, 8, Duration::from_millis(250))
+            .unwrap();
+        let gif = std::fs::read(&ruta).unwrap();
+        assert_eq!(&gif[..10], b"GIF89a\x18\x00\x10\x00");
+        assert_eq!(gif.last(), Some(&0x3b));
+        let cuadros = gif
+            .windows(6)
+            .filter(|v| v == b"\x21\xf9\x04\x00\x19\x00")
+            .count();
+        assert_eq!(cuadros, 3);
+    }
+}
@@ -0,0 +1,642 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use super::advertencia::Advertencia;
+use super::celda::Celda;
+use super::error::BombermanError;
+use super::estadistica::EstadisticaBomba;
+use super::json::{self, Json};
+use super::motor::{Bomberman, ResultadoJuego};
+use super::punto::{Columna, Fila, Punto};
+use super::reglas::Reglas;
+use super::tablero::Tablero;
+use super::ubicacion;
+
+/// Escribe el tablero como texto directamente en el escritor, casilla por casilla, sin armar un String por fila
+/// ni por tablero, asi la memoria usada no depende del tamanio del tablero. El formato es el de
+/// `Tablero::to_texto`: columnas separadas por espacios y filas por saltos de linea, sin salto al final.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let mut escritor = BufWriter::new(salida);
+/// escribir_tablero(&tablero, &mut escritor)?;
+/// escritor.flush()?;
+/// ```
+pub fn escribir_tablero(tablero: &Tablero, escritor: &mut impl Write) -> io::Result<()> {
+    tablero.escribir(escritor)
+}
+
+/// Escribe el tablero como JSON por bloques: una linea por cada grupo de hasta `filas_por_bloque` filas, con la forma
+/// `{"desde":0,"filas":[["B1","_"],["_","W"]]}`, donde `desde` es el indice de la primera fila del bloque.
+/// Quien lo lee puede procesar cada bloque por separado sin cargar el tablero entero.
+///
+/// # Argumentos
+///
+/// * tablero: El tablero; cada fila se escribe directo de sus Celdas, sin armar antes las filas de texto.
+/// * filas_por_bloque: Cantidad maxima de filas por linea (si es 0 se usa 1).
+/// * escritor: Donde se escribe.
+pub fn escribir_tablero_json(
+    tablero: &Tablero,
+    filas_por_bloque: usize,
+    escritor: &mut impl Write,
+) -> io::Result<()> {
+    let filas_por_bloque = filas_por_bloque.max(1);
+    let mut texto = String::new();
+    for desde in (0..tablero.alto()).step_by(filas_por_bloque) {
+        write!(escritor, "{{\"desde\":{},\"filas\":[", desde)?;
+        for y in desde..(desde + filas_por_bloque).min(tablero.alto()) {
+            if y > desde {
+                escritor.write_all(b",")?;
+            }
+            escribir_fila_json(tablero, y, &mut texto, escritor)?;
+        }
+        escritor.write_all(b"]}\n")?;
+    }
+    Ok(())
+}
+
+/// Escribe la fila y del tablero como un arreglo JSON con el texto de cada casilla, dato opaco incluido, directo de
+/// sus Celdas. `texto` es donde se arma cada casilla antes de escaparla, para no pedir memoria por casilla.
+fn escribir_fila_json(
+    tablero: &Tablero,
+    y: usize,
+    texto: &mut String,
+    escritor: &mut impl Write,
+) -> io::Result<()> {
+    use std::fmt::Write as _;
+    escritor.write_all(b"[")?;
+    for (x, celda) in tablero.fila(y).unwrap_or_default().iter().enumerate() {
+        if x > 0 {
+            escritor.write_all(b",")?;
+        }
+        texto.clear();
+        let _ = write!(texto, "{}", celda);
+        if let Some(dato) = tablero.dato(Punto { x, y }) {
+            let _ = write!(texto, "{{{}}}", dato);
+        }
+        escribir_cadena_json(texto, escritor)?;
+    }
+    escritor.write_all(b"]")
+}
+
+/// Escribe un texto como cadena JSON, escapando las comillas, las barras y los caracteres de control.
+pub fn escribir_cadena_json(texto: &str, escritor: &mut impl Write) -> io::Result<()> {
+    escritor.write_all(b"\"")?;
+    for caracter in texto.chars() {
+        match caracter {
+            '"' => escritor.write_all(b"\\\"")?,
+            '\\' => escritor.write_all(b"\\\\")?,
+            c if c.is_control() => write!(escritor, "\\u{:04x}", c as u32)?,
+            c => write!(escritor, "{}", c)?,
+        }
+    }
+    escritor.write_all(b"\"")
+}
+
+/// Toma la ruta a un archivo de texto con las filas de un tablero rectangular, con las casillas separadas por espacios y las filas por saltos de linea, y devuelve un Result con el Tablero o un Err(BombermanError) con la descripcion del error.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let  tablero: Tablero;
+/// match tablero_desde_archivo("entradas/entrada.txt") {
+///     Err(e) => return Err(e),
+///     Ok(tab) => tablero = tab
+/// }
+/// ```
+///
+/// # Argumentos
+///
+///  * ruta_entrada: La ruta hasta el archivo de texto con la matriz.
+///
+/// # Devuelve
+///
+/// Un Result con el Tablero o un Err con la descripcion del error, que para una casilla invalida indica su linea y columna
+/// entre las casillas y su Ubicacion en el archivo. El Tablero recuerda la Ubicacion de cada casilla.
+pub fn tablero_desde_archivo(ruta_entrada: impl AsRef<Path>) -> Result<Tablero, BombermanError> {
+    tablero_desde_archivo_con_reglas(ruta_entrada, &Reglas::default())
+}
+
+/// Como `tablero_desde_archivo`, pero valida el alcance de las bombas y la vida de los enemigos con los topes de
+/// las reglas dadas (ver `Reglas::alcance_maximo` y `Reglas::vida_maxima`) en lugar de los de la consigna.
+pub fn tablero_desde_archivo_con_reglas(
+    ruta_entrada: impl AsRef<Path>,
+    reglas: &Reglas,
+) -> Result<Tablero, BombermanError> {
+    let mut texto = String::new();
+    File::open(ruta_entrada)
+        .map_err(BombermanError::Apertura)?
+        .read_to_string(&mut texto)?;
+    tablero_desde_texto_con_reglas(&texto, reglas)
+}
+
+/// Como `tablero_desde_archivo_con_reglas`, pero con el texto del archivo ya leido.
+pub fn tablero_desde_texto_con_reglas(
+    texto: &str,
+    reglas: &Reglas,
+) -> Result<Tablero, BombermanError> {
+    let casillas = ubicacion::casillas_con_ubicacion(texto);
+    let filas: Vec<Vec<String>> = casillas
+        .iter()
+        .map(|fila| {
+            fila.iter()
+                .map(|(casilla, _)| casilla.to_string())
+                .collect()
+        })
+        .collect();
+    match primera_casilla_invalida(&filas, reglas) {
+        Some(Punto { x, y }) => Err(BombermanError::ArchivoInvalido {
+            linea: y + 1,
+            columna: x + 1,
+            ubicacion: Some(casillas[y][x].1),
+        }),
+        None => Tablero::desde_texto(texto),
+    }
+}
+
+/// Lee un tablero, detona la bomba de las coordenadas dadas y devuelve el tablero final como texto, todo en memoria:
+/// no abre archivos ni escribe en la salida, asi que sirve como objetivo de `cargo fuzz` o para usar el simulador
+/// desde otro programa. Valida el tablero con las reglas de la consigna, igual que la linea de comandos.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// # use tp1_bomberman::ejecutar;
+/// assert_eq!(ejecutar(b"B1 F1\n_ W", Columna(0), Fila(0))?, "_ _\n_ W");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Argumentos
+///
+/// * bytes: El contenido de un archivo de tablero, que tiene que ser UTF-8.
+/// * columna, fila: Las coordenadas de la bomba a detonar.
+///
+/// # Devuelve
+///
+/// Un Result con el texto del tablero final, con el formato de `Tablero::to_texto`, o un Err(BombermanError) si el
+/// texto no es UTF-8, el tablero es invalido o en las coordenadas no hay una bomba.
+pub fn ejecutar(bytes: &[u8], columna: Columna, fila: Fila) -> Result<String, BombermanError> {
+    let texto = std::str::from_utf8(bytes)
+        .map_err(|e| BombermanError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    let tablero = tablero_desde_texto_con_reglas(texto, &Reglas::default())?;
+    let salida = Bomberman::new(tablero).simular(columna, fila)?;
+    Ok(salida.tablero.to_texto())
+}
+
+/// Lee las filas de un tablero de `--format json` sin validar sus casillas. El documento puede ser directamente el
+/// arreglo de filas o un objeto con ese arreglo en `tablero` y, opcionalmente, cualquier valor en `metadatos`.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::io::filas_desde_json;
+/// let (filas, metadatos) = filas_desde_json(r#"{"tablero":[["B1","_"]],"metadatos":{"nivel":1}}"#)?;
+/// assert_eq!(filas, vec![vec!["B1".to_string(), "_".to_string()]]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con las filas y los metadatos tal cual se leyeron, para copiarlos en la salida, o un
+/// Err(BombermanError::JsonInvalido) si el texto no es JSON o no tiene esa forma.
+pub fn filas_desde_json(texto: &str) -> Result<(Vec<Vec<String>>, Option<Json>), BombermanError> {
+    filas_desde_documento_json(&json::parsear(texto)?)
+}
+
+/// Como `filas_desde_json`, pero con el documento ya leido, para no volver a leer un texto que tambien tiene otros
+/// campos.
+pub fn filas_desde_documento_json(
+    documento: &Json,
+) -> Result<(Vec<Vec<String>>, Option<Json>), BombermanError> {
+    let forma = |causa: String| BombermanError::JsonInvalido {
+        posicion: None,
+        causa,
+    };
+    let (tablero, metadatos) = match documento {
+        Json::Lista(_) => (documento, None),
+        Json::Objeto(_) => (
+            documento
+                .campo("tablero")
+                .ok_or_else(|| forma("falta el campo tablero".to_string()))?,
+            documento.campo("metadatos").cloned(),
+        ),
+        _ => {
+            return Err(forma(
+                "se esperaba un arreglo de filas o un objeto".to_string(),
+            ))
+        }
+    };
+    let Json::Lista(filas) = tablero else {
+        return Err(forma(
+            "tablero tiene que ser un arreglo de filas".to_string(),
+        ));
+    };
+    let filas = filas
+        .iter()
+        .enumerate()
+        .map(|(y, fila)| match fila {
+            Json::Lista(casillas) => casillas
+                .iter()
+                .enumerate()
+                .map(|(x, casilla)| match casilla {
+                    Json::Texto(casilla) => Ok(casilla.clone()),
+                    _ => Err(forma(format!("la casilla [{}][{}] no es un texto", y, x))),
+                })
+                .collect(),
+            _ => Err(forma(format!("la fila {} no es un arreglo", y))),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((filas, metadatos))
+}
+
+/// Como `tablero_desde_archivo_con_reglas`, pero para el texto de un tablero de `--format json` (ver
+/// `filas_desde_json`). Una casilla invalida se informa con su fila y columna contando desde 1.
+pub fn tablero_desde_json(
+    texto: &str,
+    reglas: &Reglas,
+) -> Result<(Tablero, Option<Json>), BombermanError> {
+    tablero_desde_documento_json(&json::parsear(texto)?, reglas)
+}
+
+/// Como `tablero_desde_json`, pero con el documento ya leido (ver `filas_desde_documento_json`).
+pub fn tablero_desde_documento_json(
+    documento: &Json,
+    reglas: &Reglas,
+) -> Result<(Tablero, Option<Json>), BombermanError> {
+    let (filas, metadatos) = filas_desde_documento_json(documento)?;
+    Ok((tablero_desde_filas_con_reglas(filas, reglas)?, metadatos))
+}
+
+/// Arma un Tablero con filas que ya estan separadas en casillas, validandolas con los topes de las reglas dadas como
+/// `tablero_desde_archivo_con_reglas`. Una casilla invalida se informa con su fila y columna contando desde 1.
+pub fn tablero_desde_filas_con_reglas(
+    filas: Vec<Vec<String>>,
+    reglas: &Reglas,
+) -> Result<Tablero, BombermanError> {
+    if let Some(Punto { x, y }) = primera_casilla_invalida(&filas, reglas) {
+        return Err(BombermanError::ArchivoInvalido {
+            linea: y + 1,
+            columna: x + 1,
+            ubicacion: None,
+        });
+    }
+    Tablero::desde_filas(filas)
+}
+
+/// Escribe el resultado de una simulacion para `--format json`, en una sola linea con la forma
+/// `{"id":"...","version":"0.1.0","tablero":[["_","W"]],"resultado":{"enemigos_eliminados":1,...,"gano":true},
+/// "bombas":[...],"advertencias":[],"metadatos":{}}`, con una fila de `EstadisticaBomba::a_json` por bomba detonada.
+/// El id identifica la jugada (el tablero inicial, la bomba y las reglas) para comparar resultados de distintas
+/// versiones del programa, y la version es la de este crate. El id y los metadatos, que son los de la entrada, se
+/// omiten si no se tienen.
+pub fn escribir_resultado_json(
+    id: Option<&str>,
+    tablero: &Tablero,
+    resultado: &ResultadoJuego,
+    estadisticas: &[EstadisticaBomba],
+    advertencias: &[Advertencia],
+    metadatos: Option<&Json>,
+    escritor: &mut impl Write,
+) -> io::Result<()> {
+    escritor.write_all(b"{")?;
+    if let Some(id) = id {
+        escritor.write_all(b"\"id\":")?;
+        escribir_cadena_json(id, escritor)?;
+        escritor.write_all(b",")?;
+    }
+    write!(
+        escritor,
+        "\"version\":\"{}\",\"tablero\":[",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    let mut texto = String::new();
+    for y in 0..tablero.alto() {
+        if y > 0 {
+            escritor.write_all(b",")?;
+        }
+        escribir_fila_json(tablero, y, &mut texto, escritor)?;
+    }
+    write!(
+        escritor,
+        "],\"resultado\":{{\"enemigos_eliminados\":{},\"enemigos_restantes\":{},\"detonaciones\":{},\
+         \"casillas_alcanzadas\":{},\"gano\":{}}},\"bombas\":[{}],\"advertencias\":[",
+        resultado.enemigos_eliminados,
+        resultado.enemigos_restantes,
+        resultado.detonaciones,
+        resultado.casillas_alcanzadas,
+        resultado.gano(),
+        estadisticas
+            .iter()
+            .map(EstadisticaBomba::a_json)
+            .collect::<Vec<_>>()
+            .join(",")
+    )?;
+    for (i, advertencia) in advertencias.iter().enumerate() {
+        if i > 0 {
+            escritor.write_all(b",")?;
+        }
+        escribir_cadena_json(&advertencia.to_string(), escritor)?;
+    }
+    escritor.write_all(b"]")?;
+    if let Some(metadatos) = metadatos {
+        write!(escritor, ",\"metadatos\":{}", metadatos)?;
+    }
+    escritor.write_all(b"}\n")
+}
+
+/// Lee las filas de un archivo de tablero sin validar sus casillas, salteando las lineas vacias y las de comentario.
+///
+/// # Devuelve
+///
+/// Un Result con la matriz de Strings o un Err(BombermanError) si no se pudo abrir o leer el archivo.
+pub fn leer_filas(ruta_entrada: impl AsRef<Path>) -> Result<Vec<Vec<String>>, BombermanError> {
+    let archivo_entrada_result = File::open(ruta_entrada);
+
+    let file_input: File = match archivo_entrada_result {
+        Ok(file) => file,
+        Err(e) => return Err(BombermanError::Apertura(e)),
+    };
+
+    let reader = BufReader::new(file_input);
+
+    // Creo el tablero de Juego
+    let mut tablero: Vec<Vec<String>> = Vec::new();
+    for linea in reader.lines() {
+        let casillas: String = linea?;
+        // Las lineas que empiezan con '#' son comentarios, como el encabezado de `--con-procedencia`.
+        if casillas.starts_with('#') || casillas.trim().is_empty() {
+            continue;
+        }
+        let palabras: Vec<String> = casillas.split_whitespace().map(String::from).collect();
+        tablero.push(palabras)
+    }
+    Ok(tablero)
+}
+
+/// Reemplaza por `_` cada casilla que no sea valida con las reglas dadas, y las de los portales sin pareja, para
+/// simular tableros con casillas desconocidas (`--recuperar`).
+///
+/// # Devuelve
+///
+/// Una Advertencia por cada casilla reemplazada.
+pub fn recuperar_tablero(tablero: &mut [Vec<String>], reglas: &Reglas) -> Vec<Advertencia> {
+    let mut advertencias = Vec::new();
+    for (y, fila) in tablero.iter_mut().enumerate() {
+        for (x, casilla) in fila.iter_mut().enumerate() {
+            if !es_casilla_valida(casilla, reglas) {
+                advertencias.push(Advertencia::CasillaReemplazada {
+                    punto: Punto { x, y },
+                    casilla: std::mem::replace(casilla, "_".to_string()),
+                });
+            }
+        }
+    }
+    let mut portales: HashMap<usize, Vec<Punto>> = HashMap::new();
+    for (y, fila) in tablero.iter().enumerate() {
+        for (x, casilla) in fila.iter().enumerate() {
+            if let Some(Celda::Portal { numero }) = Celda::parsear(casilla) {
+                portales.entry(numero).or_default().push(Punto { x, y });
+            }
+        }
+    }
+    let mut sin_pareja: Vec<Punto> = portales
+        .into_values()
+        .filter(|puntos| puntos.len() != 2)
+        .flatten()
+        .collect();
+    sin_pareja.sort_by_key(|punto| (punto.y, punto.x));
+    for punto in sin_pareja {
+        advertencias.push(Advertencia::CasillaReemplazada {
+            punto,
+            casilla: std::mem::replace(&mut tablero[punto.y][punto.x], "_".to_string()),
+        });
+    }
+    advertencias
+}
+
+/// Toma una matriz de Strings y evalua todas las casillas para ver si son validas para un juego de Bomberman.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let tablero = vec![
+///     vec!["W".to_string(),"B1".to_string()],
+///     vec!["S3".to_string(),"X".to_string()]
+///     ];
+/// assert_eq!(primera_casilla_invalida(&tablero, &Reglas::default()), Some(Punto { x: 1, y: 1 }));
+/// ```
+///
+/// # Argumentos
+///
+/// * tablero: La matriz de Strings que se quiere evaluar si es valida.
+/// * reglas: Las reglas con los topes de alcance y vida.
+///
+/// # Devuelve
+///
+/// Un Option con la posicion de la primera casilla invalida, leyendo por filas, o None si la matriz es valida.
+fn primera_casilla_invalida(tablero: &[Vec<String>], reglas: &Reglas) -> Option<Punto> {
+    tablero.iter().enumerate().find_map(|(y, fila)| {
+        fila.iter()
+            .position(|casilla| !es_casilla_valida(casilla, reglas))
+            .map(|x| Punto { x, y })
+    })
+}
+
+/// Indica si el texto es una casilla valida para un juego de Bomberman con las reglas dadas: ademas de que se pueda
+/// interpretar, las bombas, las minas y los enemigos no pueden superar `Reglas::alcance_maximo` y `Reglas::vida_maxima`, y los
+/// ladrillos y las mejoras solo valen con `Reglas::ladrillos` y `Reglas::mejoras`.
+pub fn es_casilla_valida(elem: &str, reglas: &Reglas) -> bool {
+    match Celda::parsear(elem) {
+        Some(Celda::Enemigo { vida, .. }) => vida <= reglas.vida_maxima,
+        Some(Celda::Bomba(bomba)) => reglas
+            .alcance_maximo
+            .is_none_or(|maximo| bomba.alcance_maximo() <= maximo),
+        Some(Celda::Mina { alcance }) => {
+            reglas.alcance_maximo.is_none_or(|maximo| alcance <= maximo)
+        }
+        Some(Celda::Ladrillo) => reglas.ladrillos,
+        Some(Celda::Mejora(_)) => reglas.mejoras,
+        Some(_) => true,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ejecutar, escribir_resultado_json, escribir_tablero, escribir_tablero_json,
+        filas_desde_json, primera_casilla_invalida, recuperar_tablero, tablero_desde_json,
+    };
+    use crate::bomberman::error::BombermanError;
+    use crate::bomberman::motor::Bomberman;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::reglas::Reglas;
+    use crate::bomberman::tablero::Tablero;
+
+    fn tablero() -> Tablero {
+        Tablero::desde_filas(filas()).unwrap()
+    }
+
+    fn filas() -> Vec<Vec<String>> {
+        ["B1 _", "_ W", "F1 R"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test01_texto_igual_al_formato_de_salida() {
+        let mut salida = Vec::new();
+        escribir_tablero(&tablero(), &mut salida).unwrap();
+        assert_eq!(String::from_utf8(salida).unwrap(), "B1 _\n_ W\nF1 R");
+    }
+
+    #[test]
+    fn test02_json_por_bloques() {
+        let mut salida = Vec::new();
+        escribir_tablero_json(&tablero(), 2, &mut salida).unwrap();
+        assert_eq!(
+            String::from_utf8(salida).unwrap(),
+            "{\"desde\":0,\"filas\":[[\"B1\",\"_\"],[\"_\",\"W\"]]}\n{\"desde\":2,\"filas\":[[\"F1\",\"R\"]]}\n"
+        );
+        let mut salida = Vec::new();
+        let con_dato = Tablero::desde_texto("F2{id=\"a\"} _").unwrap();
+        escribir_tablero_json(&con_dato, 0, &mut salida).unwrap();
+        assert_eq!(
+            String::from_utf8(salida).unwrap(),
+            "{\"desde\":0,\"filas\":[[\"F2{id=\\\"a\\\"}\",\"_\"]]}\n"
+        );
+    }
+
+    #[test]
+    fn test03_valida_y_recupera_las_casillas() {
+        let reglas = Reglas::default();
+        let mut filas = filas();
+        assert_eq!(primera_casilla_invalida(&filas, &reglas), None);
+        filas[1][0] = "F256".to_string();
+        filas[2][1] = "X".to_string();
+        assert_eq!(
+            primera_casilla_invalida(&filas, &reglas),
+            Some(Punto { x: 0, y: 1 })
+        );
+        assert_eq!(recuperar_tablero(&mut filas, &reglas).len(), 2);
+        assert_eq!(primera_casilla_invalida(&filas, &reglas), None);
+
+        // Un portal sin pareja es una casilla valida, pero el tablero no.
+        filas[0][1] = "P1".to_string();
+        assert_eq!(primera_casilla_invalida(&filas, &reglas), None);
+        assert_eq!(recuperar_tablero(&mut filas, &reglas).len(), 1);
+        assert!(Tablero::desde_filas(filas).is_ok());
+    }
+
+    #[test]
+    fn test04_los_topes_de_las_reglas() {
+        let mut filas = filas();
+        filas[0][0] = "B0".to_string();
+        assert_eq!(
+            primera_casilla_invalida(&filas, &Reglas::default()),
+            Some(Punto { x: 0, y: 0 })
+        );
+        filas[0][0] = "B12".to_string();
+        filas[2][0] = "F5".to_string();
+        let mut reglas = Reglas::preset("clasico").unwrap();
+        assert_eq!(
+            primera_casilla_invalida(&filas, &reglas),
+            Some(Punto { x: 0, y: 2 })
+        );
+        reglas.vida_maxima = 5;
+        assert_eq!(primera_casilla_invalida(&filas, &reglas), None);
+        filas[2][0] = "F255".to_string();
+        assert!(primera_casilla_invalida(&filas, &reglas).is_some());
+        reglas.vida_maxima = Reglas::default().vida_maxima;
+        assert_eq!(primera_casilla_invalida(&filas, &reglas), None);
+        filas[2][0] = "F256".to_string();
+        assert!(primera_casilla_invalida(&filas, &reglas).is_some());
+        filas[2][0] = "F255".to_string();
+        reglas.alcance_maximo = Some(9);
+        assert_eq!(
+            primera_casilla_invalida(&filas, &reglas),
+            Some(Punto { x: 0, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test05_json_de_entrada_y_de_salida() {
+        assert_eq!(
+            filas_desde_json("[[\"B1\",\"_\"],[\"_\",\"W\"],[\"F1\",\"R\"]]")
+                .unwrap()
+                .0,
+            filas()
+        );
+        let entrada = "{\"tablero\":[[\"B1\",\"F1\"]],\"metadatos\":{\"nivel\":2}}";
+        let (inicial, metadatos) = tablero_desde_json(entrada, &Reglas::default()).unwrap();
+        let salida = Bomberman::new(inicial.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let mut texto = Vec::new();
+        escribir_resultado_json(
+            Some("abc-0-0"),
+            &salida.tablero,
+            &salida.resultado(&inicial),
+            &salida.estadisticas,
+            &salida.advertencias,
+            metadatos.as_ref(),
+            &mut texto,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(texto)
+                .unwrap()
+                .replace(env!("CARGO_PKG_VERSION"), "VERSION"),
+            "{\"id\":\"abc-0-0\",\"version\":\"VERSION\",\"tablero\":[[\"_\",\"_\"]],\"resultado\":{\
+             \"enemigos_eliminados\":1,\"enemigos_restantes\":0,\"detonaciones\":1,\"casillas_alcanzadas\":2,\
+             \"gano\":true},\"bombas\":[{\"x\":0,\"y\":0,\"tipo\":\"B\",\"casillas_afectadas\":1,\
+             \"enemigos_daniados\":1,\"bombas_disparadas\":0,\"rafagas\":{\"L\":0,\"U\":0,\"R\":1,\"D\":0}}],\
+             \"advertencias\":[],\"metadatos\":{\"nivel\":2}}\n"
+        );
+        for (entrada, detalle) in [
+            ("{\"filas\":[]}", "falta el campo tablero"),
+            ("[[\"B1\",1]]", "la casilla [0][1] no es un texto"),
+            ("[[\"B1\"]", "byte 7: se esperaba ',' o ']'"),
+        ] {
+            let error = tablero_desde_json(entrada, &Reglas::default()).unwrap_err();
+            assert_eq!(error.detalle(), Some(detalle.to_string()), "{}", entrada);
+        }
+        assert!(matches!(
+            tablero_desde_json("[[\"B1\",\"X\"]]", &Reglas::default()),
+            Err(BombermanError::ArchivoInvalido {
+                linea: 1,
+                columna: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test06_ejecutar_en_memoria() {
+        assert_eq!(
+            ejecutar(b"# nivel 1\nB1 F1\n_ W", Columna(0), Fila(0)).unwrap(),
+            "_ _\n_ W"
+        );
+        assert!(matches!(
+            ejecutar(b"B1 F300", Columna(0), Fila(0)),
+            Err(BombermanError::ArchivoInvalido {
+                linea: 1,
+                columna: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            ejecutar(b"B1 F1", Columna(5), Fila(0)),
+            Err(BombermanError::CoordenadasInvalidas { x: 5, y: 0 })
+        ));
+        assert!(matches!(
+            ejecutar(&[0x42, 0x31, 0xff], Columna(0), Fila(0)),
+            Err(BombermanError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,341 @@
+use super::error::BombermanError;
+
+/// Cuantos arreglos y objetos puede haber uno dentro de otro. Un tablero usa dos niveles y sus metadatos algunos mas;
+/// el tope evita que una entrada maliciosa agote la pila del parser recursivo.
+const PROFUNDIDAD_MAXIMA: usize = 64;
+
+/// Un valor JSON. Los objetos conservan el orden de sus claves, para volver a escribirlos igual que se leyeron.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Nulo,
+    Booleano(bool),
+    Numero(f64),
+    Texto(String),
+    Lista(Vec<Json>),
+    Objeto(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Devuelve el valor de una clave si es un objeto que la tiene.
+    pub fn campo(&self, clave: &str) -> Option<&Json> {
+        match self {
+            Json::Objeto(campos) => campos
+                .iter()
+                .find(|(nombre, _)| nombre == clave)
+                .map(|(_, valor)| valor),
+            _ => None,
+        }
+    }
+}
+
+/// Interpreta un documento JSON completo.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::json::{parsear, Json};
+/// let json = parsear(r#"{"tablero": [["B1", "_"]]}"#)?;
+/// assert!(matches!(json.campo("tablero"), Some(Json::Lista(_))));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con el valor o un Err(BombermanError::JsonInvalido) con el byte en el que se encontro el problema.
+pub fn parsear(texto: &str) -> Result<Json, BombermanError> {
+    let mut lector = Lector { texto, posicion: 0 };
+    let valor = lector.valor(0)?;
+    lector.espacios();
+    if lector.posicion < texto.len() {
+        return Err(lector.error("sobra texto despues del documento"));
+    }
+    Ok(valor)
+}
+
+/// Recorre el texto de un documento JSON byte por byte.
+struct Lector<'a> {
+    texto: &'a str,
+    posicion: usize,
+}
+
+impl Lector<'_> {
+    fn error(&self, causa: &str) -> BombermanError {
+        BombermanError::JsonInvalido {
+            posicion: Some(self.posicion),
+            causa: causa.to_string(),
+        }
+    }
+
+    fn siguiente(&self) -> Option<u8> {
+        self.texto.as_bytes().get(self.posicion).copied()
+    }
+
+    fn espacios(&mut self) {
+        while matches!(self.siguiente(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.posicion += 1;
+        }
+    }
+
+    fn esperar(&mut self, byte: u8, causa: &str) -> Result<(), BombermanError> {
+        self.espacios();
+        if self.siguiente() != Some(byte) {
+            return Err(self.error(causa));
+        }
+        self.posicion += 1;
+        Ok(())
+    }
+
+    fn palabra(&mut self, palabra: &str, valor: Json) -> Result<Json, BombermanError> {
+        if !self.texto[self.posicion..].starts_with(palabra) {
+            return Err(self.error("valor desconocido"));
+        }
+        self.posicion += palabra.len();
+        Ok(valor)
+    }
+
+    fn valor(&mut self, profundidad: usize) -> Result<Json, BombermanError> {
+        self.espacios();
+        match self.siguiente() {
+            Some(b'[' | b'{') if profundidad >= PROFUNDIDAD_MAXIMA => {
+                Err(self.error("demasiados niveles anidados"))
+            }
+            Some(b'[') => self.lista(profundidad),
+            Some(b'{') => self.objeto(profundidad),
+            Some(b'"') => self.texto().map(Json::Texto),
+            Some(b't') => self.palabra("true", Json::Booleano(true)),
+            Some(b'f') => self.palabra("false", Json::Booleano(false)),
+            Some(b'n') => self.palabra("null", Json::Nulo),
+            Some(b'-' | b'0'..=b'9') => self.numero(),
+            Some(_) => Err(self.error("valor desconocido")),
+            None => Err(self.error("el documento termina antes de tiempo")),
+        }
+    }
+
+    fn lista(&mut self, profundidad: usize) -> Result<Json, BombermanError> {
+        self.posicion += 1;
+        let mut valores = Vec::new();
+        self.espacios();
+        if self.siguiente() == Some(b']') {
+            self.posicion += 1;
+            return Ok(Json::Lista(valores));
+        }
+        loop {
+            valores.push(self.valor(profundidad + 1)?);
+            self.espacios();
+            match self.siguiente() {
+                Some(b',') => self.posicion += 1,
+                Some(b']') => {
+                    self.posicion += 1;
+                    return Ok(Json::Lista(valores));
+                }
+                _ => return Err(self.error("se esperaba ',' o ']'")),
+            }
+        }
+    }
+
+    fn objeto(&mut self, profundidad: usize) -> Result<Json, BombermanError> {
+        self.posicion += 1;
+        let mut campos = Vec::new();
+        self.espacios();
+        if self.siguiente() == Some(b'}') {
+            self.posicion += 1;
+            return Ok(Json::Objeto(campos));
+        }
+        loop {
+            self.espacios();
+            if self.siguiente() != Some(b'"') {
+                return Err(self.error("se esperaba una clave entre comillas"));
+            }
+            let clave = self.texto()?;
+            self.esperar(b':', "se esperaba ':'")?;
+            campos.push((clave, self.valor(profundidad + 1)?));
+            self.espacios();
+            match self.siguiente() {
+                Some(b',') => self.posicion += 1,
+                Some(b'}') => {
+                    self.posicion += 1;
+                    return Ok(Json::Objeto(campos));
+                }
+                _ => return Err(self.error("se esperaba ',' o '}'")),
+            }
+        }
+    }
+
+    fn numero(&mut self) -> Result<Json, BombermanError> {
+        let inicio = self.posicion;
+        while matches!(
+            self.siguiente(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.posicion += 1;
+        }
+        match self.texto[inicio..self.posicion].parse() {
+            Ok(numero) => Ok(Json::Numero(numero)),
+            Err(_) => {
+                self.posicion = inicio;
+                Err(self.error("numero invalido"))
+            }
+        }
+    }
+
+    fn texto(&mut self) -> Result<String, BombermanError> {
+        self.posicion += 1;
+        let mut texto = String::new();
+        loop {
+            let resto = &self.texto[self.posicion..];
+            let Some(caracter) = resto.chars().next() else {
+                return Err(self.error("falta cerrar las comillas"));
+            };
+            match caracter {
+                '"' => {
+                    self.posicion += 1;
+                    return Ok(texto);
+                }
+                '\\' => {
+                    self.posicion += 1;
+                    texto.push(self.escape()?);
+                }
+                c if c.is_control() => return Err(self.error("caracter de control sin escapar")),
+                c => {
+                    self.posicion += c.len_utf8();
+                    texto.push(c);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, BombermanError> {
+        let caracter = match self.siguiente() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.posicion += 1;
+                let alto = self.hexadecimal()?;
+                let codigo = if (0xD800..0xDC00).contains(&alto) {
+                    // Los caracteres fuera del plano basico vienen como un par de sustitutos.
+                    if !self.texto[self.posicion..].starts_with("\\u") {
+                        return Err(self.error("falta la segunda mitad del par de sustitutos"));
+                    }
+                    self.posicion += 2;
+                    let bajo = self.hexadecimal()?;
+                    if !(0xDC00..0xE000).contains(&bajo) {
+                        return Err(self.error("par de sustitutos invalido"));
+                    }
+                    0x10000 + ((alto - 0xD800) << 10) + (bajo - 0xDC00)
+                } else {
+                    alto
+                };
+                return char::from_u32(codigo).ok_or_else(|| self.error("escape \\u invalido"));
+            }
+            _ => return Err(self.error("escape invalido")),
+        };
+        self.posicion += 1;
+        Ok(caracter)
+    }
+
+    fn hexadecimal(&mut self) -> Result<u32, BombermanError> {
+        let digitos = self
+            .texto
+            .get(self.posicion..self.posicion + 4)
+            .ok_or_else(|| self.error("escape \\u incompleto"))?;
+        let codigo =
+            u32::from_str_radix(digitos, 16).map_err(|_| self.error("escape \\u invalido"))?;
+        self.posicion += 4;
+        Ok(codigo)
+    }
+}
+
+/// Se escribe en una sola linea, sin espacios, con las cadenas escapadas como en `io::escribir_cadena_json`.
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Nulo => write!(f, "null"),
+            Json::Booleano(valor) => write!(f, "{}", valor),
+            Json::Numero(numero) if numero.is_finite() => write!(f, "{}", numero),
+            Json::Numero(_) => write!(f, "null"),
+            Json::Texto(texto) => escribir_cadena(texto, f),
+            Json::Lista(valores) => {
+                write!(f, "[")?;
+                for (i, valor) in valores.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", valor)?;
+                }
+                write!(f, "]")
+            }
+            Json::Objeto(campos) => {
+                write!(f, "{{")?;
+                for (i, (clave, valor)) in campos.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    escribir_cadena(clave, f)?;
+                    write!(f, ":{}", valor)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn escribir_cadena(texto: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for caracter in texto.chars() {
+        match caracter {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parsear, Json};
+    use crate::bomberman::error::BombermanError;
+
+    #[test]
+    fn test01_se_vuelve_a_escribir_igual() {
+        let texto = r#"{"tablero":[["B1","_"],["F2{id=\"a\"}","W"]],"metadatos":{"nivel":3,"ok":true,"x":null,"s":"💣"}}"#;
+        let json = parsear(texto).unwrap();
+        assert_eq!(json.to_string(), texto);
+        assert_eq!(
+            parsear(" [ \"\\u00f1\\ud83d\\udca3\\n\" , -1.5e1 ] ").unwrap(),
+            Json::Lista(vec![Json::Texto("ñ💣\n".to_string()), Json::Numero(-15.0)])
+        );
+    }
+
+    #[test]
+    fn test02_errores_con_su_posicion() {
+        let errores = [
+            ("[\"B1\" \"_\"]", 6, "se esperaba ',' o ']'"),
+            ("{\"a\" 1}", 5, "se esperaba ':'"),
+            ("[\"B1", 4, "falta cerrar las comillas"),
+            ("[1] x", 4, "sobra texto despues del documento"),
+            ("", 0, "el documento termina antes de tiempo"),
+        ];
+        for (texto, esperada, esperado) in errores {
+            match parsear(texto) {
+                Err(BombermanError::JsonInvalido { posicion, causa }) => {
+                    assert_eq!(
+                        (posicion, causa.as_str()),
+                        (Some(esperada), esperado),
+                        "{}",
+                        texto
+                    )
+                }
+                otro => panic!("{}: {:?}", texto, otro),
+            }
+        }
+        assert!(parsear(&"[".repeat(100_000)).is_err());
+    }
+}
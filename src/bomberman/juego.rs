@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use super::bomba::Bomba;
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::error::BombermanError;
+use super::evento::Evento;
+use super::motor::Resultado;
+use super::movimiento::{Estrategia, Quietos, Situacion};
+use super::punto::Punto;
+use super::reglas::Reglas;
+use super::simulacion::SimulacionBuilder;
+use super::tablero::Tablero;
+
+/// Cuantos tics tarda en explotar una bomba que coloca el jugador.
+pub const MECHA: usize = 3;
+
+/// Una partida con un jugador (`P`) que se mueve por el tablero y coloca bombas, en lugar de una sola detonacion.
+/// Cada accion del jugador es un tic: cuando se termina la mecha de una bomba que coloco, explota con toda su
+/// reaccion en cadena como en `Bomberman::simular`, y si alguna rafaga lo alcanza el jugador muere.
+///
+/// Mientras el jugador esta parado sobre la bomba que acaba de colocar, la casilla muestra la bomba. Al final de
+/// cada tic los enemigos se mueven segun la `Estrategia` del juego; por defecto se quedan quietos.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let mut juego = Juego::new(Tablero::desde_texto("P _ F1\n_ _ _")?)?;
+/// juego.colocar_bomba(2)?;
+/// juego.mover(Direccion::Abajo)?;
+/// juego.mover(Direccion::Derecha)?;
+/// juego.esperar()?;
+/// assert_eq!(juego.tablero().to_texto(), "_ _ _\n_ P _");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Juego {
+    tablero: Tablero,
+    reglas: Reglas,
+    /// Donde esta el jugador, o None si murio.
+    jugador: Option<Punto>,
+    /// Las bombas que coloco el jugador y cuantos tics les faltan para explotar, en el orden en que las coloco.
+    colocadas: Vec<(Punto, usize)>,
+    tic: usize,
+    eventos: Vec<Evento>,
+    estrategia: Box<dyn Estrategia>,
+    /// La direccion en la que se movio por ultima vez cada enemigo, por donde esta ahora.
+    direcciones: HashMap<Punto, Direccion>,
+}
+
+impl Juego {
+    /// Empieza una partida sobre el tablero dado, con las reglas por defecto.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el Juego, o un Err(String) si el tablero no tiene exactamente un jugador.
+    pub fn new(tablero: Tablero) -> Result<Juego, String> {
+        let jugadores: Vec<Punto> = tablero
+            .celdas()
+            .filter(|(_, celda)| **celda == Celda::Jugador)
+            .map(|(punto, _)| punto)
+            .collect();
+        let [jugador] = jugadores[..] else {
+            return Err(format!(
+                "Error: un juego necesita exactamente un jugador (P) y el tablero tiene {}",
+                jugadores.len()
+            ));
+        };
+        Ok(Juego {
+            tablero,
+            reglas: Reglas::default(),
+            jugador: Some(jugador),
+            colocadas: Vec::new(),
+            tic: 0,
+            eventos: Vec::new(),
+            estrategia: Box::new(Quietos),
+            direcciones: HashMap::new(),
+        })
+    }
+
+    /// Las variantes de reglas con las que explotan las bombas. Con `Reglas::toroidal` el jugador tambien puede salir
+    /// por un borde y entrar por el otro.
+    pub fn reglas(mut self, reglas: Reglas) -> Juego {
+        self.reglas = reglas;
+        self
+    }
+
+    /// Como se mueven los enemigos (ver `movimiento`).
+    pub fn estrategia(mut self, estrategia: Box<dyn Estrategia>) -> Juego {
+        self.estrategia = estrategia;
+        self
+    }
+
+    /// El tablero como esta ahora.
+    pub fn tablero(&self) -> &Tablero {
+        &self.tablero
+    }
+
+    /// Donde esta el jugador, o None si murio.
+    pub fn jugador(&self) -> Option<Punto> {
+        self.jugador
+    }
+
+    /// Cuantos tics pasaron desde que empezo la partida.
+    pub fn tic(&self) -> usize {
+        self.tic
+    }
+
+    /// Los eventos de todas las explosiones de la partida, en orden, y la muerte del jugador si lo atrapo un
+    /// enemigo.
+    pub fn eventos(&self) -> &[Evento] {
+        &self.eventos
+    }
+
+    /// Como va la partida: Derrota si murio el jugador, Victoria si murieron todos los enemigos y Parcial mientras
+    /// quede alguno.
+    pub fn resultado(&self) -> Resultado {
+        if self.jugador.is_none() {
+            Resultado::Derrota
+        } else if self
+            .tablero
+            .celdas()
+            .any(|(_, celda)| matches!(celda, Celda::Enemigo { .. }))
+        {
+            Resultado::Parcial
+        } else {
+            Resultado::Victoria
+        }
+    }
+
+    /// Mueve al jugador una casilla en la direccion dada, si esta vacia: las paredes, las rocas y todo lo demas lo
+    /// frenan. Aunque no se pueda mover, pasa un tic.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con true si el jugador se movio y false si no pudo o ya estaba muerto (entonces no pasa el tic), o el
+    /// Err(BombermanError) de una bomba que exploto en ese tic.
+    pub fn mover(&mut self, direccion: Direccion) -> Result<bool, BombermanError> {
+        let Some(jugador) = self.jugador else {
+            return Ok(false);
+        };
+        let destino = self
+            .tablero
+            .avanzar(jugador, direccion, self.reglas.toroidal)
+            .filter(|destino| self.tablero.get(*destino) == Some(&Celda::Vacio));
+        if let Some(destino) = destino {
+            // Si estaba sobre su bomba, la bomba queda.
+            if self.tablero.get(jugador) == Some(&Celda::Jugador) {
+                self.tablero.set(jugador, Celda::Vacio);
+            }
+            self.tablero.set(destino, Celda::Jugador);
+            self.jugador = Some(destino);
+        }
+        self.avanzar_tic()?;
+        Ok(destino.is_some())
+    }
+
+    /// Coloca una bomba normal con el alcance dado en la casilla del jugador al terminar el tic, y explota `MECHA`
+    /// tics despues.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con true si se coloco la bomba y false si el alcance es 0, si el jugador ya esta sobre una bomba o
+    /// si ya estaba muerto (entonces no pasa el tic), o el Err(BombermanError) de una bomba que exploto en ese tic.
+    pub fn colocar_bomba(&mut self, alcance: usize) -> Result<bool, BombermanError> {
+        let Some(jugador) = self.jugador else {
+            return Ok(false);
+        };
+        let colocada = alcance > 0 && self.tablero.get(jugador) == Some(&Celda::Jugador);
+        self.avanzar_tic()?;
+        // La mecha empieza a contar en el tic siguiente. Si en este tic exploto otra bomba y mato al jugador, ya no
+        // la coloca.
+        let colocada = colocada && self.jugador.is_some();
+        if colocada {
+            let bomba = Bomba {
+                traspaso: false,
+                alcances: [alcance; 4],
+                mecha: None,
+            };
+            self.tablero.set(jugador, Celda::Bomba(bomba));
+            self.colocadas.push((jugador, MECHA));
+        }
+        Ok(colocada)
+    }
+
+    /// Deja pasar un tic sin que el jugador haga nada.
+    pub fn esperar(&mut self) -> Result<(), BombermanError> {
+        self.avanzar_tic()
+    }
+
+    /// Resta un tic a la mecha de cada bomba colocada y hace explotar, en el orden en que se colocaron, las que se
+    /// terminaron. Una bomba colocada que exploto antes por la reaccion en cadena de otra ya no cuenta.
+    fn avanzar_tic(&mut self) -> Result<(), BombermanError> {
+        self.tic += 1;
+        let mut terminadas = Vec::new();
+        for (punto, mecha) in &mut self.colocadas {
+            *mecha -= 1;
+            if *mecha == 0 {
+                terminadas.push(*punto);
+            }
+        }
+        for punto in terminadas {
+            if !matches!(self.tablero.get(punto), Some(Celda::Bomba(_))) {
+                continue;
+            }
+            let salida = SimulacionBuilder::new(self.tablero.clone())
+                .reglas(self.reglas.clone())
+                .construir()
+                .simular(punto.columna(), punto.fila())?;
+            let alcanzado = self.jugador.filter(|j| salida.alcanzadas.contains(j));
+            self.eventos.extend(salida.eventos.iter().cloned());
+            if let Some(jugador) = alcanzado {
+                // Si estaba sobre una bomba que exploto, ninguna rafaga paso por la casilla del jugador, pero igual
+                // muere.
+                let muerte = Evento::JugadorMuerto { punto: jugador };
+                if !salida.eventos.contains(&muerte) {
+                    self.eventos.push(muerte);
+                }
+                self.jugador = None;
+            }
+            self.tablero = salida.tablero;
+        }
+        let tablero = &self.tablero;
+        self.colocadas.retain(|(punto, mecha)| {
+            *mecha > 0 && matches!(tablero.get(*punto), Some(Celda::Bomba(_)))
+        });
+        self.mover_enemigos();
+        Ok(())
+    }
+
+    /// Mueve cada enemigo, en el orden de lectura del tablero, a donde lo mande la estrategia si esa casilla esta
+    /// libre. El enemigo que entra a la casilla del jugador lo mata.
+    fn mover_enemigos(&mut self) {
+        let enemigos: Vec<Punto> = self
+            .tablero
+            .celdas()
+            .filter(|(_, celda)| matches!(celda, Celda::Enemigo { .. }))
+            .map(|(punto, _)| punto)
+            .collect();
+        let mut direcciones = HashMap::new();
+        for enemigo in enemigos {
+            let situacion = Situacion {
+                tablero: &self.tablero,
+                enemigo,
+                jugador: self.jugador,
+                anterior: self.direcciones.get(&enemigo).copied(),
+                toroidal: self.reglas.toroidal,
+            };
+            let paso = self
+                .estrategia
+                .elegir(&situacion)
+                .and_then(|direccion| Some((direccion, situacion.destino(direccion)?)));
+            let Some((direccion, destino)) = paso else {
+                if let Some(anterior) = situacion.anterior {
+                    direcciones.insert(enemigo, anterior);
+                }
+                continue;
+            };
+            if let Some(celda) = self.tablero.set(enemigo, Celda::Vacio) {
+                if self.tablero.set(destino, celda) == Some(Celda::Jugador) {
+                    self.jugador = None;
+                    self.eventos.push(Evento::JugadorMuerto { punto: destino });
+                }
+            }
+            direcciones.insert(destino, direccion);
+        }
+        self.direcciones = direcciones;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Juego;
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::evento::Evento;
+    use crate::bomberman::motor::Resultado;
+    use crate::bomberman::movimiento::{Patrullar, Perseguir};
+    use crate::bomberman::punto::Punto;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_el_jugador_se_mueve_y_su_bomba_explota_a_los_tres_tics() {
+        let mut juego = Juego::new(Tablero::desde_texto("P _ F1\n_ W R\nB1 _ _").unwrap()).unwrap();
+        assert!(!juego.mover(Direccion::Izquierda).unwrap());
+        assert_eq!(juego.resultado(), Resultado::Parcial);
+        assert!(juego.colocar_bomba(2).unwrap());
+        assert!(!juego.colocar_bomba(2).unwrap());
+        assert!(juego.mover(Direccion::Abajo).unwrap());
+        assert_eq!(juego.tablero().to_texto(), "B2 _ F1\nP W R\nB1 _ _");
+        // La pared lo frena.
+        assert!(!juego.mover(Direccion::Derecha).unwrap());
+        assert_eq!(juego.tic(), 5);
+        // La bomba se coloco en el tic 2 y exploto en el 5. La rafaga hacia abajo mato al jugador y detono la otra.
+        assert_eq!(juego.jugador(), None);
+        // Aunque tambien murio el enemigo.
+        assert_eq!(juego.resultado(), Resultado::Derrota);
+        assert_eq!(juego.tablero().to_texto(), "_ _ _\n_ W R\n_ _ _");
+        assert!(juego.eventos().contains(&Evento::JugadorMuerto {
+            punto: Punto { x: 0, y: 1 }
+        }));
+        assert!(!juego.mover(Direccion::Arriba).unwrap());
+        assert_eq!(juego.tic(), 5);
+
+        assert!(Juego::new(Tablero::desde_texto("P P").unwrap()).is_err());
+        assert!(Juego::new(Tablero::desde_texto("B1 _").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test02_la_bomba_bajo_el_jugador_lo_mata() {
+        let mut juego = Juego::new(Tablero::desde_texto("P W\nW _").unwrap()).unwrap();
+        juego.colocar_bomba(1).unwrap();
+        juego.esperar().unwrap();
+        juego.esperar().unwrap();
+        assert_eq!(juego.jugador(), Some(Punto { x: 0, y: 0 }));
+        juego.esperar().unwrap();
+        assert_eq!(juego.jugador(), None);
+        assert_eq!(juego.tablero().to_texto(), "_ W\nW _");
+        assert_eq!(
+            juego.eventos().last(),
+            Some(&Evento::JugadorMuerto {
+                punto: Punto { x: 0, y: 0 }
+            })
+        );
+    }
+
+    #[test]
+    fn test03_los_enemigos_se_mueven_en_cada_tic() {
+        let tablero = Tablero::desde_texto("P _ _ F1\nW _ W _").unwrap();
+        let mut juego = Juego::new(tablero.clone())
+            .unwrap()
+            .estrategia(Box::new(Perseguir));
+        juego.esperar().unwrap();
+        assert_eq!(juego.tablero().to_texto(), "P _ F1 _\nW _ W _");
+        juego.esperar().unwrap();
+        assert_eq!(juego.jugador(), Some(Punto { x: 0, y: 0 }));
+        juego.esperar().unwrap();
+        assert_eq!(juego.jugador(), None);
+        assert_eq!(
+            juego.eventos(),
+            [Evento::JugadorMuerto {
+                punto: Punto { x: 0, y: 0 }
+            }]
+        );
+
+        // Va y viene entre la pared y el borde.
+        let tablero = Tablero::desde_texto("P W _ _ F1").unwrap();
+        let mut juego = Juego::new(tablero)
+            .unwrap()
+            .estrategia(Box::new(Patrullar(Direccion::Izquierda)));
+        let mut recorrido = Vec::new();
+        for _ in 0..5 {
+            juego.esperar().unwrap();
+            recorrido.push(juego.tablero().to_texto());
+        }
+        assert_eq!(
+            recorrido,
+            [
+                "P W _ F1 _",
+                "P W F1 _ _",
+                "P W _ F1 _",
+                "P W _ _ F1",
+                "P W _ F1 _"
+            ]
+        );
+    }
+}
@@ -0,0 +1,25 @@
+/// Una mejora que deja un enemigo al morir con `Reglas::mejoras`, para que un juego interactivo la pueda juntar. Se
+/// escribe con `+` y su letra, y las rafagas pasan por su casilla sin cambiarla.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mejora {
+    /// `+R`: mas alcance para las bombas.
+    Alcance,
+}
+
+impl Mejora {
+    /// Interpreta la letra de una mejora, o devuelve None si no es ninguna.
+    pub fn desde_letra(letra: char) -> Option<Mejora> {
+        match letra {
+            'R' => Some(Mejora::Alcance),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Mejora {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mejora::Alcance => write!(f, "+R"),
+        }
+    }
+}
@@ -0,0 +1,2229 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+use tracing::{debug, debug_span};
+
+use super::advertencia::Advertencia;
+use super::analizador::Analizador;
+use super::bomba::Bomba;
+use super::celda::Celda;
+use super::comparacion::Diferencia;
+use super::danio::{DanioUnitario, Golpe, ModeloDanio};
+use super::direccion::Direccion;
+use super::disparo::{Disparo, NodoDisparo};
+use super::error::BombermanError;
+use super::estadistica::{EstadisticaBomba, ResumenSimulacion};
+use super::evento::{Evento, Observador};
+use super::hexagonal::{self, TableroHex};
+use super::mejora::Mejora;
+use super::punto::{self, Columna, Fila, Punto};
+use super::reglas::{Limites, Reglas};
+use super::simulacion::Simulacion;
+use super::tablero::Tablero;
+
+/// Representa un juego de Bomberman sobre un Tablero de Celdas.
+///
+/// El recorrido de cada simulacion queda en `tracing`, en el nivel debug: un span `bomba` por cada bomba que explota,
+/// adentro uno `rafaga` por cada direccion y adentro de ese un evento `casilla` por cada casilla que alcanza, con lo
+/// que habia en ella antes de que la afectara.
+pub struct Bomberman {
+    pub tablero: Tablero,
+    pub pila_bombas: Vec<punto::Punto>,
+    /// Los mismos puntos que `pila_bombas`, para saber si una bomba ya esta apilada sin recorrer la pila.
+    apiladas: HashSet<Punto>,
+    /// Las situaciones no fatales que se corrigieron durante la simulacion.
+    pub advertencias: Vec<Advertencia>,
+    /// Las variantes de reglas con las que se simula.
+    pub reglas: Reglas,
+    /// Los limites que cortan la simulacion. Sin limite de alcance se usa `alcance_maximo_util`.
+    pub limites: Limites,
+    /// Quienes reciben cada Evento de la simulacion.
+    pub observadores: Vec<Box<dyn Observador>>,
+    /// Cuanta vida le quita cada golpe a un enemigo; por defecto `DanioUnitario`. Las simulaciones de `evaluar` usan
+    /// el mismo modelo.
+    pub modelo_danio: Arc<dyn ModeloDanio>,
+    /// Todos los eventos de la simulacion, en el orden en que ocurrieron.
+    pub eventos: Vec<Evento>,
+    /// Las casillas que cambio cada detonacion, en orden.
+    cambios: Vec<Vec<Diferencia>>,
+    /// Las bombas detonadas y las casillas por las que paso alguna rafaga.
+    alcanzadas: HashSet<Punto>,
+    /// Que bomba hizo explotar a cual, en orden.
+    disparos: Vec<Disparo>,
+    /// Lo que hizo cada bomba detonada, en orden.
+    estadisticas: Vec<EstadisticaBomba>,
+    /// Las casillas por las que pasaron las rafagas de la bomba que esta explotando, para su EstadisticaBomba.
+    casillas_bomba: HashSet<Punto>,
+    /// Las casillas que recorrio cada rafaga de la bomba que esta explotando, en orden, para su EstadisticaBomba.
+    recorridos_bomba: Vec<Vec<Punto>>,
+    /// Como estaban antes las casillas que cambio la bomba que esta explotando, para registrar sus cambios y para
+    /// dejarlas como estaban si la detonacion termina en un error.
+    originales: HashMap<Punto, Celda>,
+    /// La bomba y el alcance inicial de la rafaga que se esta propagando, para calcular la distancia de cada Disparo.
+    rafaga_actual: Option<(Punto, usize)>,
+    /// Cuantas vidas perdio cada enemigo en esta onda, para `Reglas::golpes_por_onda`.
+    golpes: HashMap<Punto, usize>,
+    /// Las bombas temporizadas con la mecha encendida y cuantos tics les faltan, en el orden en que se encendieron.
+    encendidas: Vec<(Punto, usize)>,
+    /// El tic actual: 0 durante la reaccion en cadena de la jugada, y uno mas cada vez que se consumen las mechas.
+    tic: usize,
+    /// La pareja de cada portal del tablero, que se arma la primera vez que una rafaga entra a uno. El motor nunca
+    /// cambia los portales, asi que solo hay que volver a armarla si cambia el tablero entre jugadas.
+    portales: Option<HashMap<Punto, Punto>>,
+    /// El alcance maximo util del tablero (ver `alcance_maximo_util`), que se calcula la primera vez que se recorta una
+    /// bomba. Depende de las dimensiones y de los desvios y portales, asi que se arma de nuevo junto con `portales`.
+    alcance_util: Option<usize>,
+    /// false en las simulaciones de `evaluar`, que no guardan eventos, cambios ni disparos.
+    registrar: bool,
+    detonaciones: usize,
+    pasos: usize,
+    inicio: Option<Instant>,
+    /// El estado antes de cada llamada a `comenzar`, para `deshacer`.
+    deshechas: Vec<Instantanea>,
+    /// El estado que dejo cada jugada deshecha, para `rehacer`.
+    rehechas: Vec<Instantanea>,
+    /// true si el tablero es un TableroHex, cuyas bombas lanzan rafagas en las 6 `hexagonal::DIRECCIONES`.
+    pub(super) hexagonal: bool,
+}
+
+/// Todo lo que cambia una jugada en un Bomberman, para volver a ese momento con `deshacer` o `rehacer`.
+struct Instantanea {
+    tablero: Tablero,
+    pila_bombas: Vec<Punto>,
+    advertencias: Vec<Advertencia>,
+    eventos: Vec<Evento>,
+    cambios: Vec<Vec<Diferencia>>,
+    alcanzadas: HashSet<Punto>,
+    disparos: Vec<Disparo>,
+    estadisticas: Vec<EstadisticaBomba>,
+    golpes: HashMap<Punto, usize>,
+    encendidas: Vec<(Punto, usize)>,
+    tic: usize,
+    detonaciones: usize,
+    pasos: usize,
+    inicio: Option<Instant>,
+}
+
+impl Bomberman {
+    /// Crea un Bomberman listo para comenzar con el tablero dado y la configuracion por defecto.
+    /// Para configurar reglas, limites u observadores se puede usar `SimulacionBuilder`.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let mut bomberman = Bomberman::new(tablero);
+    /// bomberman.comenzar(Columna(x), Fila(y))?;
+    /// ```
+    pub fn new(tablero: Tablero) -> Bomberman {
+        Bomberman {
+            tablero,
+            pila_bombas: Vec::new(),
+            apiladas: HashSet::new(),
+            advertencias: Vec::new(),
+            reglas: Reglas::default(),
+            limites: Limites::default(),
+            observadores: Vec::new(),
+            modelo_danio: Arc::new(DanioUnitario),
+            eventos: Vec::new(),
+            cambios: Vec::new(),
+            alcanzadas: HashSet::new(),
+            disparos: Vec::new(),
+            estadisticas: Vec::new(),
+            casillas_bomba: HashSet::new(),
+            recorridos_bomba: Vec::new(),
+            originales: HashMap::new(),
+            rafaga_actual: None,
+            golpes: HashMap::new(),
+            encendidas: Vec::new(),
+            tic: 0,
+            portales: None,
+            alcance_util: None,
+            registrar: true,
+            detonaciones: 0,
+            pasos: 0,
+            inicio: None,
+            deshechas: Vec::new(),
+            rehechas: Vec::new(),
+            hexagonal: false,
+        }
+    }
+
+    /// Como `new`, pero sobre un tablero hexagonal (ver `TableroHex`). Las coordenadas de las detonaciones y los
+    /// puntos de los eventos son las coordenadas axiales, y el tablero final se escribe como el inicial.
+    pub fn hexagonal(tablero: TableroHex) -> Bomberman {
+        let mut bomberman = Bomberman::new(tablero.into_tablero());
+        bomberman.hexagonal = true;
+        bomberman
+    }
+
+    /// Toma un Bomberman inicializado y explota la bomba ubicada en las coordenadas dadas, iniciando el juego.
+    /// Devuelve un Result con un OK(()) o un Err(BombermanError) con la descripcion del mismo.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let mut bomberman = bomberman::Bomberman::new(tablero);
+    /// if let Err(e) = bomberman::Bomberman::comenzar(&mut bomberman, punto_bomba.columna(), punto_bomba.fila()){
+    ///     return devolver_error(e.to_string(), salida)
+    /// }
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * self: Un Bomberman con un tablero ya inicializado.
+    /// * columna: Columna de la Bomba (su coordenada X).
+    /// * fila: Fila de la Bomba (su coordenada Y).
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result Ok(()) si todo sale bien, o un Err(BombermanError) con la descripcion del mismo.
+    pub fn comenzar(&mut self, columna: Columna, fila: Fila) -> Result<(), BombermanError> {
+        if self.registrar {
+            let antes = self.instantanea();
+            self.deshechas.push(antes);
+            self.rehechas.clear();
+        }
+        self.jugar(&[Punto::new(columna, fila)])
+    }
+
+    /// Como `comenzar`, pero detona en el mismo instante las bombas de todos los puntos dados, por ejemplo para
+    /// despejar dos esquinas del mapa a la vez. El orden esta definido: primero explotan las bombas pedidas, en el
+    /// orden dado y cada una sobre el tablero que dejaron las anteriores, y recien despues las que alcanzaron, como
+    /// en `comenzar`. Si la rafaga de una bomba pedida alcanza a otra de las pedidas, esa explota una sola vez.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let esquinas = [Punto { x: 0, y: 0 }, Punto { x: 6, y: 6 }];
+    /// bomberman.comenzar_simultaneas(&esquinas)?;
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result Ok(()) si todo sale bien, o un Err(BombermanError::CoordenadasInvalidas) con el primer punto en el
+    /// que no hay una bomba, antes de detonar ninguna.
+    pub fn comenzar_simultaneas(&mut self, puntos: &[Punto]) -> Result<(), BombermanError> {
+        if self.registrar {
+            let antes = self.instantanea();
+            self.deshechas.push(antes);
+            self.rehechas.clear();
+        }
+        self.jugar(puntos)
+    }
+
+    /// Detona las bombas en los puntos dados y toda su reaccion en cadena (ver `comenzar_simultaneas`), sin guardar
+    /// el estado para `deshacer`. Despues de la reaccion en cadena, que ocurre toda en el tic 0, avanza de a un tic
+    /// hasta que exploten todas las bombas temporizadas que se encendieron (ver `siguiente_bomba`).
+    fn jugar(&mut self, iniciales: &[Punto]) -> Result<(), BombermanError> {
+        self.revisar_tamanio()?;
+        if let Some(&Punto { x, y }) = iniciales.iter().find(|punto| {
+            !matches!(
+                self.tablero.get(**punto),
+                Some(Celda::Bomba(_) | Celda::Mina { .. })
+            )
+        }) {
+            return Err(BombermanError::CoordenadasInvalidas { x, y });
+        }
+        self.encendidas.clear();
+        self.golpes.clear();
+        self.tic = 0;
+        self.portales = None;
+        self.alcance_util = None;
+        for (i, punto) in iniciales.iter().enumerate() {
+            if !iniciales[..i].contains(punto) {
+                self.detonar(*punto)?;
+            }
+        }
+        // Las bombas alcanzadas se detonan desde pila_bombas en un ciclo y no recursivamente, asi las cadenas largas
+        // no desbordan la pila de llamadas.
+        while let Some(punto) = self.siguiente_bomba() {
+            // Una bomba pedida que alcanzo otra de las pedidas ya exploto en el primer ciclo.
+            if !iniciales.contains(&punto) {
+                self.detonar(punto)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Saca de pila_bombas la proxima bomba a detonar. Si no queda ninguna pero hay bombas temporizadas encendidas,
+    /// avanza de a un tic restando uno a cada mecha hasta que se termine alguna, y devuelve las que se terminaron en
+    /// ese tic en el orden en que se encendieron; cada una explota con toda su reaccion en cadena antes que la
+    /// siguiente, y las que se encienden en ese tic empiezan a contar en el proximo.
+    ///
+    /// # Devuelve
+    ///
+    /// La bomba, o None si ya no queda ninguna por explotar.
+    pub(super) fn siguiente_bomba(&mut self) -> Option<Punto> {
+        while self.pila_bombas.is_empty() && !self.encendidas.is_empty() {
+            self.tic += 1;
+            let mut consumidas = Vec::new();
+            self.encendidas.retain_mut(|(punto, mecha)| {
+                *mecha -= 1;
+                if *mecha == 0 {
+                    consumidas.push(*punto);
+                }
+                *mecha > 0
+            });
+            for punto in &consumidas {
+                self.notificar(Evento::MechaConsumida {
+                    punto: *punto,
+                    tic: self.tic,
+                });
+            }
+            for punto in consumidas.into_iter().rev() {
+                self.apilar(punto);
+            }
+        }
+        let punto = self.pila_bombas.pop()?;
+        self.apiladas.remove(&punto);
+        Some(punto)
+    }
+
+    /// Deja la bomba en `punto` en pila_bombas, salvo que ya este.
+    ///
+    /// # Devuelve
+    ///
+    /// Si la bomba no estaba en la pila.
+    fn apilar(&mut self, punto: Punto) -> bool {
+        let nueva = self.apiladas.insert(punto);
+        if nueva {
+            self.pila_bombas.push(punto);
+        }
+        nueva
+    }
+
+    /// El tic en que ocurre lo que se esta simulando (ver `siguiente_bomba`).
+    pub(super) fn tic(&self) -> usize {
+        self.tic
+    }
+
+    /// Detona la bomba en el punto dado y aplica su explosion al tablero, dejando en pila_bombas las bombas que alcanzo.
+    /// Una bomba temporizada explota igual, aunque tenga la mecha encendida, y una mina explota como una bomba normal.
+    pub(super) fn detonar(&mut self, punto: Punto) -> Result<(), BombermanError> {
+        let Punto { x, y } = punto;
+        let (mut bomba, tipo) = match self.tablero.get(punto) {
+            Some(Celda::Bomba(bomba)) => (bomba.clone(), bomba.tipo()),
+            Some(Celda::Mina { alcance }) => (
+                Bomba {
+                    traspaso: false,
+                    alcances: [*alcance; 4],
+                    mecha: None,
+                },
+                'M',
+            ),
+            _ => return Err(BombermanError::CoordenadasInvalidas { x, y }),
+        };
+        self.encendidas.retain(|(encendida, _)| *encendida != punto);
+        for alcance in &mut bomba.alcances {
+            *alcance = self.recortar_alcance(punto, *alcance);
+        }
+        self.inicio.get_or_insert_with(Instant::now);
+        self.contar_detonacion()?;
+        self.alcanzadas.insert(Punto { x, y });
+        self.notificar(Evento::Detonacion {
+            punto: Punto { x, y },
+            tipo,
+            alcance: bomba.alcance_maximo(),
+        });
+        let primer_evento = self.eventos.len();
+        self.casillas_bomba.clear();
+        self.recorridos_bomba.clear();
+        self.originales.clear();
+        let explosion = debug_span!("bomba", x, y, alcance = bomba.alcance_maximo())
+            .in_scope(|| Self::explosion(self, x, y, &bomba));
+        let rafagas = match explosion {
+            Err(e) => {
+                for (punto, celda) in self.originales.drain() {
+                    self.tablero.set(punto, celda);
+                }
+                return Err(e);
+            }
+            Ok(rafagas) => rafagas,
+        };
+        if self.registrar {
+            self.cambios
+                .push(diferencias(&self.originales, &self.tablero));
+        }
+        if self.registrar {
+            let eventos = &self.eventos[primer_evento..];
+            let contar = |es: fn(&Evento) -> bool| eventos.iter().filter(|e| es(e)).count();
+            self.estadisticas.push(EstadisticaBomba {
+                punto: Punto { x, y },
+                tipo,
+                casillas_afectadas: self.casillas_bomba.len(),
+                enemigos_daniados: contar(|e| matches!(e, Evento::EnemigoDaniado { .. })),
+                bombas_disparadas: contar(|e| matches!(e, Evento::BombaAlcanzada { .. })),
+                rafagas,
+                recorridos: std::mem::take(&mut self.recorridos_bomba),
+            });
+        }
+        Ok(())
+    }
+
+    /// Toma un Bomberman recien creado, explota la bomba en las coordenadas dadas como `comenzar` y devuelve el resultado completo.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let salida = Bomberman::new(tablero).simular(Columna(x), Fila(y))?;
+    /// for advertencia in &salida.advertencias {
+    ///     eprintln!("{}", advertencia);
+    /// }
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con la SalidaSimulacion o un Err(BombermanError) con el error que la impidio.
+    pub fn simular(
+        mut self,
+        columna: Columna,
+        fila: Fila,
+    ) -> Result<SalidaSimulacion, BombermanError> {
+        self.jugar(&[Punto::new(columna, fila)])?;
+        Ok(self.salida())
+    }
+
+    /// Como `simular`, pero detona a la vez las bombas de todos los puntos dados (ver `comenzar_simultaneas`).
+    pub fn simular_simultaneas(
+        mut self,
+        puntos: &[Punto],
+    ) -> Result<SalidaSimulacion, BombermanError> {
+        self.jugar(puntos)?;
+        Ok(self.salida())
+    }
+
+    /// Vuelve al estado anterior a la ultima llamada a `comenzar` (aunque haya terminado con un error): el tablero, los
+    /// eventos, las advertencias y todo lo demas que registra la simulacion. Sirve para probar otra bomba sobre el
+    /// mismo tablero sin volver a leerlo. A los observadores no se les avisa nada.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// bomberman.comenzar(Columna(0), Fila(0))?;
+    /// println!("{}", bomberman.tablero.to_texto());
+    /// bomberman.deshacer();
+    /// bomberman.comenzar(Columna(2), Fila(4))?;
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// true si habia una jugada para deshacer y false si no.
+    pub fn deshacer(&mut self) -> bool {
+        let Some(anterior) = self.deshechas.pop() else {
+            return false;
+        };
+        let actual = self.instantanea();
+        self.rehechas.push(actual);
+        self.restaurar(anterior);
+        true
+    }
+
+    /// Vuelve a aplicar la ultima jugada deshecha con `deshacer`, sin simularla de nuevo. Una llamada a `comenzar`
+    /// despues de deshacer descarta las jugadas que se podian rehacer.
+    ///
+    /// # Devuelve
+    ///
+    /// true si habia una jugada para rehacer y false si no.
+    pub fn rehacer(&mut self) -> bool {
+        let Some(siguiente) = self.rehechas.pop() else {
+            return false;
+        };
+        let actual = self.instantanea();
+        self.deshechas.push(actual);
+        self.restaurar(siguiente);
+        true
+    }
+
+    /// Copia el estado que cambia una jugada.
+    fn instantanea(&self) -> Instantanea {
+        Instantanea {
+            tablero: self.tablero.clone(),
+            pila_bombas: self.pila_bombas.clone(),
+            advertencias: self.advertencias.clone(),
+            eventos: self.eventos.clone(),
+            cambios: self.cambios.clone(),
+            alcanzadas: self.alcanzadas.clone(),
+            disparos: self.disparos.clone(),
+            estadisticas: self.estadisticas.clone(),
+            golpes: self.golpes.clone(),
+            encendidas: self.encendidas.clone(),
+            tic: self.tic,
+            detonaciones: self.detonaciones,
+            pasos: self.pasos,
+            inicio: self.inicio,
+        }
+    }
+
+    /// Vuelve al estado de una Instantanea.
+    fn restaurar(&mut self, instantanea: Instantanea) {
+        self.tablero = instantanea.tablero;
+        self.apiladas = instantanea.pila_bombas.iter().copied().collect();
+        self.pila_bombas = instantanea.pila_bombas;
+        self.advertencias = instantanea.advertencias;
+        self.eventos = instantanea.eventos;
+        self.cambios = instantanea.cambios;
+        self.alcanzadas = instantanea.alcanzadas;
+        self.disparos = instantanea.disparos;
+        self.estadisticas = instantanea.estadisticas;
+        self.golpes = instantanea.golpes;
+        self.encendidas = instantanea.encendidas;
+        self.tic = instantanea.tic;
+        self.detonaciones = instantanea.detonaciones;
+        self.pasos = instantanea.pasos;
+        self.inicio = instantanea.inicio;
+    }
+
+    /// Toma un Bomberman recien creado y devuelve una Simulacion que detona la bomba en las coordenadas dadas y las
+    /// de la reaccion en cadena de a una, en el mismo orden que `comenzar`, para ver como se desarrolla.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// for paso in Bomberman::new(tablero).paso_a_paso(Columna(x), Fila(y)) {
+    ///     println!("{}\n", paso?.tablero.to_texto());
+    /// }
+    /// ```
+    pub fn paso_a_paso(self, columna: Columna, fila: Fila) -> Simulacion {
+        Simulacion::new(self, Punto::new(columna, fila))
+    }
+
+    /// Arma la SalidaSimulacion con lo que quedo de las detonaciones hechas hasta ahora.
+    pub(super) fn salida(self) -> SalidaSimulacion {
+        SalidaSimulacion {
+            tablero: self.tablero,
+            advertencias: self.advertencias,
+            eventos: self.eventos,
+            cambios: self.cambios,
+            alcanzadas: self.alcanzadas,
+            disparos: self.disparos,
+            estadisticas: self.estadisticas,
+        }
+    }
+
+    /// Las casillas que cambio la ultima detonacion.
+    pub(super) fn ultimos_cambios(&self) -> &[Diferencia] {
+        self.cambios.last().map_or(&[], Vec::as_slice)
+    }
+
+    /// Simula la detonacion de la bomba en las coordenadas dadas sobre una copia del tablero actual y devuelve solo el
+    /// resultado, sin modificar este Bomberman. No avisa a los observadores ni guarda eventos, cambios o disparos, asi
+    /// que es la forma mas rapida de probar muchas jugadas (por ejemplo desde un optimizador).
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let mejor = bombas.iter().max_by_key(|p| bomberman.evaluar(p.columna(), p.fila()).map(|r| r.enemigos_eliminados).unwrap_or(0));
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el ResultadoJuego o un Err(BombermanError) con el error que impidio la simulacion.
+    pub fn evaluar(&self, columna: Columna, fila: Fila) -> Result<ResultadoJuego, BombermanError> {
+        let enemigos_iniciales = contar_enemigos(&self.tablero);
+        let mut copia = self.copia_sin_registro();
+        copia.jugar(&[Punto::new(columna, fila)])?;
+        let enemigos_restantes = contar_enemigos(&copia.tablero);
+        Ok(ResultadoJuego {
+            enemigos_eliminados: enemigos_iniciales - enemigos_restantes,
+            enemigos_restantes,
+            detonaciones: copia.detonaciones,
+            casillas_alcanzadas: copia.alcanzadas.len(),
+        })
+    }
+
+    /// Calcula que casillas tocaria la bomba del punto dado si se la detonara, contando toda la reaccion en cadena:
+    /// las bombas que explotarian y las casillas por las que pasaria alguna rafaga, incluidas las paredes y rocas que
+    /// la frenarian. Como `evaluar`, simula sobre una copia y no modifica este Bomberman, asi que sirve para resaltar
+    /// una jugada antes de hacerla.
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let tocadas = bomberman.celdas_afectadas_por(Punto { x: 0, y: 0 })?;
+    /// assert!(tocadas.contains(&Punto { x: 0, y: 0 }));
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las casillas o un Err(BombermanError) si en ese punto no hay una bomba o la simulacion falla.
+    pub fn celdas_afectadas_por(&self, punto: Punto) -> Result<HashSet<Punto>, BombermanError> {
+        let mut copia = self.copia_sin_registro();
+        copia.jugar(&[punto])?;
+        Ok(copia.alcanzadas)
+    }
+
+    /// Una copia del tablero actual con las mismas reglas, limites y modelo de danio, que no avisa a los observadores
+    /// ni guarda eventos, cambios o disparos.
+    fn copia_sin_registro(&self) -> Bomberman {
+        let mut copia = Bomberman::new(self.tablero.clone());
+        copia.reglas = self.reglas.clone();
+        copia.limites = self.limites.clone();
+        copia.modelo_danio = Arc::clone(&self.modelo_danio);
+        copia.registrar = false;
+        copia.hexagonal = self.hexagonal;
+        copia
+    }
+
+    /// Devuelve una vista de solo lectura del tablero actual con las mismas reglas, limites y modelo de danio, que se
+    /// puede compartir entre hilos para analizarlo sin modificar este Bomberman (ver `Analizador`).
+    ///
+    /// # Ejemplos
+    /// ```ignore
+    /// let mapa = bomberman.analizador().mapa_de_peligro()?;
+    /// bomberman.comenzar(Columna(x), Fila(y))?;
+    /// ```
+    pub fn analizador(&self) -> Analizador {
+        Analizador::new(
+            self.tablero.clone(),
+            self.reglas.clone(),
+            self.limites.clone(),
+        )
+        .con_modelo_danio(Arc::clone(&self.modelo_danio))
+    }
+
+    /// Le pasa el evento a todos los observadores y lo guarda en el registro de eventos.
+    fn notificar(&mut self, evento: Evento) {
+        if !self.registrar {
+            return;
+        }
+        for observador in &mut self.observadores {
+            observador.notificar(&evento);
+        }
+        self.eventos.push(evento);
+    }
+
+    /// Devuelve un error si el tablero tiene mas casillas que `Limites::casillas`.
+    pub(super) fn revisar_tamanio(&self) -> Result<(), BombermanError> {
+        let casillas = self.tablero.ancho() * self.tablero.alto();
+        match self.limites.casillas {
+            Some(maximo) if casillas > maximo => {
+                Err(BombermanError::LimiteTablero { casillas, maximo })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Cuenta una bomba mas en la cadena, devolviendo un error si se supera `Limites::cadena`.
+    fn contar_detonacion(&mut self) -> Result<(), BombermanError> {
+        self.detonaciones += 1;
+        match self.limites.cadena {
+            Some(max) if self.detonaciones > max => Err(BombermanError::LimiteCadena),
+            _ => Ok(()),
+        }
+    }
+
+    /// Cuenta una casilla mas recorrida por las rafagas, devolviendo un error si se supera `Limites::pasos`
+    /// o si la simulacion ya lleva mas de `Limites::tiempo`.
+    fn contar_paso(&mut self) -> Result<(), BombermanError> {
+        self.pasos += 1;
+        if let Some(max) = self.limites.pasos {
+            if self.pasos > max {
+                return Err(BombermanError::LimitePasos);
+            }
+        }
+        if let (Some(tiempo), Some(inicio)) = (self.limites.tiempo, self.inicio) {
+            if inicio.elapsed() > tiempo {
+                return Err(BombermanError::LimiteTiempo);
+            }
+        }
+        Ok(())
+    }
+
+    /// Agrega una advertencia, salvo que ya se haya informado la misma.
+    fn advertir(&mut self, advertencia: Advertencia) {
+        if !self.advertencias.contains(&advertencia) {
+            self.advertencias.push(advertencia);
+        }
+    }
+
+    /// Anota que la rafaga que se esta propagando, a la que le quedaba `alcance`, alcanzo la bomba en `destino`.
+    fn anotar_disparo(&mut self, destino: Punto, direccion: Direccion, alcance: usize) {
+        if let (true, Some((origen, alcance_inicial))) = (self.registrar, self.rafaga_actual) {
+            self.disparos.push(Disparo {
+                origen,
+                destino,
+                direccion,
+                distancia: alcance_inicial - alcance + 1,
+            });
+        }
+    }
+
+    /// Deja en pila_bombas la mina en `punto`, que activo la rafaga que se esta propagando al pasar por ella o por su
+    /// lado, salvo que ya este. El Disparo tiene la distancia hasta la casilla por la que paso la rafaga.
+    fn activar_mina(&mut self, punto: Punto, direccion: Direccion, alcance: usize) {
+        if !self.apilar(punto) {
+            return;
+        }
+        self.anotar_disparo(punto, direccion, alcance);
+        self.notificar(Evento::MinaActivada { punto });
+    }
+
+    /// La otra casilla del portal en el punto dado, o None si no tiene pareja (un Tablero armado con `Tablero::new` no
+    /// controla que la tenga) y la rafaga tiene que pasar de largo.
+    fn pareja_de_portal(&mut self, punto: Punto) -> Option<Punto> {
+        let tablero = &self.tablero;
+        let portales = self.portales.get_or_insert_with(|| {
+            let mut parejas = HashMap::new();
+            for puntos in tablero.portales().into_values() {
+                if let [a, b] = puntos[..] {
+                    parejas.insert(a, b);
+                    parejas.insert(b, a);
+                }
+            }
+            parejas
+        });
+        portales.get(&punto).copied()
+    }
+
+    /// La casilla a la que avanza una rafaga desde el punto dado, con `Reglas::toroidal` (ver `Tablero::avanzar` y
+    /// `TableroHex::avanzar`).
+    fn avanzar(&self, punto: Punto, direccion: Direccion) -> Option<Punto> {
+        match self.hexagonal {
+            true => hexagonal::avanzar(&self.tablero, punto, direccion, self.reglas.toroidal),
+            false => self.tablero.avanzar(punto, direccion, self.reglas.toroidal),
+        }
+    }
+
+    /// Las direcciones en las que una bomba lanza sus rafagas, que son tambien las de las casillas vecinas.
+    fn direcciones(&self) -> &'static [Direccion] {
+        match self.hexagonal {
+            true => &hexagonal::DIRECCIONES,
+            false => &Direccion::TODAS,
+        }
+    }
+
+    /// Si el desvio en el punto dado manda la rafaga fuera del tablero, lo advierte.
+    fn revisar_desvio(&mut self, punto: Punto, direccion: Direccion) {
+        if self.avanzar(punto, direccion).is_none() {
+            self.advertir(Advertencia::DesvioFueraDelTablero { punto, direccion });
+        }
+    }
+
+    /// Recorta el alcance de la bomba en el punto dado al limite configurado, o al alcance maximo util del tablero si no hay limite,
+    /// y ademas a `Reglas::alcance_maximo`, dejando una advertencia cuando lo recorta.
+    fn recortar_alcance(&mut self, punto: Punto, alcance: usize) -> usize {
+        let tablero = &self.tablero;
+        let limite = match self.limites.alcance {
+            Some(limite) => limite,
+            None => *self
+                .alcance_util
+                .get_or_insert_with(|| alcance_maximo_util(tablero)),
+        }
+        .min(self.reglas.alcance_maximo.unwrap_or(usize::MAX));
+        if alcance <= limite {
+            return alcance;
+        }
+        self.advertir(Advertencia::AlcanceRecortado {
+            punto,
+            alcance,
+            limite,
+        });
+        limite
+    }
+
+    /// Toma las coordenadas de una Bomba en un tablero de Bomberman con sus características y aplica su explosion sobre el
+    /// mismo tablero, anotando en `originales` como estaba cada casilla que cambia. No copia el tablero: si la explosion
+    /// termina en un error, `detonar` deshace los cambios con esas casillas.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// match Self::explosion(self, x, y, &bomba){
+    ///     Err(e) => return Err(e),
+    ///     Ok(rafagas) => self.estadisticas.push(...)
+    /// }
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * self: Un Bomberman con un tablero inicializado.
+    /// * x: Coordenada x de la bomba.
+    /// * y: Coordenada y de la bomba.
+    /// * bomba: El tipo de la bomba y la cantidad de celdas que puede afectar en linea recta en cada direccion.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con cuantas casillas recorrio la rafaga de cada direccion, o en su defecto un Err(BombermanError) con el
+    /// error que ocurrio.
+    fn explosion(
+        &mut self,
+        x: usize,
+        y: usize,
+        bomba: &Bomba,
+    ) -> Result<Vec<(Direccion, usize)>, BombermanError> {
+        // se llama por cada bomba que se active y deja el estado final en el tablero
+        let origen = Punto { x, y };
+        if let Some(celda) = self.tablero.set(origen, Celda::Vacio) {
+            self.originales.insert(origen, celda);
+        }
+        let mut resultados: Vec<Result<(), BombermanError>> = Vec::new();
+        let mut rafagas = Vec::new();
+        // Con `Reglas::un_golpe_por_bomba` todas las rafagas comparten los enemigos afectados.
+        let mut afectados_por_la_bomba = HashSet::new();
+        for &direccion in self.direcciones() {
+            // En un TableroHex las bombas tienen el mismo alcance en todas las direcciones.
+            let alcance = match self.hexagonal {
+                true => bomba.alcance_maximo(),
+                false => bomba.alcance(direccion),
+            };
+            self.rafaga_actual = Some((origen, alcance));
+            let pasos = self.pasos;
+            if self.registrar {
+                self.recorridos_bomba.push(Vec::new());
+            }
+            let inicio = self.avanzar(origen, direccion);
+            let mut afectados_por_la_rafaga = HashSet::new();
+            let afectados = match self.reglas.un_golpe_por_bomba {
+                true => &mut afectados_por_la_bomba,
+                false => &mut afectados_por_la_rafaga,
+            };
+            resultados.push(debug_span!("rafaga", %direccion).in_scope(|| {
+                Self::explosion_dirigida(
+                    self,
+                    alcance,
+                    inicio,
+                    bomba.traspaso,
+                    afectados,
+                    direccion,
+                )
+            }));
+            rafagas.push((direccion, self.pasos - pasos));
+        }
+        for resultado in resultados {
+            resultado?
+        }
+        Ok(rafagas)
+    }
+
+    /// Toma la ubicacion actual de la explosion, con algunas caracteristicas de la bomba que la creo y un set de los enemigos que ya fueron afectados por esta rama.
+    /// Luego devuelve un Ok(()) o un Err(BombermanError) con el error que lo ocasiono.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let mut resultados: Vec<Result<(),BombermanError>> = Vec::new();
+    ///
+    /// resultados.push(Self::explosion_dirigida(self,alcance,Direccion::Izquierda.aplicar(origen),bomba.traspaso,&mut HashSet::new(),Direccion::Izquierda,));
+    /// resultados.push(Self::explosion_dirigida(self,alcance,Direccion::Arriba.aplicar(origen),bomba.traspaso,&mut HashSet::new(),Direccion::Arriba,));
+    ///
+    /// for resultado in resultados{
+    ///     if let Err(e) = resultado{
+    ///         return Err(e)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * self: Un Bomberman inicializado.
+    /// * alcance: La cantidad de celdas que le quedan por recorrer a esa rafaga (0 si ya no tiene que afectar la posicion que se le pasa).
+    /// * punto: Ubicacion actual de la rafaga/rama de la explosion, o None si la rafaga ya salio del tablero por arriba o por la izquierda.
+    /// * traspaso: true si la rafaga es de una bomba de traspaso, que atraviesa las rocas.
+    /// * enemigos afectados: Un HashSet con las posiciones de los enemigos a los que no tiene que lastimar la rafaga/rama de la explosion.
+    /// * direccion: La direccion en la que avanza la rafaga.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con un Ok(()) si todo sale bien o un Err(BombermanError) con el error que lo ocasiono.
+    fn explosion_dirigida(
+        &mut self,
+        alcance: usize,
+        punto: Option<Punto>,
+        traspaso: bool,
+        enemigos_afectados: &mut HashSet<punto::Punto>,
+        direccion: Direccion,
+    ) -> Result<(), BombermanError> {
+        // Cada vuelta del ciclo avanza la rafaga una casilla; antes era una llamada recursiva por casilla.
+        let mut alcance = alcance;
+        let mut punto = punto;
+        let mut direccion = direccion;
+        // Los desvios por los que ya paso esta rafaga y hacia donde la mandaron: si vuelve a uno en la misma
+        // direccion, de ahi en mas solo repetiria el mismo recorrido.
+        let mut desvios_visitados: HashSet<(Punto, Direccion)> = HashSet::new();
+        loop {
+            let actual = match punto {
+                Some(actual) if alcance > 0 && self.tablero.en_rango(actual) => actual,
+                _ => return Ok(()),
+            };
+            self.contar_paso()?;
+            self.alcanzadas.insert(actual);
+            if self.registrar {
+                self.casillas_bomba.insert(actual);
+                if let Some(recorrido) = self.recorridos_bomba.last_mut() {
+                    recorrido.push(actual);
+                }
+            }
+
+            // Desde donde sigue la rafaga: la misma casilla, o la pareja si entro a un portal.
+            let mut salida = actual;
+            let celda = &self.tablero[actual];
+            debug!(x = actual.x, y = actual.y, %celda, "casilla");
+            let gasta_alcance =
+                self.reglas.desvios_consumen_alcance || !matches!(celda, Celda::Desvio(_));
+            match *celda {
+                Celda::Vacio | Celda::Mejora(_) => {}
+                Celda::Portal { .. } => {
+                    if let Some(pareja) = self.pareja_de_portal(actual) {
+                        self.alcanzadas.insert(pareja);
+                        if self.registrar {
+                            self.casillas_bomba.insert(pareja);
+                            if let Some(recorrido) = self.recorridos_bomba.last_mut() {
+                                recorrido.push(pareja);
+                            }
+                        }
+                        self.notificar(Evento::Teletransporte {
+                            entrada: actual,
+                            salida: pareja,
+                        });
+                        salida = pareja;
+                    }
+                }
+                Celda::Desvio(nueva) => {
+                    if !desvios_visitados.insert((actual, nueva)) {
+                        self.notificar(Evento::CicloDeDesvios {
+                            punto: actual,
+                            direccion: nueva,
+                        });
+                        return Ok(());
+                    }
+                    self.revisar_desvio(actual, nueva);
+                    self.notificar(Evento::Desvio {
+                        punto: actual,
+                        direccion: nueva,
+                    });
+                    direccion = nueva;
+                }
+                Celda::Roca => {
+                    if !traspaso {
+                        self.notificar(Evento::RafagaFrenada {
+                            punto: actual,
+                            casilla: celda.to_string(),
+                        });
+                        return Ok(());
+                    }
+                    self.notificar(Evento::RocaAtravesada { punto: actual });
+                }
+                Celda::Bomba(Bomba {
+                    mecha: Some(mecha), ..
+                }) => {
+                    // Tambien puede estar en pila_bombas si su mecha se termino en este tic.
+                    let encendida = self.encendidas.iter().any(|(punto, _)| *punto == actual);
+                    if !encendida && !self.apiladas.contains(&actual) {
+                        self.encendidas.push((actual, mecha));
+                        self.anotar_disparo(actual, direccion, alcance);
+                        self.notificar(Evento::MechaEncendida {
+                            punto: actual,
+                            mecha,
+                        });
+                    }
+                    return Ok(());
+                }
+                Celda::Bomba(_) => {
+                    if !self.apilar(actual) {
+                        self.advertir(Advertencia::BombaRepetida { punto: actual });
+                    } else {
+                        self.anotar_disparo(actual, direccion, alcance);
+                        self.notificar(Evento::BombaAlcanzada { punto: actual });
+                    }
+                    return Ok(());
+                }
+                Celda::Enemigo { vida, blindado } => {
+                    let celda = Celda::Enemigo { vida, blindado };
+                    let golpes = self.golpes.get(&actual).copied().unwrap_or(0);
+                    let agotado = self.reglas.golpes_por_onda.is_some_and(|max| golpes >= max);
+                    if blindado && !traspaso {
+                        self.notificar(Evento::BlindajeResiste { punto: actual });
+                    } else if agotado && !enemigos_afectados.contains(&actual) {
+                        self.notificar(Evento::GolpesAgotados { punto: actual });
+                    } else {
+                        let origen = self.rafaga_actual.map_or(actual, |(origen, _)| origen);
+                        let golpe = Golpe {
+                            punto: actual,
+                            vida,
+                            origen,
+                            traspaso,
+                            blindado,
+                            dato: None,
+                        };
+                        self.originales
+                            .entry(actual)
+                            .or_insert_with(|| celda.clone());
+                        match afectar_enemigo(
+                            enemigos_afectados,
+                            golpe,
+                            &mut self.tablero,
+                            self.modelo_danio.as_ref(),
+                        ) {
+                            Some(vida) => {
+                                self.golpes.insert(actual, golpes + 1);
+                                self.notificar(Evento::EnemigoDaniado {
+                                    punto: actual,
+                                    vida,
+                                });
+                                if vida == 0 && self.reglas.mejoras {
+                                    self.tablero.set(actual, Celda::Mejora(Mejora::Alcance));
+                                    self.notificar(Evento::MejoraSoltada {
+                                        punto: actual,
+                                        mejora: Mejora::Alcance,
+                                    });
+                                }
+                            }
+                            None => self.notificar(Evento::EnemigoYaAfectado { punto: actual }),
+                        }
+                    }
+                    if self.reglas.enemigos_bloquean && !traspaso {
+                        self.notificar(Evento::RafagaFrenada {
+                            punto: actual,
+                            casilla: celda.to_string(),
+                        });
+                        return Ok(());
+                    }
+                }
+                Celda::Pared if traspaso && self.reglas.traspaso_atraviesa_paredes => {
+                    self.notificar(Evento::ParedAtravesada { punto: actual });
+                }
+                Celda::Pared => {
+                    self.notificar(Evento::RafagaFrenada {
+                        punto: actual,
+                        casilla: celda.to_string(),
+                    });
+                    return Ok(());
+                }
+                Celda::Ladrillo => {
+                    self.originales.entry(actual).or_insert(Celda::Ladrillo);
+                    self.tablero.set(actual, Celda::Vacio);
+                    self.notificar(Evento::LadrilloRoto { punto: actual });
+                    return Ok(());
+                }
+                Celda::Mina { .. } => {
+                    self.activar_mina(actual, direccion, alcance);
+                    return Ok(());
+                }
+                Celda::Jugador => {
+                    self.originales.entry(actual).or_insert(Celda::Jugador);
+                    self.tablero.set(actual, Celda::Vacio);
+                    self.notificar(Evento::JugadorMuerto { punto: actual });
+                }
+            }
+            // La rafaga sigue de largo por esta casilla, asi que tambien activa las minas de al lado (y las de la
+            // salida del portal, si entro a uno).
+            for pasada in [Some(actual), (salida != actual).then_some(salida)]
+                .into_iter()
+                .flatten()
+            {
+                for &hacia in self.direcciones() {
+                    if let Some(vecina) = self.avanzar(pasada, hacia) {
+                        if matches!(self.tablero.get(vecina), Some(Celda::Mina { .. })) {
+                            self.activar_mina(vecina, direccion, alcance);
+                        }
+                    }
+                }
+            }
+            if gasta_alcance {
+                alcance -= 1;
+            }
+            punto = self.avanzar(salida, direccion);
+        }
+    }
+}
+
+/// El resultado de una simulacion completa: el tablero final, lo que se advirtio en el camino y todo lo que ocurrio.
+pub struct SalidaSimulacion {
+    pub tablero: Tablero,
+    pub advertencias: Vec<Advertencia>,
+    pub eventos: Vec<Evento>,
+    /// Las casillas que cambio cada detonacion, en orden. Ver `celda_en`.
+    pub cambios: Vec<Vec<Diferencia>>,
+    /// Las bombas detonadas y las casillas por las que paso alguna rafaga, incluidas las paredes y rocas que la frenaron.
+    pub alcanzadas: HashSet<Punto>,
+    /// Que bomba hizo explotar a cual, en orden. Ver `NodoDisparo::arbol`.
+    pub disparos: Vec<Disparo>,
+    /// Lo que hizo cada bomba detonada, en el orden en que explotaron.
+    pub estadisticas: Vec<EstadisticaBomba>,
+}
+
+impl SalidaSimulacion {
+    /// Resume la simulacion con los mismos numeros que `Bomberman::evaluar`.
+    ///
+    /// # Argumentos
+    ///
+    /// * inicial: El tablero con el que empezo la simulacion, para contar los enemigos eliminados.
+    pub fn resultado(&self, inicial: &Tablero) -> ResultadoJuego {
+        let enemigos_restantes = contar_enemigos(&self.tablero);
+        ResultadoJuego {
+            enemigos_eliminados: contar_enemigos(inicial).saturating_sub(enemigos_restantes),
+            enemigos_restantes,
+            detonaciones: self
+                .eventos
+                .iter()
+                .filter(|evento| matches!(evento, Evento::Detonacion { .. }))
+                .count(),
+            casillas_alcanzadas: self.alcanzadas.len(),
+        }
+    }
+
+    /// Resume la simulacion: ademas de los numeros de `resultado`, cuantos enemigos y rocas alcanzaron las rafagas y
+    /// que tan larga fue la reaccion en cadena.
+    ///
+    /// # Argumentos
+    ///
+    /// * inicial: El tablero con el que empezo la simulacion, para contar los enemigos eliminados.
+    pub fn resumen(&self, inicial: &Tablero) -> ResumenSimulacion {
+        let resultado = self.resultado(inicial);
+        let daniados: HashSet<Punto> = self
+            .eventos
+            .iter()
+            .filter_map(|evento| match evento {
+                Evento::EnemigoDaniado { punto, .. } => Some(*punto),
+                _ => None,
+            })
+            .collect();
+        let profundidad_cadena = self.estadisticas.first().map_or(0, |primera| {
+            NodoDisparo::arbol(primera.punto, &self.disparos).profundidad()
+        });
+        ResumenSimulacion {
+            bombas_detonadas: resultado.detonaciones,
+            enemigos_eliminados: resultado.enemigos_eliminados,
+            enemigos_daniados: daniados.len(),
+            rocas_atravesadas: self
+                .eventos
+                .iter()
+                .filter(|evento| matches!(evento, Evento::RocaAtravesada { .. }))
+                .count(),
+            casillas_alcanzadas: resultado.casillas_alcanzadas,
+            profundidad_cadena,
+        }
+    }
+
+    /// Reconstruye el contenido de una casilla despues de la detonacion numero `paso`, deshaciendo sobre el tablero final
+    /// los cambios de las detonaciones posteriores.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let salida = Bomberman::new(tablero).simular(Columna(0), Fila(0))?;
+    /// salida.celda_en(0, Punto { x: 4, y: 0 }); // Some("F1"): antes de cualquier detonacion
+    /// salida.celda_en(1, Punto { x: 4, y: 0 }); // Some("_"): despues de la primera
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * paso: La cantidad de detonaciones ya ocurridas (0 es el tablero inicial). Un paso mayor a la cantidad de
+    ///   detonaciones equivale al tablero final.
+    /// * punto: La casilla a consultar.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con el texto de la casilla, o None si el punto esta fuera del tablero.
+    pub fn celda_en(&self, paso: usize, punto: Punto) -> Option<String> {
+        let mut celda = self.tablero.casilla(punto)?;
+        for cambios in self.cambios.iter().skip(paso).rev() {
+            if let Some(cambio) = cambios.iter().find(|cambio| cambio.punto == punto) {
+                celda = cambio.antes.clone()?;
+            }
+        }
+        Some(celda)
+    }
+
+    /// Como `celda_en`, pero con todas las casillas: las filas del tablero despues de la detonacion numero `paso`,
+    /// por ejemplo para escribir un cuadro por detonacion.
+    pub fn filas_en(&self, paso: usize) -> Vec<Vec<String>> {
+        let mut filas = self.tablero.filas();
+        for cambios in self.cambios.iter().skip(paso).rev() {
+            for cambio in cambios {
+                if let Some(antes) = &cambio.antes {
+                    filas[cambio.punto.y][cambio.punto.x] = antes.clone();
+                }
+            }
+        }
+        filas
+    }
+}
+
+/// El resultado de `Bomberman::evaluar`: solo los numeros de la jugada, sin el detalle de lo que ocurrio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultadoJuego {
+    pub enemigos_eliminados: usize,
+    pub enemigos_restantes: usize,
+    /// La cantidad de bombas que explotaron, incluida la primera.
+    pub detonaciones: usize,
+    /// La cantidad de casillas distintas por las que paso alguna rafaga, incluidas las bombas detonadas.
+    pub casillas_alcanzadas: usize,
+}
+
+impl ResultadoJuego {
+    /// Indica si la jugada elimino a todos los enemigos del tablero.
+    pub fn gano(&self) -> bool {
+        self.enemigos_restantes == 0
+    }
+
+    /// Como termino la jugada: Victoria si no quedan enemigos y Parcial si no. Una simulacion nunca es una Derrota,
+    /// porque no hay un jugador que pueda morir (ver `Juego::resultado`).
+    pub fn desenlace(&self) -> Resultado {
+        if self.gano() {
+            Resultado::Victoria
+        } else {
+            Resultado::Parcial
+        }
+    }
+}
+
+/// Como termino una jugada o una partida.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resultado {
+    /// Murieron todos los enemigos.
+    Victoria,
+    /// Quedo algun enemigo vivo.
+    Parcial,
+    /// Murio el jugador de un `Juego`, aunque no queden enemigos.
+    Derrota,
+}
+
+/// Las casillas que cambio una detonacion, a partir de como estaban antes las que toco, ordenadas por fila y columna
+/// como `comparacion::diferencias`. Como el motor nunca cambia el tamanio del tablero ni los datos opacos, alcanza con
+/// comparar las Celdas, y el dato de cada casilla es el mismo antes y despues.
+fn diferencias(originales: &HashMap<Punto, Celda>, despues: &Tablero) -> Vec<Diferencia> {
+    let mut cambios: Vec<Diferencia> = originales
+        .iter()
+        .filter(|(punto, antes)| despues.get(**punto) != Some(*antes))
+        .map(|(punto, antes)| Diferencia {
+            punto: *punto,
+            antes: Some(match despues.dato(*punto) {
+                Some(dato) => format!("{}{{{}}}", antes, dato),
+                None => antes.to_string(),
+            }),
+            despues: despues.casilla(*punto),
+        })
+        .collect();
+    cambios.sort_by_key(|cambio| (cambio.punto.y, cambio.punto.x));
+    cambios
+}
+
+/// Cuenta los enemigos que quedan en el tablero.
+fn contar_enemigos(tablero: &Tablero) -> usize {
+    tablero
+        .celdas()
+        .filter(|(_, celda)| matches!(celda, Celda::Enemigo { .. }))
+        .count()
+}
+
+/// Calcula el mayor alcance que puede tener efecto en el tablero, para no recorrer casillas de mas con bombas enormes.
+///
+/// Sin desvios una rafaga va en linea recta, asi que nunca recorre mas casillas que el lado mas largo del tablero.
+/// Con desvios puede doblar (y con portales, saltar), pero despues de pasar por cada casilla en cada una de las 8 direcciones (contando las
+/// diagonales) solo puede repetir un recorrido que ya hizo, por lo que ese es el limite.
+///
+/// # Argumentos
+///
+/// * tablero: El Tablero del juego.
+///
+/// # Devuelve
+///
+/// El alcance a partir del cual una bomba mas grande no cambia el resultado.
+fn alcance_maximo_util(tablero: &Tablero) -> usize {
+    let alto = tablero.alto();
+    let ancho = tablero.ancho();
+    let hay_desvios = tablero
+        .celdas()
+        .any(|(_, celda)| matches!(celda, Celda::Desvio(_) | Celda::Portal { .. }));
+    if hay_desvios {
+        8 * ancho * alto
+    } else {
+        ancho.max(alto)
+    }
+}
+
+/// Toma el golpe de una rafaga a un enemigo, un Set de los enemigos que ya fueron afectados, el tablero en el que esta
+/// ubicado y el modelo que decide cuanta vida le quita el golpe.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// match afectar_enemigo(enemigos_afectados, golpe, tablero, self.modelo_danio.as_ref()) {
+///     Some(vida) => self.notificar(Evento::EnemigoDaniado { punto, vida }),
+///     None => self.notificar(Evento::EnemigoYaAfectado { punto }),
+/// }
+/// ```
+///
+/// # Argumentos
+///
+/// * enemigos_afectados: Un HashSet de los enemigos a los que no hay que afectar mas de una vez.
+/// * golpe: El enemigo, su vida restante y la rafaga que lo alcanzo. Su dato opaco se completa con el del tablero.
+/// * tablero: El Tablero en el que se encuentra el enemigo.
+/// * modelo: El ModeloDanio de la simulacion.
+///
+/// # Devuelve
+///
+/// Muta el tablero y devuelve Some(vida) con la vida que le queda al enemigo (0 si murio), o None si ya habia sido afectado.
+fn afectar_enemigo(
+    enemigos_afectados: &mut HashSet<punto::Punto>,
+    golpe: Golpe,
+    tablero: &mut Tablero,
+    modelo: &dyn ModeloDanio,
+) -> Option<usize> {
+    let punto = golpe.punto;
+    if !enemigos_afectados.insert(punto) {
+        return None;
+    }
+    let danio = modelo.danio(&Golpe {
+        dato: tablero.dato(punto),
+        ..golpe
+    });
+    let vida = golpe.vida.saturating_sub(danio);
+    let nueva = if vida == 0 {
+        Celda::Vacio
+    } else {
+        Celda::Enemigo {
+            vida,
+            blindado: golpe.blindado,
+        }
+    };
+    tablero.set(punto, nueva);
+    Some(vida)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    use super::{Bomberman, Resultado, ResultadoJuego};
+    use crate::bomberman::advertencia::Advertencia;
+    use crate::bomberman::celda::Celda;
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::error::BombermanError;
+    use crate::bomberman::evento::Evento;
+    use crate::bomberman::mejora::Mejora;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::reglas::Reglas;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_bomba_explota() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "B1".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(1), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test02_bombas_explotan_si_son_afectadas() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "B1".to_string(), "S1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(1), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test03_bombas_no_afectan_si_no_alcanzan() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["B1".to_string(), "_".to_string(), "S1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "S1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test04_solo_bombas_traspaso_atraviesan_rocas_y_nadie_atraviesa_paredes() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["B1".to_string(), "R".to_string(), "B2".to_string()],
+            vec!["_".to_string(), "_".to_string(), "R".to_string()],
+            vec!["B1".to_string(), "W".to_string(), "S2".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["B1".to_string(), "R".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "R".to_string()],
+            vec!["B1".to_string(), "W".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(2), Fila(2)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test05_desvios_cambian_direccion() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "S1".to_string()],
+            vec!["_".to_string(), "B2".to_string(), "DU".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "DU".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(1), Fila(2)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test06_enemigos_mueren_si_no_tienen_vida() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["B1".to_string(), "F1".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test07_enemigos_no_mueren_si_tienen_vida() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["B1".to_string(), "F2".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "F1".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test08_enemigos_sufren_bombas_distintas() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["B2".to_string(), "F2".to_string(), "S1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test09_enemigos_no_sufren_dos_veces_la_misma_bomba() {
+        let mut bomber: Bomberman;
+        let tab_inicial = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["B3".to_string(), "F2".to_string(), "DL".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "F1".to_string(), "DL".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test10_alcance_enorme_se_recorta_al_tablero() {
+        let tab_inicial = vec![
+            vec!["F1".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "W".to_string(), "_".to_string()],
+            vec!["B9".to_string(), "_".to_string(), "F2".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "W".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "F1".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(2)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+        assert_eq!(
+            bomber.advertencias[0].to_string(),
+            "Advertencia: el alcance 9 de la bomba en (0, 2) se recorto a 3"
+        );
+    }
+
+    #[test]
+    fn test11_limite_de_alcance_configurable() {
+        let tab_inicial = vec![
+            vec!["B3".to_string(), "F1".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial.clone()).unwrap());
+        bomber.limites.alcance = Some(1);
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(0)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+        assert_eq!(bomber.advertencias.len(), 1);
+
+        // El tope de las reglas recorta igual que el limite.
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        bomber.reglas.alcance_maximo = Some(1);
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(tab_final, bomber.tablero);
+    }
+
+    #[test]
+    fn test12_enemigos_bloquean_solo_bombas_normales() {
+        let tab_inicial = vec![
+            vec!["B3".to_string(), "F2".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["S3".to_string(), "F2".to_string(), "F1".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "F1".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "F1".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        bomber.reglas.enemigos_bloquean = true;
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(0)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test13_bomba_alcanzada_dos_veces_explota_una_vez() {
+        let tab_inicial = vec![
+            vec!["B4".to_string(), "_".to_string(), "DD".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["DR".to_string(), "_".to_string(), "B1".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "DD".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["DR".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        bomber.simular(Columna(0), Fila(0)).map_or_else(
+            |e| panic!("{}", e),
+            |salida| {
+                assert_eq!(tab_final, salida.tablero);
+                assert!(salida.advertencias.contains(&Advertencia::BombaRepetida {
+                    punto: Punto { x: 2, y: 2 }
+                }));
+            },
+        );
+    }
+
+    #[test]
+    fn test14_desvio_hacia_afuera_se_advierte() {
+        let tab_inicial = vec![
+            vec!["_".to_string(), "DU".to_string(), "_".to_string()],
+            vec!["_".to_string(), "B1".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "DU".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(1), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+        assert_eq!(
+            bomber.advertencias,
+            vec![Advertencia::DesvioFueraDelTablero {
+                punto: Punto { x: 1, y: 0 },
+                direccion: Direccion::Arriba
+            }]
+        );
+    }
+
+    #[test]
+    fn test15_celda_en_reconstruye_estados_intermedios() {
+        let tab_inicial = vec![
+            vec!["B1".to_string(), "B1".to_string(), "F2".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let salida = match Bomberman::new(Tablero::try_from(tab_inicial.clone()).unwrap())
+            .simular(Columna(0), Fila(0))
+        {
+            Ok(salida) => salida,
+            Err(e) => panic!("{}", e),
+        };
+        let enemigo = Punto { x: 2, y: 0 };
+        assert_eq!(salida.celda_en(0, enemigo), Some("F2".to_string()));
+        assert_eq!(salida.celda_en(1, enemigo), Some("F2".to_string()));
+        assert_eq!(salida.celda_en(2, enemigo), Some("F1".to_string()));
+        assert_eq!(
+            salida.celda_en(1, Punto { x: 1, y: 0 }),
+            Some("B1".to_string())
+        );
+        assert_eq!(
+            salida.celda_en(2, Punto { x: 1, y: 0 }),
+            Some("_".to_string())
+        );
+        assert_eq!(salida.celda_en(0, Punto { x: 3, y: 0 }), None);
+        assert_eq!(salida.filas_en(0), tab_inicial);
+        assert_eq!(salida.filas_en(1)[0], ["_", "B1", "F2"]);
+        assert_eq!(salida.filas_en(5), salida.tablero.filas());
+    }
+
+    #[test]
+    fn test16_rafaga_hacia_la_izquierda_avanza() {
+        let tab_inicial = vec![
+            vec!["F1".to_string(), "_".to_string(), "B2".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(2), Fila(0)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test17_alcance_distinto_por_direccion() {
+        let tab_inicial = vec![
+            vec!["_".to_string(), "F1".to_string(), "_".to_string()],
+            vec![
+                "F1".to_string(),
+                "B[U1,R0,L1]".to_string(),
+                "F1".to_string(),
+            ],
+            vec!["_".to_string(), "F1".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "F1".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(1), Fila(1)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test18_los_datos_opacos_se_conservan() {
+        let tab_inicial = vec![
+            vec![
+                "B1{c=rojo}".to_string(),
+                "F2{id=1}".to_string(),
+                "_".to_string(),
+            ],
+            vec!["F1{id=2}".to_string(), "W{x}".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec![
+                "_{c=rojo}".to_string(),
+                "F1{id=1}".to_string(),
+                "_".to_string(),
+            ],
+            vec!["_{id=2}".to_string(), "W{x}".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        match Bomberman::comenzar(&mut bomber, Columna(0), Fila(0)) {
+            Ok(()) => assert_eq!(tab_final, bomber.tablero),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test19_golpes_por_onda_entre_bombas_encadenadas() {
+        let tab_inicial = vec![
+            vec!["B2".to_string(), "F2".to_string(), "B1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial.clone()).unwrap());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.filas()[0][1], "_");
+
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        bomber.reglas.golpes_por_onda = Some(1);
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.filas()[0][1], "F1");
+        assert!(bomber.eventos.contains(&Evento::GolpesAgotados {
+            punto: Punto { x: 1, y: 0 }
+        }));
+    }
+
+    #[test]
+    fn test20_evaluar_no_modifica_el_bomberman() {
+        let tab_inicial = vec![
+            vec!["B2".to_string(), "F1".to_string(), "B1".to_string()],
+            vec!["_".to_string(), "W".to_string(), "F2".to_string()],
+            vec!["F1".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let bomber = Bomberman::new(Tablero::try_from(tab_inicial.clone()).unwrap());
+        let resultado = bomber.evaluar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(
+            resultado,
+            ResultadoJuego {
+                enemigos_eliminados: 2,
+                enemigos_restantes: 1,
+                detonaciones: 2,
+                casillas_alcanzadas: 6,
+            }
+        );
+        assert!(!resultado.gano());
+        assert_eq!(resultado.desenlace(), Resultado::Parcial);
+        assert_eq!(tab_inicial, bomber.tablero);
+        assert!(bomber.eventos.is_empty());
+        assert!(bomber.evaluar(Columna(1), Fila(1)).is_err());
+    }
+
+    #[test]
+    fn test21_cadenas_largas_no_desbordan_la_pila() {
+        let largo = 3000;
+        let mut fila = vec!["B1".to_string(); largo];
+        fila.push("F1".to_string());
+        let mut bomber = Bomberman::new(Tablero::try_from(vec![fila]).unwrap());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert!(bomber
+            .tablero
+            .celdas()
+            .all(|(_, celda)| *celda == Celda::Vacio));
+
+        let mut filas = vec![vec!["_".to_string(); 1000]; 1000];
+        filas[0][0] = "B[L0,U0,R0,D999]".to_string();
+        filas[999][0] = "F1".to_string();
+        let mut bomber = Bomberman::new(Tablero::desde_filas(filas).unwrap());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero[Punto { x: 0, y: 999 }], Celda::Vacio);
+    }
+
+    #[test]
+    fn test23_limite_de_casillas_del_tablero() {
+        let tab_inicial = vec![
+            vec!["B1".to_string(), "_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial.clone()).unwrap());
+        bomber.limites.casillas = Some(5);
+        match bomber.comenzar(Columna(0), Fila(0)) {
+            Err(BombermanError::LimiteTablero { casillas, maximo }) => {
+                assert_eq!((casillas, maximo), (6, 5))
+            }
+            resultado => panic!("{:?}", resultado),
+        }
+        assert_eq!(tab_inicial, bomber.tablero);
+        bomber.limites.casillas = Some(6);
+        assert!(bomber.comenzar(Columna(0), Fila(0)).is_ok());
+    }
+
+    #[test]
+    fn test24_deshacer_y_rehacer_jugadas() {
+        let tablero = Tablero::desde_texto("B1 F2 B1\n_ W _").unwrap();
+        let mut bomber = Bomberman::new(tablero.clone());
+        assert!(!bomber.deshacer());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        let primera = bomber.tablero.clone();
+        assert_eq!(primera.to_texto(), "_ F1 B1\n_ W _");
+        bomber.comenzar(Columna(2), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ _ _\n_ W _");
+
+        assert!(bomber.deshacer());
+        assert_eq!(bomber.tablero, primera);
+        assert!(bomber.deshacer());
+        assert_eq!(bomber.tablero, tablero);
+        assert!(bomber.eventos.is_empty());
+        assert!(bomber.rehacer());
+        assert_eq!(bomber.tablero, primera);
+        assert_eq!(bomber.eventos.len(), 2);
+
+        assert!(bomber.comenzar(Columna(0), Fila(0)).is_err());
+        assert!(!bomber.rehacer());
+        assert!(bomber.deshacer());
+        assert_eq!(bomber.tablero, primera);
+    }
+
+    #[test]
+    fn test25_una_detonacion_que_termina_en_error_no_cambia_el_tablero() {
+        let tablero = Tablero::desde_texto("B2 F2{id=a} B2 F1 F1").unwrap();
+        let mut bomber = Bomberman::new(tablero);
+        bomber.limites.pasos = Some(4);
+        assert!(bomber.comenzar(Columna(0), Fila(0)).is_err());
+        assert_eq!(bomber.tablero.to_texto(), "_ F1{id=a} B2 F1 F1");
+        assert_eq!(bomber.cambios.len(), 1);
+        let cambios: Vec<String> = bomber.cambios[0].iter().map(|c| c.to_string()).collect();
+        assert_eq!(cambios, ["(0, 0): B2 -> _", "(1, 0): F2{id=a} -> F1{id=a}"]);
+    }
+
+    #[test]
+    fn test26_los_ciclos_de_desvios_se_cortan() {
+        for (texto, desvios_esperados, final_) in [
+            ("B9 DR DL F1", 2, "_ DR DL F1"),
+            ("B9 DR DD\nW DU DL", 4, "_ DR DD\nW DU DL"),
+        ] {
+            let mut bomber = Bomberman::new(Tablero::desde_texto(texto).unwrap());
+            bomber.comenzar(Columna(0), Fila(0)).unwrap();
+            let desvios = bomber
+                .eventos
+                .iter()
+                .filter(|evento| matches!(evento, Evento::Desvio { .. }))
+                .count();
+            assert_eq!(desvios, desvios_esperados, "{}", texto);
+            assert!(
+                bomber.eventos.contains(&Evento::CicloDeDesvios {
+                    punto: Punto { x: 1, y: 0 },
+                    direccion: Direccion::Derecha
+                }),
+                "{}",
+                texto
+            );
+            assert_eq!(bomber.tablero.to_texto(), final_);
+        }
+    }
+
+    #[test]
+    fn test27_bombas_simultaneas() {
+        let tablero = Tablero::desde_texto("B1 F1 _ W B2\n_ W _ _ F2\nF1 _ _ R B1").unwrap();
+        let esquinas = [Punto { x: 0, y: 0 }, Punto { x: 4, y: 0 }];
+        let salida = Bomberman::new(tablero.clone())
+            .simular_simultaneas(&esquinas)
+            .unwrap();
+        assert_eq!(
+            salida.tablero.to_texto(),
+            "_ _ _ W _\n_ W _ _ _\nF1 _ _ R _"
+        );
+        let detonaciones: Vec<Punto> = salida
+            .eventos
+            .iter()
+            .filter_map(|evento| match evento {
+                Evento::Detonacion { punto, .. } => Some(*punto),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            detonaciones,
+            [esquinas[0], esquinas[1], Punto { x: 4, y: 2 }]
+        );
+
+        // La rafaga de la primera alcanza a la segunda, que igual explota una sola vez.
+        let tablero = Tablero::desde_texto("B2 B1 F2").unwrap();
+        let mut bomber = Bomberman::new(tablero.clone());
+        bomber
+            .comenzar_simultaneas(&[Punto { x: 0, y: 0 }, Punto { x: 1, y: 0 }])
+            .unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ _ F1");
+        assert_eq!(bomber.estadisticas.len(), 2);
+
+        let mut bomber = Bomberman::new(tablero.clone());
+        assert!(matches!(
+            bomber.comenzar_simultaneas(&[Punto { x: 0, y: 0 }, Punto { x: 2, y: 0 }]),
+            Err(BombermanError::CoordenadasInvalidas { x: 2, y: 0 })
+        ));
+        assert_eq!(bomber.tablero, tablero);
+    }
+
+    #[test]
+    fn test28_los_ladrillos_se_rompen_y_frenan_la_rafaga() {
+        let tablero = Tablero::desde_texto("S3 X F1\nX W _\nF1 _ _").unwrap();
+        let mut bomber = Bomberman::new(tablero);
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ _ F1\n_ W _\nF1 _ _");
+        assert!(bomber.eventos.contains(&Evento::LadrilloRoto {
+            punto: Punto { x: 1, y: 0 }
+        }));
+        let cambios: Vec<String> = bomber.cambios[0].iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            cambios,
+            ["(0, 0): S3 -> _", "(1, 0): X -> _", "(0, 1): X -> _"]
+        );
+    }
+
+    #[test]
+    fn test29_bombas_temporizadas() {
+        let tablero = Tablero::desde_texto(
+            "B1 T2 F1
+B1 F1 _",
+        )
+        .unwrap();
+        let mut bomber = Bomberman::new(tablero.clone());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(
+            bomber.tablero.to_texto(),
+            "_ _ _
+_ _ _"
+        );
+        let eventos: Vec<String> = bomber
+            .eventos
+            .iter()
+            .filter(|evento| {
+                matches!(
+                    evento,
+                    Evento::Detonacion { .. }
+                        | Evento::MechaEncendida { .. }
+                        | Evento::MechaConsumida { .. }
+                )
+            })
+            .map(Evento::to_string)
+            .collect();
+        assert_eq!(
+            eventos,
+            [
+                "explota B1 en (0, 0)",
+                "se enciende la mecha de la bomba en (1, 0), que explota en 2 tics",
+                "explota B1 en (0, 1)",
+                "en el tic 2 se consume la mecha de la bomba en (1, 0)",
+                "explota B1 en (1, 0)",
+            ]
+        );
+        let tics: Vec<(Punto, usize)> = Bomberman::new(tablero)
+            .paso_a_paso(Columna(0), Fila(0))
+            .map(|paso| paso.map(|paso| (paso.bomba, paso.tic)).unwrap())
+            .collect();
+        assert_eq!(
+            tics,
+            [
+                (Punto { x: 0, y: 0 }, 0),
+                (Punto { x: 0, y: 1 }, 0),
+                (Punto { x: 1, y: 0 }, 2)
+            ]
+        );
+
+        // Una temporizada detonada a mano explota enseguida.
+        let mut bomber = Bomberman::new(Tablero::desde_texto("T3S2 R F1").unwrap());
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ R _");
+    }
+
+    #[test]
+    fn test30_una_mecha_se_enciende_una_sola_vez() {
+        // (1, 0) explota primero en el tic 1 y su rafaga llega a (1, 2), que se consumio en el mismo tic.
+        let tablero = Tablero::desde_texto("_ T1B2 _\n_ B1 _\n_ T1 _").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(1), Fila(1))
+            .unwrap();
+        assert_eq!(salida.tablero.to_texto(), "_ _ _\n_ _ _\n_ _ _");
+        let contar = |es: fn(&Evento) -> bool| salida.eventos.iter().filter(|e| es(e)).count();
+        assert_eq!(contar(|e| matches!(e, Evento::MechaEncendida { .. })), 2);
+        assert_eq!(contar(|e| matches!(e, Evento::Detonacion { .. })), 3);
+
+        // Las dos rafagas llegan a la misma temporizada, que explota una vez.
+        let tablero = Tablero::desde_texto("B1 T1\n_ B1").unwrap();
+        let mut bomber = Bomberman::new(tablero);
+        bomber
+            .comenzar_simultaneas(&[Punto { x: 0, y: 0 }, Punto { x: 1, y: 1 }])
+            .unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ _\n_ _");
+        assert_eq!(bomber.estadisticas.len(), 3);
+        assert_eq!(bomber.disparos.len(), 1);
+    }
+
+    #[test]
+    fn test31_las_minas_explotan_si_la_rafaga_pasa_al_lado() {
+        let tablero = Tablero::desde_texto("B2 _ _\n_ M1 F1\nF1 _ _").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(salida.tablero.to_texto(), "_ _ _\n_ _ _\n_ _ _");
+        let activadas: Vec<&Evento> = salida
+            .eventos
+            .iter()
+            .filter(|evento| matches!(evento, Evento::MinaActivada { .. }))
+            .collect();
+        assert_eq!(
+            activadas,
+            [&Evento::MinaActivada {
+                punto: Punto { x: 1, y: 1 }
+            }]
+        );
+        assert_eq!(salida.estadisticas[1].tipo, 'M');
+
+        // La rafaga que frena la pared no pasa al lado de la mina.
+        let tablero = Tablero::desde_texto("B1 W M1\n_ _ _").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(salida.tablero.to_texto(), "_ W M1\n_ _ _");
+    }
+
+    #[test]
+    fn test32_los_blindados_solo_pierden_vida_con_traspaso() {
+        let tablero = Tablero::desde_texto("B2 G1 S1\n_ _ G2{id=a}").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(salida.tablero.to_texto(), "_ _ _\n_ _ G1{id=a}");
+        assert!(salida.eventos.contains(&Evento::BlindajeResiste {
+            punto: Punto { x: 1, y: 0 }
+        }));
+
+        // Con enemigos que bloquean, el blindado igual frena la rafaga normal.
+        let tablero = Tablero::desde_texto("B3 G1 F1").unwrap();
+        let mut bomber = Bomberman::new(tablero);
+        bomber.reglas.enemigos_bloquean = true;
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ G1 F1");
+    }
+
+    #[test]
+    fn test33_los_enemigos_muertos_dejan_mejoras() {
+        let tablero = Tablero::desde_texto("B3 F1{id=a} F2 B1").unwrap();
+        let mut bomber = Bomberman::new(tablero.clone());
+        bomber.reglas.mejoras = true;
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        // La rafaga de (3, 0) pasa por la mejora de (2, 0) sin cambiarla y llega a (1, 0).
+        assert_eq!(bomber.tablero.to_texto(), "_ +R{id=a} +R _");
+        assert!(bomber.eventos.contains(&Evento::MejoraSoltada {
+            punto: Punto { x: 1, y: 0 },
+            mejora: Mejora::Alcance
+        }));
+
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        assert_eq!(salida.tablero.to_texto(), "_ _{id=a} _ _");
+    }
+
+    #[test]
+    fn test34_los_desvios_diagonales_doblan_la_rafaga_en_diagonal() {
+        let tablero = Tablero::desde_texto("B3 DDR _ _\n_ _ F1 _\n_ _ _ F1\nF1 _ _ _").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        // La rafaga hacia abajo llega a (0, 3) y la de la derecha dobla en (1, 0) hacia abajo a la derecha.
+        assert_eq!(
+            salida.tablero.to_texto(),
+            "_ DDR _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _"
+        );
+        assert!(salida.eventos.contains(&Evento::Desvio {
+            punto: Punto { x: 1, y: 0 },
+            direccion: Direccion::AbajoDerecha
+        }));
+
+        // Un desvio diagonal en el borde manda la rafaga fuera del tablero.
+        let tablero = Tablero::desde_texto("B1 DUR").unwrap();
+        let mut bomber = Bomberman::new(tablero);
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ DUR");
+        assert_eq!(
+            bomber.advertencias,
+            vec![Advertencia::DesvioFueraDelTablero {
+                punto: Punto { x: 1, y: 0 },
+                direccion: Direccion::ArribaDerecha
+            }]
+        );
+    }
+
+    #[test]
+    fn test35_los_portales_llevan_la_rafaga_a_su_pareja() {
+        let tablero = Tablero::desde_texto("B3 P1 _ W\nF1 _ P1 F1\nW _ _ F1").unwrap();
+        let salida = Bomberman::new(tablero)
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        // La rafaga de la derecha entra por (1, 0), sale por (2, 1) y le quedan 2 casillas: llega a (3, 1) y se
+        // sale del tablero. El enemigo de (3, 2) no se entera.
+        assert_eq!(salida.tablero.to_texto(), "_ P1 _ W\n_ _ P1 _\nW _ _ F1");
+        assert!(salida.eventos.contains(&Evento::Teletransporte {
+            entrada: Punto { x: 1, y: 0 },
+            salida: Punto { x: 2, y: 1 }
+        }));
+
+        // Un portal sin pareja, que solo puede estar en un Tablero armado a mano, no hace nada.
+        let celdas = ["B2", "P1", "F1"].map(|casilla| Celda::parsear(casilla).unwrap());
+        let mut bomber = Bomberman::new(Tablero::new(vec![celdas.to_vec()]));
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ P1 _");
+    }
+
+    #[test]
+    fn test36_en_un_tablero_toroidal_la_rafaga_sigue_por_el_otro_borde() {
+        let tablero = Tablero::desde_texto("F1 _ _ B1\n_ _ _ _\n_ _ _ F1").unwrap();
+        let mut bomber = Bomberman::new(tablero.clone());
+        bomber.comenzar(Columna(3), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "F1 _ _ _\n_ _ _ _\n_ _ _ F1");
+
+        // Hacia la derecha sigue en (0, 0) y hacia arriba en (3, 2).
+        let mut bomber = Bomberman::new(tablero);
+        bomber.reglas.toroidal = true;
+        bomber.comenzar(Columna(3), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.to_texto(), "_ _ _ _\n_ _ _ _\n_ _ _ _");
+    }
+
+    #[test]
+    fn test37_variantes_de_reglas_de_la_rafaga() {
+        let jugar = |texto: &str, reglas: Reglas| {
+            let mut bomber = Bomberman::new(Tablero::desde_texto(texto).unwrap());
+            bomber.reglas = reglas;
+            bomber.comenzar(Columna(0), Fila(0)).unwrap();
+            bomber.tablero.to_texto()
+        };
+        let traspaso = Reglas {
+            traspaso_atraviesa_paredes: true,
+            ..Reglas::default()
+        };
+        assert_eq!(jugar("S2 W F1", Reglas::default()), "_ W F1");
+        assert_eq!(jugar("S2 W F1", traspaso), "_ W _");
+
+        let desvios_gratis = Reglas {
+            desvios_consumen_alcance: false,
+            ..Reglas::default()
+        };
+        assert_eq!(
+            jugar("B2 DD\n_ _\n_ F1", Reglas::default()),
+            "_ DD\n_ _\n_ F1"
+        );
+        assert_eq!(jugar("B2 DD\n_ _\n_ F1", desvios_gratis), "_ DD\n_ _\n_ _");
+
+        // Las dos rafagas desviadas llegan a (1, 1).
+        let un_golpe = Reglas {
+            un_golpe_por_bomba: true,
+            ..Reglas::default()
+        };
+        assert_eq!(jugar("B3 DD\nDR F2", Reglas::default()), "_ DD\nDR _");
+        assert_eq!(jugar("B3 DD\nDR F2", un_golpe), "_ DD\nDR F1");
+    }
+
+    #[test]
+    fn test38_celdas_afectadas_por_no_modifica_el_bomberman() {
+        let tablero = Tablero::desde_texto("B1 W B1\nB1 _ F1\n_ R _").unwrap();
+        let bomber = Bomberman::new(tablero.clone());
+        let tocadas = bomber.celdas_afectadas_por(Punto { x: 0, y: 0 }).unwrap();
+        let esperadas: HashSet<Punto> = [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]
+            .map(|(x, y)| Punto { x, y })
+            .into();
+        assert_eq!(tocadas, esperadas);
+        assert_eq!(bomber.tablero, tablero);
+        assert!(bomber.eventos.is_empty());
+        assert!(bomber.celdas_afectadas_por(Punto { x: 1, y: 1 }).is_err());
+    }
+
+    /// Para leer lo que escribio el suscriptor de tracing.
+    #[derive(Clone, Default)]
+    struct Escrito(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Escrito {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test39_cada_bomba_y_cada_rafaga_es_un_span() {
+        let escrito = Escrito::default();
+        let copia = escrito.clone();
+        let suscriptor = tracing_subscriber::fmt()
+            .with_writer(move || copia.clone())
+            .with_max_level(tracing::Level::DEBUG)
+            .with_span_events(FmtSpan::NEW)
+            .with_target(false)
+            .with_ansi(false)
+            .without_time()
+            .finish();
+        tracing::subscriber::with_default(suscriptor, || {
+            let mut bomber = Bomberman::new(Tablero::desde_texto("B2 DD\nW B1").unwrap());
+            bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        });
+        let escrito = String::from_utf8(escrito.0.lock().unwrap().clone()).unwrap();
+        let lineas: Vec<&str> = escrito.lines().collect();
+        assert_eq!(lineas.len(), 15);
+        assert_eq!(lineas[0], "DEBUG bomba{x=0 y=0 alcance=2}: new");
+        assert_eq!(
+            lineas[5],
+            "DEBUG bomba{x=0 y=0 alcance=2}:rafaga{direccion=R}: casilla x=1 y=1 celda=B1"
+        );
+        assert_eq!(lineas[8], "DEBUG bomba{x=1 y=1 alcance=1}: new");
+    }
+
+    #[test]
+    fn test40_golpes_por_onda_se_cuentan_de_nuevo_en_cada_detonacion() {
+        let mut bomber = Bomberman::new(Tablero::desde_texto("B1 F3 B1").unwrap());
+        bomber.reglas.golpes_por_onda = Some(1);
+        bomber.comenzar(Columna(0), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.filas()[0][1], "F2");
+        bomber.comenzar(Columna(2), Fila(0)).unwrap();
+        assert_eq!(bomber.tablero.filas()[0][1], "F1");
+    }
+
+    #[test]
+    fn test41_enemigos_con_vida_de_varios_digitos() {
+        let tab_inicial = vec![
+            vec![
+                "F10".to_string(),
+                "B1".to_string(),
+                "F255{id=1}".to_string(),
+            ],
+            vec!["_".to_string(), "S12".to_string(), "_".to_string()],
+        ];
+        let tab_final = vec![
+            vec!["F9".to_string(), "_".to_string(), "F254{id=1}".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ];
+        let mut bomber = Bomberman::new(Tablero::try_from(tab_inicial).unwrap());
+        bomber.comenzar(Columna(1), Fila(0)).unwrap();
+        assert_eq!(tab_final, bomber.tablero);
+    }
+}
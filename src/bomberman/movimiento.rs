@@ -0,0 +1,170 @@
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::optimizador::Aleatorio;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// Lo que ve un enemigo de un `Juego` para decidir hacia donde moverse en un tic.
+#[derive(Debug, Clone, Copy)]
+pub struct Situacion<'a> {
+    pub tablero: &'a Tablero,
+    /// Donde esta el enemigo.
+    pub enemigo: Punto,
+    /// Donde esta el jugador, o None si murio.
+    pub jugador: Option<Punto>,
+    /// La direccion en la que se movio el enemigo la ultima vez, o None si todavia no se movio.
+    pub anterior: Option<Direccion>,
+    /// Con `Reglas::toroidal`, los enemigos tambien salen por un borde y entran por el otro.
+    pub toroidal: bool,
+}
+
+impl Situacion<'_> {
+    /// La casilla a la que llegaria el enemigo moviendose en la direccion dada, o None si no puede: esta fuera del
+    /// tablero o no esta vacia (las paredes, las rocas, las bombas y los otros enemigos lo frenan). La casilla del
+    /// jugador si esta libre, y el enemigo que entra lo mata.
+    pub fn destino(&self, direccion: Direccion) -> Option<Punto> {
+        self.tablero
+            .avanzar(self.enemigo, direccion, self.toroidal)
+            .filter(|destino| {
+                matches!(
+                    self.tablero.get(*destino),
+                    Some(Celda::Vacio | Celda::Jugador)
+                )
+            })
+    }
+}
+
+/// Decide como se mueven los enemigos de un `Juego`, una casilla por tic en una de las 4 `Direccion::TODAS`, para
+/// probar distintos comportamientos sin tocar el juego. Cada tic el juego le pregunta por cada enemigo, en el orden de
+/// lectura del tablero, despues de la accion del jugador y de las explosiones.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// struct HaciaLaIzquierda;
+///
+/// impl Estrategia for HaciaLaIzquierda {
+///     fn elegir(&mut self, situacion: &Situacion) -> Option<Direccion> {
+///         Some(Direccion::Izquierda)
+///     }
+/// }
+///
+/// let juego = Juego::new(tablero)?.estrategia(Box::new(HaciaLaIzquierda));
+/// ```
+pub trait Estrategia {
+    /// La direccion en la que se mueve el enemigo, o None si se queda quieto. Si la casilla de esa direccion no
+    /// esta libre (ver `Situacion::destino`), tambien se queda quieto.
+    fn elegir(&mut self, situacion: &Situacion) -> Option<Direccion>;
+}
+
+/// Los enemigos no se mueven, como en una simulacion. Es la estrategia por defecto.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quietos;
+
+impl Estrategia for Quietos {
+    fn elegir(&mut self, _situacion: &Situacion) -> Option<Direccion> {
+        None
+    }
+}
+
+/// Cada enemigo va hacia una casilla libre al azar, o se queda si no tiene ninguna. La misma semilla da siempre los
+/// mismos movimientos.
+#[derive(Debug)]
+pub struct AlAzar {
+    aleatorio: Aleatorio,
+}
+
+impl AlAzar {
+    pub fn new(semilla: u64) -> AlAzar {
+        AlAzar {
+            aleatorio: Aleatorio::new(semilla),
+        }
+    }
+}
+
+impl Estrategia for AlAzar {
+    fn elegir(&mut self, situacion: &Situacion) -> Option<Direccion> {
+        let libres: Vec<Direccion> = Direccion::TODAS
+            .into_iter()
+            .filter(|direccion| situacion.destino(*direccion).is_some())
+            .collect();
+        if libres.is_empty() {
+            return None;
+        }
+        Some(libres[self.aleatorio.siguiente_hasta(libres.len())])
+    }
+}
+
+/// Cada enemigo se acerca al jugador: da el paso libre que mas achica la distancia (sumando filas y columnas), y si
+/// ninguno la achica se queda quieto.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Perseguir;
+
+impl Estrategia for Perseguir {
+    fn elegir(&mut self, situacion: &Situacion) -> Option<Direccion> {
+        let jugador = situacion.jugador?;
+        let distancia = |punto: Punto| punto.x.abs_diff(jugador.x) + punto.y.abs_diff(jugador.y);
+        let actual = distancia(situacion.enemigo);
+        Direccion::TODAS
+            .into_iter()
+            .filter_map(|direccion| Some((direccion, distancia(situacion.destino(direccion)?))))
+            .filter(|(_, nueva)| *nueva < actual)
+            .min_by_key(|(_, nueva)| *nueva)
+            .map(|(direccion, _)| direccion)
+    }
+}
+
+/// Cada enemigo va y viene: sigue en la direccion en la que venia y, cuando algo lo frena, se da vuelta. Los que
+/// todavia no se movieron empiezan hacia la direccion dada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Patrullar(pub Direccion);
+
+impl Estrategia for Patrullar {
+    fn elegir(&mut self, situacion: &Situacion) -> Option<Direccion> {
+        let direccion = situacion.anterior.unwrap_or(self.0);
+        [direccion, direccion.opuesta()]
+            .into_iter()
+            .find(|direccion| situacion.destino(*direccion).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlAzar, Estrategia, Patrullar, Perseguir, Situacion};
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::punto::Punto;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_cada_estrategia_elige_un_paso_libre() {
+        let tablero = Tablero::desde_texto("W _ _\nR F1 B1\n_ F1 P").unwrap();
+        let situacion = Situacion {
+            tablero: &tablero,
+            enemigo: Punto { x: 1, y: 1 },
+            jugador: Some(Punto { x: 2, y: 2 }),
+            anterior: None,
+            toroidal: false,
+        };
+        // Solo puede subir: a la izquierda hay una roca, a la derecha una bomba y abajo otro enemigo.
+        assert_eq!(
+            situacion.destino(Direccion::Arriba),
+            Some(Punto { x: 1, y: 0 })
+        );
+        assert_eq!(situacion.destino(Direccion::Derecha), None);
+        assert_eq!(Perseguir.elegir(&situacion), None);
+        let mut azar = AlAzar::new(7);
+        for _ in 0..5 {
+            assert_eq!(azar.elegir(&situacion), Some(Direccion::Arriba));
+        }
+        assert_eq!(
+            Patrullar(Direccion::Abajo).elegir(&situacion),
+            Some(Direccion::Arriba)
+        );
+
+        let abajo = Situacion {
+            enemigo: Punto { x: 1, y: 2 },
+            ..situacion
+        };
+        assert_eq!(Perseguir.elegir(&abajo), Some(Direccion::Derecha));
+    }
+}
@@ -0,0 +1,118 @@
+use super::celda::Celda;
+use super::punto::Punto;
+use super::SalidaSimulacion;
+
+/// Lo que hay que lograr con la detonacion para ganar un mapa. La consigna original solo pide eliminar a los
+/// enemigos, pero los mapas de tipo puzzle pueden pedir otra cosa.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Objetivo {
+    /// No tiene que quedar ningun enemigo en el tablero final.
+    EliminarEnemigos,
+    /// Todas las rocas tienen que ser alcanzadas por alguna rafaga. Como las rafagas no rompen las rocas, una
+    /// roca cuenta como destruida en cuanto alguna rafaga llega a ella.
+    DestruirRocas,
+    /// Alguna rafaga (o la propia detonacion) tiene que llegar a la casilla dada.
+    AlcanzarCelda(Punto),
+}
+
+impl Objetivo {
+    /// Interpreta un objetivo escrito como `eliminar_enemigos`, `destruir_rocas` o `alcanzar_celda:x,y`.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el Objetivo o un Err(String) si el texto no es un objetivo valido.
+    pub fn parsear(texto: &str) -> Result<Objetivo, String> {
+        match texto.split_once(':') {
+            None if texto == "eliminar_enemigos" => Ok(Objetivo::EliminarEnemigos),
+            None if texto == "destruir_rocas" => Ok(Objetivo::DestruirRocas),
+            Some(("alcanzar_celda", coordenadas)) => {
+                let punto = coordenadas.split_once(',').and_then(|(x, y)| {
+                    Some(Punto {
+                        x: x.trim().parse().ok()?,
+                        y: y.trim().parse().ok()?,
+                    })
+                });
+                match punto {
+                    Some(punto) => Ok(Objetivo::AlcanzarCelda(punto)),
+                    None => Err(format!("Error: objetivo invalido {}", texto)),
+                }
+            }
+            _ => Err(format!("Error: objetivo invalido {}", texto)),
+        }
+    }
+
+    /// Indica si la simulacion cumplio el objetivo.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// let salida = Bomberman::new(tablero).simular(Columna(x), Fila(y))?;
+    /// if Objetivo::DestruirRocas.cumplido(&salida) { ... }
+    /// ```
+    pub fn cumplido(&self, salida: &SalidaSimulacion) -> bool {
+        let mut casillas = salida.tablero.celdas();
+        match self {
+            Objetivo::EliminarEnemigos => {
+                !casillas.any(|(_, celda)| matches!(celda, Celda::Enemigo { .. }))
+            }
+            Objetivo::DestruirRocas => casillas
+                .filter(|(_, celda)| **celda == Celda::Roca)
+                .all(|(punto, _)| salida.alcanzadas.contains(&punto)),
+            Objetivo::AlcanzarCelda(punto) => salida.alcanzadas.contains(punto),
+        }
+    }
+}
+
+impl std::fmt::Display for Objetivo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Objetivo::EliminarEnemigos => write!(f, "eliminar_enemigos"),
+            Objetivo::DestruirRocas => write!(f, "destruir_rocas"),
+            Objetivo::AlcanzarCelda(punto) => write!(f, "alcanzar_celda:{},{}", punto.x, punto.y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Objetivo;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    fn simular(filas: &[&str], x: usize, y: usize) -> crate::bomberman::SalidaSimulacion {
+        let filas = filas
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect();
+        match Bomberman::new(Tablero::desde_filas(filas).unwrap()).simular(Columna(x), Fila(y)) {
+            Ok(salida) => salida,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test01_parsear_y_mostrar() {
+        for texto in ["eliminar_enemigos", "destruir_rocas", "alcanzar_celda:2,1"] {
+            assert_eq!(Objetivo::parsear(texto).unwrap().to_string(), texto);
+        }
+        assert!(Objetivo::parsear("alcanzar_celda:2").is_err());
+        assert!(Objetivo::parsear("ganar").is_err());
+    }
+
+    #[test]
+    fn test02_destruir_rocas_pide_alcanzar_todas_las_rocas() {
+        let salida = simular(&["B1 R _", "R _ _", "_ _ R"], 0, 0);
+        assert!(!Objetivo::DestruirRocas.cumplido(&salida));
+        let salida = simular(&["B1 R _", "R _ _", "_ _ _"], 0, 0);
+        assert!(Objetivo::DestruirRocas.cumplido(&salida));
+        assert!(Objetivo::EliminarEnemigos.cumplido(&salida));
+    }
+
+    #[test]
+    fn test03_alcanzar_celda() {
+        let salida = simular(&["B2 _ _", "_ _ _", "_ _ _"], 0, 0);
+        assert!(Objetivo::AlcanzarCelda(Punto { x: 2, y: 0 }).cumplido(&salida));
+        assert!(!Objetivo::AlcanzarCelda(Punto { x: 1, y: 1 }).cumplido(&salida));
+    }
+}
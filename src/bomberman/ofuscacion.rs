@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use super::celda::Celda;
+use super::direccion::Direccion;
+use super::optimizador::Aleatorio;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// Una de las 8 simetrias de un tablero (rotaciones y reflejos). Aplicada a todo el tablero, incluidos los desvios y
+/// los alcances por direccion de las bombas, no cambia el resultado de ninguna simulacion: solo lo mueve de lugar.
+/// Primero se transpone (si corresponde) y despues se invierten las columnas y las filas del resultado.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Simetria {
+    /// Intercambia filas por columnas.
+    pub transponer: bool,
+    /// Refleja el tablero de izquierda a derecha.
+    pub invertir_columnas: bool,
+    /// Refleja el tablero de arriba a abajo.
+    pub invertir_filas: bool,
+}
+
+impl Simetria {
+    /// Elige una de las 8 simetrias a partir de una semilla; la misma semilla da siempre la misma.
+    pub fn al_azar(semilla: u64) -> Simetria {
+        let eleccion = Aleatorio::new(semilla).siguiente_hasta(8);
+        Simetria {
+            transponer: eleccion & 1 != 0,
+            invertir_columnas: eleccion & 2 != 0,
+            invertir_filas: eleccion & 4 != 0,
+        }
+    }
+
+    /// Devuelve donde queda un punto de un tablero del ancho y alto dados.
+    pub fn punto(&self, punto: Punto, ancho: usize, alto: usize) -> Punto {
+        let (mut x, mut y, ancho, alto) = match self.transponer {
+            true => (punto.y, punto.x, alto, ancho),
+            false => (punto.x, punto.y, ancho, alto),
+        };
+        if self.invertir_columnas {
+            x = ancho - 1 - x;
+        }
+        if self.invertir_filas {
+            y = alto - 1 - y;
+        }
+        Punto { x, y }
+    }
+
+    /// Devuelve hacia donde queda apuntando una direccion.
+    pub fn direccion(&self, direccion: Direccion) -> Direccion {
+        let (mut dx, mut dy) = direccion.componentes();
+        if self.transponer {
+            (dx, dy) = (dy, dx);
+        }
+        if self.invertir_columnas {
+            dx = -dx;
+        }
+        if self.invertir_filas {
+            dy = -dy;
+        }
+        Direccion::desde_componentes(dx, dy).expect("una simetria no cambia el largo de un paso")
+    }
+
+    /// Aplica la simetria a una celda: cambia la direccion de los desvios y reparte los alcances de las bombas.
+    fn celda(&self, celda: &Celda) -> Celda {
+        match celda {
+            Celda::Desvio(direccion) => Celda::Desvio(self.direccion(*direccion)),
+            Celda::Bomba(bomba) => {
+                let mut nueva = bomba.clone();
+                for direccion in Direccion::todas() {
+                    nueva.alcances[self.direccion(direccion).indice()] = bomba.alcance(direccion);
+                }
+                Celda::Bomba(nueva)
+            }
+            otra => otra.clone(),
+        }
+    }
+}
+
+/// Se escribe como `identidad` o como la lista de lo que hace, por ejemplo `transpone, invierte las filas`.
+impl std::fmt::Display for Simetria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let partes: Vec<&str> = [
+            (self.transponer, "transpone"),
+            (self.invertir_columnas, "invierte las columnas"),
+            (self.invertir_filas, "invierte las filas"),
+        ]
+        .iter()
+        .filter(|(activa, _)| *activa)
+        .map(|(_, parte)| *parte)
+        .collect();
+        match partes.is_empty() {
+            true => write!(f, "identidad"),
+            false => write!(f, "{}", partes.join(", ")),
+        }
+    }
+}
+
+/// Arma una copia de un tablero para compartirla sin mostrar el original: quita los datos opacos de las casillas,
+/// salvo el `id` de los enemigos, que se renumera desde 1 en el orden en que aparecen, y le aplica la simetria dada.
+/// Los comentarios y metadatos del archivo ya no estan en el Tablero. Con `Simetria::default()` las casillas quedan
+/// en su lugar.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::ofuscacion::{ofuscar, Simetria};
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let tablero = Tablero::desde_texto("F2{id=alumno7,nota=9} B1\nDR _")?;
+/// let simetria = Simetria { invertir_columnas: true, ..Simetria::default() };
+/// assert_eq!(ofuscar(&tablero, simetria).to_texto(), "B1 F2{id=1}\n_ DL");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn ofuscar(tablero: &Tablero, simetria: Simetria) -> Tablero {
+    let (ancho, alto) = match simetria.transponer {
+        true => (tablero.alto(), tablero.ancho()),
+        false => (tablero.ancho(), tablero.alto()),
+    };
+    let mut filas = vec![vec![String::new(); ancho]; alto];
+    let mut ids: Vec<Vec<Option<&str>>> = vec![vec![None; ancho]; alto];
+    for (punto, celda) in tablero.celdas() {
+        let nuevo = simetria.punto(punto, tablero.ancho(), tablero.alto());
+        filas[nuevo.y][nuevo.x] = simetria.celda(celda).to_string();
+        if matches!(celda, Celda::Enemigo { .. }) {
+            ids[nuevo.y][nuevo.x] = tablero.dato(punto).and_then(id_de);
+        }
+    }
+    let mut numeros: HashMap<&str, usize> = HashMap::new();
+    for (fila, ids) in filas.iter_mut().zip(ids) {
+        for (casilla, id) in fila.iter_mut().zip(ids) {
+            if let Some(id) = id {
+                let siguiente = numeros.len() + 1;
+                let numero = *numeros.entry(id).or_insert(siguiente);
+                casilla.push_str(&format!("{{id={}}}", numero));
+            }
+        }
+    }
+    Tablero::desde_filas(filas).expect("las casillas salen de un tablero valido")
+}
+
+/// El valor de `id` en un dato opaco como `id=7,nota=9`, si lo tiene.
+fn id_de(dato: &str) -> Option<&str> {
+    dato.split(',')
+        .find_map(|par| par.trim().strip_prefix("id="))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ofuscar, Simetria};
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_quita_los_datos_y_renumera_los_ids() {
+        let tablero = Tablero::desde_texto("F2{id=b,nota=9} B1{x}\nF1{id=a} F3{id=b}").unwrap();
+        assert_eq!(
+            ofuscar(&tablero, Simetria::default()).to_texto(),
+            "F2{id=1} B1\nF1{id=2} F3{id=1}"
+        );
+    }
+
+    #[test]
+    fn test02_las_simetrias_no_cambian_la_simulacion() {
+        let tablero =
+            Tablero::desde_texto("B[R3,D1] _ F1 DD\nR F1 F2 _\nS2 DR DUL B2\n_ F1 _ W").unwrap();
+        let original = Bomberman::new(tablero.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        for eleccion in 0..8 {
+            let simetria = Simetria {
+                transponer: eleccion & 1 != 0,
+                invertir_columnas: eleccion & 2 != 0,
+                invertir_filas: eleccion & 4 != 0,
+            };
+            let inicio = simetria.punto(Punto { x: 0, y: 0 }, tablero.ancho(), tablero.alto());
+            let ofuscada = Bomberman::new(ofuscar(&tablero, simetria))
+                .simular(inicio.columna(), inicio.fila())
+                .unwrap();
+            assert_eq!(
+                ofuscada.tablero,
+                ofuscar(&original.tablero, simetria),
+                "{}",
+                simetria
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use super::analizador::Analizador;
+use super::celda::Celda;
+use super::error::BombermanError;
+use super::reglas::{Limites, Reglas};
+use super::tablero::Tablero;
+
+/// Optimizador de niveles por recocido simulado: parte de un tablero semilla y mueve paredes, rocas y enemigos
+/// a casillas vacias hasta que la dificultad que calcula el Analizador se acerca a la pedida.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let (nivel, dificultad) = Recocido::new(0.5).iteraciones(500).semilla(7).optimizar(tablero, reglas, limites)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recocido {
+    objetivo: f64,
+    iteraciones: usize,
+    temperatura: f64,
+    semilla: u64,
+}
+
+impl Recocido {
+    /// Crea un Recocido que busca la dificultad dada (entre 0 y 1), con 1000 iteraciones y semilla 1.
+    pub fn new(objetivo: f64) -> Recocido {
+        Recocido {
+            objetivo,
+            iteraciones: 1000,
+            temperatura: 0.1,
+            semilla: 1,
+        }
+    }
+
+    /// Cantidad de perturbaciones que se prueban.
+    pub fn iteraciones(mut self, iteraciones: usize) -> Self {
+        self.iteraciones = iteraciones;
+        self
+    }
+
+    /// Semilla del generador pseudoaleatorio; la misma semilla y el mismo tablero dan siempre el mismo nivel.
+    pub fn semilla(mut self, semilla: u64) -> Self {
+        self.semilla = semilla;
+        self
+    }
+
+    /// Busca el tablero cuya dificultad este mas cerca del objetivo. La temperatura baja linealmente hasta 0,
+    /// asi que al principio se aceptan peores movimientos con mas frecuencia que al final.
+    ///
+    /// # Argumentos
+    ///
+    /// * tablero: El tablero semilla, que ya tiene que ser valido.
+    /// * reglas, limites: Con los que se simula cada candidato.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el mejor tablero encontrado y su dificultad, o un Err(BombermanError) si alguna simulacion falla.
+    pub fn optimizar(
+        &self,
+        tablero: Tablero,
+        reglas: Reglas,
+        limites: Limites,
+    ) -> Result<(Tablero, f64), BombermanError> {
+        let puntuar = |tablero: &Tablero| {
+            Analizador::new(tablero.clone(), reglas.clone(), limites.clone()).dificultad()
+        };
+        let mut aleatorio = Aleatorio::new(self.semilla);
+        let mut actual = tablero;
+        let mut costo_actual = (puntuar(&actual)? - self.objetivo).abs();
+        let mut mejor = (actual.clone(), costo_actual);
+        for iteracion in 0..self.iteraciones {
+            if mejor.1 == 0.0 {
+                break;
+            }
+            let candidato = match perturbar(&actual, &mut aleatorio) {
+                Some(candidato) => candidato,
+                None => break,
+            };
+            let costo = (puntuar(&candidato)? - self.objetivo).abs();
+            let temperatura = self.temperatura * (1.0 - iteracion as f64 / self.iteraciones as f64);
+            let acepta = costo <= costo_actual
+                || (temperatura > 0.0
+                    && aleatorio.siguiente_f64() < ((costo_actual - costo) / temperatura).exp());
+            if acepta {
+                actual = candidato;
+                costo_actual = costo;
+                if costo < mejor.1 {
+                    mejor = (actual.clone(), costo);
+                }
+            }
+        }
+        let dificultad = puntuar(&mejor.0)?;
+        Ok((mejor.0, dificultad))
+    }
+}
+
+/// Mueve una pared, roca, ladrillo o enemigo elegido al azar a una casilla vacia elegida al azar.
+///
+/// # Devuelve
+///
+/// Un Option con el tablero perturbado, o None si no hay nada que mover o ningun lugar vacio.
+fn perturbar(tablero: &Tablero, aleatorio: &mut Aleatorio) -> Option<Tablero> {
+    let mut movibles = Vec::new();
+    let mut vacias = Vec::new();
+    for (punto, celda) in tablero.celdas() {
+        match celda {
+            Celda::Pared | Celda::Roca | Celda::Ladrillo | Celda::Enemigo { .. } => {
+                movibles.push(punto)
+            }
+            Celda::Vacio => vacias.push(punto),
+            _ => {}
+        }
+    }
+    if movibles.is_empty() || vacias.is_empty() {
+        return None;
+    }
+    let origen = movibles[aleatorio.siguiente_hasta(movibles.len())];
+    let destino = vacias[aleatorio.siguiente_hasta(vacias.len())];
+    let mut nuevo = tablero.clone();
+    let movida = nuevo.set(origen, Celda::Vacio)?;
+    nuevo.set(destino, movida);
+    Some(nuevo)
+}
+
+/// Generador pseudoaleatorio xorshift64*: alcanza para perturbar tableros y, a diferencia de uno del sistema,
+/// da la misma secuencia en cualquier maquina para la misma semilla.
+#[derive(Debug)]
+pub(crate) struct Aleatorio {
+    estado: u64,
+}
+
+impl Aleatorio {
+    pub(crate) fn new(semilla: u64) -> Aleatorio {
+        // El estado de xorshift no puede ser 0.
+        Aleatorio {
+            estado: semilla.max(1),
+        }
+    }
+
+    fn siguiente(&mut self) -> u64 {
+        self.estado ^= self.estado >> 12;
+        self.estado ^= self.estado << 25;
+        self.estado ^= self.estado >> 27;
+        self.estado.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Un numero en [0, limite).
+    pub(crate) fn siguiente_hasta(&mut self, limite: usize) -> usize {
+        (self.siguiente() % limite as u64) as usize
+    }
+
+    /// Un numero en [0, 1).
+    pub(crate) fn siguiente_f64(&mut self) -> f64 {
+        (self.siguiente() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recocido;
+    use crate::bomberman::analizador::Analizador;
+    use crate::bomberman::reglas::{Limites, Reglas};
+    use crate::bomberman::tablero::Tablero;
+
+    fn tablero() -> Tablero {
+        let filas = ["B1 F1 _ _", "_ _ _ _", "_ W _ _", "_ _ _ F1"]
+            .iter()
+            .map(|fila| fila.split_whitespace().map(String::from).collect())
+            .collect();
+        Tablero::desde_filas(filas).unwrap()
+    }
+
+    #[test]
+    fn test01_alcanza_la_dificultad_pedida() {
+        let (nivel, dificultad) = Recocido::new(1.0)
+            .semilla(3)
+            .optimizar(tablero(), Reglas::default(), Limites::default())
+            .unwrap();
+        assert_eq!(dificultad, 1.0);
+        let analizador = Analizador::new(nivel, Reglas::default(), Limites::default());
+        assert_eq!(analizador.dificultad().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test02_es_reproducible_con_la_misma_semilla() {
+        let optimizar = |semilla| {
+            Recocido::new(0.5)
+                .semilla(semilla)
+                .iteraciones(50)
+                .optimizar(tablero(), Reglas::default(), Limites::default())
+                .unwrap()
+        };
+        assert_eq!(optimizar(9), optimizar(9));
+    }
+}
@@ -0,0 +1,20 @@
+//! Los tipos que se usan casi siempre para simular, para importarlos en una sola linea:
+//!
+//! ```ignore
+//! use bomberman::prelude::*;
+//!
+//! let salida = SimulacionBuilder::new(tablero).reglas(Reglas::default()).construir().simular(Columna(x), Fila(y))?;
+//! ```
+pub use super::advertencia::Advertencia;
+pub use super::bomba::Bomba;
+pub use super::celda::Celda;
+pub use super::direccion::Direccion;
+pub use super::error::BombermanError;
+pub use super::evento::{Evento, Observador};
+pub use super::idioma::Idioma;
+pub use super::juego::Juego;
+pub use super::motor::{Bomberman, Resultado, ResultadoJuego, SalidaSimulacion};
+pub use super::punto::{Columna, Fila, Punto};
+pub use super::reglas::{Limites, Reglas};
+pub use super::simulacion::{Paso, Simulacion, SimulacionBuilder};
+pub use super::tablero::Tablero;
@@ -1,5 +1,99 @@
-#[derive(Eq, PartialEq, Hash)]
+/// Una casilla del tablero: `x` es la columna y `y` la fila, contando desde 0 en la esquina de arriba a la izquierda.
+/// Las funciones que reciben una coordenada suelta usan Columna y Fila en lugar de dos usize, para que intercambiarlas
+/// sea un error de compilacion.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let punto = Punto::new(Columna(4), Fila(0));
+/// assert_eq!(punto, Punto { x: 4, y: 0 });
+/// assert_eq!(punto.fila(), Fila(0));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Punto {
     pub x: usize,
     pub y: usize,
 }
+
+/// Una columna del tablero, la coordenada `x` de un Punto.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Columna(pub usize);
+
+/// Una fila del tablero, la coordenada `y` de un Punto.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Fila(pub usize);
+
+impl Punto {
+    /// Arma el punto de la columna y la fila dadas.
+    pub fn new(columna: Columna, fila: Fila) -> Punto {
+        Punto {
+            x: columna.0,
+            y: fila.0,
+        }
+    }
+
+    /// La columna del punto.
+    pub fn columna(&self) -> Columna {
+        Columna(self.x)
+    }
+
+    /// La fila del punto.
+    pub fn fila(&self) -> Fila {
+        Fila(self.y)
+    }
+}
+
+impl From<(Columna, Fila)> for Punto {
+    fn from((columna, fila): (Columna, Fila)) -> Punto {
+        Punto::new(columna, fila)
+    }
+}
+
+impl std::fmt::Display for Columna {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::fmt::Display for Fila {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Lee una columna escrita como numero natural, por ejemplo de la linea de comandos.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// assert_eq!("3".parse(), Ok(Columna(3)));
+/// assert!("-1".parse::<Columna>().is_err());
+/// ```
+impl std::str::FromStr for Columna {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Columna, String> {
+        parsear_coordenada(texto).map(Columna)
+    }
+}
+
+/// Lee una fila escrita como numero natural, igual que una Columna.
+impl std::str::FromStr for Fila {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Fila, String> {
+        parsear_coordenada(texto).map(Fila)
+    }
+}
+
+fn parsear_coordenada(texto: &str) -> Result<usize, String> {
+    texto.parse().map_err(|_| {
+        format!(
+            "tiene que ser un numero natural (desde 0), no \"{}\"",
+            texto
+        )
+    })
+}
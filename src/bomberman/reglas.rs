@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+/// Variantes de las reglas de Bomberman-R que se pueden activar en una simulacion.
+/// Por defecto todas estan desactivadas y los topes son los de la consigna original.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reglas {
+    /// Si es true, un enemigo absorbe el resto de la rafaga de una bomba normal ('B'), asi que las casillas
+    /// que estan detras de el no se ven afectadas. Las rafagas de las bombas de traspaso ('S') lo siguen atravesando.
+    pub enemigos_bloquean: bool,
+    /// Cantidad maxima de vidas que puede perder un mismo enemigo en toda la onda (la reaccion en cadena que
+    /// empieza con una detonacion), aunque lo alcancen varias bombas. Si es None, solo vale el limite de un golpe
+    /// por bomba; con Some(1), un enemigo atrapado entre dos bombas encadenadas pierde una sola vida.
+    pub golpes_por_onda: Option<usize>,
+    /// Alcance maximo que puede tener una bomba en cualquier direccion, o None si no hay tope. Una casilla con una
+    /// bomba mas grande es invalida al leer el tablero (ver `io::es_casilla_valida`), y si igual llega al motor (por
+    /// ejemplo con `--recuperar` o un Tablero armado a mano) su alcance se recorta con una advertencia.
+    pub alcance_maximo: Option<usize>,
+    /// Vida maxima que puede tener un enemigo; una casilla con un enemigo con mas vida es invalida al leer el tablero.
+    /// Por defecto es 255, asi que se aceptan enemigos como `F10` o `F255`; la consigna admite hasta 3, que es lo que
+    /// usa el preset `clasico`.
+    pub vida_maxima: usize,
+    /// Si es true, el tablero puede tener ladrillos (`X`, ver `Celda::Ladrillo`), como los bloques blandos del
+    /// Bomberman clasico. Si es false un ladrillo es una casilla invalida, como en la consigna.
+    pub ladrillos: bool,
+    /// Si es true, cada enemigo que muere deja en su casilla una mejora de alcance (`+R`, ver `Celda::Mejora`) en
+    /// lugar de dejarla vacia. Si es false una mejora es una casilla invalida.
+    pub mejoras: bool,
+    /// Si es true, el tablero es toroidal: una rafaga que sale por un borde sigue por el borde opuesto en lugar de
+    /// frenarse (ver `Tablero::avanzar`).
+    pub toroidal: bool,
+    /// Si es true, las rafagas de las bombas de traspaso ('S') tambien atraviesan las paredes, no solo las rocas.
+    pub traspaso_atraviesa_paredes: bool,
+    /// Si es false, pasar por un desvio no gasta alcance: la rafaga recorre tantas casillas como su alcance sin
+    /// contar los desvios. La consigna los cuenta como cualquier otra casilla.
+    pub desvios_consumen_alcance: bool,
+    /// Si es true, un enemigo pierde como mucho una vida por bomba aunque lo alcancen varias de sus rafagas (por
+    /// ejemplo, una directa y otra desviada). Si es false cada rafaga lo puede herir una vez.
+    pub un_golpe_por_bomba: bool,
+}
+
+impl Default for Reglas {
+    fn default() -> Reglas {
+        Reglas {
+            enemigos_bloquean: false,
+            golpes_por_onda: None,
+            alcance_maximo: None,
+            vida_maxima: 255,
+            ladrillos: false,
+            mejoras: false,
+            toroidal: false,
+            traspaso_atraviesa_paredes: false,
+            desvios_consumen_alcance: true,
+            un_golpe_por_bomba: false,
+        }
+    }
+}
+
+impl Reglas {
+    /// Nombres de los conjuntos de reglas predefinidos que acepta `Reglas::preset`.
+    pub const PRESETS: [&'static str; 2] = ["clasico", "arcade"];
+
+    /// Devuelve un conjunto de reglas predefinido:
+    ///
+    /// * `clasico`: La consigna original, sin variantes y con enemigos de hasta 3 de vida.
+    /// * `arcade`: Los enemigos bloquean las rafagas de las bombas normales.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con las Reglas, o None si no existe un preset con ese nombre.
+    pub fn preset(nombre: &str) -> Option<Reglas> {
+        match nombre {
+            "clasico" => Some(Reglas {
+                vida_maxima: 3,
+                ..Reglas::default()
+            }),
+            "arcade" => Some(Reglas {
+                enemigos_bloquean: true,
+                ..Reglas::default()
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Limites que cortan una simulacion con un error si se superan. Por defecto no hay limites
+/// (salvo el recorte de alcance, que nunca cambia el resultado).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Limites {
+    /// Alcance maximo con el que se lanza una rafaga; las bombas mas grandes se recortan con una advertencia.
+    /// Si es None se usa el alcance maximo util del tablero.
+    pub alcance: Option<usize>,
+    /// Cantidad maxima de bombas que pueden explotar en una misma cadena, contando la inicial.
+    pub cadena: Option<usize>,
+    /// Cantidad maxima de casillas que pueden recorrer entre todas las rafagas.
+    pub pasos: Option<usize>,
+    /// Tiempo maximo que puede durar la simulacion.
+    pub tiempo: Option<Duration>,
+    /// Cantidad maxima de casillas (ancho por alto) que puede tener el tablero. La memoria de una simulacion crece con
+    /// el tablero, asi que junto con los demas limites permite acotar lo que puede costar una entrada cualquiera.
+    pub casillas: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reglas;
+
+    #[test]
+    fn test01_presets_conocidos() {
+        for nombre in Reglas::PRESETS {
+            assert!(Reglas::preset(nombre).is_some());
+        }
+        assert_eq!(
+            Reglas::preset("clasico"),
+            Some(Reglas {
+                vida_maxima: 3,
+                ..Reglas::default()
+            })
+        );
+        assert_eq!(Reglas::preset("moderno"), None);
+    }
+}
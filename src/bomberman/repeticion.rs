@@ -0,0 +1,279 @@
+use super::evento::Evento;
+use super::punto::Punto;
+use super::reglas::Reglas;
+use super::simulacion::{Simulacion, SimulacionBuilder};
+use super::tablero::Tablero;
+
+/// Primera linea de un archivo de repeticion, para reconocerlo.
+const ENCABEZADO: &str = "# repeticion de tp1-bomberman";
+
+/// Todo lo necesario para volver a ver una simulacion: el tablero inicial, las reglas, la bomba detonada y los eventos
+/// que ocurrieron, en orden. Los eventos se guardan como texto (ver `Evento`), asi que la repeticion se puede leer
+/// sin el programa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeticion {
+    pub tablero: Tablero,
+    pub reglas: Reglas,
+    pub detonacion: Punto,
+    pub eventos: Vec<String>,
+}
+
+impl Repeticion {
+    /// Arma la repeticion de una simulacion ya hecha.
+    pub fn grabar(
+        tablero: Tablero,
+        reglas: Reglas,
+        detonacion: Punto,
+        eventos: &[Evento],
+    ) -> Repeticion {
+        Repeticion {
+            tablero,
+            reglas,
+            detonacion,
+            eventos: eventos.iter().map(Evento::to_string).collect(),
+        }
+    }
+
+    /// Escribe la repeticion con el formato que lee `desde_texto`: el encabezado, una linea con las reglas, otra con
+    /// la detonacion y las secciones `tablero:` y `eventos:`, con una fila o un evento por linea.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```text
+    /// # repeticion de tp1-bomberman
+    /// reglas: enemigos_bloquean=false golpes_por_onda=- alcance_maximo=- vida_maxima=255 ladrillos=false mejoras=false toroidal=false traspaso_atraviesa_paredes=false desvios_consumen_alcance=true un_golpe_por_bomba=false
+    /// detonacion: 0 0
+    /// tablero:
+    /// B1 F1
+    /// eventos:
+    /// explota B1 en (0, 0)
+    /// muere el enemigo en (1, 0)
+    /// ```
+    pub fn a_texto(&self) -> String {
+        let opcional = |valor: Option<usize>| valor.map_or("-".to_string(), |v| v.to_string());
+        let mut texto = format!(
+            "{}\nreglas: enemigos_bloquean={} golpes_por_onda={} alcance_maximo={} vida_maxima={} ladrillos={} \
+             mejoras={} toroidal={} traspaso_atraviesa_paredes={} desvios_consumen_alcance={} \
+             un_golpe_por_bomba={}\n\
+             detonacion: {} {}\ntablero:\n{}\neventos:\n",
+            ENCABEZADO,
+            self.reglas.enemigos_bloquean,
+            opcional(self.reglas.golpes_por_onda),
+            opcional(self.reglas.alcance_maximo),
+            self.reglas.vida_maxima,
+            self.reglas.ladrillos,
+            self.reglas.mejoras,
+            self.reglas.toroidal,
+            self.reglas.traspaso_atraviesa_paredes,
+            self.reglas.desvios_consumen_alcance,
+            self.reglas.un_golpe_por_bomba,
+            self.detonacion.x,
+            self.detonacion.y,
+            self.tablero.to_texto()
+        );
+        for evento in &self.eventos {
+            texto.push_str(evento);
+            texto.push('\n');
+        }
+        texto
+    }
+
+    /// Lee una repeticion escrita por `a_texto`.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con la Repeticion o un Err(String) con la linea y la causa del primer error.
+    pub fn desde_texto(texto: &str) -> Result<Repeticion, String> {
+        let mut lineas = texto.lines().enumerate().map(|(i, linea)| (i + 1, linea));
+        if lineas.next().map(|(_, linea)| linea.trim()) != Some(ENCABEZADO) {
+            return Err(format!(
+                "Error: linea 1: falta el encabezado \"{}\"",
+                ENCABEZADO
+            ));
+        }
+        let mut campo = |nombre: &str| {
+            let (numero, linea) = lineas.next().unwrap_or((0, ""));
+            linea
+                .strip_prefix(nombre)
+                .and_then(|resto| resto.strip_prefix(':'))
+                .map(|valor| (numero, valor.trim().to_string()))
+                .ok_or_else(|| format!("Error: linea {}: se esperaba {}:", numero, nombre))
+        };
+        let (numero, reglas) = campo("reglas")?;
+        let reglas = parsear_reglas(&reglas)
+            .ok_or_else(|| format!("Error: linea {}: reglas invalidas", numero))?;
+        let (numero, detonacion) = campo("detonacion")?;
+        let detonacion = parsear_punto(&detonacion)
+            .ok_or_else(|| format!("Error: linea {}: detonacion invalida", numero))?;
+        let (numero, resto) = campo("tablero")?;
+        if !resto.is_empty() {
+            return Err(format!("Error: linea {}: se esperaba tablero:", numero));
+        }
+        let mut filas = Vec::new();
+        let mut eventos: Option<Vec<String>> = None;
+        for (_, linea) in lineas {
+            match &mut eventos {
+                Some(eventos) => eventos.push(linea.to_string()),
+                None if linea.trim() == "eventos:" => eventos = Some(Vec::new()),
+                None => filas.push(linea),
+            }
+        }
+        let eventos = eventos.ok_or("Error: a la repeticion le falta la seccion eventos:")?;
+        let tablero = Tablero::desde_texto(&filas.join("\n"))
+            .map_err(|e| format!("Error: el tablero de la repeticion es invalido: {}", e))?;
+        Ok(Repeticion {
+            tablero,
+            reglas,
+            detonacion,
+            eventos,
+        })
+    }
+
+    /// Vuelve a simular la detonacion de a un paso por vez, con las reglas grabadas.
+    pub fn reproducir(&self) -> Simulacion {
+        SimulacionBuilder::new(self.tablero.clone())
+            .reglas(self.reglas.clone())
+            .construir()
+            .paso_a_paso(self.detonacion.columna(), self.detonacion.fila())
+    }
+
+    /// Compara los eventos grabados con los de una nueva simulacion, por ejemplo despues de cambiar el motor.
+    ///
+    /// # Devuelve
+    ///
+    /// None si son los mismos, o el indice del primer evento distinto con el grabado y el nuevo (None si en esa
+    /// posicion ya no hay evento).
+    pub fn divergencia(
+        &self,
+        eventos: &[Evento],
+    ) -> Option<(usize, Option<String>, Option<String>)> {
+        let nuevos: Vec<String> = eventos.iter().map(Evento::to_string).collect();
+        (0..self.eventos.len().max(nuevos.len()))
+            .map(|i| (i, self.eventos.get(i).cloned(), nuevos.get(i).cloned()))
+            .find(|(_, grabado, nuevo)| grabado != nuevo)
+    }
+}
+
+/// Lee la linea de reglas de `a_texto`, con las claves en cualquier orden. Las que faltan quedan como en
+/// `Reglas::default`, asi se siguen leyendo las repeticiones grabadas antes de alguna de las reglas.
+fn parsear_reglas(texto: &str) -> Option<Reglas> {
+    let mut reglas = Reglas::default();
+    let opcional = |valor: &str| match valor {
+        "-" => Some(None),
+        numero => numero.parse().ok().map(Some),
+    };
+    for par in texto.split_whitespace() {
+        match par.split_once('=')? {
+            ("enemigos_bloquean", valor) => reglas.enemigos_bloquean = valor.parse().ok()?,
+            ("golpes_por_onda", valor) => reglas.golpes_por_onda = opcional(valor)?,
+            ("alcance_maximo", valor) => reglas.alcance_maximo = opcional(valor)?,
+            ("vida_maxima", valor) => reglas.vida_maxima = valor.parse().ok()?,
+            ("ladrillos", valor) => reglas.ladrillos = valor.parse().ok()?,
+            ("mejoras", valor) => reglas.mejoras = valor.parse().ok()?,
+            ("toroidal", valor) => reglas.toroidal = valor.parse().ok()?,
+            ("traspaso_atraviesa_paredes", valor) => {
+                reglas.traspaso_atraviesa_paredes = valor.parse().ok()?
+            }
+            ("desvios_consumen_alcance", valor) => {
+                reglas.desvios_consumen_alcance = valor.parse().ok()?
+            }
+            ("un_golpe_por_bomba", valor) => reglas.un_golpe_por_bomba = valor.parse().ok()?,
+            _ => return None,
+        }
+    }
+    Some(reglas)
+}
+
+/// Lee un punto escrito como `x y`.
+fn parsear_punto(texto: &str) -> Option<Punto> {
+    let mut partes = texto.split_whitespace().map(str::parse);
+    match (partes.next(), partes.next(), partes.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some(Punto { x, y }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Repeticion;
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::reglas::Reglas;
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_la_repeticion_se_lee_como_se_escribio() {
+        let tablero = Tablero::desde_texto("B2 F1{id=a} B1\n_ W R").unwrap();
+        let reglas = Reglas {
+            golpes_por_onda: Some(1),
+            ladrillos: true,
+            mejoras: true,
+            toroidal: true,
+            desvios_consumen_alcance: false,
+            ..Reglas::default()
+        };
+        let salida = Bomberman::new(tablero.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let repeticion = Repeticion::grabar(tablero, reglas, Punto { x: 0, y: 0 }, &salida.eventos);
+        let leida = Repeticion::desde_texto(&repeticion.a_texto()).unwrap();
+        assert_eq!(leida, repeticion);
+        assert_eq!(leida.eventos[0], "explota B2 en (0, 0)");
+    }
+
+    #[test]
+    fn test02_reproducir_da_los_mismos_cuadros_y_eventos() {
+        let tablero = Tablero::desde_texto("B2 F1 B1\n_ W R").unwrap();
+        let salida = Bomberman::new(tablero.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let mut repeticion = Repeticion::grabar(
+            tablero,
+            Reglas::default(),
+            Punto { x: 0, y: 0 },
+            &salida.eventos,
+        );
+        let cuadros: Vec<String> = repeticion
+            .reproducir()
+            .map(|paso| paso.unwrap().tablero.to_texto())
+            .collect();
+        assert_eq!(cuadros, ["_ _ B1\n_ W R", "_ _ _\n_ W R"]);
+        assert_eq!(repeticion.divergencia(&salida.eventos), None);
+        repeticion.eventos.pop();
+        let ultimo = salida.eventos.last().unwrap().to_string();
+        assert_eq!(
+            repeticion.divergencia(&salida.eventos),
+            Some((salida.eventos.len() - 1, None, Some(ultimo)))
+        );
+    }
+
+    #[test]
+    fn test03_errores() {
+        let errores = [
+            (
+                "tablero:\nB1",
+                "Error: linea 1: falta el encabezado \"# repeticion de tp1-bomberman\"",
+            ),
+            (
+                "# repeticion de tp1-bomberman\nreglas: vida_maxima=tres",
+                "Error: linea 2: reglas invalidas",
+            ),
+            (
+                "# repeticion de tp1-bomberman\nreglas:\ndetonacion: 0",
+                "Error: linea 3: detonacion invalida",
+            ),
+            (
+                "# repeticion de tp1-bomberman\nreglas:\ndetonacion: 0 0\ntablero:\nB1",
+                "Error: a la repeticion le falta la seccion eventos:",
+            ),
+        ];
+        for (texto, error) in errores {
+            assert_eq!(
+                Repeticion::desde_texto(texto),
+                Err(error.to_string()),
+                "{}",
+                texto
+            );
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::io::{self, Write};
+
+use super::celda::Celda;
+use super::tablero::Tablero;
+
+/// Los tipos de casilla, con el nombre que tienen en un archivo de simbolos y el simbolo con el que empieza su texto
+/// en los archivos de tablero. Lo que sigue al simbolo (la vida, el alcance, la direccion, el dato opaco) no cambia.
+pub const TIPOS: [(&str, &str); 12] = [
+    ("vacio", "_"),
+    ("pared", "W"),
+    ("roca", "R"),
+    ("ladrillo", "X"),
+    ("desvio", "D"),
+    ("enemigo", "F"),
+    ("blindado", "G"),
+    ("bomba", "B"),
+    ("traspaso", "S"),
+    ("mina", "M"),
+    ("portal", "P"),
+    ("mejora", "+"),
+];
+
+/// Los simbolos con los que empiezan las casillas de los tableros de otra herramienta, para leerlos y escribirlos
+/// sin convertirlos antes, por ejemplo `#` para las paredes o `E3` para un enemigo con 3 de vida. Por defecto son los
+/// de `TIPOS`, asi que no cambian nada.
+///
+/// Ningun simbolo puede empezar con otro (ni ser igual), asi cada casilla tiene un solo tipo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simbolos {
+    /// El simbolo externo de cada tipo, en el orden de `TIPOS`.
+    simbolos: Vec<String>,
+}
+
+impl Default for Simbolos {
+    fn default() -> Simbolos {
+        Simbolos {
+            simbolos: TIPOS
+                .iter()
+                .map(|(_, simbolo)| simbolo.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Simbolos {
+    /// Arma los simbolos cambiando los de algunos tipos; los demas quedan como en `TIPOS`.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::simbolos::Simbolos;
+    /// let simbolos = Simbolos::new(&[("pared", "#"), ("enemigo", "E")])?;
+    /// assert_eq!(simbolos.leer("E3"), "F3");
+    /// assert_eq!(simbolos.escribir("W"), "#");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con los Simbolos o un Err(String) si algun tipo no existe, si un simbolo es vacio o tiene espacios,
+    /// digitos, corchetes o llaves, o si uno empieza con el de otro tipo.
+    pub fn new(cambios: &[(&str, &str)]) -> Result<Simbolos, String> {
+        let mut simbolos = Simbolos::default();
+        for (nombre, simbolo) in cambios {
+            let Some(indice) = TIPOS.iter().position(|(tipo, _)| tipo == nombre) else {
+                return Err(format!("Error: tipo de casilla desconocido {}", nombre));
+            };
+            if simbolo.is_empty()
+                || simbolo.contains(|c: char| {
+                    c.is_whitespace() || c.is_ascii_digit() || "[]{}".contains(c)
+                })
+            {
+                return Err(format!(
+                    "Error: el simbolo de {} no puede ser vacio ni tener espacios, digitos, corchetes o llaves",
+                    nombre
+                ));
+            }
+            simbolos.simbolos[indice] = simbolo.to_string();
+        }
+        for (i, simbolo) in simbolos.simbolos.iter().enumerate() {
+            for (j, otro) in simbolos.simbolos.iter().enumerate() {
+                if i != j && otro.starts_with(simbolo.as_str()) {
+                    return Err(format!(
+                        "Error: el simbolo de {} ({}) es el comienzo del de {} ({})",
+                        TIPOS[i].0, simbolo, TIPOS[j].0, otro
+                    ));
+                }
+            }
+        }
+        Ok(simbolos)
+    }
+
+    /// Si son los de `TIPOS`, y leer y escribir no cambian nada.
+    pub fn es_identidad(&self) -> bool {
+        *self == Simbolos::default()
+    }
+
+    /// Pasa una casilla de estos simbolos a los de `TIPOS`. Una casilla que no empieza con ninguno queda igual, y
+    /// despues no va a ser valida.
+    pub fn leer(&self, casilla: &str) -> String {
+        traducir(
+            casilla,
+            self.simbolos.iter().map(String::as_str),
+            TIPOS.map(|(_, s)| s),
+        )
+    }
+
+    /// Pasa una casilla escrita con los simbolos de `TIPOS` a estos; la inversa de `leer`.
+    pub fn escribir(&self, casilla: &str) -> String {
+        traducir(
+            casilla,
+            TIPOS.map(|(_, s)| s),
+            self.simbolos.iter().map(String::as_str),
+        )
+    }
+
+    /// Pasa el texto de un archivo de tablero a los simbolos de `TIPOS`, casilla por casilla, sin tocar las lineas
+    /// vacias ni las de comentario, asi los numeros de linea de los errores siguen siendo los del archivo (las
+    /// columnas no, si los simbolos tienen otro largo). Si algun simbolo empieza con `#`, una linea que empieza con
+    /// `#` es una fila del tablero cuando todas sus palabras son casillas validas, y si no es un comentario.
+    pub fn leer_texto(&self, texto: &str) -> String {
+        let mut traducido = String::with_capacity(texto.len());
+        for linea_completa in texto.split_inclusive('\n') {
+            let linea = linea_completa.trim_end_matches(['\n', '\r']);
+            let es_fila = !linea.trim().is_empty()
+                && (!linea.starts_with('#')
+                    || self.simbolos.iter().any(|simbolo| simbolo.starts_with('#'))
+                        && linea
+                            .split_whitespace()
+                            .all(|casilla| Celda::parsear(&self.leer(casilla)).is_some()));
+            if !es_fila {
+                traducido.push_str(linea_completa);
+                continue;
+            }
+            let casillas: Vec<String> = linea.split_whitespace().map(|c| self.leer(c)).collect();
+            traducido.push_str(&casillas.join(" "));
+            traducido.push_str(&linea_completa[linea.len()..]);
+        }
+        traducido
+    }
+
+    /// Escribe el tablero como `Tablero::escribir`, pero con estos simbolos.
+    pub fn escribir_tablero(&self, tablero: &Tablero, escritor: &mut impl Write) -> io::Result<()> {
+        if self.es_identidad() {
+            return tablero.escribir(escritor);
+        }
+        for (y, fila) in tablero.filas().iter().enumerate() {
+            if y > 0 {
+                escritor.write_all(b"\n")?;
+            }
+            let casillas: Vec<String> = fila.iter().map(|casilla| self.escribir(casilla)).collect();
+            escritor.write_all(casillas.join(" ").as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// El texto de `escribir_tablero`, como `Tablero::to_texto`.
+    pub fn a_texto(&self, tablero: &Tablero) -> String {
+        let mut texto = Vec::new();
+        // Escribir en un Vec en memoria no puede fallar.
+        self.escribir_tablero(tablero, &mut texto)
+            .expect("no se pudo escribir el tablero en memoria");
+        String::from_utf8(texto).expect("el texto de las casillas es UTF-8")
+    }
+}
+
+/// Reemplaza el simbolo de `desde` con el que empieza la casilla por el del mismo tipo en `hacia`. Como los simbolos
+/// de cada lado no empiezan unos con otros, hay uno solo.
+fn traducir<'a>(
+    casilla: &str,
+    desde: impl IntoIterator<Item = &'a str>,
+    hacia: impl IntoIterator<Item = &'a str>,
+) -> String {
+    desde
+        .into_iter()
+        .zip(hacia)
+        .find_map(|(desde, hacia)| {
+            casilla
+                .strip_prefix(desde)
+                .map(|resto| hacia.to_string() + resto)
+        })
+        .unwrap_or_else(|| casilla.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Simbolos;
+    use crate::bomberman::tablero::Tablero;
+
+    #[test]
+    fn test01_lee_y_escribe_con_otros_simbolos() {
+        let simbolos = Simbolos::new(&[("pared", "#"), ("enemigo", "E"), ("vacio", ".")]).unwrap();
+        let texto = "# nivel de otra herramienta\n# B1 E2{id=a}\n. # E1\r\n";
+        let leido = simbolos.leer_texto(texto);
+        assert_eq!(
+            leido,
+            "# nivel de otra herramienta\nW B1 F2{id=a}\n_ W F1\r\n"
+        );
+        let tablero = Tablero::desde_texto(&leido).unwrap();
+        assert_eq!(simbolos.a_texto(&tablero), "# B1 E2{id=a}\n. # E1");
+        assert_eq!(simbolos.leer("DUL"), "DUL");
+        assert_eq!(simbolos.escribir("B[R3,D1]"), "B[R3,D1]");
+
+        let identidad = Simbolos::default();
+        assert!(identidad.es_identidad());
+        assert_eq!(identidad.leer_texto(texto), texto);
+        assert_eq!(identidad.a_texto(&tablero), tablero.to_texto());
+    }
+
+    #[test]
+    fn test02_simbolos_invalidos() {
+        for (cambios, error) in [
+            (
+                vec![("muro", "#")],
+                "Error: tipo de casilla desconocido muro",
+            ),
+            (
+                vec![("enemigo", "E1")],
+                "Error: el simbolo de enemigo no puede ser vacio ni tener espacios, digitos, corchetes o llaves",
+            ),
+            (
+                vec![("pared", "B")],
+                "Error: el simbolo de pared (B) es el comienzo del de bomba (B)",
+            ),
+            (
+                vec![("roca", "Wr")],
+                "Error: el simbolo de pared (W) es el comienzo del de roca (Wr)",
+            ),
+        ] {
+            assert_eq!(Simbolos::new(&cambios), Err(error.to_string()));
+        }
+        // Intercambiar dos simbolos es valido aunque en el medio se repitan.
+        let cambiados = Simbolos::new(&[("pared", "R"), ("roca", "W")]).unwrap();
+        assert_eq!(cambiados.leer("W"), "R");
+    }
+}
@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use super::comparacion::Diferencia;
+use super::danio::{DanioUnitario, ModeloDanio};
+use super::error::BombermanError;
+use super::evento::{Evento, Observador};
+use super::hexagonal::TableroHex;
+use super::punto::Punto;
+use super::reglas::{Limites, Reglas};
+use super::tablero::Tablero;
+use super::{Bomberman, SalidaSimulacion};
+
+/// Arma un Bomberman configurando todo lo necesario antes de comenzar, en lugar de asignar sus campos uno por uno.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let mut bomberman = SimulacionBuilder::new(tablero)
+///     .reglas(Reglas { enemigos_bloquean: true, ..Reglas::default() })
+///     .limites(Limites { cadena: Some(50), tiempo: Some(Duration::from_secs(1)), ..Limites::default() })
+///     .construir();
+/// bomberman.comenzar(Columna(x), Fila(y))?;
+/// ```
+pub struct SimulacionBuilder {
+    tablero: Tablero,
+    reglas: Reglas,
+    limites: Limites,
+    observadores: Vec<Box<dyn Observador>>,
+    modelo_danio: Arc<dyn ModeloDanio>,
+    hexagonal: bool,
+}
+
+impl SimulacionBuilder {
+    /// Empieza a configurar una simulacion sobre el tablero dado, con las reglas por defecto y sin limites.
+    pub fn new(tablero: Tablero) -> SimulacionBuilder {
+        SimulacionBuilder {
+            tablero,
+            reglas: Reglas::default(),
+            limites: Limites::default(),
+            observadores: Vec::new(),
+            modelo_danio: Arc::new(DanioUnitario),
+            hexagonal: false,
+        }
+    }
+
+    /// Como `new`, pero sobre un tablero hexagonal (ver `Bomberman::hexagonal`).
+    pub fn hexagonal(tablero: TableroHex) -> SimulacionBuilder {
+        SimulacionBuilder {
+            hexagonal: true,
+            ..SimulacionBuilder::new(tablero.into_tablero())
+        }
+    }
+
+    /// Las variantes de reglas con las que se simula.
+    pub fn reglas(mut self, reglas: Reglas) -> SimulacionBuilder {
+        self.reglas = reglas;
+        self
+    }
+
+    /// Los limites que cortan la simulacion (ver `Limites`).
+    pub fn limites(mut self, limites: Limites) -> SimulacionBuilder {
+        self.limites = limites;
+        self
+    }
+
+    /// Agrega un Observador que recibe cada Evento de la simulacion. Se pueden agregar varios.
+    pub fn observador(mut self, observador: Box<dyn Observador>) -> SimulacionBuilder {
+        self.observadores.push(observador);
+        self
+    }
+
+    /// El ModeloDanio que decide cuanta vida le quita cada golpe a un enemigo; por defecto `DanioUnitario`. Se pasa en
+    /// un Arc para poder usar el mismo modelo en muchas simulaciones.
+    pub fn modelo_danio(mut self, modelo: Arc<dyn ModeloDanio>) -> SimulacionBuilder {
+        self.modelo_danio = modelo;
+        self
+    }
+
+    /// Termina la configuracion y devuelve el Bomberman listo para comenzar.
+    pub fn construir(self) -> Bomberman {
+        let mut bomberman = Bomberman::new(self.tablero);
+        bomberman.reglas = self.reglas;
+        bomberman.limites = self.limites;
+        bomberman.observadores = self.observadores;
+        bomberman.modelo_danio = self.modelo_danio;
+        bomberman.hexagonal = self.hexagonal;
+        bomberman
+    }
+}
+
+/// Una detonacion de una Simulacion paso a paso.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paso {
+    /// Cuantas bombas explotaron hasta este paso, incluida la de este: 1 para la primera.
+    pub numero: usize,
+    /// La bomba que exploto en este paso.
+    pub bomba: Punto,
+    /// El tic en que exploto: 0 en la reaccion en cadena de la bomba inicial, y despues el de la bomba temporizada
+    /// que la empezo.
+    pub tic: usize,
+    /// El tablero como quedo despues de esta detonacion.
+    pub tablero: Tablero,
+    /// Los eventos de esta detonacion, empezando por su `Evento::Detonacion` o, si es la primera de un tic, por los
+    /// `Evento::MechaConsumida` de ese tic.
+    pub eventos: Vec<Evento>,
+    /// Las casillas que cambio esta detonacion.
+    pub cambios: Vec<Diferencia>,
+}
+
+/// Una simulacion que avanza de a una detonacion, con el mismo resultado que `Bomberman::simular`. Como iterador
+/// devuelve un Paso por bomba detonada, y termina despues de la ultima o del primer error. Se obtiene con
+/// `Bomberman::paso_a_paso`.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let mut simulacion = SimulacionBuilder::new(tablero).construir().paso_a_paso(Columna(0), Fila(0));
+/// let primero = simulacion.next().unwrap()?;
+/// let salida = simulacion.terminar()?; // las detonaciones que faltaban
+/// ```
+pub struct Simulacion {
+    bomberman: Bomberman,
+    /// La bomba inicial, hasta el primer paso; las demas salen de `Bomberman::siguiente_bomba`.
+    inicial: Option<Punto>,
+    numero: usize,
+    terminada: bool,
+}
+
+impl Simulacion {
+    pub(super) fn new(bomberman: Bomberman, inicial: Punto) -> Simulacion {
+        Simulacion {
+            bomberman,
+            inicial: Some(inicial),
+            numero: 0,
+            terminada: false,
+        }
+    }
+
+    /// El tablero despues de los pasos dados hasta ahora.
+    pub fn tablero(&self) -> &Tablero {
+        &self.bomberman.tablero
+    }
+
+    /// Hace las detonaciones que faltan y devuelve el resultado completo, como `Bomberman::simular`.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con la SalidaSimulacion o un Err(BombermanError) con el error que corto la simulacion.
+    pub fn terminar(mut self) -> Result<SalidaSimulacion, BombermanError> {
+        for paso in self.by_ref() {
+            paso?;
+        }
+        Ok(self.bomberman.salida())
+    }
+}
+
+impl Iterator for Simulacion {
+    type Item = Result<Paso, BombermanError>;
+
+    fn next(&mut self) -> Option<Result<Paso, BombermanError>> {
+        if self.terminada {
+            return None;
+        }
+        let primer_evento = self.bomberman.eventos.len();
+        let bomba = match self.inicial.take() {
+            Some(inicial) => inicial,
+            None => match self.bomberman.siguiente_bomba() {
+                Some(bomba) => bomba,
+                None => {
+                    self.terminada = true;
+                    return None;
+                }
+            },
+        };
+        let detonacion = match self.numero {
+            0 => self
+                .bomberman
+                .revisar_tamanio()
+                .and_then(|_| self.bomberman.detonar(bomba)),
+            _ => self.bomberman.detonar(bomba),
+        };
+        if let Err(e) = detonacion {
+            self.terminada = true;
+            return Some(Err(e));
+        }
+        self.numero += 1;
+        Some(Ok(Paso {
+            numero: self.numero,
+            bomba,
+            tic: self.bomberman.tic(),
+            tablero: self.bomberman.tablero.clone(),
+            eventos: self.bomberman.eventos[primer_evento..].to_vec(),
+            cambios: self.bomberman.ultimos_cambios().to_vec(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::SimulacionBuilder;
+    use crate::bomberman::error::BombermanError;
+    use crate::bomberman::evento::{Evento, Observador};
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::reglas::Limites;
+    use crate::bomberman::tablero::Tablero;
+
+    struct Registro(Rc<RefCell<Vec<Evento>>>);
+
+    impl Observador for Registro {
+        fn notificar(&mut self, evento: &Evento) {
+            self.0.borrow_mut().push(evento.clone());
+        }
+    }
+
+    fn tablero() -> Tablero {
+        Tablero::desde_filas(vec![
+            vec!["B1".to_string(), "B1".to_string(), "B1".to_string()],
+            vec!["_".to_string(), "W".to_string(), "F1".to_string()],
+            vec!["_".to_string(), "_".to_string(), "_".to_string()],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test01_observadores_reciben_los_eventos() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+        let mut bomberman = SimulacionBuilder::new(tablero())
+            .observador(Box::new(Registro(Rc::clone(&eventos))))
+            .construir();
+        assert!(bomberman.comenzar(Columna(0), Fila(0)).is_ok());
+        let eventos = eventos.borrow();
+        assert_eq!(
+            eventos[0],
+            Evento::Detonacion {
+                punto: Punto { x: 0, y: 0 },
+                tipo: 'B',
+                alcance: 1
+            }
+        );
+        assert!(eventos.contains(&Evento::EnemigoDaniado {
+            punto: Punto { x: 2, y: 1 },
+            vida: 0
+        }));
+    }
+
+    #[test]
+    fn test02_max_cadena_corta_la_simulacion() {
+        let mut bomberman = SimulacionBuilder::new(tablero())
+            .limites(Limites {
+                cadena: Some(2),
+                ..Limites::default()
+            })
+            .construir();
+        assert!(matches!(
+            bomberman.comenzar(Columna(0), Fila(0)),
+            Err(BombermanError::LimiteCadena)
+        ));
+        let mut bomberman = SimulacionBuilder::new(tablero())
+            .limites(Limites {
+                cadena: Some(3),
+                ..Limites::default()
+            })
+            .construir();
+        assert!(bomberman.comenzar(Columna(0), Fila(0)).is_ok());
+    }
+
+    #[test]
+    fn test03_max_pasos_corta_la_simulacion() {
+        let mut bomberman = SimulacionBuilder::new(tablero())
+            .limites(Limites {
+                pasos: Some(1),
+                ..Limites::default()
+            })
+            .construir();
+        assert!(matches!(
+            bomberman.comenzar(Columna(0), Fila(0)),
+            Err(BombermanError::LimitePasos)
+        ));
+    }
+
+    #[test]
+    fn test04_paso_a_paso_llega_al_mismo_resultado() {
+        let completa = SimulacionBuilder::new(tablero())
+            .construir()
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let pasos: Vec<_> = SimulacionBuilder::new(tablero())
+            .construir()
+            .paso_a_paso(Columna(0), Fila(0))
+            .map(Result::unwrap)
+            .collect();
+        let bombas: Vec<Punto> = pasos.iter().map(|paso| paso.bomba).collect();
+        assert_eq!(
+            bombas,
+            [
+                Punto { x: 0, y: 0 },
+                Punto { x: 1, y: 0 },
+                Punto { x: 2, y: 0 }
+            ]
+        );
+        assert_eq!(pasos[0].tablero.to_texto(), "_ B1 B1\n_ W F1\n_ _ _");
+        assert_eq!(pasos[2].tablero, completa.tablero);
+        assert_eq!(
+            pasos
+                .iter()
+                .flat_map(|paso| paso.eventos.clone())
+                .collect::<Vec<_>>(),
+            completa.eventos
+        );
+
+        let mut simulacion = SimulacionBuilder::new(tablero())
+            .construir()
+            .paso_a_paso(Columna(0), Fila(0));
+        assert_eq!(simulacion.next().unwrap().unwrap().numero, 1);
+        assert_eq!(simulacion.terminar().unwrap().tablero, completa.tablero);
+    }
+}
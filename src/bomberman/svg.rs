@@ -0,0 +1,210 @@
+use super::celda::Celda;
+use super::motor::SalidaSimulacion;
+use super::punto::Punto;
+use super::tablero::Tablero;
+
+/// El lado de cada casilla, en pixeles.
+const LADO: usize = 40;
+
+/// Los colores de las rafagas: todas las de una bomba van del mismo color, y cada bomba de la cadena usa el siguiente.
+const COLORES_RAFAGAS: [&str; 6] = [
+    "#d62728", "#1f77b4", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf",
+];
+
+/// Dibuja el tablero como SVG: una grilla con el texto de cada casilla y un color de fondo por tipo.
+///
+/// # Ejemplos
+///
+/// ```no_run
+/// # use tp1_bomberman::bomberman::{prelude::*, svg};
+/// std::fs::write("tablero.svg", svg::tablero(&Tablero::desde_texto("B2 R F1\n_ W _")?))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn tablero(tablero: &Tablero) -> String {
+    let mut svg = abrir(tablero);
+    svg += "</svg>\n";
+    svg
+}
+
+/// Dibuja el tablero inicial de una simulacion como `tablero` y encima el recorrido de cada rafaga (ver
+/// `EstadisticaBomba::recorridos`): una linea con una flecha desde el centro de la bomba que la lanzo, que dobla en los
+/// desvios. Las rafagas de cada bomba tienen su color y la bomba un circulo de ese color, y pasando el mouse por una
+/// linea se ve de que bomba y direccion es. Cuando la rafaga salta a una casilla que no es vecina (la pareja de un
+/// portal, o el otro borde de un tablero toroidal) la linea se corta y sigue desde ahi.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let salida = Bomberman::new(inicial.clone()).simular(Columna(0), Fila(0))?;
+/// std::fs::write("cadena.svg", svg::simulacion(&inicial, &salida))?;
+/// ```
+///
+/// # Argumentos
+///
+/// * inicial: El tablero con el que empezo la simulacion, que es el que se dibuja debajo de los recorridos.
+/// * salida: La simulacion.
+pub fn simulacion(inicial: &Tablero, salida: &SalidaSimulacion) -> String {
+    let mut svg = abrir(inicial);
+    for (i, estadistica) in salida.estadisticas.iter().enumerate() {
+        let color = COLORES_RAFAGAS[i % COLORES_RAFAGAS.len()];
+        let origen = estadistica.punto;
+        for ((direccion, _), recorrido) in estadistica.rafagas.iter().zip(&estadistica.recorridos) {
+            let mut tramos: Vec<Vec<Punto>> = vec![vec![origen]];
+            let mut anterior = origen;
+            for &punto in recorrido {
+                if !son_vecinas(anterior, punto) {
+                    tramos.push(Vec::new());
+                }
+                if let Some(tramo) = tramos.last_mut() {
+                    tramo.push(punto);
+                }
+                anterior = punto;
+            }
+            for tramo in tramos.iter().filter(|tramo| tramo.len() > 1) {
+                let puntos: Vec<String> = tramo
+                    .iter()
+                    .map(|p| format!("{},{}", centro(p.x), centro(p.y)))
+                    .collect();
+                svg += &format!(
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"3\" \
+                     stroke-opacity=\"0.8\" marker-end=\"url(#flecha{})\"><title>rafaga {} de la bomba en \
+                     ({}, {})</title></polyline>\n",
+                    puntos.join(" "),
+                    color,
+                    i % COLORES_RAFAGAS.len(),
+                    direccion,
+                    origen.x,
+                    origen.y
+                );
+            }
+        }
+        svg += &format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"6\" fill=\"{}\"/>\n",
+            centro(origen.x),
+            centro(origen.y),
+            color
+        );
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+/// El encabezado del SVG, con una flecha por color de `COLORES_RAFAGAS`, y las casillas del tablero.
+fn abrir(tablero: &Tablero) -> String {
+    let (ancho, alto) = (tablero.ancho() * LADO, tablero.alto() * LADO);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" \
+         font-family=\"monospace\" font-size=\"14\">\n<defs>\n",
+        ancho, alto, ancho, alto
+    );
+    for (i, color) in COLORES_RAFAGAS.iter().enumerate() {
+        svg += &format!(
+            "<marker id=\"flecha{}\" viewBox=\"0 0 10 10\" refX=\"8\" refY=\"5\" markerWidth=\"5\" \
+             markerHeight=\"5\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"{}\"/></marker>\n",
+            i, color
+        );
+    }
+    svg += "</defs>\n";
+    for (y, fila) in tablero.filas().iter().enumerate() {
+        for (x, casilla) in fila.iter().enumerate() {
+            svg += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#404040\"/>\n",
+                x * LADO,
+                y * LADO,
+                LADO,
+                LADO,
+                relleno(tablero.get(Punto { x, y }))
+            );
+            if casilla != "_" {
+                svg += &format!(
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    centro(x),
+                    centro(y),
+                    escapar(casilla)
+                );
+            }
+        }
+    }
+    svg
+}
+
+/// El color de fondo de una casilla.
+fn relleno(celda: Option<&Celda>) -> &'static str {
+    match celda {
+        Some(Celda::Pared) => "#9090a0",
+        Some(Celda::Roca) => "#c8a882",
+        Some(Celda::Ladrillo) => "#e0a070",
+        Some(Celda::Desvio(_)) => "#a0c4f0",
+        Some(Celda::Enemigo { .. }) => "#f0a0a0",
+        Some(Celda::Bomba(_)) => "#b0b0b0",
+        Some(Celda::Mina { .. }) => "#f0e090",
+        Some(Celda::Portal { .. }) => "#a0e8e8",
+        Some(Celda::Mejora(_)) => "#b0e0b0",
+        Some(Celda::Jugador) => "#f0c0f0",
+        Some(Celda::Vacio) | None => "#f4f4e8",
+    }
+}
+
+/// La coordenada del centro de la casilla en la columna o fila dada.
+fn centro(posicion: usize) -> usize {
+    posicion * LADO + LADO / 2
+}
+
+/// Si una rafaga puede pasar de una casilla a la otra sin saltar: son distintas y se tocan, de lado o en diagonal.
+fn son_vecinas(a: Punto, b: Punto) -> bool {
+    a != b && a.x.abs_diff(b.x) <= 1 && a.y.abs_diff(b.y) <= 1
+}
+
+/// Escapa los caracteres especiales de XML, que pueden aparecer en los datos opacos de una casilla.
+fn escapar(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simulacion, tablero};
+    use crate::bomberman::punto::{Columna, Fila, Punto};
+    use crate::bomberman::tablero::Tablero;
+    use crate::bomberman::Bomberman;
+
+    #[test]
+    fn test01_dibuja_el_recorrido_de_cada_rafaga() {
+        let inicial = Tablero::desde_texto("B3 DD _\nP1 _ P1{x=<1>}\nW B1 F1").unwrap();
+        let salida = Bomberman::new(inicial.clone())
+            .simular(Columna(0), Fila(0))
+            .unwrap();
+        let recorridos = &salida.estadisticas[0].recorridos;
+        // La rafaga hacia la derecha dobla en el desvio y llega a la otra bomba.
+        assert_eq!(
+            recorridos[2],
+            [
+                Punto { x: 1, y: 0 },
+                Punto { x: 1, y: 1 },
+                Punto { x: 1, y: 2 }
+            ]
+        );
+        // La de abajo entra al portal y sigue bajando desde su pareja, asi que su linea se corta en el salto.
+        assert_eq!(
+            recorridos[3],
+            [
+                Punto { x: 0, y: 1 },
+                Punto { x: 2, y: 1 },
+                Punto { x: 2, y: 2 }
+            ]
+        );
+
+        let dibujo = simulacion(&inicial, &salida);
+        assert!(dibujo.contains("<polyline points=\"20,20 60,20 60,60 60,100\""));
+        assert!(dibujo.contains("<polyline points=\"20,20 20,60\""));
+        assert!(dibujo.contains("<polyline points=\"100,60 100,100\""));
+        assert!(dibujo.contains("<title>rafaga D de la bomba en (0, 0)</title>"));
+        // La segunda bomba de la cadena tiene otro color.
+        assert!(dibujo.contains("<circle cx=\"60\" cy=\"100\" r=\"6\" fill=\"#1f77b4\"/>"));
+        assert!(dibujo.contains(">P1{x=&lt;1&gt;}</text>"));
+        assert!(!tablero(&inicial).contains("<polyline"));
+    }
+}
@@ -0,0 +1,762 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use std::ops::{Index, IndexMut};
+
+use super::celda::{self, Celda};
+use super::direccion::Direccion;
+use super::error::BombermanError;
+use super::punto::Punto;
+use super::ubicacion::{self, Ubicacion};
+
+/// El tablero de un juego de Bomberman: una matriz rectangular (no necesariamente cuadrada) de Celdas ya
+/// interpretadas, a la que se accede por Punto en lugar de indexar `[y][x]` a mano. Los datos opacos de las
+/// casillas (ver `celda::separar_dato`) se guardan aparte, por posicion, asi siguen en su casilla aunque el motor
+/// cambie la Celda.
+///
+/// Las Celdas se guardan todas juntas, fila por fila, y la de cada Punto se encuentra con su indice (ver `indice`),
+/// asi recorrer un tablero grande no salta entre una reserva de memoria por fila.
+#[derive(Debug, Clone)]
+pub struct Tablero {
+    celdas: Vec<Celda>,
+    ancho: usize,
+    alto: usize,
+    datos: HashMap<Punto, String>,
+    /// Donde estaba cada casilla en el texto leido, con el mismo orden que `celdas`, si el tablero salio de
+    /// `desde_texto`.
+    ubicaciones: Vec<Ubicacion>,
+}
+
+impl Tablero {
+    /// Crea un tablero a partir de sus filas de Celdas, de arriba hacia abajo. Todas las filas tienen que tener la
+    /// misma cantidad de Celdas (`desde_filas` lo controla, pero aca no se valida: si alguna fila es mas corta o mas
+    /// larga que la primera, las casillas que siguen quedan corridas).
+    pub fn new(celdas: Vec<Vec<Celda>>) -> Tablero {
+        Tablero {
+            ancho: celdas.first().map_or(0, Vec::len),
+            alto: celdas.len(),
+            celdas: celdas.into_iter().flatten().collect(),
+            datos: HashMap::new(),
+            ubicaciones: Vec::new(),
+        }
+    }
+
+    /// Interpreta las filas de texto de un tablero, de arriba hacia abajo.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let tablero = Tablero::desde_filas(vec![vec!["B1".to_string(), "F2{id=3}".to_string()]])?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el Tablero o un Err(BombermanError) con la linea y columna de la primera casilla que no es una
+    /// Celda, con la primera fila que no tiene tantas casillas como la primera, o con la primera casilla de un portal
+    /// que no aparece exactamente dos veces.
+    pub fn desde_filas(filas: Vec<Vec<String>>) -> Result<Tablero, BombermanError> {
+        let mut datos = HashMap::new();
+        let esperadas = filas.first().map_or(0, Vec::len);
+        let mut celdas = Vec::with_capacity(filas.len() * esperadas);
+        for (y, fila) in filas.iter().enumerate() {
+            if fila.len() != esperadas {
+                return Err(BombermanError::FilaIrregular {
+                    linea: y + 1,
+                    casillas: fila.len(),
+                    esperadas,
+                    ubicacion: None,
+                });
+            }
+            for (x, casilla) in fila.iter().enumerate() {
+                match Celda::parsear(casilla) {
+                    Some(celda) => celdas.push(celda),
+                    None => {
+                        return Err(BombermanError::ArchivoInvalido {
+                            linea: y + 1,
+                            columna: x + 1,
+                            ubicacion: None,
+                        })
+                    }
+                }
+                if let (_, Some(dato)) = celda::separar_dato(casilla) {
+                    datos.insert(Punto { x, y }, dato.to_string());
+                }
+            }
+        }
+        let tablero = Tablero {
+            celdas,
+            ancho: esperadas,
+            alto: filas.len(),
+            datos,
+            ubicaciones: Vec::new(),
+        };
+        if let Some((Punto { x, y }, apariciones)) = tablero.portal_sin_pareja() {
+            return Err(BombermanError::PortalSinPareja {
+                linea: y + 1,
+                columna: x + 1,
+                apariciones,
+                ubicacion: None,
+            });
+        }
+        Ok(tablero)
+    }
+
+    /// Interpreta el texto de un tablero con el formato de los archivos: casillas separadas por espacios, filas por
+    /// saltos de linea, y sin contar las lineas vacias ni las de comentario, que empiezan con `#`. Es la inversa de
+    /// `to_texto`. El tablero recuerda la Ubicacion de cada casilla en el texto (ver `ubicacion`).
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con el Tablero o un Err(BombermanError) como el de `desde_filas`, que ademas tiene la Ubicacion de
+    /// la casilla o fila con el problema.
+    pub fn desde_texto(texto: &str) -> Result<Tablero, BombermanError> {
+        let casillas = ubicacion::casillas_con_ubicacion(texto);
+        let ubicaciones: Vec<Vec<Ubicacion>> = casillas
+            .iter()
+            .map(|fila| fila.iter().map(|(_, ubicacion)| *ubicacion).collect())
+            .collect();
+        let filas = casillas
+            .iter()
+            .map(|fila| {
+                fila.iter()
+                    .map(|(casilla, _)| casilla.to_string())
+                    .collect()
+            })
+            .collect();
+        let mut tablero =
+            Tablero::desde_filas(filas).map_err(|error| error.ubicar(&ubicaciones))?;
+        tablero.ubicaciones = ubicaciones.into_iter().flatten().collect();
+        Ok(tablero)
+    }
+
+    /// Las casillas de cada portal, por numero. Cada lista esta en el orden de `celdas`.
+    pub fn portales(&self) -> BTreeMap<usize, Vec<Punto>> {
+        let mut portales: BTreeMap<usize, Vec<Punto>> = BTreeMap::new();
+        for (punto, celda) in self.celdas() {
+            if let Celda::Portal { numero } = celda {
+                portales.entry(*numero).or_default().push(punto);
+            }
+        }
+        portales
+    }
+
+    /// La primera casilla, leyendo por filas, de un portal que no aparece exactamente dos veces, con las veces que
+    /// aparece, o None si todos los portales tienen pareja.
+    pub fn portal_sin_pareja(&self) -> Option<(Punto, usize)> {
+        self.portales()
+            .into_values()
+            .filter(|puntos| puntos.len() != 2)
+            .map(|puntos| (puntos[0], puntos.len()))
+            .min_by_key(|(punto, _)| (punto.y, punto.x))
+    }
+
+    /// Devuelve la Celda en el punto dado, o None si esta fuera del tablero.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```ignore
+    /// match tablero.get(Punto { x: 3, y: 0 }) {
+    ///     Some(celda) => println!("{}", celda),
+    ///     None => println!("fuera del tablero"),
+    /// }
+    /// ```
+    pub fn get(&self, punto: Punto) -> Option<&Celda> {
+        self.celdas.get(self.indice(punto)?)
+    }
+
+    /// Devuelve la Celda en el punto dado para modificarla, o None si esta fuera del tablero. Como `set`, conserva el
+    /// dato opaco de la casilla.
+    pub fn get_mut(&mut self, punto: Punto) -> Option<&mut Celda> {
+        let indice = self.indice(punto)?;
+        self.celdas.get_mut(indice)
+    }
+
+    /// Reemplaza la Celda en el punto dado, conservando el dato opaco de la casilla.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con la Celda que habia, o None si el punto esta fuera del tablero (y entonces no se cambia nada).
+    pub fn set(&mut self, punto: Punto, celda: Celda) -> Option<Celda> {
+        let indice = self.indice(punto)?;
+        Some(std::mem::replace(&mut self.celdas[indice], celda))
+    }
+
+    /// Donde estaba la casilla del punto dado en el texto del que se leyo el tablero, o None si el tablero no se leyo
+    /// de un texto (o el punto esta afuera).
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let tablero = Tablero::desde_texto("# nivel 1\nB1 F2")?;
+    /// assert_eq!(tablero.ubicacion(Punto { x: 1, y: 0 }).map(|u| u.to_string()), Some("2:4".to_string()));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ubicacion(&self, punto: Punto) -> Option<Ubicacion> {
+        self.ubicaciones.get(self.indice(punto)?).copied()
+    }
+
+    /// La posicion de la Celda del punto dado en `celdas`, o None si el punto esta fuera del tablero. Hay que
+    /// revisar la columna aparte porque una columna de mas caeria en la fila siguiente.
+    fn indice(&self, punto: Punto) -> Option<usize> {
+        (punto.x < self.ancho && punto.y < self.alto).then(|| punto.y * self.ancho + punto.x)
+    }
+
+    /// El dato opaco de la casilla en el punto dado, si tiene.
+    pub fn dato(&self, punto: Punto) -> Option<&str> {
+        self.datos.get(&punto).map(String::as_str)
+    }
+
+    /// El texto de la casilla en el punto dado, como se escribe en los archivos (con su dato opaco), o None si
+    /// esta fuera del tablero.
+    pub fn casilla(&self, punto: Punto) -> Option<String> {
+        let celda = self.get(punto)?;
+        Some(match self.dato(punto) {
+            Some(dato) => format!("{}{{{}}}", celda, dato),
+            None => celda.to_string(),
+        })
+    }
+
+    /// Las Celdas de la fila y, de izquierda a derecha, sin copiarlas, o None si la fila esta fuera del tablero.
+    pub fn fila(&self, y: usize) -> Option<&[Celda]> {
+        (y < self.alto).then(|| &self.celdas[y * self.ancho..(y + 1) * self.ancho])
+    }
+
+    /// La cantidad de columnas.
+    pub fn ancho(&self) -> usize {
+        self.ancho
+    }
+
+    /// La cantidad de filas.
+    pub fn alto(&self) -> usize {
+        self.alto
+    }
+
+    /// Indica si el punto corresponde a una casilla del tablero.
+    pub fn en_rango(&self, punto: Punto) -> bool {
+        self.get(punto).is_some()
+    }
+
+    /// Calcula la casilla vecina a un punto del tablero en la direccion dada. Todos los pasos de una rafaga pasan por
+    /// aca, asi el motor no suma ni resta coordenadas por su cuenta.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let tablero = Tablero::desde_texto("B1 _ _\n_ _ _")?;
+    /// assert_eq!(tablero.avanzar(Punto { x: 0, y: 0 }, Direccion::Izquierda, false), None);
+    /// assert_eq!(tablero.avanzar(Punto { x: 0, y: 0 }, Direccion::Izquierda, true), Some(Punto { x: 2, y: 0 }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Argumentos
+    ///
+    /// * punto: Una casilla del tablero.
+    /// * direccion: Hacia donde se avanza.
+    /// * toroidal: true si al salir por un borde se sigue por el opuesto (ver `Reglas::toroidal`).
+    ///
+    /// # Devuelve
+    ///
+    /// Un Option con la casilla vecina, o None si el punto esta fuera del tablero o el paso sale del tablero sin
+    /// ser toroidal.
+    pub fn avanzar(&self, punto: Punto, direccion: Direccion, toroidal: bool) -> Option<Punto> {
+        if !self.en_rango(punto) {
+            return None;
+        }
+        if !toroidal {
+            return direccion
+                .aplicar(punto)
+                .filter(|vecina| self.en_rango(*vecina));
+        }
+        let (dx, dy) = direccion.componentes();
+        Some(Punto {
+            x: (punto.x + self.ancho).wrapping_add_signed(dx) % self.ancho,
+            y: (punto.y + self.alto).wrapping_add_signed(dy) % self.alto,
+        })
+    }
+
+    /// Recorre todas las casillas por filas, de arriba hacia abajo y de izquierda a derecha.
+    pub fn celdas(&self) -> impl Iterator<Item = (Punto, &Celda)> {
+        let ancho = self.ancho;
+        self.celdas.iter().enumerate().map(move |(i, celda)| {
+            let punto = Punto {
+                x: i % ancho,
+                y: i / ancho,
+            };
+            (punto, celda)
+        })
+    }
+
+    /// El texto del tablero, que es el que se escribe en los archivos de salida: las casillas con su dato opaco,
+    /// separadas por espacios, y las filas por saltos de linea, sin salto al final.
+    ///
+    /// Se garantiza que `Tablero::desde_texto(&tablero.to_texto())` devuelve un tablero igual, siempre que sus Celdas
+    /// sean validas (por ejemplo, sin enemigos de vida 0) y no tenga filas sin casillas, que no se pueden escribir.
+    ///
+    /// # Ejemplos
+    ///
+    /// ```
+    /// # use tp1_bomberman::bomberman::prelude::*;
+    /// let tablero = Tablero::desde_texto("B1 F2{id=3}\n_ W")?;
+    /// assert_eq!(tablero.to_texto(), "B1 F2{id=3}\n_ W");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_texto(&self) -> String {
+        let mut texto = Vec::new();
+        // Escribir en un Vec en memoria no puede fallar.
+        self.escribir(&mut texto)
+            .expect("no se pudo escribir el tablero en memoria");
+        String::from_utf8(texto).expect("el texto de las casillas es UTF-8")
+    }
+
+    /// Escribe el texto de `to_texto` directamente en el escritor, casilla por casilla.
+    pub fn escribir(&self, escritor: &mut impl Write) -> io::Result<()> {
+        for y in 0..self.alto {
+            if y > 0 {
+                escritor.write_all(b"\n")?;
+            }
+            let fila = &self.celdas[y * self.ancho..(y + 1) * self.ancho];
+            for (x, celda) in fila.iter().enumerate() {
+                if x > 0 {
+                    escritor.write_all(b" ")?;
+                }
+                write!(escritor, "{}", celda)?;
+                if let Some(dato) = self.dato(Punto { x, y }) {
+                    write!(escritor, "{{{}}}", dato)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Las filas del tablero como texto, de arriba hacia abajo, con el mismo formato que los archivos de entrada.
+    pub fn filas(&self) -> Vec<Vec<String>> {
+        (0..self.alto)
+            .map(|y| {
+                (0..self.ancho)
+                    .filter_map(|x| self.casilla(Punto { x, y }))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Dos tableros son iguales si tienen las mismas casillas, aunque se hayan leido de textos distintos.
+impl PartialEq for Tablero {
+    fn eq(&self, otro: &Tablero) -> bool {
+        (self.ancho, self.alto) == (otro.ancho, otro.alto)
+            && self.celdas == otro.celdas
+            && self.datos == otro.datos
+    }
+}
+
+/// Interpreta las filas de texto como `Tablero::desde_filas`.
+impl TryFrom<Vec<Vec<String>>> for Tablero {
+    type Error = BombermanError;
+
+    fn try_from(filas: Vec<Vec<String>>) -> Result<Tablero, BombermanError> {
+        Tablero::desde_filas(filas)
+    }
+}
+
+/// Permite comparar directamente una matriz de Strings con un Tablero.
+impl PartialEq<Tablero> for Vec<Vec<String>> {
+    fn eq(&self, tablero: &Tablero) -> bool {
+        *self == tablero.filas()
+    }
+}
+
+/// Acceso a una Celda por Punto. Igual que con un Vec, entra en panico si el punto esta fuera del tablero;
+/// para consultar puntos que pueden estar afuera se usan `get` y `get_mut`.
+impl Index<Punto> for Tablero {
+    type Output = Celda;
+
+    fn index(&self, punto: Punto) -> &Celda {
+        match self.indice(punto) {
+            Some(indice) => &self.celdas[indice],
+            None => panic!(
+                "el punto ({}, {}) esta fuera del tablero de {}x{}",
+                punto.x, punto.y, self.ancho, self.alto
+            ),
+        }
+    }
+}
+
+impl IndexMut<Punto> for Tablero {
+    fn index_mut(&mut self, punto: Punto) -> &mut Celda {
+        match self.indice(punto) {
+            Some(indice) => &mut self.celdas[indice],
+            None => panic!(
+                "el punto ({}, {}) esta fuera del tablero de {}x{}",
+                punto.x, punto.y, self.ancho, self.alto
+            ),
+        }
+    }
+}
+
+/// Con la feature `serde`, un Tablero se guarda como sus filas de texto (ver `Tablero::filas`): en cualquier formato
+/// queda igual que en un archivo de tablero, con los datos opacos, y al leerlo se valida como en `desde_filas`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tablero {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.filas().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tablero {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Tablero, D::Error> {
+        let filas = Vec::<Vec<String>>::deserialize(deserializer)?;
+        Tablero::desde_filas(filas).map_err(|error| match error.detalle() {
+            Some(detalle) => serde::de::Error::custom(format!("{} ({})", error, detalle)),
+            None => serde::de::Error::custom(error),
+        })
+    }
+}
+
+/// El lado mas largo de los tableros de `Arbitrary`, para que las pruebas de propiedades sigan siendo rapidas.
+#[cfg(feature = "arbitrary")]
+pub const LADO_MAXIMO_ARBITRARIO: usize = 12;
+
+/// Con la feature `arbitrary`, un tablero valido con las reglas de siempre, de 1x1 a
+/// `LADO_MAXIMO_ARBITRARIO`x`LADO_MAXIMO_ARBITRARIO`, con casillas al azar (ver `Arbitrary` para Celda). Sirve para
+/// pruebas de propiedades como "simular nunca entra en panico" con `arbitrary`, `cargo fuzz` o proptest.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use arbitrary::Arbitrary;
+/// # use tp1_bomberman::bomberman::prelude::*;
+/// let mut datos = arbitrary::Unstructured::new(&[7, 3, 1, 4, 1, 5, 9, 2, 6]);
+/// let tablero = Tablero::arbitrary(&mut datos)?;
+/// # Ok::<(), arbitrary::Error>(())
+/// ```
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Tablero {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Tablero> {
+        let ancho = u.int_in_range(1..=LADO_MAXIMO_ARBITRARIO)?;
+        let alto = u.int_in_range(1..=LADO_MAXIMO_ARBITRARIO)?;
+        let celdas = (0..alto)
+            .map(|_| (0..ancho).map(|_| u.arbitrary()).collect())
+            .collect::<arbitrary::Result<_>>()?;
+        Ok(Tablero::new(celdas))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tablero;
+    use crate::bomberman::bomba::Bomba;
+    use crate::bomberman::celda::Celda;
+    use crate::bomberman::direccion::Direccion;
+    use crate::bomberman::error::BombermanError;
+    use crate::bomberman::optimizador::Aleatorio;
+    use crate::bomberman::punto::Punto;
+
+    fn tablero() -> Tablero {
+        Tablero::desde_filas(vec![
+            vec!["B1".to_string(), "_".to_string()],
+            vec!["R".to_string(), "F2{id=3}".to_string()],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test01_index_usa_x_como_columna_e_y_como_fila() {
+        let tab = tablero();
+        assert_eq!(tab[Punto { x: 1, y: 0 }], Celda::Vacio);
+        assert_eq!(tab[Punto { x: 0, y: 1 }], Celda::Roca);
+        assert_eq!((tab.ancho(), tab.alto()), (2, 2));
+    }
+
+    #[test]
+    fn test02_set_modifica_la_celda_y_conserva_el_dato() {
+        let mut tab = tablero();
+        let anterior = tab.set(
+            Punto { x: 1, y: 1 },
+            Celda::Enemigo {
+                vida: 1,
+                blindado: false,
+            },
+        );
+        assert_eq!(
+            anterior,
+            Some(Celda::Enemigo {
+                vida: 2,
+                blindado: false
+            })
+        );
+        assert_eq!(tab.filas()[1][1], "F1{id=3}");
+        assert_eq!(tab.set(Punto { x: 2, y: 1 }, Celda::Pared), None);
+    }
+
+    #[test]
+    fn test03_get_fuera_del_tablero_es_none() {
+        let tab = tablero();
+        assert_eq!(tab.get(Punto { x: 2, y: 0 }), None);
+        assert!(!tab.en_rango(Punto {
+            x: 0,
+            y: usize::MAX
+        }));
+        assert!(tab.en_rango(Punto { x: 1, y: 1 }));
+        assert!(matches!(
+            tab.get(Punto { x: 0, y: 0 }),
+            Some(Celda::Bomba(_))
+        ));
+        let mut tab = tab;
+        assert!(tab.get_mut(Punto { x: 0, y: 2 }).is_none());
+    }
+
+    #[test]
+    fn test04_desde_filas_rechaza_casillas_invalidas() {
+        let filas = vec![
+            vec!["_".to_string(), "_".to_string()],
+            vec!["_".to_string(), "Q".to_string()],
+        ];
+        assert_eq!(
+            Tablero::desde_filas(filas).unwrap_err().detalle(),
+            Some("linea 2, columna 2".to_string())
+        );
+        let irregular = vec![
+            vec!["_".to_string(), "_".to_string()],
+            vec!["_".to_string()],
+        ];
+        assert_eq!(
+            Tablero::desde_filas(irregular).unwrap_err().detalle(),
+            Some("linea 2: 1 casillas en lugar de 2".to_string())
+        );
+    }
+
+    /// Una casilla al azar de cualquier tipo, incluidos enemigos y bombas de mas de un digito, con o sin dato opaco.
+    fn casilla_al_azar(aleatorio: &mut Aleatorio) -> String {
+        let celda = match aleatorio.siguiente_hasta(6) {
+            0 => Celda::Vacio,
+            1 => Celda::Pared,
+            2 => Celda::Roca,
+            3 => Celda::Desvio(Direccion::TODAS[aleatorio.siguiente_hasta(4)]),
+            4 => Celda::Enemigo {
+                vida: 1 + aleatorio.siguiente_hasta(20),
+                blindado: aleatorio.siguiente_hasta(4) == 0,
+            },
+            _ => {
+                let mut alcances = [1 + aleatorio.siguiente_hasta(9); 4];
+                if aleatorio.siguiente_hasta(2) == 0 {
+                    alcances = alcances.map(|_| aleatorio.siguiente_hasta(15));
+                }
+                Celda::Bomba(Bomba {
+                    traspaso: aleatorio.siguiente_hasta(2) == 0,
+                    alcances,
+                    mecha: (aleatorio.siguiente_hasta(4) == 0)
+                        .then(|| 1 + aleatorio.siguiente_hasta(12)),
+                })
+            }
+        };
+        match ["", "{}", "{id=7}", "{c=rojo;v=2}"][aleatorio.siguiente_hasta(4)] {
+            "" => celda.to_string(),
+            dato => format!("{}{}", celda, dato),
+        }
+    }
+
+    #[test]
+    fn test05_to_texto_se_vuelve_a_leer_igual() {
+        let mut aleatorio = Aleatorio::new(1506);
+        for _ in 0..200 {
+            let ancho = 1 + aleatorio.siguiente_hasta(5);
+            let filas: Vec<Vec<String>> = (0..1 + aleatorio.siguiente_hasta(5))
+                .map(|_| {
+                    (0..ancho)
+                        .map(|_| casilla_al_azar(&mut aleatorio))
+                        .collect()
+                })
+                .collect();
+            let tablero = Tablero::desde_filas(filas.clone()).unwrap();
+            let texto = tablero.to_texto();
+            assert_eq!(Tablero::desde_texto(&texto).unwrap(), tablero, "{}", texto);
+            assert_eq!(filas, tablero);
+        }
+    }
+
+    #[test]
+    fn test06_tablero_rectangular() {
+        let tab = Tablero::desde_texto("B1 _ _ F1\n\n_ W _ _\n").unwrap();
+        assert_eq!((tab.ancho(), tab.alto()), (4, 2));
+        assert!(tab.en_rango(Punto { x: 3, y: 1 }));
+        assert!(!tab.en_rango(Punto { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn test07_desde_texto_recuerda_las_ubicaciones() {
+        let tab = Tablero::desde_texto("# comentario\nB1  _\n_ W").unwrap();
+        let ubicacion = tab.ubicacion(Punto { x: 1, y: 0 }).unwrap();
+        assert_eq!(
+            (ubicacion.linea, ubicacion.columna, ubicacion.inicio),
+            (2, 5, 17)
+        );
+        assert_eq!(tablero().ubicacion(Punto { x: 0, y: 0 }), None);
+        let error = Tablero::desde_texto("# comentario\nB1 _\n_ Q").unwrap_err();
+        assert_eq!(
+            error.detalle(),
+            Some("linea 2, columna 2 (en el texto 3:3, byte 20)".to_string())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test08_serde_usa_el_texto_de_las_casillas() {
+        let mut aleatorio = Aleatorio::new(1513);
+        for _ in 0..200 {
+            let ancho = 1 + aleatorio.siguiente_hasta(5);
+            let filas: Vec<Vec<String>> = (0..1 + aleatorio.siguiente_hasta(5))
+                .map(|_| {
+                    (0..ancho)
+                        .map(|_| casilla_al_azar(&mut aleatorio))
+                        .collect()
+                })
+                .collect();
+            let tablero = Tablero::desde_filas(filas.clone()).unwrap();
+            let json = serde_json::to_string(&tablero).unwrap();
+            assert_eq!(json, serde_json::to_string(&filas).unwrap());
+            assert_eq!(serde_json::from_str::<Tablero>(&json).unwrap(), tablero);
+            for fila in &filas {
+                for casilla in fila {
+                    let sin_dato = crate::bomberman::celda::separar_dato(casilla).0;
+                    let celda = Celda::parsear(sin_dato).unwrap();
+                    let json = serde_json::to_string(&celda).unwrap();
+                    assert_eq!(json, serde_json::to_string(&celda.to_string()).unwrap());
+                    assert_eq!(serde_json::from_str::<Celda>(&json).unwrap(), celda);
+                }
+            }
+        }
+        let punto = Punto { x: 3, y: 1 };
+        let json = serde_json::to_string(&punto).unwrap();
+        assert_eq!(json, "{\"x\":3,\"y\":1}");
+        assert_eq!(serde_json::from_str::<Punto>(&json).unwrap(), punto);
+        let error = serde_json::from_str::<Tablero>("[[\"B1\",\"Q\"]]").unwrap_err();
+        assert!(
+            error.to_string().contains("linea 1, columna 2"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test09_una_columna_de_mas_no_cae_en_la_fila_siguiente() {
+        let tablero = Tablero::desde_texto("B1 _ F1\nR W _").unwrap();
+        assert_eq!(tablero.get(Punto { x: 3, y: 0 }), None);
+        assert_eq!(tablero.ubicacion(Punto { x: 3, y: 0 }), None);
+        assert_eq!(tablero[Punto { x: 0, y: 1 }], Celda::Roca);
+        let celdas: Vec<(Punto, String)> = tablero
+            .celdas()
+            .skip(2)
+            .take(2)
+            .map(|(punto, celda)| (punto, celda.to_string()))
+            .collect();
+        assert_eq!(
+            celdas,
+            [
+                (Punto { x: 2, y: 0 }, "F1".to_string()),
+                (Punto { x: 0, y: 1 }, "R".to_string())
+            ]
+        );
+        let sin_casillas = Tablero::new(vec![Vec::new(), Vec::new()]);
+        assert_eq!((sin_casillas.ancho(), sin_casillas.alto()), (0, 2));
+        assert_eq!(sin_casillas.to_texto(), "\n");
+        assert_ne!(sin_casillas, Tablero::new(Vec::new()));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test10_los_tableros_arbitrarios_son_validos_y_se_pueden_simular() {
+        use crate::bomberman::punto::{Columna, Fila};
+        use crate::bomberman::Bomberman;
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut aleatorio = Aleatorio::new(1528);
+        for _ in 0..300 {
+            let bytes: Vec<u8> = (0..400)
+                .map(|_| aleatorio.siguiente_hasta(256) as u8)
+                .collect();
+            let tablero = Tablero::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert_eq!(Tablero::desde_filas(tablero.filas()).unwrap(), tablero);
+            let bombas = tablero
+                .celdas()
+                .filter(|(_, celda)| matches!(celda, Celda::Bomba(_)))
+                .map(|(punto, _)| punto);
+            for bomba in bombas {
+                let salida = Bomberman::new(tablero.clone())
+                    .simular(Columna(bomba.x), Fila(bomba.y))
+                    .unwrap();
+                for (punto, celda) in salida.tablero.celdas() {
+                    if let Celda::Enemigo { vida, .. } = celda {
+                        assert!(
+                            matches!(tablero[punto], Celda::Enemigo { vida: antes, .. } if antes >= *vida),
+                            "{}",
+                            tablero.to_texto()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test11_cada_portal_tiene_que_tener_pareja() {
+        let tablero = Tablero::desde_texto("P1 _ P2\nP2 _ P1").unwrap();
+        assert_eq!(
+            tablero.portales()[&1],
+            [Punto { x: 0, y: 0 }, Punto { x: 2, y: 1 }]
+        );
+        assert_eq!(tablero.portal_sin_pareja(), None);
+        match Tablero::desde_texto("P1 P2 P2\nP2 _ P1") {
+            Err(BombermanError::PortalSinPareja {
+                linea,
+                columna,
+                apariciones,
+                ubicacion: Some(ubicacion),
+            }) => {
+                assert_eq!((linea, columna, apariciones), (1, 2, 3));
+                assert_eq!(ubicacion.to_string(), "1:4");
+            }
+            otro => panic!("{:?}", otro),
+        }
+    }
+
+    #[test]
+    fn test12_avanzar_en_un_tablero_toroidal() {
+        let tablero = Tablero::desde_texto("B1 _ _\n_ _ _").unwrap();
+        let esquina = Punto { x: 2, y: 1 };
+        assert_eq!(tablero.avanzar(esquina, Direccion::Derecha, false), None);
+        assert_eq!(
+            tablero.avanzar(esquina, Direccion::Derecha, true),
+            Some(Punto { x: 0, y: 1 })
+        );
+        assert_eq!(
+            tablero.avanzar(esquina, Direccion::AbajoDerecha, true),
+            Some(Punto { x: 0, y: 0 })
+        );
+        assert_eq!(
+            tablero.avanzar(Punto { x: 0, y: 0 }, Direccion::Arriba, true),
+            Some(Punto { x: 0, y: 1 })
+        );
+        assert_eq!(
+            tablero.avanzar(Punto { x: 3, y: 0 }, Direccion::Izquierda, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test13_index_mut_y_get_mut_modifican_la_celda_y_conservan_el_dato() {
+        let mut tab = tablero();
+        tab[Punto { x: 1, y: 1 }] = Celda::Enemigo {
+            vida: 1,
+            blindado: false,
+        };
+        assert_eq!(tab.filas()[1][1], "F1{id=3}");
+        if let Some(celda) = tab.get_mut(Punto { x: 1, y: 0 }) {
+            *celda = Celda::Pared;
+        }
+        assert_eq!(tab[Punto { x: 1, y: 0 }], Celda::Pared);
+    }
+}
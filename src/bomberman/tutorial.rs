@@ -0,0 +1,247 @@
+use super::evento::Evento;
+use super::punto::Punto;
+
+/// Las reglas de Bomberman-R que puede explicar el modo tutorial, en el orden en que aparecen en la consigna.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReglaExplicada {
+    Detonacion,
+    ReaccionEnCadena,
+    Temporizada,
+    Mina,
+    ParedFrena,
+    RocaFrena,
+    Traspaso,
+    Ladrillo,
+    Desvio,
+    Portal,
+    EnemigoDaniado,
+    Blindaje,
+    EnemigoMuere,
+    Mejora,
+    UnGolpePorBomba,
+    GolpesPorOnda,
+    EnemigoBloquea,
+    JugadorMuere,
+}
+
+/// Titulo y explicacion de cada regla.
+const DESCRIPCIONES: [(ReglaExplicada, &str, &str); 18] = [
+    (
+        ReglaExplicada::Detonacion,
+        "Detonacion",
+        "Al explotar, una bomba se vacia y lanza una rafaga en cada una de las 4 direcciones, que recorre tantas casillas como su alcance.",
+    ),
+    (
+        ReglaExplicada::ReaccionEnCadena,
+        "Reaccion en cadena",
+        "Si una rafaga alcanza otra bomba, esa rafaga se detiene y la bomba alcanzada explota despues, con su propio tipo y alcance.",
+    ),
+    (
+        ReglaExplicada::Temporizada,
+        "Bombas temporizadas",
+        "Una rafaga que alcanza una bomba temporizada (T) se detiene y enciende su mecha: la bomba explota cuando pasan tantos tics como su mecha, despues de toda la reaccion en cadena del tic anterior.",
+    ),
+    (
+        ReglaExplicada::Mina,
+        "Minas",
+        "Una mina (M) explota como una bomba normal con su alcance cuando una rafaga la alcanza o pasa por alguna de las 4 casillas de al lado.",
+    ),
+    (
+        ReglaExplicada::ParedFrena,
+        "Paredes",
+        "Ninguna rafaga atraviesa una pared (W): la rafaga se detiene sin afectar la pared.",
+    ),
+    (
+        ReglaExplicada::RocaFrena,
+        "Rocas y bombas normales",
+        "La rafaga de una bomba normal (B) se detiene al llegar a una roca (R), que queda intacta.",
+    ),
+    (
+        ReglaExplicada::Traspaso,
+        "Bombas de traspaso",
+        "La rafaga de una bomba de traspaso (S) atraviesa las rocas y sigue avanzando. Con la regla que lo permite, tambien atraviesa las paredes.",
+    ),
+    (
+        ReglaExplicada::Ladrillo,
+        "Ladrillos",
+        "Con la regla de ladrillos, cualquier rafaga que llega a un ladrillo (X) lo rompe, dejando la casilla vacia, y se detiene ahi.",
+    ),
+    (
+        ReglaExplicada::Desvio,
+        "Desvios",
+        "Un desvio (DU, DD, DL, DR) cambia la direccion de la rafaga que pasa por el hacia arriba, abajo, la izquierda o la derecha. Los diagonales (DUL, DUR, DDL, DDR) la mandan en diagonal, una esquina por paso.",
+    ),
+    (
+        ReglaExplicada::Portal,
+        "Portales",
+        "Cada portal (P1, P2, ...) aparece dos veces en el tablero: la rafaga que entra a uno sale por el otro y sigue en la misma direccion, con el alcance que le quedaba.",
+    ),
+    (
+        ReglaExplicada::EnemigoDaniado,
+        "Enemigos heridos",
+        "Cada rafaga que alcanza un enemigo (F) le quita 1 de vida y sigue avanzando.",
+    ),
+    (
+        ReglaExplicada::Blindaje,
+        "Enemigos blindados",
+        "Un enemigo blindado (G) no pierde vida con las rafagas de las bombas normales, solo con las de traspaso (S).",
+    ),
+    (
+        ReglaExplicada::EnemigoMuere,
+        "Enemigos eliminados",
+        "Cuando un enemigo se queda sin vida desaparece y su casilla queda vacia.",
+    ),
+    (
+        ReglaExplicada::Mejora,
+        "Mejoras",
+        "Con la regla de mejoras, un enemigo que se queda sin vida deja en su casilla una mejora de alcance (+R), que las rafagas atraviesan sin cambiarla.",
+    ),
+    (
+        ReglaExplicada::UnGolpePorBomba,
+        "Un golpe por bomba",
+        "Un enemigo solo puede ser herido una vez por la misma bomba, aunque varias de sus rafagas pasen por el.",
+    ),
+    (
+        ReglaExplicada::GolpesPorOnda,
+        "Golpes por onda",
+        "Con un limite de golpes por onda, un enemigo no pierde mas vidas en toda la reaccion en cadena que las que permite el limite, aunque lo alcancen varias bombas.",
+    ),
+    (
+        ReglaExplicada::EnemigoBloquea,
+        "Enemigos que bloquean",
+        "Con la regla de enemigos que bloquean, un enemigo frena la rafaga de una bomba normal despues de recibir el golpe.",
+    ),
+    (
+        ReglaExplicada::JugadorMuere,
+        "Jugador",
+        "Cualquier rafaga que alcanza al jugador (P) lo mata, dejando su casilla vacia, y sigue avanzando.",
+    ),
+];
+
+/// Indica que regla explica un evento y en que casilla ocurrio.
+fn regla_de(evento: &Evento) -> (ReglaExplicada, Punto) {
+    match evento {
+        Evento::Detonacion { punto, .. } => (ReglaExplicada::Detonacion, *punto),
+        Evento::BombaAlcanzada { punto } => (ReglaExplicada::ReaccionEnCadena, *punto),
+        Evento::MinaActivada { punto } => (ReglaExplicada::Mina, *punto),
+        Evento::MechaEncendida { punto, .. } | Evento::MechaConsumida { punto, .. } => {
+            (ReglaExplicada::Temporizada, *punto)
+        }
+        Evento::RocaAtravesada { punto } | Evento::ParedAtravesada { punto } => {
+            (ReglaExplicada::Traspaso, *punto)
+        }
+        Evento::LadrilloRoto { punto } => (ReglaExplicada::Ladrillo, *punto),
+        Evento::RafagaFrenada { punto, casilla } => match casilla.as_str() {
+            "W" => (ReglaExplicada::ParedFrena, *punto),
+            "R" => (ReglaExplicada::RocaFrena, *punto),
+            _ => (ReglaExplicada::EnemigoBloquea, *punto),
+        },
+        Evento::Desvio { punto, .. } | Evento::CicloDeDesvios { punto, .. } => {
+            (ReglaExplicada::Desvio, *punto)
+        }
+        Evento::Teletransporte { entrada, .. } => (ReglaExplicada::Portal, *entrada),
+        Evento::EnemigoDaniado { punto, vida: 0 } => (ReglaExplicada::EnemigoMuere, *punto),
+        Evento::EnemigoDaniado { punto, .. } => (ReglaExplicada::EnemigoDaniado, *punto),
+        Evento::EnemigoYaAfectado { punto } => (ReglaExplicada::UnGolpePorBomba, *punto),
+        Evento::MejoraSoltada { punto, .. } => (ReglaExplicada::Mejora, *punto),
+        Evento::BlindajeResiste { punto } => (ReglaExplicada::Blindaje, *punto),
+        Evento::GolpesAgotados { punto } => (ReglaExplicada::GolpesPorOnda, *punto),
+        Evento::JugadorMuerto { punto } => (ReglaExplicada::JugadorMuere, *punto),
+    }
+}
+
+/// Toma los eventos de una simulacion y arma una explicacion de cada regla que se aplico, con las casillas involucradas.
+///
+/// # Ejemplos
+///
+/// ```ignore
+/// let salida = Bomberman::new(tablero).simular(Columna(x), Fila(y))?;
+/// print!("{}", tutorial::explicar(&salida.eventos));
+/// ```
+/// imprime, por ejemplo:
+/// ```text
+/// Detonacion
+///   Al explotar, una bomba se vacia y lanza una rafaga en cada una de las 4 direcciones, ...
+///   Casillas: (0, 0), (0, 2)
+/// ```
+///
+/// # Argumentos
+///
+/// * eventos: El registro de eventos de la simulacion.
+///
+/// # Devuelve
+///
+/// Un String con un bloque por regla aplicada, en el orden de la consigna, o un aviso si no se aplico ninguna.
+pub fn explicar(eventos: &[Evento]) -> String {
+    let mut bloques = Vec::new();
+    for (regla, titulo, descripcion) in DESCRIPCIONES {
+        let mut casillas: Vec<String> = Vec::new();
+        for evento in eventos {
+            let (regla_evento, punto) = regla_de(evento);
+            let casilla = format!("({}, {})", punto.x, punto.y);
+            if regla_evento == regla && !casillas.contains(&casilla) {
+                casillas.push(casilla);
+            }
+        }
+        if !casillas.is_empty() {
+            bloques.push(format!(
+                "{}\n  {}\n  Casillas: {}\n",
+                titulo,
+                descripcion,
+                casillas.join(", ")
+            ));
+        }
+    }
+    if bloques.is_empty() {
+        return "No se aplico ninguna regla.\n".to_string();
+    }
+    bloques.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::explicar;
+    use crate::bomberman::evento::Evento;
+    use crate::bomberman::punto::Punto;
+
+    #[test]
+    fn test01_explica_solo_las_reglas_aplicadas_en_orden() {
+        let eventos = vec![
+            Evento::EnemigoDaniado {
+                punto: Punto { x: 1, y: 0 },
+                vida: 0,
+            },
+            Evento::Detonacion {
+                punto: Punto { x: 0, y: 0 },
+                tipo: 'B',
+                alcance: 1,
+            },
+        ];
+        let texto = explicar(&eventos);
+        assert!(texto.starts_with("Detonacion\n"));
+        assert!(texto.contains("Enemigos eliminados"));
+        assert!(texto.contains("Casillas: (1, 0)"));
+        assert!(!texto.contains("Desvios"));
+    }
+
+    #[test]
+    fn test02_casillas_repetidas_aparecen_una_vez() {
+        let punto = Punto { x: 2, y: 1 };
+        let eventos = vec![
+            Evento::RafagaFrenada {
+                punto,
+                casilla: "W".to_string(),
+            },
+            Evento::RafagaFrenada {
+                punto,
+                casilla: "W".to_string(),
+            },
+        ];
+        assert!(explicar(&eventos).contains("Casillas: (2, 1)\n"));
+    }
+
+    #[test]
+    fn test03_sin_eventos() {
+        assert_eq!(explicar(&[]), "No se aplico ninguna regla.\n");
+    }
+}
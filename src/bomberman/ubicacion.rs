@@ -0,0 +1,100 @@
+/// Donde esta una casilla en el texto del que se leyo el tablero, para que un editor pueda senalarla exactamente.
+///
+/// A diferencia de un Punto, la linea cuenta tambien las lineas vacias y de comentario, y la columna es la del primer
+/// caracter de la casilla dentro de su linea, no su indice entre las casillas de la fila.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ubicacion {
+    /// La linea del texto, contando desde 1.
+    pub linea: usize,
+    /// El caracter de la linea en el que empieza la casilla, contando desde 1.
+    pub columna: usize,
+    /// La posicion en bytes del comienzo de la casilla desde el principio del texto.
+    pub inicio: usize,
+    /// El largo en bytes de la casilla, incluido su dato opaco.
+    pub largo: usize,
+}
+
+/// Se escribe como `linea:columna`, la forma en que la mayoria de los editores aceptan una posicion.
+impl std::fmt::Display for Ubicacion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.linea, self.columna)
+    }
+}
+
+/// Separa el texto de un tablero en casillas, salteando las lineas vacias y las de comentario (que empiezan con `#`),
+/// y devuelve cada casilla junto con su Ubicacion en el texto.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::ubicacion::{casillas_con_ubicacion, Ubicacion};
+/// let filas = casillas_con_ubicacion("# nivel 1\nB1  F2");
+/// assert_eq!(filas[0][1].0, "F2");
+/// assert_eq!(filas[0][1].1, Ubicacion { linea: 2, columna: 5, inicio: 14, largo: 2 });
+/// ```
+///
+/// # Devuelve
+///
+/// Las filas del tablero, de arriba hacia abajo, con el texto y la Ubicacion de cada casilla.
+pub fn casillas_con_ubicacion(texto: &str) -> Vec<Vec<(&str, Ubicacion)>> {
+    let mut filas = Vec::new();
+    let mut inicio_linea = 0;
+    for (indice, linea_completa) in texto.split_inclusive('\n').enumerate() {
+        let linea = linea_completa.trim_end_matches(['\n', '\r']);
+        if !linea.starts_with('#') && !linea.trim().is_empty() {
+            let fila = linea
+                .split_whitespace()
+                .map(|casilla| {
+                    // split_whitespace devuelve partes de la misma linea, asi que la resta da su posicion en ella.
+                    let desde = casilla.as_ptr() as usize - linea.as_ptr() as usize;
+                    let ubicacion = Ubicacion {
+                        linea: indice + 1,
+                        columna: linea[..desde].chars().count() + 1,
+                        inicio: inicio_linea + desde,
+                        largo: casilla.len(),
+                    };
+                    (casilla, ubicacion)
+                })
+                .collect();
+            filas.push(fila);
+        }
+        inicio_linea += linea_completa.len();
+    }
+    filas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{casillas_con_ubicacion, Ubicacion};
+
+    #[test]
+    fn test01_ubicaciones_con_comentarios_y_lineas_vacias() {
+        let filas = casillas_con_ubicacion("# objetivo: x\r\nB1 _\r\n\r\n  ñ F2{id=1}");
+        assert_eq!(filas.len(), 2);
+        assert_eq!(
+            filas[0][0],
+            (
+                "B1",
+                Ubicacion {
+                    linea: 2,
+                    columna: 1,
+                    inicio: 15,
+                    largo: 2
+                }
+            )
+        );
+        assert_eq!(
+            filas[1][1],
+            (
+                "F2{id=1}",
+                Ubicacion {
+                    linea: 4,
+                    columna: 5,
+                    inicio: 28,
+                    largo: 8
+                }
+            )
+        );
+        assert_eq!(filas[1][1].1.to_string(), "4:5");
+    }
+}
@@ -0,0 +1,75 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use super::motor::Bomberman;
+use super::punto::{Columna, Fila};
+use super::tablero::Tablero;
+
+/// La interfaz para un playground en el navegador, compilado a WebAssembly: un tablero que se lee de un texto, se
+/// simula de a una bomba y se vuelve a escribir, todo sin archivos. Se exporta a JavaScript con `wasm-bindgen`, asi que
+/// solo usa texto y numeros para entrar y salir, y los errores son el texto que mostraria la linea de comandos.
+/// La simulacion es la misma del motor, con las reglas por defecto.
+///
+/// # Ejemplos
+///
+/// ```
+/// # use tp1_bomberman::bomberman::wasm::parse_board;
+/// let mut sesion = parse_board("B2 R F1\n_ W _")?;
+/// sesion.simulate(0, 0)?;
+/// assert_eq!(sesion.board_to_string(), "_ R F1\n_ W _");
+/// # Ok::<(), String>(())
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Sesion {
+    tablero: Tablero,
+}
+
+/// Lee el tablero del texto, con el formato de los archivos (ver `Tablero::desde_texto`).
+///
+/// # Devuelve
+///
+/// Un Result con la Sesion o un Err(String) con el error de lectura, con su posicion en el texto.
+#[wasm_bindgen]
+pub fn parse_board(texto: &str) -> Result<Sesion, String> {
+    Tablero::desde_texto(texto)
+        .map(|tablero| Sesion { tablero })
+        .map_err(|e| e.to_string())
+}
+
+#[wasm_bindgen]
+impl Sesion {
+    /// Detona la bomba en la columna x y la fila y, y deja el tablero como termino la reaccion en cadena, para
+    /// seguir con otra bomba.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Ok(()) o un Err(String) con el error de la simulacion, por ejemplo si no hay una bomba en esa casilla. Con
+    /// un error el tablero no cambia.
+    pub fn simulate(&mut self, x: usize, y: usize) -> Result<(), String> {
+        let salida = Bomberman::new(self.tablero.clone())
+            .simular(Columna(x), Fila(y))
+            .map_err(|e| e.to_string())?;
+        self.tablero = salida.tablero;
+        Ok(())
+    }
+
+    /// El texto del tablero como esta ahora (ver `Tablero::to_texto`).
+    pub fn board_to_string(&self) -> String {
+        self.tablero.to_texto()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_board;
+
+    #[test]
+    fn test01_lee_simula_y_escribe_sin_archivos() {
+        let mut sesion = parse_board("B2 R F1\n_ W _\nB1 _ F2{id=3}").unwrap();
+        sesion.simulate(0, 0).unwrap();
+        assert_eq!(sesion.board_to_string(), "_ R F1\n_ W _\n_ _ F2{id=3}");
+        assert!(sesion.simulate(0, 0).is_err());
+        assert_eq!(sesion.board_to_string(), "_ R F1\n_ W _\n_ _ F2{id=3}");
+        assert!(parse_board("B2 ?").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::Path;
+
+use tp1_bomberman::bomberman::punto::Punto;
+
+/// Version del formato de la cache. Se cambia cuando una nueva version del programa puede
+/// producir otro resultado para la misma entrada, asi no se reutilizan resultados viejos.
+const VERSION_CACHE: &str = "1";
+
+/// Calcula un hash FNV-1a de 64 bits. Se usa en lugar de `DefaultHasher` porque el resultado
+/// tiene que ser el mismo entre ejecuciones y versiones del compilador para poder guardarlo en disco.
+pub fn hash_fnv(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Calcula el hash FNV-1a del texto de un tablero, con las casillas separadas por espacios y las filas por saltos de linea.
+pub fn hash_tablero(tablero: &[Vec<String>]) -> u64 {
+    let texto: Vec<String> = tablero.iter().map(|fila| fila.join(" ")).collect();
+    hash_fnv(texto.join("\n").as_bytes())
+}
+
+/// Toma un tablero ya validado y la coordenada de la bomba a detonar y arma la clave con la que se guarda su resultado.
+///
+/// # Ejemplos
+///
+/// ```
+/// let clave = cache::clave(&tablero, &punto_bomba, "limite=None");
+/// if let Some(texto) = cache::buscar(dir_cache, &clave) { ... }
+/// ```
+///
+/// # Argumentos
+///
+/// * tablero: La matriz de Strings del tablero inicial.
+/// * punto: La coordenada de la bomba que se detona.
+/// * configuracion: Descripcion de todo lo que ademas del tablero puede cambiar el resultado (por ejemplo el limite de alcance).
+///
+/// # Devuelve
+///
+/// Un String que identifica a la combinacion de tablero, coordenadas, configuracion y version de la cache.
+pub fn clave(tablero: &[Vec<String>], punto: &Punto, configuracion: &str) -> String {
+    format!(
+        "v{}-{:016x}-{}-{}-{:016x}",
+        VERSION_CACHE,
+        hash_tablero(tablero),
+        punto.x,
+        punto.y,
+        hash_fnv(configuracion.as_bytes())
+    )
+}
+
+/// Arma el id de una jugada para `--format json`: como la clave de la cache, pero solo con el tablero, las coordenadas
+/// y las reglas, y sin la version de la cache, asi la misma jugada tiene el mismo id en todas las versiones del
+/// programa y se pueden comparar sus resultados (ver `bisect`).
+pub fn id_jugada(tablero: &[Vec<String>], punto: &Punto, reglas: &str) -> String {
+    format!(
+        "{:016x}-{}-{}-{:016x}",
+        hash_tablero(tablero),
+        punto.x,
+        punto.y,
+        hash_fnv(reglas.as_bytes())
+    )
+}
+
+/// Busca en el directorio de cache el resultado guardado con la clave dada.
+///
+/// # Devuelve
+///
+/// Un Option con el texto del tablero final, o None si no estaba guardado o no se pudo leer.
+pub fn buscar(dir: &str, clave: &str) -> Option<String> {
+    fs::read_to_string(Path::new(dir).join(clave)).ok()
+}
+
+/// Guarda en el directorio de cache el texto del tablero final con la clave dada, creando el directorio si hace falta.
+/// Un error al guardar no impide la simulacion, asi que solo se avisa por stderr.
+pub fn guardar(dir: &str, clave: &str, texto: &str) {
+    let resultado =
+        fs::create_dir_all(dir).and_then(|_| fs::write(Path::new(dir).join(clave), texto));
+    if resultado.is_err() {
+        eprintln!("Error: no se pudo guardar el resultado en la cache {}", dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clave, hash_fnv};
+    use tp1_bomberman::bomberman::punto::Punto;
+
+    #[test]
+    fn test01_hash_es_estable() {
+        assert_eq!(hash_fnv(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash_fnv(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test02_clave_depende_del_tablero_las_coordenadas_y_la_configuracion() {
+        let tablero = vec![vec!["B1".to_string(), "_".to_string()]];
+        let otro = vec![vec!["B2".to_string(), "_".to_string()]];
+        let origen = Punto { x: 0, y: 0 };
+        assert_eq!(
+            clave(&tablero, &origen, ""),
+            clave(&tablero, &Punto { x: 0, y: 0 }, "")
+        );
+        assert_ne!(clave(&tablero, &origen, ""), clave(&otro, &origen, ""));
+        assert_ne!(
+            clave(&tablero, &origen, ""),
+            clave(&tablero, &Punto { x: 1, y: 0 }, "")
+        );
+        assert_ne!(
+            clave(&tablero, &origen, ""),
+            clave(&tablero, &origen, "limite=Some(1)")
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::uso;
+
+/// Version de la interfaz para herramientas externas. Se incrementa cuando cambia de forma incompatible
+/// algo de lo que se informa en `informe` (formatos, opciones o subcomandos).
+pub const VERSION_API: u32 = 1;
+
+/// Formatos de tablero que se pueden leer.
+const FORMATOS_ENTRADA: &[&str] = &["texto", "json", "escenario-toml", "hex"];
+/// Formatos en los que se puede escribir el resultado.
+const FORMATOS_SALIDA: &[&str] = &["texto", "json-bloques", "json"];
+/// Casillas validas de un tablero; `<n>` es el alcance de una bomba (ver `Bomba`) o la vida de un enemigo, hasta los
+/// topes de `Reglas::alcance_maximo` y `Reglas::vida_maxima`, y cualquier casilla puede
+/// terminar con un dato opaco entre llaves (ver `celda::separar_dato`).
+const CASILLAS: &[&str] = &[
+    "_",
+    "W",
+    "R",
+    "X",
+    "DU",
+    "DD",
+    "DL",
+    "DR",
+    "DUL",
+    "DUR",
+    "DDL",
+    "DDR",
+    "B<n>",
+    "S<n>",
+    "B[U<n>,D<n>,L<n>,R<n>]",
+    "S[U<n>,D<n>,L<n>,R<n>]",
+    "T<n>",
+    "T<n><bomba>",
+    "F<n>",
+    "G<n>",
+    "M<n>",
+    "+R",
+    "P<n>",
+    "P",
+    "<casilla>{<dato>}",
+];
+/// Variantes de las reglas que se pueden activar (ver `Reglas`).
+const REGLAS: &[&str] = &[
+    "enemigos_bloquean",
+    "golpes_por_onda",
+    "alcance_maximo",
+    "vida_maxima",
+    "ladrillos",
+    "mejoras",
+    "toroidal",
+    "traspaso_atraviesa_paredes",
+    "desvios_consumen_alcance",
+    "un_golpe_por_bomba",
+];
+/// Protocolos de `--protocol`, para atender pedidos por stdin y stdout.
+const PROTOCOLOS: &[&str] = &["jsonl"];
+/// Arma el informe de `--capacidades`, para que una herramienta externa sepa que soporta esta version del programa
+/// sin tener que interpretar textos de ayuda. Las opciones y los subcomandos salen de la linea de comandos de
+/// `uso::comando`, asi que no hay que mantenerlos a mano.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run --capacidades
+/// {"version_api":1,"version":"0.1.0","formatos_entrada":["texto"],...}
+/// ```
+///
+/// # Devuelve
+///
+/// Un String con un objeto JSON en una sola linea.
+pub fn informe() -> String {
+    let comando = uso::comando();
+    let subcomandos: Vec<&str> = comando.get_subcommands().map(|s| s.get_name()).collect();
+    let opciones: Vec<String> = comando
+        .find_subcommand("simular")
+        .into_iter()
+        .flat_map(|simular| simular.get_arguments())
+        .filter(|arg| arg.get_id() != "ayuda")
+        .filter_map(|arg| arg.get_long().map(|largo| format!("--{}", largo)))
+        .collect();
+    let opciones: Vec<&str> = opciones.iter().map(String::as_str).collect();
+    let listas = [
+        ("formatos_entrada", FORMATOS_ENTRADA),
+        ("formatos_salida", FORMATOS_SALIDA),
+        ("casillas", CASILLAS),
+        ("reglas", REGLAS),
+        ("opciones", opciones.as_slice()),
+        ("subcomandos", subcomandos.as_slice()),
+        ("protocolos", PROTOCOLOS),
+    ];
+    let mut campos = vec![
+        format!("\"version_api\":{}", VERSION_API),
+        format!("\"version\":\"{}\"", env!("CARGO_PKG_VERSION")),
+    ];
+    for (nombre, valores) in listas {
+        let valores: Vec<String> = valores.iter().map(|v| format!("\"{}\"", v)).collect();
+        campos.push(format!("\"{}\":[{}]", nombre, valores.join(",")));
+    }
+    format!("{{{}}}", campos.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::informe;
+
+    #[test]
+    fn test01_informe_es_un_objeto_json_con_la_version() {
+        let texto = informe();
+        assert!(texto.starts_with("{\"version_api\":1,"));
+        assert!(texto.ends_with("]}"));
+        assert!(texto.contains("\"subcomandos\":[\"simular\",\"validar\","));
+        assert!(texto.contains("\"--eventos\","));
+        assert!(!texto.contains("\"--help\""));
+        assert!(texto.contains("\"reglas\":[\"enemigos_bloquean\",\"golpes_por_onda\",\"alcance_maximo\",\"vida_maxima\",\"ladrillos\",\"mejoras\",\"toroidal\",\"traspaso_atraviesa_paredes\",\"desvios_consumen_alcance\",\"un_golpe_por_bomba\"]"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tp1_bomberman::bomberman::io::tablero_desde_archivo_con_reglas;
+use tp1_bomberman::bomberman::prelude::*;
+
+/// Extension del archivo con las coordenadas que acompania a cada tablero de la bandeja de entrada.
+pub const EXTENSION_COORDENADAS: &str = "coords";
+/// Nombre del diario de trabajos procesados, dentro de la bandeja de salida.
+pub const DIARIO: &str = "diario.txt";
+/// Cuanto tiempo sin cambios espera el daemon despues de un aviso de la bandeja de entrada antes de revisarla.
+pub const CALMA: Duration = Duration::from_millis(100);
+/// Subdirectorio de la bandeja de salida al que se mueven los archivos de cada trabajo procesado.
+pub const PROCESADOS: &str = "procesados";
+
+/// Un trabajo de la bandeja de entrada: un tablero `nombre.txt` y sus coordenadas en `nombre.coords`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trabajo {
+    pub nombre: String,
+    pub tablero: PathBuf,
+    pub coordenadas: PathBuf,
+}
+
+/// Busca los trabajos listos de la bandeja de entrada, ordenados por nombre. Un tablero sin su archivo de coordenadas
+/// todavia no esta listo, asi que quien deja un trabajo tiene que escribir las coordenadas al final.
+pub fn pendientes(bandeja: &Path) -> io::Result<Vec<Trabajo>> {
+    let mut trabajos: Vec<Trabajo> = fs::read_dir(bandeja)?
+        .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+        .filter(|ruta| ruta.is_file() && ruta.extension().is_some_and(|e| e == "txt"))
+        .filter_map(|tablero| {
+            let coordenadas = tablero.with_extension(EXTENSION_COORDENADAS);
+            let nombre = tablero.file_name()?.to_string_lossy().to_string();
+            coordenadas.is_file().then_some(Trabajo {
+                nombre,
+                tablero,
+                coordenadas,
+            })
+        })
+        .collect();
+    trabajos.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+    Ok(trabajos)
+}
+
+/// Lee un archivo de coordenadas, con la forma `x y`.
+pub fn leer_coordenadas(texto: &str) -> Result<Punto, String> {
+    let mut partes = texto.split_whitespace().map(str::parse);
+    match (partes.next(), partes.next(), partes.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Ok(Punto { x, y }),
+        _ => Err(format!("Error: coordenadas invalidas ({})", texto.trim())),
+    }
+}
+
+/// Simula un trabajo con las reglas y los limites dados y escribe el tablero final, o el error, en la bandeja de
+/// salida con el mismo nombre que el tablero. Despues mueve los archivos del trabajo a `procesados` y agrega una
+/// linea al diario.
+///
+/// # Devuelve
+///
+/// Un Result con la linea que se agrego al diario, por ejemplo `1718000000 entrada.txt (0, 0): ok`, o un
+/// Err(io::Error) si no se pudo escribir la salida o mover el trabajo.
+pub fn procesar(
+    trabajo: &Trabajo,
+    salida: &Path,
+    reglas: &Reglas,
+    limites: &Limites,
+) -> io::Result<String> {
+    let coordenadas = fs::read_to_string(&trabajo.coordenadas)
+        .map_err(|_| "Error: no se pudieron leer las coordenadas".to_string())
+        .and_then(|texto| leer_coordenadas(&texto));
+    let resultado = coordenadas.clone().and_then(|punto| {
+        tablero_desde_archivo_con_reglas(&trabajo.tablero, reglas)
+            .and_then(|tablero| {
+                SimulacionBuilder::new(tablero)
+                    .reglas(reglas.clone())
+                    .limites(limites.clone())
+                    .construir()
+                    .simular(punto.columna(), punto.fila())
+            })
+            .map(|salida| salida.tablero.to_texto())
+            .map_err(|e| e.to_string())
+    });
+    let (texto, estado) = match resultado {
+        Ok(texto) => (texto, "ok".to_string()),
+        Err(e) => (e.clone(), e),
+    };
+    fs::write(salida.join(&trabajo.nombre), texto)?;
+    let procesados = salida.join(PROCESADOS);
+    fs::create_dir_all(&procesados)?;
+    for archivo in [&trabajo.tablero, &trabajo.coordenadas] {
+        if let Some(nombre) = archivo.file_name() {
+            fs::rename(archivo, procesados.join(nombre))?;
+        }
+    }
+    let segundos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let linea = match coordenadas {
+        Ok(punto) => format!(
+            "{} {} ({}, {}): {}",
+            segundos, trabajo.nombre, punto.x, punto.y, estado
+        ),
+        Err(_) => format!("{} {}: {}", segundos, trabajo.nombre, estado),
+    };
+    let mut diario = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(salida.join(DIARIO))?;
+    writeln!(diario, "{}", linea)?;
+    Ok(linea)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{leer_coordenadas, pendientes, procesar, DIARIO, PROCESADOS};
+    use tp1_bomberman::bomberman::prelude::*;
+
+    #[test]
+    fn test01_coordenadas() {
+        assert_eq!(leer_coordenadas("2 3\n"), Ok(Punto { x: 2, y: 3 }));
+        assert!(leer_coordenadas("2").is_err());
+        assert!(leer_coordenadas("2 3 4").is_err());
+    }
+
+    #[test]
+    fn test02_procesa_los_trabajos_listos_y_los_anota() {
+        let base = std::env::temp_dir().join("tp1_bomberman_daemon_test02");
+        let _ = fs::remove_dir_all(&base);
+        let (entrada, salida) = (base.join("entrada"), base.join("salida"));
+        fs::create_dir_all(&entrada).unwrap();
+        fs::create_dir_all(&salida).unwrap();
+        fs::write(entrada.join("a.txt"), "B1 F1").unwrap();
+        fs::write(entrada.join("a.coords"), "0 0").unwrap();
+        fs::write(entrada.join("b.txt"), "B1 F1").unwrap();
+        fs::write(entrada.join("c.txt"), "_ F1").unwrap();
+        fs::write(entrada.join("c.coords"), "0 0").unwrap();
+
+        let trabajos = pendientes(&entrada).unwrap();
+        let nombres: Vec<&str> = trabajos.iter().map(|t| t.nombre.as_str()).collect();
+        assert_eq!(nombres, ["a.txt", "c.txt"]);
+        let lineas: Vec<String> = trabajos
+            .iter()
+            .map(|trabajo| {
+                procesar(trabajo, &salida, &Reglas::default(), &Limites::default()).unwrap()
+            })
+            .collect();
+        assert!(lineas[0].ends_with(" a.txt (0, 0): ok"));
+        assert!(lineas[1].ends_with(" c.txt (0, 0): Error: coordenadas invalidas"));
+        assert_eq!(fs::read_to_string(salida.join("a.txt")).unwrap(), "_ _");
+        assert!(salida.join(PROCESADOS).join("a.coords").is_file());
+        assert_eq!(
+            fs::read_to_string(salida.join(DIARIO))
+                .unwrap()
+                .lines()
+                .count(),
+            2
+        );
+        let nombres: Vec<String> = pendientes(&entrada)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.nombre)
+            .collect();
+        assert!(nombres.is_empty());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test03_aplica_los_limites() {
+        let base = std::env::temp_dir().join("tp1_bomberman_daemon_test03");
+        let _ = fs::remove_dir_all(&base);
+        let (entrada, salida) = (base.join("entrada"), base.join("salida"));
+        fs::create_dir_all(&entrada).unwrap();
+        fs::create_dir_all(&salida).unwrap();
+        fs::write(entrada.join("a.txt"), "B1 B1 F1").unwrap();
+        fs::write(entrada.join("a.coords"), "0 0").unwrap();
+
+        let limites = Limites {
+            cadena: Some(1),
+            ..Limites::default()
+        };
+        let trabajo = &pendientes(&entrada).unwrap()[0];
+        let linea = procesar(trabajo, &salida, &Reglas::default(), &limites).unwrap();
+        assert!(linea
+            .ends_with(" a.txt (0, 0): Error: se supero la cantidad maxima de bombas en cadena"));
+        let _ = fs::remove_dir_all(&base);
+    }
+}
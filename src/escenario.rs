@@ -0,0 +1,192 @@
+use tp1_bomberman::bomberman::prelude::*;
+
+use crate::toml::{self, Clave, Tabla, Valor};
+
+/// Claves que acepta un escenario fuera de las tablas `[[detonacion]]`.
+const CLAVES: &[&str] = &["nombre", "autor", "descripcion", "tablero"];
+
+/// Un escenario: un tablero con sus datos y las bombas que se detonan, una despues de la otra, sobre el tablero que
+/// dejo la anterior.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Escenario {
+    pub nombre: Option<String>,
+    pub autor: Option<String>,
+    pub descripcion: Option<String>,
+    /// Las filas del tablero ya separadas en casillas, sin validar.
+    pub filas: Vec<Vec<String>>,
+    pub detonaciones: Vec<Punto>,
+}
+
+/// Lee un escenario. Es un documento del subconjunto de TOML de `toml::parsear` con `nombre`, `autor` y `descripcion`
+/// opcionales, el `tablero` como un arreglo con una fila por texto y una tabla `[[detonacion]]` con `x` e `y` por cada
+/// bomba a detonar, en orden.
+///
+/// # Ejemplos
+///
+/// ```
+/// let escenario = parsear("nombre = \"demo\"\ntablero = [\"B1 _\", \"_ F1\"]\n[[detonacion]]\nx = 0\ny = 0\n")?;
+/// assert_eq!(escenario.detonaciones, vec![Punto { x: 0, y: 0 }]);
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con el Escenario o un Err(String) con la linea y la causa del primer error: una linea que no se entiende,
+/// una clave desconocida, repetida o del tipo equivocado, o un escenario sin tablero o sin detonaciones.
+pub fn parsear(texto: &str) -> Result<Escenario, String> {
+    let mut tablas = toml::parsear(texto)?.into_iter();
+    let principal = tablas.next().expect("siempre esta la tabla sin encabezado");
+    let mut escenario = Escenario {
+        nombre: None,
+        autor: None,
+        descripcion: None,
+        filas: Vec::new(),
+        detonaciones: Vec::new(),
+    };
+    for Clave {
+        nombre,
+        valor,
+        linea,
+    } in &principal.claves
+    {
+        if !CLAVES.contains(&nombre.as_str()) {
+            return Err(format!(
+                "Error: linea {}: clave desconocida {} (se aceptan {} y tablas [[detonacion]])",
+                linea,
+                nombre,
+                CLAVES.join(", ")
+            ));
+        }
+        let error =
+            |tipo: &str| format!("Error: linea {}: {} tiene que ser {}", linea, nombre, tipo);
+        match (nombre.as_str(), valor) {
+            ("nombre", Valor::Texto(texto)) => escenario.nombre = Some(texto.clone()),
+            ("autor", Valor::Texto(texto)) => escenario.autor = Some(texto.clone()),
+            ("descripcion", Valor::Texto(texto)) => escenario.descripcion = Some(texto.clone()),
+            ("tablero", valor) => {
+                escenario.filas = filas(valor).ok_or_else(|| error("un arreglo de textos"))?
+            }
+            _ => return Err(error("un texto entre comillas")),
+        }
+    }
+    if principal.clave("tablero").is_none() {
+        return Err("Error: al escenario le falta el tablero".to_string());
+    }
+    for tabla in tablas {
+        if tabla.encabezado != "[[detonacion]]" {
+            return Err(format!(
+                "Error: linea {}: tabla desconocida {} (se acepta [[detonacion]])",
+                tabla.linea, tabla.encabezado
+            ));
+        }
+        escenario.detonaciones.push(detonacion(&tabla)?);
+    }
+    if escenario.detonaciones.is_empty() {
+        return Err("Error: el escenario no tiene ninguna [[detonacion]]".to_string());
+    }
+    Ok(escenario)
+}
+
+/// Separa en casillas cada texto del arreglo del tablero.
+fn filas(valor: &Valor) -> Option<Vec<Vec<String>>> {
+    let Valor::Lista(filas) = valor else {
+        return None;
+    };
+    filas
+        .iter()
+        .map(|fila| match fila {
+            Valor::Texto(fila) => Some(fila.split_whitespace().map(String::from).collect()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lee las coordenadas de una tabla `[[detonacion]]`.
+fn detonacion(tabla: &Tabla) -> Result<Punto, String> {
+    let mut punto = Punto { x: 0, y: 0 };
+    for Clave {
+        nombre,
+        valor,
+        linea,
+    } in &tabla.claves
+    {
+        match (nombre.as_str(), valor) {
+            ("x", Valor::Numero(x)) => punto.x = *x,
+            ("y", Valor::Numero(y)) => punto.y = *y,
+            ("x" | "y", _) => {
+                return Err(format!(
+                    "Error: linea {}: {} tiene que ser un numero natural",
+                    linea, nombre
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "Error: linea {}: clave desconocida {} (se aceptan x, y)",
+                    linea, nombre
+                ))
+            }
+        }
+    }
+    for obligatoria in ["x", "y"] {
+        if tabla.clave(obligatoria).is_none() {
+            return Err(format!(
+                "Error: linea {}: a la detonacion le falta {}",
+                tabla.linea, obligatoria
+            ));
+        }
+    }
+    Ok(punto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parsear;
+    use tp1_bomberman::bomberman::prelude::*;
+
+    #[test]
+    fn test01_lee_el_escenario() {
+        let texto = "nombre = \"Cadena\"\nautor = \"Juanma\"\ntablero = [\n  \"B1 _ B2\",\n  \"_ W F1\",\n]\n\n\
+                     [[detonacion]]\nx = 0\ny = 0\n\n[[detonacion]]\ny = 1\nx = 2\n";
+        let escenario = parsear(texto).unwrap();
+        assert_eq!(escenario.nombre.as_deref(), Some("Cadena"));
+        assert_eq!(escenario.autor.as_deref(), Some("Juanma"));
+        assert_eq!(escenario.descripcion, None);
+        assert_eq!(escenario.filas[1], ["_", "W", "F1"]);
+        assert_eq!(
+            escenario.detonaciones,
+            vec![Punto { x: 0, y: 0 }, Punto { x: 2, y: 1 }]
+        );
+    }
+
+    #[test]
+    fn test02_errores() {
+        let errores = [
+            (
+                "[[detonacion]]\nx = 0\ny = 0",
+                "Error: al escenario le falta el tablero",
+            ),
+            (
+                "tablero = [\"B1\"]",
+                "Error: el escenario no tiene ninguna [[detonacion]]",
+            ),
+            (
+                "tablero = \"B1\"",
+                "Error: linea 1: tablero tiene que ser un arreglo de textos",
+            ),
+            (
+                "autor = 3",
+                "Error: linea 1: autor tiene que ser un texto entre comillas",
+            ),
+            (
+                "tablero = [\"B1\"]\n[[detonacion]]\nx = 0",
+                "Error: linea 2: a la detonacion le falta y",
+            ),
+            (
+                "tablero = [\"B1\"]\n[detonacion]",
+                "Error: linea 2: tabla desconocida [detonacion] (se acepta [[detonacion]])",
+            ),
+        ];
+        for (texto, error) in errores {
+            assert_eq!(parsear(texto), Err(error.to_string()), "{}", texto);
+        }
+    }
+}
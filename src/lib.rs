@@ -0,0 +1,16 @@
+//! Simulador de Bomberman-R: lee un tablero, detona una bomba con toda su reaccion en cadena y escribe
+//! el tablero final. El binario `tp1-bomberman` es solo la linea de comandos sobre esta biblioteca.
+//!
+//! ```no_run
+//! # use tp1_bomberman::bomberman::prelude::*;
+//! use tp1_bomberman::{escribir_tablero, tablero_desde_archivo, Bomberman};
+//!
+//! let salida = Bomberman::new(tablero_desde_archivo("entrada.txt")?).simular(Columna(0), Fila(0))?;
+//! escribir_tablero(&salida.tablero, &mut std::io::stdout())?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+pub mod bomberman;
+
+pub use bomberman::io::{ejecutar, escribir_tablero, tablero_desde_archivo};
+pub use bomberman::punto::Punto;
+pub use bomberman::Bomberman;
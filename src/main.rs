@@ -1,10 +1,51 @@
 use std::env;
 use std::fs::File;
-use std::io::Write;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, IsTerminal};
+use std::io::{BufWriter, Write};
+use std::panic;
+use std::process::{self, Command};
+use std::time::Duration;
 
-use bomberman::punto::Punto;
-mod bomberman;
+use notify::Watcher;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+use tp1_bomberman::bomberman::analizador::Analizador;
+use tp1_bomberman::bomberman::censo::{self, Caracteristica};
+use tp1_bomberman::bomberman::diagnostico::{self, Diagnostico};
+use tp1_bomberman::bomberman::disparo::NodoDisparo;
+use tp1_bomberman::bomberman::estadistica::{EstadisticaBomba, ResumenSimulacion};
+use tp1_bomberman::bomberman::generador::{self, Densidades};
+use tp1_bomberman::bomberman::hexagonal::TableroHex;
+use tp1_bomberman::bomberman::io::{
+    filas_desde_json, leer_filas, recuperar_tablero, tablero_desde_archivo_con_reglas,
+    tablero_desde_filas_con_reglas, tablero_desde_json, tablero_desde_texto_con_reglas,
+};
+use tp1_bomberman::bomberman::json::Json;
+use tp1_bomberman::bomberman::objetivo::Objetivo;
+use tp1_bomberman::bomberman::ofuscacion::{self, Simetria};
+use tp1_bomberman::bomberman::optimizador::Recocido;
+use tp1_bomberman::bomberman::prelude::*;
+use tp1_bomberman::bomberman::repeticion::Repeticion;
+use tp1_bomberman::bomberman::simbolos::Simbolos;
+use tp1_bomberman::bomberman::{self, comparacion, consulta, emoji, svg, tutorial};
+use tp1_bomberman::tablero_desde_archivo;
+use uso::{Comando, Coordenada};
+mod archivo_reglas;
+mod archivo_simbolos;
+mod bisect;
+mod cache;
+mod capacidades;
+mod daemon;
+mod escenario;
+mod manifiesto;
+mod paquete;
+mod protocolo;
+mod selector;
+mod toml;
+#[cfg(feature = "tui")]
+mod tui;
+mod uso;
 
 /// Toma los argumentos de la consola y ejecuta el juego Bomberman-R, luego imprime el resultado o un error en el archivo destino.
 ///
@@ -35,237 +76,2690 @@ mod bomberman;
 ///
 /// # Argumentos
 ///
-/// * 1: Archivo de input que contiene la tabla inicial.
+/// * 1: Archivo de input que contiene la tabla inicial. Si termina en `.toml` es un escenario con el tablero y las
+///   bombas a detonar en orden (ver `simular_escenario`), y no se pasan las coordenadas.
 /// * 2: Ruta hacia el output que tendra el mismo nombre que el archivo de input.
 /// * 3: Coordenada X de alguna Bomba.
 /// * 4: Coordenada Y de alguna Bomba.
+///   Si las dos coordenadas son `?`, la bomba se elige moviendo un cursor sobre el tablero (ver `selector::elegir`).
+/// * `--al-terminar "comando"` (opcional): Comando que se ejecuta al terminar la simulacion. Acepta los marcadores
+///   `{salida}`, `{resultado}`, `{bombas}` y `{enemigos}`, que se reemplazan por las variables de entorno `SALIDA`,
+///   `RESULTADO`, `BOMBAS` y `ENEMIGOS` (ver `ejecutar_al_terminar`).
+/// * `--cache directorio` (opcional): Directorio donde se guardan los resultados ya calculados, para devolverlos
+///   sin volver a simular si se repite el mismo tablero con las mismas coordenadas. Las advertencias de la simulacion
+///   tambien se guardan, para imprimirlas igual que si se hubiera simulado.
+/// * `--limite-alcance N` (opcional): Alcance maximo de las bombas; las que lo superan se recortan con una advertencia.
+/// * `--max-cadena N`, `--max-pasos N`, `--tiempo-maximo MS` (opcionales): Cortan la simulacion con un error si explotan
+///   mas de N bombas en cadena, si las rafagas recorren mas de N casillas o si tarda mas de MS milisegundos.
+/// * `--max-casillas N` (opcional): Rechaza con un error los tableros de mas de N casillas, antes de simular.
+/// * `--enemigos-bloquean` (opcional): Los enemigos frenan las rafagas de las bombas normales (ver `Reglas`).
+/// * `--ladrillos` (opcional): Acepta ladrillos (`X`), que cualquier rafaga rompe y que la frenan (ver `Reglas`).
+/// * `--mejoras` (opcional): Cada enemigo que muere deja una mejora de alcance (`+R`) en su casilla (ver `Reglas`).
+/// * `--toroidal` (opcional): Las rafagas que salen por un borde del tablero siguen por el opuesto (ver `Reglas`).
+/// * `--golpes-por-onda N` (opcional): Un enemigo pierde como mucho N vidas en toda la reaccion en cadena (ver `Reglas`).
+/// * `--alcance-maximo N`, `--vida-maxima N` (opcionales): Cambian los topes con los que se valida el tablero: el alcance
+///   de las bombas (por defecto sin tope) y la vida de los enemigos (por defecto 255; la consigna admite hasta 3,
+///   como el preset `clasico`).
+/// * `--preset NOMBRE` (opcional): Empieza con las reglas de un preset (`clasico` o `arcade`, ver `Reglas::preset`).
+///   El archivo de `--rules` y las demas opciones de reglas lo pisan.
+/// * `--rules ARCHIVO` (opcional): Toma las reglas y `max_cadena` de un archivo TOML con un `preset` opcional y una
+///   clave por regla, entre ellas algunas que no tienen opcion propia: `traspaso_atraviesa_paredes`,
+///   `desvios_consumen_alcance` y `un_golpe_por_bomba` (ver `archivo_reglas::aplicar`). Las demas opciones de
+///   reglas y `--max-cadena` la pisan.
+/// * `--simbolos ARCHIVO` (opcional): Lee y escribe los tableros con otros simbolos para las casillas, por ejemplo `#`
+///   para las paredes, tomados de un archivo TOML con el simbolo de cada tipo (ver `archivo_simbolos::parsear`). Solo
+///   con la salida de texto.
+/// * `--eventos` (opcional): Imprime por stderr cada evento de la simulacion a medida que ocurre.
+/// * `--verbose` (opcional): Imprime por stderr el recorrido de la simulacion, con una linea por bomba, por rafaga y
+///   por casilla que alcanza, precedidas por la bomba y la rafaga en la que ocurren (ver `registrar`). Tambien se
+///   activa con `RUST_LOG=debug`. No se imprime cuando el resultado sale de la cache.
+/// * `--objetivo OBJETIVO` (opcional): Informa por stderr si se cumplio el objetivo del mapa (`eliminar_enemigos`,
+///   `destruir_rocas` o `alcanzar_celda:x,y`, ver `Objetivo`). Si no se pasa se usa el de la linea `# objetivo: ...`
+///   del archivo de entrada, si la tiene. Con un objetivo no se usa la cache.
+/// * `--json-bloques N` (opcional): Escribe el tablero final como JSON, una linea cada N filas (ver
+///   `bomberman::io::escribir_tablero_json`), y al final una linea con el arbol de la reaccion en cadena (ver `NodoDisparo`). No usa la cache y no agrega la procedencia, las advertencias ni el tutorial,
+///   para que cada linea del archivo siga siendo JSON.
+/// * `--format json` (opcional): Lee la entrada como un documento JSON con las filas del tablero y opcionalmente sus
+///   metadatos (ver `bomberman::io::filas_desde_json`), y escribe el resultado como JSON con el tablero final, los
+///   numeros de la jugada, las advertencias y los mismos metadatos, junto con un id de la jugada que no cambia entre
+///   versiones para compararlas con `bisect-helper` (ver `bomberman::io::escribir_resultado_json`). Un
+///   error se escribe como `{"error":"..."}`. Como `--json-bloques`, no usa la cache ni agrega la procedencia, las
+///   advertencias ni el tutorial. Si los metadatos tienen un `objetivo` se usa como el de `# objetivo: ...`.
+/// * `--format hex` (opcional): Lee el tablero de texto como uno hexagonal, con la columna y la fila como coordenadas
+///   axiales, y simula con rafagas en 6 direcciones (ver `bomberman::hexagonal::TableroHex`). El resultado se escribe
+///   igual que siempre. No se puede elegir la bomba con el cursor ni usar `--mapa-peligro` o `--grabar-replay`.
+/// * `--recuperar` (opcional): En lugar de rechazar un tablero con casillas desconocidas, las reemplaza por `_`
+///   y deja una advertencia por cada una.
+/// * `--con-procedencia` (opcional): Agrega al principio del output de donde salio (ver `texto_procedencia`).
+/// * `--con-advertencias` (opcional): Agrega al final del output las advertencias de la simulacion (ver `texto_advertencias`).
+/// * `--inspeccionar PASO:X,Y` (opcional): Imprime por stderr el contenido de la casilla (X, Y) despues de la detonacion
+///   numero PASO (0 es el tablero inicial). Se puede repetir, y no usa la cache.
+/// * `--tambien X,Y` (opcional): Detona tambien la bomba en (X, Y) en el mismo instante que la de las coordenadas,
+///   despues de ella y antes de las que alcancen (ver `Bomberman::comenzar_simultaneas`). Se puede repetir, y no se
+///   puede usar con `--grabar-replay`.
+/// * `--mapa-peligro` (opcional): Imprime por stderr, para cada casilla, cuantas bombas del tablero la afectarian. No
+///   usa la cache.
+/// * `--modo-tutorial` (opcional): Agrega al final del output una explicacion de cada regla que se aplico (ver `texto_tutorial`).
+/// * `--resumen` (opcional): Agrega al final del output cuantas bombas explotaron, cuantos enemigos murieron o
+///   recibieron algun golpe, cuantas rocas se atravesaron, cuantas casillas alcanzaron las rafagas y la profundidad
+///   de la reaccion en cadena (ver `texto_resumen`).
+/// * `--grabar-replay archivo` (opcional): Escribe en el archivo una repeticion de la simulacion, con el tablero
+///   inicial, las reglas, la bomba detonada y los eventos, para verla con el subcomando `replay` (ver `Repeticion`).
+///   No usa la cache.
+/// * `--style emoji` (opcional): Escribe el tablero final con emoji y un recuadro (ver `emoji::dibujar`), para
+///   mostrarlo en una terminal; `--style texto`, el de siempre, es el unico que se puede volver a leer. Solo con la
+///   salida de texto y sin `--simbolos`, y no usa la cache.
+/// * `--svg archivo` (opcional): Escribe en el archivo el tablero inicial como SVG con el recorrido de cada rafaga
+///   encima, incluidas las vueltas en los desvios, para explicar una reaccion en cadena (ver `svg::simulacion`). No
+///   usa la cache.
+/// * `--diff archivo` (opcional): Escribe en el archivo, ademas del output, una linea por casilla que cambio entre el
+///   tablero inicial y el final, como `(4, 0): F1 -> _` (ver `escribir_diferencias`). En un escenario compara el
+///   tablero inicial con el que queda despues de todas las detonaciones.
+/// * `--frames directorio` (opcional): Escribe en el directorio un tablero por cada estado de la reaccion en cadena,
+///   `frame_000.txt` con el inicial y uno mas por cada detonacion, para armar una animacion (ver `escribir_cuadros`).
+///   En un escenario siguen numerandose de una detonacion a la otra. No usa la cache.
+/// * `--estadisticas-bombas` (opcional): Agrega al final del output una tabla con lo que hizo cada bomba de la reaccion
+///   en cadena (ver `texto_estadisticas`). Con `--format json` esas filas siempre estan, en `bombas`.
+/// * `--codigo-salida` (opcional): Termina con un codigo que dice como salio la jugada, para usarlo desde un script: 0
+///   si murieron todos los enemigos, 3 si quedo alguno y 1 si hubo un error (ver `codigo_de_salida`).
+/// * `--lang es|en` (opcional): El idioma de los errores del motor y de la simulacion, en el archivo de salida y en
+///   stderr (ver `Idioma`). Por defecto `es`; los errores de uso de la linea de comandos siguen en castellano.
+///
+/// Los argumentos se interpretan con clap (ver `uso::Cli`): una coordenada que no es un numero natural ni `?` o una
+/// opcion desconocida se informan por stderr con el uso del subcomando, y terminan con el codigo 2. Despues se revisan
+/// juntos los demas problemas (faltan coordenadas, opciones que no se pueden usar juntas, la entrada y el directorio
+/// al reves, un directorio de salida que no existe y, junto con esos, las coordenadas fuera del tablero) y se informan
+/// con la invocacion corregida (ver `uso::revisar_simulacion`).
+/// Los mismos argumentos se pueden escribir despues del subcomando `simular`. Con `--help` imprime la ayuda con cada
+/// subcomando y con `<subcomando> --help` la de ese subcomando.
+/// Tambien acepta los subcomandos `validar entrada.txt` (ver `validar`), `lote casos.toml` y
+/// `manifiesto init|validar` (ver `lote` y `comando_manifiesto`), `comparar a.txt b.txt` (ver `comparar`), `consultar tablero.txt "consulta"` (ver `consultar`),
+/// `empaquetar paquete.bmp archivos...` y `desempaquetar paquete.bmp directorio` (ver `empaquetar` y `desempaquetar`).
+/// `comparar-reglas --presets a,b entrada.txt x y` (ver `comparar_reglas`).
+/// `replay repeticion.txt [--cuadros]` (ver `replay`).
+/// `daemon bandeja_entrada bandeja_salida` (ver `comando_daemon`).
+/// `bisect-helper antes.json despues.json` (ver `bisect_helper`).
+/// `grafo entrada.txt x y` (ver `grafo`).
+/// `resolver entrada.txt` (ver `resolver`).
+/// `replay-corpus directorio` (ver `replay_corpus`).
+/// `censo directorio` (ver `censo`).
+/// `ofuscar entrada.txt salida.txt [--semilla N]` (ver `ofuscar`).
+/// `generar nivel.txt ancho alto [--semilla N]` (ver `generar`).
+/// `tui entrada.txt [--pausa MS]` (ver `comando_tui`).
+/// `optimizar-nivel semilla.txt nivel.txt dificultad` (ver `optimizar_nivel`).
+/// Con `--capacidades` imprime en JSON lo que soporta el programa (ver `capacidades::informe`).
+/// Con `--protocol jsonl [opciones]` atiende pedidos de simulacion por stdin y stdout, uno por linea (ver
+/// `comando_protocolo`).
 ///
 /// # Devuelve
 ///
 /// Escribe en el archivo de output el estado final del juego o una descripcion del Error que lo impidio.
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 5 {
-        return print!("Error: faltan argumentos");
+    let cli = uso::leer_argumentos();
+    if cli.capacidades {
+        return println!("{}", capacidades::informe());
+    }
+    match &cli.comando {
+        // La interfaz de terminal ocupa toda la pantalla, y el recorrido la romperia.
+        #[cfg(feature = "tui")]
+        Some(Comando::Tui { .. }) => {}
+        Some(Comando::Simular(args)) => registrar(args.opciones.verbose),
+        _ => registrar(false),
+    }
+    if cli.protocol.is_some() {
+        process::exit(comando_protocolo(&cli.reglas, &cli.limites));
+    }
+    let codigo = match cli.comando {
+        None => {
+            eprintln!("Error: falta el subcomando\n{}", uso::uso_de(""));
+            2
+        }
+        Some(Comando::Simular(args)) => comando_simular(*args),
+        Some(Comando::Validar { entrada, reglas }) => validar(&entrada, &reglas),
+        Some(Comando::Manifiesto { accion }) => comando_manifiesto(accion),
+        Some(Comando::Lote { manifiesto }) => lote(&manifiesto),
+        Some(Comando::Comparar { a, b }) => comparar(&[a, b]),
+        Some(Comando::CompararReglas {
+            presets,
+            entrada,
+            x,
+            y,
+        }) => comparar_reglas(&presets, &entrada, x, y),
+        Some(Comando::Grafo {
+            entrada,
+            x,
+            y,
+            reglas,
+        }) => grafo(&entrada, x, y, &reglas),
+        Some(Comando::Resolver {
+            entrada,
+            minimo,
+            reglas,
+            limites,
+        }) => resolver(&entrada, minimo, &reglas, &limites),
+        Some(Comando::BisectHelper { antes, despues }) => bisect_helper(&[antes, despues]),
+        Some(Comando::Daemon {
+            entrada,
+            salida,
+            una_vez,
+            reglas,
+            limites,
+        }) => comando_daemon(&entrada, &salida, una_vez, &reglas, &limites),
+        Some(Comando::Replay {
+            repeticion,
+            cuadros,
+        }) => replay(&repeticion, cuadros),
+        Some(Comando::ReplayCorpus {
+            directorio,
+            reglas,
+            limites,
+        }) => replay_corpus(&directorio, &reglas, &limites),
+        Some(Comando::Censo { directorio, reglas }) => censo(&directorio, &reglas),
+        Some(Comando::Ofuscar {
+            entrada,
+            salida,
+            semilla,
+            reglas,
+        }) => ofuscar(&entrada, &salida, semilla, &reglas),
+        Some(Comando::Generar {
+            salida,
+            ancho,
+            alto,
+            semilla,
+            densidades,
+        }) => generar(&salida, ancho, alto, semilla, &densidades),
+        #[cfg(feature = "tui")]
+        Some(Comando::Tui {
+            entrada,
+            pausa,
+            reglas,
+            limites,
+        }) => comando_tui(&entrada, pausa, &reglas, &limites),
+        Some(Comando::Consultar { tablero, consulta }) => consultar(&tablero, &consulta),
+        Some(Comando::Empaquetar { paquete, archivos }) => empaquetar(&paquete, &archivos),
+        Some(Comando::Desempaquetar {
+            paquete,
+            directorio,
+        }) => desempaquetar(&paquete, &directorio),
+        Some(Comando::OptimizarNivel {
+            entrada,
+            salida,
+            dificultad,
+            iteraciones,
+            semilla,
+        }) => optimizar_nivel(&entrada, &salida, dificultad, iteraciones, semilla),
+    };
+    process::exit(codigo);
+}
+
+/// Instala el suscriptor de `tracing` que escribe por stderr el recorrido de las simulaciones (ver `Bomberman`): una
+/// linea al empezar cada bomba y cada rafaga, y una por cada casilla que alcanza, con la bomba y la rafaga en las que
+/// ocurre. Que se escribe lo decide un EnvFilter armado con `RUST_LOG`, al que `--verbose` le agrega el nivel debug
+/// de la biblioteca. Sin ninguno de los dos no se instala nada.
+///
+/// # Ejemplos
+///
+/// ```text
+/// $ RUST_LOG=debug tp1-bomberman entrada.txt salidas 0 0
+/// DEBUG bomba{x=0 y=0 alcance=2}: new
+/// DEBUG bomba{x=0 y=0 alcance=2}:rafaga{direccion=R}: new
+/// DEBUG bomba{x=0 y=0 alcance=2}:rafaga{direccion=R}: casilla x=1 y=0 celda=DD
+/// ```
+fn registrar(verbose: bool) {
+    if !verbose && env::var_os("RUST_LOG").is_none() {
+        return;
+    }
+    let mut filtro = EnvFilter::from_default_env();
+    if verbose {
+        filtro = filtro.add_directive("tp1_bomberman=debug".parse().expect("directiva valida"));
     }
-    let ruta_entrada: String = args[1].clone();
-    let ruta_salida: String = args[2].clone() + "/" + &extraer_archivo_destino(&ruta_entrada);
+    tracing_subscriber::fmt()
+        .with_env_filter(filtro)
+        .with_writer(io::stderr)
+        .with_span_events(FmtSpan::NEW)
+        .with_target(false)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .init();
+}
+
+/// Simula con los argumentos de `simular`: primero los revisa todos, para informar juntos los problemas que tengan
+/// (ver `uso::revisar_simulacion`), y despues detona la bomba pedida, o las del escenario, y escribe el resultado.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 2 si los argumentos son incorrectos, el de `codigo_de_salida` con
+/// `--codigo-salida` y si no 0.
+fn comando_simular(args: uso::ArgsSimular) -> i32 {
+    let opciones = Opciones::de_simulacion(&args.opciones);
+    let dimensiones = || {
+        let reglas = opciones
+            .as_ref()
+            .map_or(Reglas::default(), |op| op.reglas.clone());
+        tablero_desde_archivo_con_reglas(&args.entrada, &reglas)
+            .ok()
+            .map(|tablero| (tablero.ancho(), tablero.alto()))
+    };
+    let revision = uso::revisar_simulacion(&args, opciones.as_ref().err().cloned(), dimensiones);
+    let opciones: Opciones = match (revision, opciones) {
+        (Ok(()), Ok(op)) => op,
+        (revision, _) => {
+            eprintln!("{}", revision.err().unwrap_or_default());
+            eprintln!("{}", uso::uso_de("simular"));
+            return 2;
+        }
+    };
+    let ruta_salida = args.directorio.clone() + "/" + &extraer_archivo_destino(&args.entrada);
 
     let salida: File = match File::create(ruta_salida.clone()) {
         Ok(f) => f,
-        _ => return print!("Error: ruta de salida invalida"),
+        _ => {
+            eprintln!(
+                "{}",
+                opciones
+                    .idioma
+                    .formatear("ruta_de_salida_invalida", &[&ruta_salida])
+            );
+            return 2;
+        }
     };
 
-    let tablero: Vec<Vec<String>> = match tablero_desde_archivo(ruta_entrada) {
-        Err(e) => return devolver_error(e, salida),
-        Ok(tab) => tab,
-    };
-    let tamanio = tablero.len();
-    let mut bomberman = bomberman::Bomberman {
-        tablero,
-        pila_bombas: Vec::new(),
+    let resultado = match (args.x, args.y) {
+        (Some(x), Some(y)) => simular(x, y, &args.entrada, salida, &opciones),
+        _ => simular_escenario(&args.entrada, salida, &opciones),
     };
+    if let Some(comando) = &opciones.al_terminar {
+        ejecutar_al_terminar(comando, &ruta_salida, &resultado, opciones.idioma);
+    }
+    match opciones.codigo_salida {
+        true => codigo_de_salida(&resultado),
+        false => 0,
+    }
+}
 
-    let punto_bomba: Punto = match extraer_coord(args, tamanio, 3, 4) {
-        Err(e) => return devolver_error(e, salida),
-        Ok(pt) => pt,
+/// Lee un tablero sin simularlo e informa si es valido con las reglas pedidas. Si no lo es, imprime por stderr todas
+/// las casillas con problemas y su posicion en el archivo (ver `diagnostico::diagnosticar`).
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run validar entrada.txt --vida-maxima 5
+/// entrada.txt: tablero valido de 7x7
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta del tablero.
+/// * reglas: Las opciones de reglas, con las que se valida.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si el tablero es valido, 1 si no lo es y 2 si los argumentos son incorrectos.
+fn validar(ruta: &str, reglas: &uso::ArgsReglas) -> i32 {
+    let opciones = match Opciones::leer(reglas, &uso::ArgsLimites::default()) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
     };
+    match leer_tablero(ruta, &opciones) {
+        Ok(tablero) => {
+            println!(
+                "{}: tablero valido de {}x{}",
+                ruta,
+                tablero.ancho(),
+                tablero.alto()
+            );
+            0
+        }
+        Err(e) => {
+            let problemas = problemas_de_lectura(&e, ruta, &opciones);
+            let mensaje = e.mensaje(opciones.idioma);
+            match e.detalle_en(opciones.idioma) {
+                _ if !problemas.is_empty() => {
+                    eprintln!("{}: {}", ruta, listar_problemas(&e, &problemas))
+                }
+                Some(detalle) => eprintln!("{}: {} ({})", ruta, mensaje, detalle),
+                None => eprintln!("{}: {}", ruta, mensaje),
+            }
+            1
+        }
+    }
+}
 
-    if let Err(e) = bomberman::Bomberman::comenzar(&mut bomberman, punto_bomba.x, punto_bomba.y) {
-        return devolver_error(e, salida);
+/// Lee y valida un manifiesto de casos (ver `manifiesto::parsear`), incluyendo que existan sus archivos.
+///
+/// # Devuelve
+///
+/// Un Result con los casos y el directorio desde el que se resuelven sus rutas, o un Err con todos los errores.
+fn leer_manifiesto(ruta: &str) -> Result<(Vec<manifiesto::Caso>, std::path::PathBuf), Vec<String>> {
+    let texto = std::fs::read_to_string(ruta)
+        .map_err(|_| vec![format!("Error: no se pudo leer el manifiesto {}", ruta)])?;
+    let casos = manifiesto::parsear(&texto).map_err(|e| vec![format!("{}: {}", ruta, e)])?;
+    let base = std::path::Path::new(ruta)
+        .parent()
+        .unwrap_or(std::path::Path::new(""))
+        .to_path_buf();
+    let faltantes = manifiesto::archivos_faltantes(&casos, &base);
+    if !faltantes.is_empty() {
+        return Err(faltantes
+            .iter()
+            .map(|e| format!("{}: {}", ruta, e))
+            .collect());
     }
-    escribir_tablero_final_en_archivo(bomberman.tablero, salida);
+    Ok((casos, base))
 }
 
-/// Toma la ruta a un archivo de texto en el que haya una matriz de Strings NxN separada por espacios y saltos de linea y devuelve un Result con la Matriz o un Err(String) con la descripcion del error.
+/// Crea o revisa un manifiesto de casos para `lote`.
 ///
 /// # Ejemplos
 ///
 /// ```
-/// let  tablero: Vec<Vec<String>>;
-/// match tablero_desde_archivo(ruta_entrada) {
-///     Err(e) => {return devolver_error(e, salida)}
-///     Ok(tab) => tablero = tab
-/// }
+/// $ cargo run manifiesto init entradas salidas
+/// Se escribio casos.toml con 1 casos
+/// $ cargo run manifiesto validar casos.toml
+/// casos.toml: 1 casos
 /// ```
 ///
 /// # Argumentos
 ///
-///  * ruta_entrada: La ruta hasta el archivo de texto con la matriz.
+/// * accion: `init` con el directorio de entradas y opcionalmente el de salidas esperadas (ver
+///   `manifiesto::casos_de_directorio`), o `validar` con la ruta del manifiesto.
 ///
 /// # Devuelve
 ///
-/// Un Result con la matriz de Strings o un Err con la descripcion del.
-fn tablero_desde_archivo(ruta_entrada: String) -> Result<Vec<Vec<String>>, String> {
-    let archivo_entrada_result = File::open(ruta_entrada);
+/// El codigo de salida del proceso: 0 si se escribio o el manifiesto es valido, 1 si no lo es y 2 si hubo un error.
+fn comando_manifiesto(accion: uso::AccionManifiesto) -> i32 {
+    match accion {
+        uso::AccionManifiesto::Init { entradas, salidas } => {
+            if std::path::Path::new(manifiesto::NOMBRE).exists() {
+                eprintln!("Error: ya existe {}", manifiesto::NOMBRE);
+                return 2;
+            }
+            let resultado = manifiesto::casos_de_directorio(&entradas, salidas.as_deref())
+                .and_then(|casos| {
+                    std::fs::write(manifiesto::NOMBRE, manifiesto::escribir(&casos))
+                        .map(|_| casos.len())
+                        .map_err(|_| format!("Error: no se pudo escribir {}", manifiesto::NOMBRE))
+                });
+            match resultado {
+                Ok(cantidad) => {
+                    println!("Se escribio {} con {} casos", manifiesto::NOMBRE, cantidad);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    2
+                }
+            }
+        }
+        uso::AccionManifiesto::Validar { manifiesto } => match leer_manifiesto(&manifiesto) {
+            Ok((casos, _)) => {
+                println!("{}: {} casos", manifiesto, casos.len());
+                0
+            }
+            Err(errores) => {
+                for error in errores {
+                    eprintln!("{}", error);
+                }
+                1
+            }
+        },
+    }
+}
 
-    let file_input: File = match archivo_entrada_result {
-        Ok(file) => file,
-        _ => return Err("Error: no se pudo abrir correctamente el archivo".to_string()),
+/// Simula cada caso de un manifiesto y, si el caso tiene salida esperada, la compara con el resultado, como lo haria
+/// un corrector. El resultado de un caso es el texto que se escribiria en su archivo de salida.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run lote casos.toml
+/// entradas/entrada.txt (0, 0) clasico: ok
+/// entradas/otra.txt (2, 1) arcade: difiere de salidas/otra.txt
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta del manifiesto.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si todos los casos con salida esperada coinciden, 1 si alguno difiere y 2 si el
+/// manifiesto es invalido.
+fn lote(ruta: &str) -> i32 {
+    let (casos, base) = match leer_manifiesto(ruta) {
+        Ok(leido) => leido,
+        Err(errores) => {
+            for error in errores {
+                eprintln!("{}", error);
+            }
+            return 2;
+        }
     };
+    let mut todos_ok = true;
+    for caso in &casos {
+        let reglas = Reglas::preset(&caso.preset).unwrap_or_default();
+        let resultado = tablero_desde_archivo_con_reglas(base.join(&caso.entrada), &reglas)
+            .and_then(|tablero| {
+                SimulacionBuilder::new(tablero)
+                    .reglas(reglas)
+                    .construir()
+                    .simular(Columna(caso.x), Fila(caso.y))
+            })
+            .map(|salida| salida.tablero.to_texto())
+            .unwrap_or_else(|e| e.to_string());
+        let estado = match &caso.esperado {
+            None => "sin salida esperada".to_string(),
+            Some(esperado) => match std::fs::read_to_string(base.join(esperado)) {
+                Ok(texto) if texto.trim_end() == resultado.trim_end() => "ok".to_string(),
+                _ => {
+                    todos_ok = false;
+                    format!("difiere de {}", esperado)
+                }
+            },
+        };
+        println!(
+            "{} ({}, {}) {}: {}",
+            caso.entrada, caso.x, caso.y, caso.preset, estado
+        );
+    }
+    if todos_ok {
+        0
+    } else {
+        1
+    }
+}
 
-    let reader = io::BufReader::new(file_input);
+/// Lee los dos tableros pedidos, validandolos como cualquier entrada, e imprime cada casilla en la que difieren.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run comparar salidas/entrada.txt esperados/entrada.txt
+/// (4, 0): F1 -> _
+/// ```
+///
+/// # Argumentos
+///
+/// * rutas: Las rutas de los dos tableros.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si los tableros son iguales, 1 si difieren y 2 si hubo un error.
+fn comparar(rutas: &[String; 2]) -> i32 {
+    let mut tableros = Vec::new();
+    for ruta in rutas {
+        match tablero_desde_archivo(ruta.clone()) {
+            Ok(tab) => tableros.push(tab),
+            Err(e) => {
+                eprintln!("{} ({})", e, ruta);
+                return 2;
+            }
+        }
+    }
+    let diferencias = comparacion::diferencias(&tableros[0].filas(), &tableros[1].filas());
+    for diferencia in &diferencias {
+        println!("{}", diferencia);
+    }
+    if diferencias.is_empty() {
+        0
+    } else {
+        1
+    }
+}
 
-    // Creo el tablero de Juego
-    let mut tablero: Vec<Vec<String>> = Vec::new();
-    for linea in reader.lines() {
-        let casillas: String = match linea {
-            Ok(f) => f,
-            _ => return Err("Error: no se pudo leer correctamente el archivo".to_string()),
-        };
-        let palabras: Vec<String> = casillas.split_whitespace().map(String::from).collect();
-        tablero.push(palabras)
+/// Simula la misma entrada con cada uno de los presets de reglas pedidos (ver `Reglas::preset`) e imprime, para cada
+/// preset a partir del segundo, las casillas en las que su tablero final difiere del obtenido con el primero.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run comparar-reglas --presets clasico,arcade entrada.txt 0 0
+/// clasico vs arcade:
+/// (2, 0): _ -> F1
+/// ```
+///
+/// # Argumentos
+///
+/// * presets: Los nombres de `--presets`, que se escriben separados por comas.
+/// * ruta: La ruta de la entrada.
+/// * x, y: Las coordenadas de la bomba.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si todos los presets dan el mismo tablero, 1 si alguno difiere y 2 si hubo un error.
+fn comparar_reglas(nombres: &[String], ruta: &str, x: Columna, y: Fila) -> i32 {
+    let mut presets = Vec::new();
+    for nombre in nombres {
+        match Reglas::preset(nombre) {
+            Some(reglas) => presets.push((nombre, reglas)),
+            None => {
+                eprintln!(
+                    "Error: preset desconocido {} (se aceptan {})",
+                    nombre,
+                    Reglas::PRESETS.join(", ")
+                );
+                return 2;
+            }
+        }
+    }
+    if presets.len() < 2 {
+        eprintln!("Error: comparar-reglas necesita al menos dos presets");
+        return 2;
+    }
+    let tablero = match tablero_desde_archivo(ruta) {
+        Ok(tab) => tab,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let punto = match punto_en_tablero(&tablero, x, y, Idioma::default()) {
+        Ok(pt) => pt,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let mut finales = Vec::new();
+    for (nombre, reglas) in &presets {
+        let bomberman = SimulacionBuilder::new(tablero.clone())
+            .reglas(reglas.clone())
+            .construir();
+        match bomberman.simular(punto.columna(), punto.fila()) {
+            Ok(salida) => finales.push(salida.tablero),
+            Err(e) => {
+                eprintln!("{}: {}", nombre, e);
+                return 2;
+            }
+        }
     }
-    if es_tablero_valido(&mut tablero) {
-        return Ok(tablero);
+    let mut hay_diferencias = false;
+    for ((nombre, _), final_) in presets.iter().zip(&finales).skip(1) {
+        let diferencias = comparacion::diferencias(&finales[0].filas(), &final_.filas());
+        if diferencias.is_empty() {
+            continue;
+        }
+        hay_diferencias = true;
+        println!("{} vs {}:", presets[0].0, nombre);
+        for diferencia in &diferencias {
+            println!("{}", diferencia);
+        }
+    }
+    if hay_diferencias {
+        1
+    } else {
+        0
     }
-    Err("Error: archivo de entrada invalido".to_string())
 }
 
-/// Toma una matriz de Strings y evalua todas las casillas para ver si son validas para un juego de Bomberman.
+/// Detona la bomba pedida e imprime la reaccion en cadena como un grafo de Graphviz (ver `NodoDisparo::a_dot`), con
+/// una flecha desde cada bomba hacia las que hizo explotar.
 ///
 /// # Ejemplos
 ///
 /// ```
-/// let tablero = Vec![
-///     Vec!["W".to_string(),"B1".to_string()],
-///     Vec!["S3".to_string(),"R".to_string()]
-///     ];
-/// if es_tablero_valido(&mut tablero){
-///     return Ok(tablero)
-/// }
-/// return Err("Error: tablero invalido".to_string())
+/// $ cargo run grafo entradas/entrada.txt 0 0 | dot -Tsvg -o cadena.svg
 /// ```
 ///
 /// # Argumentos
 ///
-/// * tablero: La matriz de Strings que se quiere evaluar si es valida.
+/// * ruta: La ruta de la entrada.
+/// * x, y: Las coordenadas de la bomba.
+/// * reglas: Las opciones de reglas, con las que se lee y se simula.
 ///
 /// # Devuelve
 ///
-/// Un booleano indicando si la matriz es valida (true) o si no es valida (false).
-fn es_tablero_valido(tablero: &mut Vec<Vec<String>>) -> bool {
-    let validos_no_bomba = [
-        "_".to_string(),
-        "W".to_string(),
-        "R".to_string(),
-        "DU".to_string(),
-        "DL".to_string(),
-        "DR".to_string(),
-        "DD".to_string(),
-        "F1".to_string(),
-        "F2".to_string(),
-        "F3".to_string(),
-    ];
-    for fila in tablero {
-        for elem in fila {
-            if validos_no_bomba.contains(elem) {
-                continue;
+/// El codigo de salida del proceso: 0 si se imprimio el grafo, 1 si la entrada es invalida o la simulacion termino
+/// con un error y 2 si los argumentos son incorrectos.
+fn grafo(ruta: &str, x: Columna, y: Fila, reglas: &uso::ArgsReglas) -> i32 {
+    let opciones = match Opciones::leer(reglas, &uso::ArgsLimites::default()) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let tablero = match tablero_desde_archivo_con_reglas(ruta, &opciones.reglas) {
+        Ok(tablero) => tablero,
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            return 1;
+        }
+    };
+    let punto = match punto_en_tablero(&tablero, x, y, opciones.idioma) {
+        Ok(punto) => punto,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let salida = SimulacionBuilder::new(tablero)
+        .reglas(opciones.reglas)
+        .construir()
+        .simular(punto.columna(), punto.fila());
+    match salida {
+        Ok(salida) => {
+            print!("{}", NodoDisparo::arbol(punto, &salida.disparos).a_dot());
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            1
+        }
+    }
+}
+
+/// Prueba detonar cada bomba del tablero, cada una sobre su propia copia, e imprime las que eliminan a todos los
+/// enemigos, una por linea con las bombas de su reaccion en cadena en el orden en que explotaron (ver
+/// `Analizador::soluciones`). Con `--minimo K` imprime en cambio la menor cantidad de detonaciones, de hasta K, que
+/// eliminan a todos los enemigos una despues de otra, y en que orden (ver `Analizador::solucion_minima`).
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run resolver entrada.txt
+/// (0, 0): (0, 0) -> (0, 2) -> (4, 2)
+/// (0, 2): (0, 2) -> (4, 2) -> (0, 0)
+/// (4, 2): (4, 2)
+/// $ cargo run resolver otra.txt --minimo 3
+/// 2 detonaciones: (2, 0), (2, 2)
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta de la entrada.
+/// * minimo: El K de `--minimo`, si se paso.
+/// * reglas, limites: Las opciones de reglas y de limites, con las que se lee y se simula.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se encontro una solucion, 1 si no hay ninguna, la entrada es invalida o
+/// alguna simulacion termino con un error y 2 si los argumentos son incorrectos.
+fn resolver(
+    ruta: &str,
+    minimo: Option<usize>,
+    reglas: &uso::ArgsReglas,
+    limites: &uso::ArgsLimites,
+) -> i32 {
+    let opciones = match Opciones::leer(reglas, limites) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let tablero = match leer_tablero(ruta, &opciones) {
+        Ok(tablero) => tablero,
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            return 1;
+        }
+    };
+    let analizador = Analizador::new(tablero, opciones.reglas, opciones.limites);
+    let coordenadas = |punto: &Punto| format!("({}, {})", punto.x, punto.y);
+    if let Some(maximo) = minimo {
+        return match analizador.solucion_minima(maximo) {
+            Ok(Some(orden)) => {
+                let orden: Vec<String> = orden.iter().map(coordenadas).collect();
+                println!("{} detonaciones: {}", orden.len(), orden.join(", "));
+                0
             }
-            let mut iter = elem.chars();
-            let prim = iter.next();
-            let sec = iter.next().unwrap_or('X');
-            if (prim == Some('S')) | (prim == Some('B')) && sec.is_ascii_digit() && sec != '0' {
-                continue;
+            Ok(None) => {
+                eprintln!(
+                    "Ninguna combinacion de hasta {} detonaciones elimina a todos los enemigos",
+                    maximo
+                );
+                1
+            }
+            Err(e) => {
+                eprintln!("{}", informar_error(e, opciones.idioma));
+                1
             }
-            return false;
+        };
+    }
+    let soluciones = match analizador.soluciones() {
+        Ok(soluciones) => soluciones,
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            return 1;
         }
+    };
+    if soluciones.is_empty() {
+        eprintln!("Ninguna bomba elimina a todos los enemigos");
+        return 1;
+    }
+    for solucion in soluciones {
+        let cadena: Vec<String> = solucion.cadena.iter().map(coordenadas).collect();
+        println!("{}: {}", coordenadas(&solucion.bomba), cadena.join(" -> "));
     }
-    true
+    0
 }
 
-/// Toma un String con una descripcion de un error y lo escribe en el archivo.
+/// Compara dos resultados de `--format json` de la misma jugada, hechos con distintas versiones del programa, e
+/// imprime en que cambiaron (ver `bisect::diferencias`), para revisar lo que cambia al actualizar.
 ///
 /// # Ejemplos
 ///
 /// ```
-/// let salida: File = File::create("ruta/de/salida.txt").unwrap();
-/// match result_casual {
-///     Ok() => {},
-///     Err(e: String) => devolver_error(e,salida)
-/// }
+/// $ cargo run bisect-helper viejo/entrada.json nuevo/entrada.json
+/// Versiones: 0.1.0 -> 0.2.0
+/// resultado.enemigos_eliminados: 1 -> 2
+/// tablero (4, 0): F1 -> _
 /// ```
 ///
 /// # Argumentos
 ///
-/// * error_string: La cadena que se quiere escribir en el archivo para informar el error.
-/// * salida: El archivo donde se quiere escribir.
-fn devolver_error(error_string: String, mut salida: File) {
-    if let Err(_e) = salida.write((error_string).as_bytes()) { // Error en la escritura
+/// * rutas: Las rutas de los dos resultados, el de antes y el de despues.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si los resultados son equivalentes, 1 si difieren y 2 si hubo un error, por
+/// ejemplo porque sus id dicen que son de jugadas distintas.
+fn bisect_helper(rutas: &[String; 2]) -> i32 {
+    let mut resultados = Vec::new();
+    for ruta in rutas {
+        let leido = std::fs::read_to_string(ruta)
+            .map_err(|_| format!("Error: no se pudo leer {}", ruta))
+            .and_then(|texto| {
+                bomberman::json::parsear(&texto).map_err(|e| format!("{}: {}", ruta, e))
+            });
+        match leido {
+            Ok(resultado) => resultados.push(resultado),
+            Err(e) => {
+                eprintln!("{}", e);
+                return 2;
+            }
+        }
+    }
+    let lineas = match bisect::diferencias(&resultados[0], &resultados[1]) {
+        Ok(lineas) => lineas,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    if let (Some(Json::Texto(a)), Some(Json::Texto(b))) = (
+        resultados[0].campo("version"),
+        resultados[1].campo("version"),
+    ) {
+        println!("Versiones: {} -> {}", a, b);
+    }
+    for linea in &lineas {
+        println!("{}", linea);
+    }
+    if lineas.is_empty() {
+        0
+    } else {
+        1
     }
 }
 
-/// Toma un vector de strings y extrae de las posiciones de los indices i1 e i2, 2 strings para pasar a usize y devolverlos en un Result o devolver un Error con un String con su descripcion.
+/// Vigila una bandeja de entrada y simula cada trabajo que aparece en ella (ver `daemon::procesar`): un tablero
+/// `nombre.txt` con sus coordenadas en `nombre.coords`. El resultado se escribe en la bandeja de salida con el mismo
+/// nombre, los archivos del trabajo se mueven a `procesados` y cada trabajo se anota en `diario.txt`. La bandeja se
+/// revisa al empezar y despues cada vez que el sistema avisa que cambio algo en ella (ver `notify`), hasta que se
+/// corta el proceso, o una sola vez con `--una-vez`. Un trabajo que no se pudo escribir o mover se informa por stderr
+/// y se vuelve a intentar en la revision siguiente, sin cortar los demas.
 ///
 /// # Ejemplos
 ///
 /// ```
-/// let punto_bomba: Punto;
-/// match extraer_coord(args, tamanio,indice_1,indice_2) {
-///     Err(e) => {return devolver_error(e, salida)},
-///     Ok(pt) => punto_bomba = pt
-/// }
+/// $ cargo run daemon entregas resultados --max-cadena 100
+/// 1718000000 alumno7.txt (0, 2): ok
+/// 1718000003 alumno8.txt (4, 4): Error: coordenadas invalidas
 /// ```
 ///
 /// # Argumentos
 ///
-/// * args: Vector de Strings que incluye las coordenadas a transformar.
-/// * tamanio: Valor que no pueden superar para que sean validas.
-/// * i1: Indice de la coordenada X.
-/// * i2: Indice de la coordenada Y.
+/// * entrada, salida: Las dos bandejas.
+/// * una_vez: Si se paso `--una-vez`.
+/// * reglas, limites: Las opciones de reglas y de limites, con las que se validan y simulan los tableros.
 ///
 /// # Devuelve
 ///
-/// Un Result exitoso con un vector que contiene las coordenadas en usize o un Error con un String describiendo el mismo.
-fn extraer_coord(args: Vec<String>, tamanio: usize, i1: usize, i2: usize) -> Result<Punto, String> {
-    let mut punto_bomba: Punto = Punto { x: 0, y: 0 };
+/// El codigo de salida del proceso: 0 si termino la revision de `--una-vez` y 2 si los argumentos son incorrectos o
+/// no se pudo leer o vigilar la bandeja de entrada.
+fn comando_daemon(
+    ruta_entrada: &str,
+    ruta_salida: &str,
+    una_vez: bool,
+    reglas: &uso::ArgsReglas,
+    limites: &uso::ArgsLimites,
+) -> i32 {
+    let opciones = match Opciones::leer(reglas, limites) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let (entrada, salida) = (
+        std::path::Path::new(ruta_entrada),
+        std::path::Path::new(ruta_salida),
+    );
+    if !salida.is_dir() {
+        eprintln!(
+            "Error: la bandeja de salida {} no es un directorio",
+            ruta_salida
+        );
+        return 2;
+    }
+    // El vigia se arma antes de la primera revision para no perder los trabajos que lleguen mientras tanto.
+    let (avisos, cambios) = std::sync::mpsc::channel();
+    let mut vigia = None;
+    if !una_vez {
+        let vigilado = notify::recommended_watcher(avisos).and_then(|mut vigia| {
+            vigia
+                .watch(entrada, notify::RecursiveMode::NonRecursive)
+                .map(|_| vigia)
+        });
+        match vigilado {
+            Ok(vigilado) => vigia = Some(vigilado),
+            Err(e) => {
+                eprintln!(
+                    "Error: no se pudo vigilar la bandeja de entrada {} ({})",
+                    ruta_entrada, e
+                );
+                return 2;
+            }
+        }
+    }
+    loop {
+        let trabajos = match daemon::pendientes(entrada) {
+            Ok(trabajos) => trabajos,
+            Err(_) => {
+                eprintln!(
+                    "Error: no se pudo leer la bandeja de entrada {}",
+                    ruta_entrada
+                );
+                return 2;
+            }
+        };
+        for trabajo in trabajos {
+            match daemon::procesar(&trabajo, salida, &opciones.reglas, &opciones.limites) {
+                Ok(linea) => println!("{}", linea),
+                Err(e) => eprintln!("Error: no se pudo procesar {} ({})", trabajo.nombre, e),
+            }
+        }
+        if vigia.is_none() {
+            return 0;
+        }
+        // Se espera un cambio y despues a que la bandeja quede quieta, para no tomar un trabajo a medio escribir; una
+        // revision atiende todos los cambios juntos. Las lecturas tambien llegan como avisos, y las del propio daemon
+        // no tienen que provocar otra revision.
+        loop {
+            match cambios.recv() {
+                Ok(Ok(evento)) if evento.kind.is_access() => {}
+                Ok(Ok(_)) => break,
+                Ok(Err(e)) => eprintln!("Error: no se pudo vigilar la bandeja de entrada ({})", e),
+                Err(_) => return 2,
+            }
+        }
+        while cambios.recv_timeout(daemon::CALMA).is_ok() {}
+    }
+}
 
-    match args[i1].parse() {
-        Ok(x) => punto_bomba.x = x,
-        _ => return Err("Error: coordenadas invalidas".to_string()),
+/// Vuelve a simular una repeticion grabada con `--grabar-replay` (ver `Repeticion`) e imprime el tablero final, o con
+/// `--cuadros` el tablero despues de cada detonacion. Despues compara los eventos con los grabados, para confirmar que
+/// el motor sigue haciendo lo mismo, e informa por stderr el primero que no coincide.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run replay partida.txt --cuadros
+/// Paso 1: explota la bomba en (0, 0)
+/// _ _ B1
+/// _ W R
+///
+/// Paso 2: explota la bomba en (2, 0)
+/// _ _ _
+/// _ W R
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: El archivo de la repeticion.
+/// * cuadros: Si se paso `--cuadros`.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si la simulacion dio los mismos eventos, 1 si no o si termino con un error y 2
+/// si los argumentos son incorrectos o la repeticion no se pudo leer.
+fn replay(ruta: &str, cuadros: bool) -> i32 {
+    let repeticion = match std::fs::read_to_string(ruta)
+        .map_err(|_| format!("Error: no se pudo leer {}", ruta))
+        .and_then(|texto| Repeticion::desde_texto(&texto))
+    {
+        Ok(repeticion) => repeticion,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let mut simulacion = repeticion.reproducir();
+    let mut eventos = Vec::new();
+    for paso in simulacion.by_ref() {
+        let paso = match paso {
+            Ok(paso) => paso,
+            Err(e) => {
+                eprintln!("{}", informar_error(e, Idioma::Es));
+                return 1;
+            }
+        };
+        if cuadros {
+            println!(
+                "Paso {}: explota la bomba en ({}, {})\n{}\n",
+                paso.numero,
+                paso.bomba.x,
+                paso.bomba.y,
+                paso.tablero.to_texto()
+            );
+        }
+        eventos.extend(paso.eventos);
     }
-    match args[i2].parse() {
-        Ok(y) => punto_bomba.y = y,
-        _ => return Err("Error: coordenadas invalidas".to_string()),
+    if !cuadros {
+        println!("{}", simulacion.tablero().to_texto());
     }
-    if punto_bomba.x >= tamanio || punto_bomba.y >= tamanio {
-        return Err("Error: coordenadas invalidas".to_string());
+    match repeticion.divergencia(&eventos) {
+        None => 0,
+        Some((indice, grabado, nuevo)) => {
+            let texto = |evento: Option<String>| evento.unwrap_or_else(|| "(nada)".to_string());
+            eprintln!(
+                "El evento {} no coincide: se grabo \"{}\" y ahora es \"{}\"",
+                indice + 1,
+                texto(grabado),
+                texto(nuevo)
+            );
+            1
+        }
     }
-    Ok(punto_bomba)
 }
 
-/// Toma una matriz de Strings y la escribe en el archivo pedido, separando las filas con saltos de linea y las columnas con espacios.
+/// Pasa por el motor cada archivo de un directorio de entradas problematicas (de fuzzing o de reportes de errores),
+/// detonando por separado cada bomba y cada mina de cada tablero, e informa como termino cada uno. Los tableros se
+/// leen y se simulan con las reglas y los limites de las opciones, asi que un corpus con ladrillos o mejoras se
+/// prueba con `--ladrillos`, `--mejoras`, `--preset` o `--rules`. Rechazar una entrada invalida
+/// o devolver un error es un resultado valido; lo unico que no puede pasar es un panic.
 ///
 /// # Ejemplos
 ///
 /// ```
-/// let tablero: Vec<Vec<String>> = Vec![
-///     Vec!['M','A','T','R'],
-///     Vec!['_','_','_','I'],
-///     Vec!['_','_','_','Z']
-/// ];
-/// escribir_tablero_final_en_archivo(tablero,archivo_a_escribir)
+/// $ cargo run replay-corpus corpus --ladrillos
+/// corpus/alcance_enorme.txt: ok (1 bombas, 0 errores)
+/// corpus/basura.txt: rechazado (Error: archivo de entrada invalido)
 /// ```
-/// y queda en el archivo_a_escribir:
+///
+/// # Argumentos
+///
+/// * directorio: El directorio del corpus.
+/// * reglas, limites: Las opciones de reglas y de limites, con las que se lee y se simula cada entrada.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si ninguna entrada provoco un panic, 1 si alguna lo hizo y 2 si hubo un error.
+fn replay_corpus(directorio: &str, reglas: &uso::ArgsReglas, limites: &uso::ArgsLimites) -> i32 {
+    let opciones = match Opciones::leer(reglas, limites) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let mut rutas: Vec<_> = match std::fs::read_dir(directorio) {
+        Ok(dir) => dir
+            .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+            .filter(|ruta| ruta.is_file())
+            .collect(),
+        Err(_) => {
+            eprintln!("Error: no se pudo leer el directorio {}", directorio);
+            return 2;
+        }
+    };
+    rutas.sort();
+    // Los panics se informan en el resumen, asi que no hace falta que tambien se impriman por stderr.
+    let hook_anterior = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut hubo_panic = false;
+    for ruta in rutas {
+        let nombre = ruta.display().to_string();
+        let resultado = panic::catch_unwind(|| reproducir_entrada(&nombre, &opciones));
+        match resultado {
+            Ok(Ok((bombas, errores))) => {
+                println!("{}: ok ({} bombas, {} errores)", nombre, bombas, errores)
+            }
+            Ok(Err(e)) => println!("{}: rechazado ({})", nombre, e),
+            Err(causa) => {
+                hubo_panic = true;
+                let mensaje = causa
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| causa.downcast_ref::<&str>().map(|m| m.to_string()))
+                    .unwrap_or_default();
+                println!("{}: PANIC ({})", nombre, mensaje);
+            }
+        }
+    }
+    panic::set_hook(hook_anterior);
+    if hubo_panic {
+        1
+    } else {
+        0
+    }
+}
+
+/// Lee cada tablero de un directorio de pruebas e informa que tipos de casilla y formas de escribirlas usa (ver
+/// `censo::Caracteristica`), y al final cuantos tableros usan cada una, para ver que le falta cubrir a la bateria.
+///
+/// # Ejemplos
+///
 /// ```
-/// 1  M A T R
-/// 2  _ _ _ I
-/// 3  _ _ _ Z
+/// $ cargo run censo entradas
+/// entradas/entrada.txt: vacio, pared, roca, enemigo, bomba_normal
+/// vacio: 1 de 1 tableros
+/// desvio: sin cubrir
 /// ```
+///
 /// # Argumentos
 ///
-/// * tablero: Matriz de strings que se quiere escribir en el archivo.
-/// * salida: Archivo destino en el que se quiere escribir.
-fn escribir_tablero_final_en_archivo(tablero: Vec<Vec<String>>, mut salida: File) {
-    for (indice, fila) in tablero.iter().enumerate() {
-        if indice == tablero.len() - 1 {
-            if let Err(_e) = salida.write((fila.join(" ")).as_bytes()) {
-                return; // Error en la escritura
+/// * directorio: El directorio de los tableros.
+/// * reglas: Las opciones de reglas, con las que se validan los tableros.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se pudo leer el directorio y 2 si hubo un error. Los tableros invalidos se
+/// informan pero no cuentan para el resumen.
+fn censo(directorio: &str, reglas: &uso::ArgsReglas) -> i32 {
+    let opciones = match Opciones::leer(reglas, &uso::ArgsLimites::default()) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let mut rutas: Vec<_> = match std::fs::read_dir(directorio) {
+        Ok(dir) => dir
+            .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+            .filter(|ruta| ruta.is_file())
+            .collect(),
+        Err(_) => {
+            eprintln!("Error: no se pudo leer el directorio {}", directorio);
+            return 2;
+        }
+    };
+    rutas.sort();
+    let mut leidos = 0;
+    let mut usos = [0; Caracteristica::TODAS.len()];
+    for ruta in rutas {
+        let tablero = match tablero_desde_archivo_con_reglas(&ruta, &opciones.reglas) {
+            Ok(tablero) => tablero,
+            Err(e) => {
+                println!("{}: rechazado ({})", ruta.display(), e);
+                continue;
             }
-        } else if let Err(_e) = salida.write((fila.join(" ") + "\n").as_bytes()) {
-            return; // Error en la escritura
+        };
+        leidos += 1;
+        let usadas = censo::caracteristicas(&tablero);
+        for (usos, caracteristica) in usos.iter_mut().zip(Caracteristica::TODAS) {
+            *usos += usadas.contains(&caracteristica) as usize;
+        }
+        let nombres: Vec<String> = usadas.iter().map(Caracteristica::to_string).collect();
+        println!("{}: {}", ruta.display(), nombres.join(", "));
+    }
+    for (usos, caracteristica) in usos.iter().zip(Caracteristica::TODAS) {
+        match usos {
+            0 => println!("{}: sin cubrir", caracteristica),
+            _ => println!("{}: {} de {} tableros", caracteristica, usos, leidos),
+        }
+    }
+    0
+}
+
+/// Escribe una copia de un tablero que se puede compartir sin mostrar el original (ver `ofuscacion::ofuscar`): sin
+/// comentarios, metadatos ni datos opacos, salvo los `id` de los enemigos renumerados. Con `--semilla N` ademas le
+/// aplica una de las 8 simetrias del tablero, elegida por la semilla, e informa por stderr donde quedo cada bomba para
+/// detonarla en la copia con el mismo resultado.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run ofuscar privados/nivel3.txt compartir/nivel.txt --semilla 7
+/// Simetria: transpone, invierte las filas
+/// La bomba en (0, 2) queda en (4, 0)
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta_entrada, ruta_salida: La entrada y el archivo de salida.
+/// * semilla: La de `--semilla`, si se paso.
+/// * reglas: Las opciones de reglas, con las que se valida la entrada.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se escribio la copia, 1 si la entrada no es un tablero valido y 2 si los
+/// argumentos son incorrectos o no se pudo escribir la salida.
+fn ofuscar(
+    ruta_entrada: &str,
+    ruta_salida: &str,
+    semilla: Option<u64>,
+    reglas: &uso::ArgsReglas,
+) -> i32 {
+    let simetria = semilla.map_or(Simetria::default(), Simetria::al_azar);
+    let opciones = match Opciones::leer(reglas, &uso::ArgsLimites::default()) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
         }
+    };
+    let tablero = match tablero_desde_archivo_con_reglas(ruta_entrada, &opciones.reglas) {
+        Ok(tablero) => tablero,
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            return 1;
+        }
+    };
+    let copia = ofuscacion::ofuscar(&tablero, simetria);
+    if std::fs::write(ruta_salida, copia.to_texto()).is_err() {
+        eprintln!("Error: no se pudo escribir {}", ruta_salida);
+        return 2;
+    }
+    if simetria != Simetria::default() {
+        eprintln!("Simetria: {}", simetria);
+        for (punto, celda) in tablero.celdas() {
+            if let Celda::Bomba(_) = celda {
+                let nuevo = simetria.punto(punto, tablero.ancho(), tablero.alto());
+                eprintln!(
+                    "La bomba en ({}, {}) queda en ({}, {})",
+                    punto.x, punto.y, nuevo.x, nuevo.y
+                );
+            }
+        }
+    }
+    0
+}
+
+/// Escribe un tablero al azar del tamanio pedido (ver `generador::tablero_aleatorio`), para pruebas de carga o para
+/// practicar niveles nuevos. La misma semilla da siempre el mismo tablero.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run generar nivel.txt 9 7 --semilla 42 --bombas 0.1 --paredes 0.2
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: El archivo de salida.
+/// * ancho, alto: El tamanio del tablero.
+/// * semilla: La de `--semilla` (por defecto 1).
+/// * densidades: Las de `--paredes`, `--rocas`, `--bombas`, `--desvios` y `--enemigos` que cambian las de
+///   `Densidades::default`.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se escribio el tablero y 2 si los argumentos son incorrectos o no se pudo
+/// escribir la salida.
+fn generar(
+    ruta: &str,
+    ancho: usize,
+    alto: usize,
+    semilla: u64,
+    densidades: &uso::ArgsDensidades,
+) -> i32 {
+    let base = Densidades::default();
+    let densidades = Densidades {
+        paredes: densidades.paredes.unwrap_or(base.paredes),
+        rocas: densidades.rocas.unwrap_or(base.rocas),
+        bombas: densidades.bombas.unwrap_or(base.bombas),
+        desvios: densidades.desvios.unwrap_or(base.desvios),
+        enemigos: densidades.enemigos.unwrap_or(base.enemigos),
+    };
+    let resultado =
+        generador::tablero_aleatorio(ancho, alto, &densidades, semilla).and_then(|tablero| {
+            std::fs::write(ruta, tablero.to_texto())
+                .map_err(|_| format!("Error: no se pudo escribir {}", ruta))
+        });
+    match resultado {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            2
+        }
+    }
+}
+
+/// Atiende pedidos de simulacion por stdin y responde cada uno por stdout, una linea por pedido y una por respuesta
+/// (ver `protocolo::atender`), hasta que se termina la entrada. Sirve para correr muchos casos sin pagar el arranque
+/// del proceso ni los archivos de cada uno.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run -- --protocol jsonl --toroidal < pedidos.jsonl > respuestas.jsonl
+/// ```
+///
+/// # Argumentos
+///
+/// * reglas, limites: Las opciones de reglas y de limites que siguen a `--protocol jsonl`, que valen para todos los
+///   pedidos. Los limites que no se den toman los de `protocolo::limites`. No acepta `--eventos`, porque los
+///   eventos se imprimirian en la misma salida que las respuestas.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se termino la entrada, 1 si no se pudo leer o escribir y 2 si los
+/// argumentos son incorrectos. Los pedidos que no se pueden simular se responden con un error y no lo terminan.
+fn comando_protocolo(reglas: &uso::ArgsReglas, limites: &uso::ArgsLimites) -> i32 {
+    let mut opciones = match Opciones::leer(reglas, limites) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    opciones.limites = protocolo::limites(&opciones.limites);
+    let resultado = protocolo::atender(
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &opciones.reglas,
+        opciones.idioma,
+        |tablero| constructor(tablero, &opciones),
+    );
+    match resultado {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Abre la interfaz de terminal sobre un tablero (ver `tui::ejecutar`): se mueve el cursor, se detona la bomba que
+/// tiene debajo y se ve la reaccion en cadena de a una detonacion por vez, con los eventos al costado. Solo esta con
+/// la feature `tui`.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run --features tui tui entradas/entrada.txt --pausa 300 --enemigos-bloquean
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta de la entrada.
+/// * pausa: Los milisegundos de `--pausa` entre un cuadro y el siguiente.
+/// * reglas, limites: Las opciones de reglas y de limites. No acepta `--eventos`, porque los eventos ya van en el
+///   panel.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se salio de la interfaz, 1 si la entrada es invalida y 2 si los argumentos
+/// son incorrectos.
+#[cfg(feature = "tui")]
+fn comando_tui(
+    ruta: &str,
+    pausa: u64,
+    reglas: &uso::ArgsReglas,
+    limites: &uso::ArgsLimites,
+) -> i32 {
+    let opciones = match Opciones::leer(reglas, limites) {
+        Ok(opciones) => opciones,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    let tablero = match leer_tablero(ruta, &opciones) {
+        Ok(tablero) => tablero,
+        Err(e) => {
+            eprintln!("{}", informar_error(e, opciones.idioma));
+            return 1;
+        }
+    };
+    let resultado = tui::abrir(
+        tablero,
+        |tablero| constructor(tablero, &opciones),
+        Duration::from_millis(pausa),
+    );
+    match resultado {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// Lee una entrada del corpus con las reglas de las opciones y detona cada una de sus bombas y minas sobre una copia
+/// del tablero, con los limites de las opciones.
+///
+/// # Devuelve
+///
+/// Un Result con la cantidad de bombas y minas probadas y cuantas de ellas terminaron con un error, o un Err(String) si
+/// el archivo no es un tablero valido.
+fn reproducir_entrada(ruta: &str, opciones: &Opciones) -> Result<(usize, usize), String> {
+    let tablero = leer_tablero(ruta, opciones).map_err(|e| e.to_string())?;
+    let mut bombas = 0;
+    let mut errores = 0;
+    for (punto, celda) in tablero.celdas() {
+        if !matches!(celda, Celda::Bomba(_) | Celda::Mina { .. }) {
+            continue;
+        }
+        bombas += 1;
+        if SimulacionBuilder::new(tablero.clone())
+            .reglas(opciones.reglas.clone())
+            .limites(opciones.limites.clone())
+            .construir()
+            .simular(punto.columna(), punto.fila())
+            .is_err()
+        {
+            errores += 1;
+        }
+    }
+    Ok((bombas, errores))
+}
+
+/// Lee un tablero (una entrada o un resultado ya escrito) y ejecuta sobre el una consulta del mini lenguaje de `consulta`.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run consultar entradas/entrada.txt "list B where alcance>2"
+/// (0, 2): B5
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta del tablero.
+/// * texto: La consulta.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si la consulta se pudo ejecutar y 2 si hubo un error.
+fn consultar(ruta: &str, texto: &str) -> i32 {
+    let resultado = tablero_desde_archivo(ruta)
+        .map_err(|e| e.to_string())
+        .and_then(|tablero| {
+            consulta::parsear(texto).and_then(|c| consulta::ejecutar(&c, &tablero.filas()))
+        });
+    match resultado {
+        Ok(lineas) => {
+            for linea in lineas {
+                println!("{}", linea);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            2
+        }
+    }
+}
+
+/// Parte de un tablero semilla y busca, moviendo paredes, rocas y enemigos, un nivel con la dificultad pedida
+/// (la proporcion de enemigos que ninguna bomba alcanza). Escribe el nivel en la ruta de salida.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run optimizar-nivel semilla.txt nivel.txt 0.5 --iteraciones 2000 --semilla 7
+/// Dificultad: 0.50 (objetivo 0.50)
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta_entrada, ruta_salida: El tablero semilla y el archivo donde se escribe el nivel.
+/// * dificultad: La dificultad pedida, entre 0 y 1.
+/// * iteraciones, semilla: Las de `--iteraciones` y `--semilla`, si se pasaron (ver `Recocido`).
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se escribio el nivel y 2 si hubo un error.
+fn optimizar_nivel(
+    ruta_entrada: &str,
+    ruta_salida: &str,
+    dificultad: f64,
+    iteraciones: Option<usize>,
+    semilla: Option<u64>,
+) -> i32 {
+    let mut recocido = Recocido::new(dificultad);
+    if let Some(iteraciones) = iteraciones {
+        recocido = recocido.iteraciones(iteraciones);
+    }
+    if let Some(semilla) = semilla {
+        recocido = recocido.semilla(semilla);
+    }
+    match generar_nivel(ruta_entrada, ruta_salida, recocido) {
+        Ok(alcanzada) => {
+            println!("Dificultad: {:.2} (objetivo {:.2})", alcanzada, dificultad);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            2
+        }
+    }
+}
+
+/// Optimiza el tablero semilla con el recocido pedido y escribe el nivel.
+///
+/// # Devuelve
+///
+/// Un Result con la dificultad alcanzada, o un Err(String) con la descripcion del error.
+fn generar_nivel(ruta_entrada: &str, ruta_salida: &str, recocido: Recocido) -> Result<f64, String> {
+    let tablero = tablero_desde_archivo(ruta_entrada).map_err(|e| e.to_string())?;
+    let (nivel, dificultad) = recocido
+        .optimizar(tablero, Reglas::default(), Limites::default())
+        .map_err(|e| e.to_string())?;
+    let archivo =
+        File::create(ruta_salida).map_err(|_| "Error: ruta de salida invalida".to_string())?;
+    let mut escritor = BufWriter::new(archivo);
+    bomberman::io::escribir_tablero(&nivel, &mut escritor)
+        .and_then(|_| escritor.flush())
+        .map_err(|_| "Error: no se pudo escribir el nivel".to_string())?;
+    Ok(dificultad)
+}
+
+/// Junta en un unico archivo de paquete los tableros, manifiestos y salidas esperadas pedidos, para compartir
+/// una bateria de pruebas completa.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run empaquetar pruebas.bmp entradas esperados
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta del paquete.
+/// * archivos: Los archivos o directorios relativos que se empaquetan.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se escribio el paquete y 2 si hubo un error.
+fn empaquetar(ruta: &str, archivos: &[String]) -> i32 {
+    let resultado = paquete::leer_entradas(archivos).and_then(|entradas| {
+        std::fs::write(ruta, paquete::empaquetar(&entradas))
+            .map_err(|_| format!("Error: no se pudo escribir el paquete {}", ruta))
+    });
+    match resultado {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            2
+        }
+    }
+}
+
+/// Extrae todos los archivos de un paquete hecho con `empaquetar` dentro del directorio pedido.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run desempaquetar pruebas.bmp .
+/// ```
+///
+/// # Argumentos
+///
+/// * ruta: La ruta del paquete.
+/// * directorio: El directorio de destino.
+///
+/// # Devuelve
+///
+/// El codigo de salida del proceso: 0 si se extrajo el paquete y 2 si hubo un error.
+fn desempaquetar(ruta: &str, directorio: &str) -> i32 {
+    let resultado = std::fs::read_to_string(ruta)
+        .map_err(|_| format!("Error: no se pudo leer el paquete {}", ruta))
+        .and_then(|texto| paquete::desempaquetar(&texto))
+        .and_then(|entradas| paquete::escribir_entradas(directorio, &entradas));
+    match resultado {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            2
+        }
+    }
+}
+
+/// Cantidades que se informan al comando `--al-terminar` luego de una simulacion exitosa.
+struct Resumen {
+    bombas_detonadas: usize,
+    enemigos_eliminados: usize,
+    /// Si quedaron enemigos, para el codigo de salida de `--codigo-salida`.
+    desenlace: Resultado,
+}
+
+/// Lee el tablero, detona la bomba pedida y escribe el resultado (o el error) en el archivo de salida.
+///
+/// # Argumentos
+///
+/// * x, y: Las coordenadas de la bomba, o `? ?` para elegirla con el cursor.
+/// * ruta_entrada: La ruta hasta el archivo con el tablero inicial.
+/// * salida: El archivo donde se escribe el resultado.
+/// * opciones: Las opciones opcionales de la linea de comandos.
+///
+/// # Devuelve
+///
+/// Un Result con el Resumen de la simulacion o un Err(String) con la descripcion del error que ya fue escrito en la salida.
+fn simular(
+    x: uso::Coordenada,
+    y: uso::Coordenada,
+    ruta_entrada: &str,
+    salida: File,
+    opciones: &Opciones,
+) -> Result<Resumen, String> {
+    let (tablero, mut advertencias, metadatos) = match leer_entrada(ruta_entrada, opciones) {
+        Err(e) if !opciones.json && !opciones.recuperar => {
+            let problemas = problemas_de_lectura(&e, ruta_entrada, opciones);
+            if problemas.is_empty() {
+                return Err(devolver_error(
+                    informar_error(e, opciones.idioma),
+                    salida,
+                    opciones,
+                ));
+            }
+            eprintln!("{}", listar_problemas(&e, &problemas));
+            return Err(devolver_error(
+                e.mensaje(opciones.idioma).to_string(),
+                salida,
+                opciones,
+            ));
+        }
+        Err(e) => {
+            return Err(devolver_error(
+                informar_error(e, opciones.idioma),
+                salida,
+                opciones,
+            ))
+        }
+        Ok(leido) => leido,
+    };
+    let elegido = match (x, y) {
+        (Coordenada::Cursor, Coordenada::Cursor) if opciones.hexagonal => {
+            Err(opciones.idioma.texto("cursor_hexagonal").to_string())
+        }
+        (Coordenada::Cursor, Coordenada::Cursor) => {
+            let analizador = Analizador::new(
+                tablero.clone(),
+                opciones.reglas.clone(),
+                opciones.limites.clone(),
+            );
+            selector::elegir(
+                &analizador,
+                &mut io::stdin().lock(),
+                &mut io::stderr(),
+                opciones.idioma,
+            )
+        }
+        (Coordenada::Numero(x), Coordenada::Numero(y)) => {
+            punto_en_tablero(&tablero, Columna(x), Fila(y), opciones.idioma)
+        }
+        _ => {
+            eprintln!("{}", opciones.idioma.texto("cursor_incompleto"));
+            Err(opciones.idioma.texto("coordenadas_invalidas").to_string())
+        }
+    };
+    let punto_bomba: Punto = match elegido {
+        Err(e) => return Err(devolver_error(e, salida, opciones)),
+        Ok(pt) => pt,
+    };
+
+    let configuracion = format!(
+        "limites={:?} reglas={:?} con_advertencias={} modo_tutorial={} recuperar={} estadisticas_bombas={} resumen={} \
+         simultaneas={:?} simbolos={:?} hexagonal={}",
+        opciones.limites,
+        opciones.reglas,
+        opciones.con_advertencias,
+        opciones.modo_tutorial,
+        opciones.recuperar,
+        opciones.estadisticas_bombas,
+        opciones.resumen,
+        opciones.simultaneas,
+        opciones.simbolos,
+        opciones.hexagonal
+    );
+    let clave_cache = cache::clave(&tablero.filas(), &punto_bomba, &configuracion);
+    // La procedencia depende del nombre del archivo de entrada, asi que no se guarda en la cache.
+    let procedencia = if opciones.con_procedencia && opciones.salida_de_texto() {
+        texto_procedencia(ruta_entrada, &punto_bomba, &opciones.reglas, &tablero)
+    } else {
+        String::new()
+    };
+    let objetivo = match &opciones.objetivo {
+        Some(objetivo) => Some(objetivo.clone()),
+        None => match metadatos
+            .as_ref()
+            .and_then(|metadatos| metadatos.campo("objetivo"))
+        {
+            Some(Json::Texto(texto)) => Some(texto.clone()),
+            _ => leer_metadato(ruta_entrada, "objetivo"),
+        }
+        .and_then(|texto| {
+            Objetivo::parsear(&texto)
+                .map_err(|e| eprintln!("{}", e))
+                .ok()
+        }),
+    };
+    // El objetivo del tablero se revisa con el resultado completo, asi que tampoco se puede usar la cache.
+    let usa_cache = opciones.usa_cache() && objetivo.is_none();
+    // Las advertencias de la simulacion van por stderr, asi que se guardan en la cache aparte del resultado.
+    let clave_advertencias = format!("{}.advertencias", clave_cache);
+    if let (Some(dir_cache), true) = (&opciones.cache, usa_cache) {
+        if let (Some(texto), Some(avisos)) = (
+            cache::buscar(dir_cache, &clave_cache),
+            cache::buscar(dir_cache, &clave_advertencias),
+        ) {
+            for advertencia in &advertencias {
+                eprintln!("{}", advertencia);
+            }
+            for aviso in avisos.lines() {
+                eprintln!("{}", aviso);
+            }
+            let tablero_final: Vec<Vec<String>> = texto
+                .lines()
+                .take_while(|linea| !linea.is_empty())
+                .map(|linea| {
+                    linea
+                        .split_whitespace()
+                        .map(|casilla| opciones.simbolos.leer(casilla))
+                        .collect()
+                })
+                .collect();
+            escribir_texto_en_archivo(&(procedencia + &texto), salida);
+            if let Some(ruta) = &opciones.diff {
+                escribir_diferencias(ruta, &tablero.filas(), &tablero_final, opciones.idioma);
+            }
+            return Ok(resumir(&tablero.filas(), &tablero_final));
+        }
+    }
+
+    let bomberman: Bomberman = match constructor(tablero.clone(), opciones) {
+        Err(e) => {
+            eprintln!("{}", e);
+            return Err(devolver_error(e, salida, opciones));
+        }
+        Ok(constructor) => constructor.construir(),
+    };
+    if opciones.mapa_peligro {
+        match bomberman.analizador().mapa_de_peligro() {
+            Ok(mapa) => {
+                for fila in mapa {
+                    let fila: Vec<String> = fila.iter().map(usize::to_string).collect();
+                    eprintln!("{}", fila.join(" "));
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    let mut detonadas = vec![punto_bomba];
+    detonadas.extend(&opciones.simultaneas);
+    let resultado: SalidaSimulacion = match bomberman.simular_simultaneas(&detonadas) {
+        Err(e) => {
+            return Err(devolver_error(
+                informar_error(e, opciones.idioma),
+                salida,
+                opciones,
+            ))
+        }
+        Ok(res) => res,
+    };
+    if let Some(ruta) = &opciones.grabar_replay {
+        let repeticion = Repeticion::grabar(
+            tablero.clone(),
+            opciones.reglas.clone(),
+            punto_bomba,
+            &resultado.eventos,
+        );
+        if std::fs::write(ruta, repeticion.a_texto()).is_err() {
+            eprintln!(
+                "{}",
+                opciones.idioma.formatear("escritura_repeticion", &[ruta])
+            );
+        }
+    }
+    if let Some(ruta) = &opciones.svg {
+        if std::fs::write(ruta, svg::simulacion(&tablero, &resultado)).is_err() {
+            eprintln!("{}", opciones.idioma.formatear("escritura_svg", &[ruta]));
+        }
+    }
+    if let Some(directorio) = &opciones.frames {
+        if let Err(e) = escribir_cuadros(directorio, 0, 0, &resultado, opciones) {
+            eprintln!("{}", e);
+        }
+    }
+    if let Some(ruta) = &opciones.diff {
+        escribir_diferencias(
+            ruta,
+            &tablero.filas(),
+            &resultado.tablero.filas(),
+            opciones.idioma,
+        );
+    }
+    advertencias.extend(resultado.advertencias.iter().cloned());
+    for advertencia in &advertencias {
+        eprintln!("{}", advertencia);
+    }
+    if let Some(objetivo) = objetivo {
+        let estado = if objetivo.cumplido(&resultado) {
+            "cumplido"
+        } else {
+            "no cumplido"
+        };
+        eprintln!("Objetivo {}: {}", objetivo, estado);
+    }
+    for (paso, punto) in &opciones.inspecciones {
+        match resultado.celda_en(*paso, *punto) {
+            Some(celda) => eprintln!("({}, {}) en el paso {}: {}", punto.x, punto.y, paso, celda),
+            None => eprintln!("({}, {}) esta fuera del tablero", punto.x, punto.y),
+        }
+    }
+    let resumen = resumir(&tablero.filas(), &resultado.tablero.filas());
+    let mut agregados = String::new();
+    if opciones.con_advertencias {
+        agregados += &texto_advertencias(&advertencias);
+    }
+    if opciones.modo_tutorial {
+        agregados += &texto_tutorial(&resultado.eventos);
+    }
+    if opciones.estadisticas_bombas {
+        agregados += &texto_estadisticas(&resultado.estadisticas);
+    }
+    if opciones.resumen {
+        agregados += &texto_resumen(&resultado.resumen(&tablero));
+    }
+    if let (Some(dir_cache), true) = (&opciones.cache, usa_cache) {
+        let texto = opciones.simbolos.a_texto(&resultado.tablero) + &agregados;
+        cache::guardar(dir_cache, &clave_cache, &texto);
+        let avisos: String = resultado
+            .advertencias
+            .iter()
+            .map(|advertencia| format!("{}\n", advertencia))
+            .collect();
+        cache::guardar(dir_cache, &clave_advertencias, &avisos);
+    }
+    // El tablero se escribe directo al archivo, sin armar el texto completo, porque puede ser enorme.
+    let mut escritor = BufWriter::new(salida);
+    let escrito = escritor
+        .write_all(procedencia.as_bytes())
+        .and_then(|_| match opciones.json_bloques {
+            _ if opciones.json => bomberman::io::escribir_resultado_json(
+                Some(&cache::id_jugada(
+                    &tablero.filas(),
+                    &punto_bomba,
+                    &format!("{:?}", opciones.reglas),
+                )),
+                &resultado.tablero,
+                &resultado.resultado(&tablero),
+                &resultado.estadisticas,
+                &advertencias,
+                metadatos.as_ref(),
+                &mut escritor,
+            ),
+            Some(filas) => {
+                bomberman::io::escribir_tablero_json(&resultado.tablero, filas, &mut escritor)
+                    .and_then(|_| {
+                        let arbol = NodoDisparo::arbol(punto_bomba, &resultado.disparos);
+                        writeln!(
+                            escritor,
+                            "{{\"profundidad\":{},\"arbol\":{}}}",
+                            arbol.profundidad(),
+                            arbol.a_json()
+                        )
+                    })
+            }
+            None if opciones.emoji => {
+                escritor.write_all(emoji::dibujar(&resultado.tablero).as_bytes())
+            }
+            None => opciones
+                .simbolos
+                .escribir_tablero(&resultado.tablero, &mut escritor),
+        })
+        .and_then(|_| match opciones.salida_de_texto() {
+            false => Ok(()),
+            true => escritor.write_all(agregados.as_bytes()),
+        })
+        .and_then(|_| escritor.flush());
+    if escrito.is_err() {
+        eprintln!("{}", opciones.idioma.texto("escritura_salida"));
+    }
+    Ok(resumen)
+}
+
+/// Simula un escenario `.toml` (ver `escenario::parsear`): detona cada bomba de sus `[[detonacion]]` en orden, sobre el
+/// tablero que dejo la anterior, y escribe en la salida el tablero final. Las casillas se validan con las reglas de
+/// las opciones y cada detonacion respeta sus limites. Con `--con-advertencias` se agregan las advertencias de todas
+/// las detonaciones.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run escenario.toml salidas
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con el Resumen del tablero inicial contra el final o un Err(String) con la descripcion del error que ya
+/// fue escrito en la salida.
+fn simular_escenario(
+    ruta_entrada: &str,
+    salida: File,
+    opciones: &Opciones,
+) -> Result<Resumen, String> {
+    if !opciones.salida_de_texto() {
+        let error = opciones.idioma.texto("escenario_sin_texto").to_string();
+        eprintln!("{}", error);
+        return Err(devolver_error(error, salida, opciones));
+    }
+    let leido = std::fs::read_to_string(ruta_entrada)
+        .map_err(|e| informar_error(BombermanError::Apertura(e), opciones.idioma))
+        .and_then(|texto| {
+            escenario::parsear(&texto).map_err(|e| {
+                eprintln!("{}", e);
+                opciones.idioma.texto("escenario_invalido").to_string()
+            })
+        })
+        .and_then(|escenario| {
+            let filas = escenario
+                .filas
+                .iter()
+                .map(|fila| fila.iter().map(|c| opciones.simbolos.leer(c)).collect())
+                .collect();
+            tablero_desde_filas_con_reglas(filas, &opciones.reglas)
+                .map(|tablero| (escenario, tablero))
+                .map_err(|e| informar_error(e, opciones.idioma))
+        });
+    let (escenario, inicial) = match leido {
+        Err(e) => return Err(devolver_error(e, salida, opciones)),
+        Ok(leido) => leido,
+    };
+    let mut tablero = inicial.clone();
+    let mut advertencias = Vec::new();
+    let mut cuadro = 0;
+    for (numero, punto) in escenario.detonaciones.iter().enumerate() {
+        if !tablero.en_rango(*punto) {
+            eprintln!(
+                "{}",
+                opciones.idioma.formatear(
+                    "detonacion_fuera_del_tablero",
+                    &[
+                        &(numero + 1),
+                        &punto.x,
+                        &punto.y,
+                        &tablero.ancho(),
+                        &tablero.alto()
+                    ]
+                )
+            );
+            return Err(devolver_error(
+                opciones.idioma.texto("coordenadas_invalidas").to_string(),
+                salida,
+                opciones,
+            ));
+        }
+        let constructor = match constructor(tablero, opciones) {
+            Err(e) => {
+                eprintln!("{}", e);
+                return Err(devolver_error(e, salida, opciones));
+            }
+            Ok(constructor) => constructor,
+        };
+        let resultado = match constructor
+            .construir()
+            .simular(punto.columna(), punto.fila())
+        {
+            Err(e) => {
+                eprintln!("En la detonacion {}:", numero + 1);
+                return Err(devolver_error(
+                    informar_error(e, opciones.idioma),
+                    salida,
+                    opciones,
+                ));
+            }
+            Ok(resultado) => resultado,
+        };
+        if let Some(directorio) = &opciones.frames {
+            // El tablero inicial de cada detonacion es el ultimo cuadro de la anterior.
+            let desde = if numero == 0 { 0 } else { 1 };
+            match escribir_cuadros(directorio, cuadro, desde, &resultado, opciones) {
+                Ok(siguiente) => cuadro = siguiente,
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        advertencias.extend(resultado.advertencias);
+        tablero = resultado.tablero;
+    }
+    for advertencia in &advertencias {
+        eprintln!("{}", advertencia);
+    }
+    let mut texto = match opciones.emoji {
+        true => emoji::dibujar(&tablero),
+        false => opciones.simbolos.a_texto(&tablero),
+    };
+    if opciones.con_advertencias {
+        texto += &texto_advertencias(&advertencias);
+    }
+    escribir_texto_en_archivo(&texto, salida);
+    if let Some(ruta) = &opciones.diff {
+        escribir_diferencias(ruta, &inicial.filas(), &tablero.filas(), opciones.idioma);
+    }
+    Ok(resumir(&inicial.filas(), &tablero.filas()))
+}
+
+/// Lee el tablero de entrada en el formato pedido, reemplazando las casillas invalidas si se paso `--recuperar`.
+///
+/// # Devuelve
+///
+/// Un Result con el Tablero, las advertencias de las casillas reemplazadas y los metadatos de la entrada JSON, o un
+/// Err(BombermanError) si no se pudo leer o no es valido.
+fn leer_entrada(
+    ruta_entrada: &str,
+    opciones: &Opciones,
+) -> Result<(Tablero, Vec<Advertencia>, Option<Json>), BombermanError> {
+    if opciones.json {
+        let texto = std::fs::read_to_string(ruta_entrada).map_err(BombermanError::Apertura)?;
+        if !opciones.recuperar {
+            let (tablero, metadatos) = tablero_desde_json(&texto, &opciones.reglas)?;
+            return Ok((tablero, Vec::new(), metadatos));
+        }
+        let (mut filas, metadatos) = filas_desde_json(&texto)?;
+        let advertencias = recuperar_tablero(&mut filas, &opciones.reglas);
+        return Ok((Tablero::desde_filas(filas)?, advertencias, metadatos));
+    }
+    if opciones.recuperar {
+        let mut filas = leer_filas(ruta_entrada)?;
+        for casilla in filas.iter_mut().flatten() {
+            *casilla = opciones.simbolos.leer(casilla);
+        }
+        let advertencias = recuperar_tablero(&mut filas, &opciones.reglas);
+        return Ok((Tablero::desde_filas(filas)?, advertencias, None));
+    }
+    Ok((leer_tablero(ruta_entrada, opciones)?, Vec::new(), None))
+}
+
+/// Empieza a armar la simulacion de un tablero con las reglas, los limites y el observador de `--eventos` de las
+/// opciones, sobre un TableroHex con `--format hex`.
+///
+/// # Devuelve
+///
+/// Un Result con el SimulacionBuilder, o un Err(String) si el tablero no puede ser hexagonal (ver `TableroHex::new`).
+fn constructor(tablero: Tablero, opciones: &Opciones) -> Result<SimulacionBuilder, String> {
+    let constructor = match opciones.hexagonal {
+        true => SimulacionBuilder::hexagonal(TableroHex::new(tablero)?),
+        false => SimulacionBuilder::new(tablero),
+    };
+    let constructor = constructor
+        .reglas(opciones.reglas.clone())
+        .limites(opciones.limites.clone());
+    Ok(match opciones.eventos {
+        true => constructor.observador(Box::new(ImpresorEventos)),
+        false => constructor,
+    })
+}
+
+/// Escribe los tableros de `--frames` en el directorio, creandolo si no existe: uno por cada estado de la
+/// simulacion desde el paso `desde` (ver `SalidaSimulacion::filas_en`), numerados `frame_NNN.txt` a partir de
+/// `primero` y con los simbolos de las opciones.
+///
+/// # Devuelve
+///
+/// Un Result con el numero del cuadro que seguiria o un Err(String) si no se pudo escribir alguno.
+fn escribir_cuadros(
+    directorio: &str,
+    primero: usize,
+    desde: usize,
+    resultado: &SalidaSimulacion,
+    opciones: &Opciones,
+) -> Result<usize, String> {
+    let simbolos = &opciones.simbolos;
+    let error = |_| {
+        opciones
+            .idioma
+            .formatear("escritura_cuadros", &[&directorio])
+    };
+    std::fs::create_dir_all(directorio).map_err(error)?;
+    let mut numero = primero;
+    for paso in desde..=resultado.cambios.len() {
+        let filas: Vec<String> = resultado
+            .filas_en(paso)
+            .iter()
+            .map(|fila| {
+                let casillas: Vec<String> = fila.iter().map(|c| simbolos.escribir(c)).collect();
+                casillas.join(" ")
+            })
+            .collect();
+        let ruta = std::path::Path::new(directorio).join(format!("frame_{:03}.txt", numero));
+        std::fs::write(ruta, filas.join("\n")).map_err(error)?;
+        numero += 1;
+    }
+    Ok(numero)
+}
+
+/// Lee un archivo de tablero de texto con las reglas y los simbolos de las opciones.
+fn leer_tablero(ruta: &str, opciones: &Opciones) -> Result<Tablero, BombermanError> {
+    if opciones.simbolos.es_identidad() {
+        return tablero_desde_archivo_con_reglas(ruta, &opciones.reglas);
+    }
+    let texto = std::fs::read_to_string(ruta).map_err(BombermanError::Apertura)?;
+    tablero_desde_texto_con_reglas(&opciones.simbolos.leer_texto(&texto), &opciones.reglas)
+}
+
+/// Escribe el archivo de `--diff`: una linea por casilla que cambio entre el tablero inicial y el final, de la forma
+/// `(x, y): B2 -> _` (ver `comparacion::diferencias`), con las casillas de siempre aunque se use `--simbolos`.
+fn escribir_diferencias(
+    ruta: &str,
+    inicial: &[Vec<String>],
+    final_: &[Vec<String>],
+    idioma: Idioma,
+) {
+    let texto: String = comparacion::diferencias(inicial, final_)
+        .iter()
+        .map(|diferencia| format!("{}\n", diferencia))
+        .collect();
+    if std::fs::write(ruta, texto).is_err() {
+        eprintln!("{}", idioma.formatear("escritura_diferencia", &[&ruta]));
+    }
+}
+
+/// Calcula el Resumen de una simulacion comparando el tablero inicial con el final.
+fn resumir(inicial: &[Vec<String>], final_: &[Vec<String>]) -> Resumen {
+    Resumen {
+        bombas_detonadas: contar_casillas(inicial, &['B', 'S'])
+            - contar_casillas(final_, &['B', 'S']),
+        enemigos_eliminados: contar_casillas(inicial, &['F']) - contar_casillas(final_, &['F']),
+        desenlace: if contar_casillas(final_, &['F', 'G']) == 0 {
+            Resultado::Victoria
+        } else {
+            Resultado::Parcial
+        },
+    }
+}
+
+/// Cuenta las casillas del tablero cuyo primer caracter es alguno de los tipos pedidos.
+fn contar_casillas(tablero: &[Vec<String>], tipos: &[char]) -> usize {
+    tablero
+        .iter()
+        .flatten()
+        .filter(|casilla| casilla.starts_with(tipos))
+        .count()
+}
+
+/// Las opciones de la linea de comandos, ya leidas (ver `Opciones::leer` y `Opciones::de_simulacion`).
+#[derive(Default)]
+struct Opciones {
+    /// Comando de `--al-terminar`.
+    al_terminar: Option<String>,
+    /// Directorio de `--cache`.
+    cache: Option<String>,
+    /// Valores de `--limite-alcance`, `--max-cadena`, `--max-pasos`, `--tiempo-maximo` y `--max-casillas`.
+    limites: Limites,
+    /// Variantes de reglas activadas con sus opciones (por ejemplo `--enemigos-bloquean`).
+    reglas: Reglas,
+    /// Si se paso `--con-advertencias`.
+    con_advertencias: bool,
+    /// Si se paso `--eventos`.
+    eventos: bool,
+    /// Si se paso `--modo-tutorial`.
+    modo_tutorial: bool,
+    /// Archivo de `--grabar-replay`.
+    grabar_replay: Option<String>,
+    /// Si se paso `--style emoji`.
+    emoji: bool,
+    /// Archivo de `--svg`.
+    svg: Option<String>,
+    /// Archivo de `--diff`.
+    diff: Option<String>,
+    /// Directorio de `--frames`.
+    frames: Option<String>,
+    /// Si se paso `--estadisticas-bombas`.
+    estadisticas_bombas: bool,
+    /// Si se paso `--resumen`.
+    resumen: bool,
+    /// Si se paso `--mapa-peligro`.
+    mapa_peligro: bool,
+    /// Si se paso `--recuperar`.
+    recuperar: bool,
+    /// Si se paso `--con-procedencia`.
+    con_procedencia: bool,
+    /// Si se paso `--codigo-salida`.
+    codigo_salida: bool,
+    /// Valor de `--lang`.
+    idioma: Idioma,
+    /// Valor de `--objetivo`, que tiene prioridad sobre el del tablero.
+    objetivo: Option<Objetivo>,
+    /// Cantidad de filas por linea de `--json-bloques`.
+    json_bloques: Option<usize>,
+    /// Si se paso `--format json`.
+    json: bool,
+    /// Si se paso `--format hex`.
+    hexagonal: bool,
+    /// Las casillas pedidas con `--inspeccionar`, con el paso de cada una.
+    inspecciones: Vec<(usize, Punto)>,
+    /// Las bombas de `--tambien`, que explotan en el mismo instante que la de las coordenadas.
+    simultaneas: Vec<Punto>,
+    /// Los simbolos de `--simbolos`, con los que se leen la entrada y se escribe el tablero final.
+    simbolos: Simbolos,
+}
+
+impl Opciones {
+    /// Indica si el resultado se escribe como texto, que es cuando se pueden usar la cache, la procedencia y los
+    /// bloques que se agregan al final.
+    fn salida_de_texto(&self) -> bool {
+        self.json_bloques.is_none() && !self.json
+    }
+
+    /// Indica si se puede usar la cache, que guarda la salida de texto con las casillas de siempre. Las salidas
+    /// aparte que necesitan la simulacion completa, como la repeticion o las inspecciones, no se guardan, asi que
+    /// con alguna de ellas siempre se simula.
+    fn usa_cache(&self) -> bool {
+        self.salida_de_texto()
+            && !self.emoji
+            && self.grabar_replay.is_none()
+            && self.inspecciones.is_empty()
+            && self.objetivo.is_none()
+            && self.frames.is_none()
+            && self.svg.is_none()
+            && !self.mapa_peligro
+    }
+
+    /// Arma las opciones de los subcomandos que leen tableros: las reglas, los simbolos, el idioma y los limites.
+    /// Primero se aplica el `--preset`, despues el archivo de `--rules` y al final las demas opciones de reglas y de
+    /// limites, asi que pisan lo que diga el archivo.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las Opciones o un Err(String) si no se pudo leer el archivo de `--rules` o el de `--simbolos`.
+    fn leer(args: &uso::ArgsReglas, limites: &uso::ArgsLimites) -> Result<Opciones, String> {
+        let mut opciones = Opciones {
+            reglas: args.preset.clone().unwrap_or_default(),
+            idioma: args.lang.unwrap_or_default(),
+            ..Opciones::default()
+        };
+        if let Some(ruta) = &args.rules {
+            let texto = std::fs::read_to_string(ruta)
+                .map_err(|_| opciones.idioma.formatear("lectura_reglas", &[ruta]))?;
+            archivo_reglas::aplicar(&texto, &mut opciones.reglas, &mut opciones.limites)
+                .map_err(|e| format!("{}: {}", ruta, e))?;
+        }
+        if let Some(ruta) = &args.simbolos {
+            let texto = std::fs::read_to_string(ruta)
+                .map_err(|_| opciones.idioma.formatear("lectura_simbolos", &[ruta]))?;
+            opciones.simbolos =
+                archivo_simbolos::parsear(&texto).map_err(|e| format!("{}: {}", ruta, e))?;
+        }
+        let reglas = &mut opciones.reglas;
+        reglas.enemigos_bloquean |= args.enemigos_bloquean;
+        reglas.ladrillos |= args.ladrillos;
+        reglas.mejoras |= args.mejoras;
+        reglas.toroidal |= args.toroidal;
+        reglas.golpes_por_onda = args.golpes_por_onda.or(reglas.golpes_por_onda);
+        reglas.alcance_maximo = args.alcance_maximo.or(reglas.alcance_maximo);
+        reglas.vida_maxima = args.vida_maxima.unwrap_or(reglas.vida_maxima);
+        opciones.limites = Limites {
+            alcance: limites.limite_alcance,
+            cadena: limites.max_cadena.or(opciones.limites.cadena),
+            pasos: limites.max_pasos,
+            casillas: limites.max_casillas,
+            tiempo: limites.tiempo_maximo.map(Duration::from_millis),
+        };
+        Ok(opciones)
+    }
+
+    /// Arma las opciones de una simulacion: las de `Opciones::leer` y las que cambian que se escribe y que se informa.
+    ///
+    /// # Devuelve
+    ///
+    /// Un Result con las Opciones o un Err(String) si no se pudo leer algun archivo o hay opciones que no se pueden
+    /// usar juntas.
+    fn de_simulacion(args: &uso::ArgsSimulacion) -> Result<Opciones, String> {
+        let base = Opciones::leer(&args.reglas, &args.limites)?;
+        let opciones = Opciones {
+            al_terminar: args.al_terminar.clone(),
+            cache: args.cache.clone(),
+            con_advertencias: args.con_advertencias,
+            eventos: args.eventos,
+            modo_tutorial: args.modo_tutorial,
+            grabar_replay: args.grabar_replay.clone(),
+            emoji: args.style == Some(uso::Estilo::Emoji),
+            svg: args.svg.clone(),
+            diff: args.diff.clone(),
+            frames: args.frames.clone(),
+            estadisticas_bombas: args.estadisticas_bombas,
+            resumen: args.resumen,
+            mapa_peligro: args.mapa_peligro,
+            recuperar: args.recuperar,
+            con_procedencia: args.con_procedencia,
+            codigo_salida: args.codigo_salida,
+            objetivo: args.objetivo.clone(),
+            json_bloques: args.json_bloques,
+            json: args.format == Some(uso::Formato::Json),
+            hexagonal: args.format == Some(uso::Formato::Hex),
+            inspecciones: args.inspeccionar.clone(),
+            simultaneas: args.tambien.clone(),
+            ..base
+        };
+        let conflicto = if opciones.json && opciones.json_bloques.is_some() {
+            Some("json_con_bloques")
+        } else if opciones.hexagonal && (opciones.mapa_peligro || opciones.grabar_replay.is_some())
+        {
+            Some("hexagonal_sin_analisis")
+        } else if !opciones.salida_de_texto() && !opciones.simbolos.es_identidad() {
+            Some("simbolos_sin_texto")
+        } else if opciones.emoji
+            && !(opciones.salida_de_texto() && opciones.simbolos.es_identidad())
+        {
+            Some("emoji_sin_texto")
+        } else if !opciones.simultaneas.is_empty() && opciones.grabar_replay.is_some() {
+            Some("repeticion_con_tambien")
+        } else {
+            None
+        };
+        match conflicto {
+            Some(clave) => Err(opciones.idioma.texto(clave).to_string()),
+            None => Ok(opciones),
+        }
+    }
+}
+
+/// Observador de `--eventos`, que imprime cada evento por stderr a medida que ocurre.
+struct ImpresorEventos;
+
+impl Observador for ImpresorEventos {
+    fn notificar(&mut self, evento: &Evento) {
+        eprintln!("{}", evento);
+    }
+}
+
+/// Ejecuta en una shell el comando pedido por el usuario al terminar la simulacion. Los valores van en variables de
+/// entorno y los marcadores del comando se reemplazan por esas variables, nunca por los valores mismos, para que una
+/// ruta con espacios o con caracteres de la shell no cambie el comando.
+///
+/// # Ejemplos
+///
+/// ```text
+/// $ cargo run entrada.txt salidas 0 0 --al-terminar "notify-send {resultado} {salida}"
+/// $ cargo run entrada.txt salidas 0 0 --al-terminar 'notify-send "$RESULTADO" "$SALIDA"'
+/// ```
+///
+/// # Argumentos
+///
+/// * comando: El comando, con los marcadores `{salida}` (ruta del archivo de output, en `SALIDA`), `{resultado}`
+///   (`exito` o `error`, en `RESULTADO`), `{bombas}` (bombas detonadas, en `BOMBAS`) y `{enemigos}` (enemigos
+///   eliminados, en `ENEMIGOS`). Si hubo un error las cantidades valen 0. Los marcadores no van entre comillas: ya se
+///   reemplazan por la variable entre comillas.
+/// * ruta_salida: La ruta del archivo de output que se escribio.
+/// * resultado: El resultado de la simulacion.
+fn ejecutar_al_terminar(
+    comando: &str,
+    ruta_salida: &str,
+    resultado: &Result<Resumen, String>,
+    idioma: Idioma,
+) {
+    let (estado, bombas, enemigos) = match resultado {
+        Ok(r) => ("exito", r.bombas_detonadas, r.enemigos_eliminados),
+        Err(_) => ("error", 0, 0),
+    };
+    let variables = [
+        ("salida", "SALIDA", ruta_salida.to_string()),
+        ("resultado", "RESULTADO", estado.to_string()),
+        ("bombas", "BOMBAS", bombas.to_string()),
+        ("enemigos", "ENEMIGOS", enemigos.to_string()),
+    ];
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut comando = comando.to_string();
+    for (marcador, variable, _) in &variables {
+        let referencia = match cfg!(windows) {
+            true => format!("\"%{}%\"", variable),
+            false => format!("\"${}\"", variable),
+        };
+        comando = comando.replace(&format!("{{{}}}", marcador), &referencia);
+    }
+    let mut proceso = Command::new(shell);
+    proceso.arg(flag).arg(&comando);
+    for (_, variable, valor) in &variables {
+        proceso.env(variable, valor);
+    }
+    match proceso.status() {
+        Ok(estado) if !estado.success() => {
+            eprintln!("{}", idioma.formatear("al_terminar_fallo", &[&estado]))
+        }
+        Err(_) => eprintln!("{}", idioma.texto("al_terminar_no_ejecutado")),
+        _ => {}
+    }
+}
+
+/// El codigo de salida de `--codigo-salida`: 0 con una Victoria, 3 si quedaron enemigos (Parcial) y 1 si la
+/// simulacion no se pudo hacer. El 2 sigue siendo el de los argumentos incorrectos.
+fn codigo_de_salida(resultado: &Result<Resumen, String>) -> i32 {
+    match resultado {
+        Ok(Resumen {
+            desenlace: Resultado::Victoria,
+            ..
+        }) => 0,
+        Ok(_) => 3,
+        Err(_) => 1,
+    }
+}
+
+/// Busca en los comentarios del archivo de entrada una linea de la forma `# clave: valor` y devuelve el valor.
+///
+/// # Ejemplos
+///
+/// ```
+/// // entrada.txt empieza con "# objetivo: destruir_rocas"
+/// assert_eq!(leer_metadato("entrada.txt", "objetivo"), Some("destruir_rocas".to_string()));
+/// ```
+fn leer_metadato(ruta: &str, clave: &str) -> Option<String> {
+    let archivo = File::open(ruta).ok()?;
+    io::BufReader::new(archivo)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|linea| {
+            let (nombre, valor) = linea.strip_prefix('#')?.split_once(':')?;
+            (nombre.trim() == clave).then(|| valor.trim().to_string())
+        })
+        .next()
+}
+
+/// Toma un String con una descripcion de un error y lo escribe en el archivo.
+///
+/// # Ejemplos
+///
+/// ```
+/// let salida: File = File::create("ruta/de/salida.txt").unwrap();
+/// match result_casual {
+///     Ok() => {},
+///     Err(e: String) => devolver_error(e, salida, &opciones)
+/// }
+/// ```
+///
+/// # Argumentos
+///
+/// * error_string: La cadena que se quiere escribir en el archivo para informar el error.
+/// * salida: El archivo donde se quiere escribir.
+/// * opciones: Con `--format json` el error se escribe como `{"error":"..."}`.
+///
+/// # Devuelve
+///
+/// El mismo String del error, para poder seguir propagandolo.
+fn devolver_error(error_string: String, mut salida: File, opciones: &Opciones) -> String {
+    let texto = match opciones.json {
+        true => format!(
+            "{}\n",
+            Json::Objeto(vec![(
+                "error".to_string(),
+                Json::Texto(error_string.clone())
+            )])
+        ),
+        false => error_string.clone(),
+    };
+    if let Err(_e) = salida.write(texto.as_bytes()) { // Error en la escritura
+    }
+    error_string
+}
+
+/// Muestra por stderr el detalle del error, que no se escribe en la salida para no cambiar su texto, y devuelve ese texto,
+/// los dos en el idioma de `--lang`.
+fn informar_error(error: BombermanError, idioma: Idioma) -> String {
+    let mensaje = error.mensaje(idioma);
+    if let Some(detalle) = error.detalle_en(idioma) {
+        eprintln!("{} ({})", mensaje, detalle);
+    }
+    mensaje.to_string()
+}
+
+/// Si el error es el de un archivo de tablero con casillas o filas invalidas, vuelve a leerlo y junta todos sus
+/// problemas, no solo el primero. Para cualquier otro error, o si el archivo ya no se puede leer, no devuelve ninguno.
+/// Con `--simbolos`, las casillas de los problemas estan escritas con los simbolos de siempre.
+fn problemas_de_lectura(
+    error: &BombermanError,
+    ruta: &str,
+    opciones: &Opciones,
+) -> Vec<Diagnostico> {
+    match error {
+        BombermanError::ArchivoInvalido { .. }
+        | BombermanError::FilaIrregular { .. }
+        | BombermanError::PortalSinPareja { .. } => std::fs::read_to_string(ruta)
+            .map(|texto| {
+                diagnostico::diagnosticar(&opciones.simbolos.leer_texto(&texto), &opciones.reglas)
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Arma el mensaje de un tablero invalido con un problema por linea, como el de `uso::revisar_simulacion`.
+///
+/// # Ejemplos
+///
+/// ```text
+/// Error: archivo de entrada invalido: hay 2 problemas en el tablero:
+///   - linea 1, columna 2 (en el texto 1:4): X no es una casilla
+///   - linea 2, columna 1 (en el texto 2:1): F300 tiene 300 de vida y la maxima es 255
+/// ```
+fn listar_problemas(error: &BombermanError, problemas: &[Diagnostico]) -> String {
+    let mut texto = match problemas.len() {
+        1 => format!("{}: hay 1 problema en el tablero:", error),
+        cantidad => format!("{}: hay {} problemas en el tablero:", error, cantidad),
+    };
+    for problema in problemas {
+        texto.push_str(&format!("\n  - {}", problema));
+    }
+    texto
+}
+
+/// Arma el punto de las coordenadas pedidas si esta dentro del tablero, que puede no ser cuadrado.
+///
+/// # Ejemplos
+///
+/// ```
+/// let punto_bomba: Punto;
+/// match punto_en_tablero(&tablero, Columna(x), Fila(y)) {
+///     Err(e) => {return devolver_error(e, salida, &opciones)},
+///     Ok(pt) => punto_bomba = pt
+/// }
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con el Punto o un Error con un String describiendo el mismo. El detalle va por stderr, para que el error
+/// en la salida siga siendo el de siempre.
+fn punto_en_tablero(
+    tablero: &Tablero,
+    columna: Columna,
+    fila: Fila,
+    idioma: Idioma,
+) -> Result<Punto, String> {
+    let punto_bomba = Punto::new(columna, fila);
+    if !tablero.en_rango(punto_bomba) {
+        eprintln!(
+            "{}",
+            idioma.formatear(
+                "fuera_del_tablero",
+                &[&columna, &fila, &tablero.ancho(), &tablero.alto()]
+            )
+        );
+        return Err(idioma.texto("coordenadas_invalidas").to_string());
+    }
+    Ok(punto_bomba)
+}
+
+/// Toma el texto del resultado (el tablero final, y ocasionalmente su bloque de advertencias) y lo escribe en el archivo pedido.
+///
+/// # Ejemplos
+///
+/// ```
+/// escribir_texto_en_archivo(&tablero.to_texto(), archivo_a_escribir)
+/// ```
+///
+/// # Argumentos
+///
+/// * texto: El texto que se quiere escribir en el archivo.
+/// * salida: Archivo destino en el que se quiere escribir.
+fn escribir_texto_en_archivo(texto: &str, mut salida: File) {
+    if let Err(_e) = salida.write(texto.as_bytes()) { // Error en la escritura
+    }
+}
+
+/// Arma el encabezado que se agrega antes del tablero con `--con-procedencia`, para poder saber despues como se
+/// produjo un archivo de salida. Cada linea empieza con `# `, igual que las advertencias.
+///
+/// # Ejemplos
+///
+/// ```
+/// # entrada: entradas/entrada.txt
+/// # coordenadas: 0 0
+/// # reglas: clasico
+/// # version: 0.1.0
+/// # tablero: 3f1a9c0b2d4e5f60
+/// _ R R _ _ _ _
+/// ```
+fn texto_procedencia(
+    ruta_entrada: &str,
+    punto: &Punto,
+    reglas: &Reglas,
+    tablero: &Tablero,
+) -> String {
+    let nombre_reglas = Reglas::PRESETS
+        .iter()
+        .find(|nombre| Reglas::preset(nombre).as_ref() == Some(reglas))
+        .map(|nombre| nombre.to_string())
+        .unwrap_or_else(|| format!("{:?}", reglas));
+    format!(
+        "# entrada: {}\n# coordenadas: {} {}\n# reglas: {}\n# version: {}\n# tablero: {:016x}\n",
+        ruta_entrada,
+        punto.x,
+        punto.y,
+        nombre_reglas,
+        env!("CARGO_PKG_VERSION"),
+        cache::hash_tablero(&tablero.filas())
+    )
+}
+
+/// Arma el bloque que se agrega despues del tablero con `--con-advertencias`: una linea en blanco y una linea
+/// por advertencia, cada una empezando con `# ` para distinguirlas de las filas del tablero.
+///
+/// # Ejemplos
+///
+/// ```
+/// _ _ _
+/// _ _ _
+///
+/// # Advertencia: el alcance 9 de la bomba en (0, 2) se recorto a 3
+/// ```
+fn texto_advertencias(advertencias: &[Advertencia]) -> String {
+    let mut texto = String::new();
+    if !advertencias.is_empty() {
+        texto.push('\n');
+    }
+    for advertencia in advertencias {
+        texto += &format!("\n# {}", advertencia);
+    }
+    texto
+}
+
+/// Arma el bloque que se agrega despues del tablero con `--estadisticas-bombas`: una linea en blanco y una tabla con
+/// una fila por bomba detonada, en el orden en que explotaron, con las casillas por las que pasaron sus rafagas, los
+/// golpes a enemigos, las bombas que hizo explotar y el largo de la rafaga de cada direccion (las 6 de un tablero
+/// hexagonal con `--format hex`).
+///
+/// # Ejemplos
+///
+/// ```
+/// _ _ _
+/// _ _ _
+///
+/// # Bomba     Tipo  Casillas  Enemigos  Bombas  L  U  R  D
+/// # (0, 0)    B            4         2       1  0  0  2  2
+/// ```
+fn texto_estadisticas(estadisticas: &[EstadisticaBomba]) -> String {
+    let direcciones: Vec<String> = match estadisticas.first() {
+        Some(estadistica) => estadistica
+            .rafagas
+            .iter()
+            .map(|(direccion, _)| format!("{:>3}", direccion.to_string()))
+            .collect(),
+        None => Direccion::todas()
+            .map(|direccion| format!("{:>3}", direccion.to_string()))
+            .collect(),
+    };
+    let mut texto = format!(
+        "\n\n# Bomba     Tipo  Casillas  Enemigos  Bombas{}",
+        direcciones.concat()
+    );
+    for estadistica in estadisticas {
+        let punto = format!("({}, {})", estadistica.punto.x, estadistica.punto.y);
+        let rafagas: Vec<String> = estadistica
+            .rafagas
+            .iter()
+            .map(|(_, largo)| format!("{:>3}", largo))
+            .collect();
+        texto += &format!(
+            "\n# {:<10}{:<4}{:>10}{:>10}{:>8}{}",
+            punto,
+            estadistica.tipo,
+            estadistica.casillas_afectadas,
+            estadistica.enemigos_daniados,
+            estadistica.bombas_disparadas,
+            rafagas.concat()
+        );
+    }
+    texto
+}
+
+/// Arma el bloque que se agrega despues del tablero con `--resumen`: una linea en blanco y los numeros de
+/// `SalidaSimulacion::resumen`, uno por linea.
+///
+/// # Ejemplos
+///
+/// ```
+/// _ _ _
+/// _ _ _
+///
+/// # Bombas detonadas: 2
+/// # Enemigos eliminados: 1
+/// # Enemigos daniados: 2
+/// # Rocas atravesadas: 1
+/// # Casillas alcanzadas: 6
+/// # Profundidad de la cadena: 2
+/// ```
+fn texto_resumen(resumen: &ResumenSimulacion) -> String {
+    format!(
+        "\n\n# Bombas detonadas: {}\n# Enemigos eliminados: {}\n# Enemigos daniados: {}\n# Rocas atravesadas: {}\n\
+         # Casillas alcanzadas: {}\n# Profundidad de la cadena: {}",
+        resumen.bombas_detonadas,
+        resumen.enemigos_eliminados,
+        resumen.enemigos_daniados,
+        resumen.rocas_atravesadas,
+        resumen.casillas_alcanzadas,
+        resumen.profundidad_cadena
+    )
+}
+
+/// Arma el bloque que se agrega despues del tablero con `--modo-tutorial`: una linea en blanco y la explicacion
+/// de `tutorial::explicar`, con cada linea empezando con `# ` igual que las advertencias.
+///
+/// # Ejemplos
+///
+/// ```
+/// _ _ _
+/// _ _ _
+///
+/// # Detonacion
+/// #   Al explotar, una bomba se vacia y lanza una rafaga en cada una de las 4 direcciones, ...
+/// #   Casillas: (0, 0)
+/// ```
+fn texto_tutorial(eventos: &[Evento]) -> String {
+    let mut texto = String::from("\n");
+    for linea in tutorial::explicar(eventos).lines() {
+        texto += format!("\n# {}", linea).trim_end();
     }
+    texto
 }
 
 /// Toma una ruta que puede contener directorios y extrae el nombre del archivo destino.
@@ -290,8 +2784,8 @@ fn escribir_tablero_final_en_archivo(tablero: Vec<Vec<String>>, mut salida: File
 ///
 /// Un String con el nombre del archivo destino de la ruta provista o, en caso de error, la misma ruta provista.
 fn extraer_archivo_destino(ruta: &String) -> String {
-    let separado = ruta.split('/');
-    match separado.last() {
+    let mut separado = ruta.split('/');
+    match separado.next_back() {
         Some(r) => r.to_string(),
         None => ruta.to_string(),
     }
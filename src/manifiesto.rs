@@ -0,0 +1,280 @@
+use std::fs;
+use std::path::Path;
+
+use tp1_bomberman::bomberman::prelude::*;
+use tp1_bomberman::tablero_desde_archivo;
+
+use crate::toml::{self, Clave, Tabla, Valor};
+
+/// Nombre con el que `manifiesto init` escribe el manifiesto.
+pub const NOMBRE: &str = "casos.toml";
+
+/// Claves que acepta cada `[[caso]]`.
+const CLAVES: &[&str] = &["entrada", "x", "y", "preset", "esperado"];
+
+/// Un caso del manifiesto: que entrada simular, donde detonar, con que reglas y, si se quiere corregir, contra que
+/// salida comparar el resultado. Las rutas son relativas al directorio del manifiesto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caso {
+    pub entrada: String,
+    pub x: usize,
+    pub y: usize,
+    /// Nombre de un preset de `Reglas::preset`; por defecto `clasico`.
+    pub preset: String,
+    pub esperado: Option<String>,
+    /// La linea del `[[caso]]` en el manifiesto, para los mensajes de error.
+    pub linea: usize,
+}
+
+/// Lee un manifiesto de casos: un documento del subconjunto de TOML de `toml::parsear` con una tabla `[[caso]]` por
+/// caso.
+///
+/// # Ejemplos
+///
+/// ```
+/// let casos = parsear("[[caso]]\nentrada = \"entradas/a.txt\"\nx = 0\ny = 2\n")?;
+/// assert_eq!(casos[0].preset, "clasico");
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con los casos en el orden del archivo o un Err(String) con la linea y la causa del primer error: una
+/// linea que no se entiende, una clave desconocida, repetida o del tipo equivocado, un preset que no existe o un caso
+/// al que le falta la entrada o alguna coordenada.
+pub fn parsear(texto: &str) -> Result<Vec<Caso>, String> {
+    let mut casos = Vec::new();
+    for tabla in toml::parsear(texto)? {
+        if tabla.encabezado.is_empty() {
+            if let Some(clave) = tabla.claves.first() {
+                return Err(format!(
+                    "Error: linea {}: {} esta fuera de un [[caso]]",
+                    clave.linea, clave.nombre
+                ));
+            }
+            continue;
+        }
+        if tabla.encabezado != "[[caso]]" {
+            return Err(format!(
+                "Error: linea {}: tabla desconocida {} (se acepta [[caso]])",
+                tabla.linea, tabla.encabezado
+            ));
+        }
+        casos.push(armar_caso(&tabla)?);
+    }
+    Ok(casos)
+}
+
+/// Arma un Caso con las claves de un `[[caso]]`, revisando que esten las obligatorias y que cada una tenga su tipo.
+fn armar_caso(tabla: &Tabla) -> Result<Caso, String> {
+    let mut caso = Caso {
+        entrada: String::new(),
+        x: 0,
+        y: 0,
+        preset: "clasico".to_string(),
+        esperado: None,
+        linea: tabla.linea,
+    };
+    for Clave {
+        nombre,
+        valor,
+        linea,
+    } in &tabla.claves
+    {
+        if !CLAVES.contains(&nombre.as_str()) {
+            return Err(format!(
+                "Error: linea {}: clave desconocida {} (se aceptan {})",
+                linea,
+                nombre,
+                CLAVES.join(", ")
+            ));
+        }
+        let error =
+            |tipo: &str| format!("Error: linea {}: {} tiene que ser {}", linea, nombre, tipo);
+        match (nombre.as_str(), valor) {
+            ("entrada", Valor::Texto(texto)) => caso.entrada = texto.clone(),
+            ("esperado", Valor::Texto(texto)) => caso.esperado = Some(texto.clone()),
+            ("preset", Valor::Texto(texto)) => {
+                if Reglas::preset(texto).is_none() {
+                    return Err(format!(
+                        "Error: linea {}: preset desconocido {} (se aceptan {})",
+                        linea,
+                        texto,
+                        Reglas::PRESETS.join(", ")
+                    ));
+                }
+                caso.preset = texto.clone();
+            }
+            ("x", Valor::Numero(x)) => caso.x = *x,
+            ("y", Valor::Numero(y)) => caso.y = *y,
+            ("x" | "y", _) => return Err(error("un numero natural")),
+            _ => return Err(error("un texto entre comillas")),
+        }
+    }
+    for obligatoria in ["entrada", "x", "y"] {
+        if tabla.clave(obligatoria).is_none() {
+            return Err(format!(
+                "Error: linea {}: al caso le falta {}",
+                tabla.linea, obligatoria
+            ));
+        }
+    }
+    Ok(caso)
+}
+
+/// Escribe los casos con el formato que lee `parsear`.
+pub fn escribir(casos: &[Caso]) -> String {
+    let mut texto = String::new();
+    for caso in casos {
+        if !texto.is_empty() {
+            texto.push('\n');
+        }
+        texto += &format!(
+            "[[caso]]\nentrada = {}\nx = {}\ny = {}\npreset = {}\n",
+            toml::texto(&caso.entrada),
+            caso.x,
+            caso.y,
+            toml::texto(&caso.preset)
+        );
+        if let Some(esperado) = &caso.esperado {
+            texto += &format!("esperado = {}\n", toml::texto(esperado));
+        }
+    }
+    texto
+}
+
+/// Revisa que existan los archivos de entrada y de salida esperada de cada caso.
+///
+/// # Argumentos
+///
+/// * casos: Los casos del manifiesto.
+/// * base: El directorio del manifiesto, desde el que se resuelven las rutas.
+///
+/// # Devuelve
+///
+/// Un error por cada archivo que falta, con la linea de su caso.
+pub fn archivos_faltantes(casos: &[Caso], base: &Path) -> Vec<String> {
+    let mut errores = Vec::new();
+    for caso in casos {
+        let rutas = std::iter::once(("la entrada", &caso.entrada)).chain(
+            caso.esperado
+                .iter()
+                .map(|esperado| ("la salida esperada", esperado)),
+        );
+        for (que, ruta) in rutas {
+            if !base.join(ruta).is_file() {
+                errores.push(format!(
+                    "Error: linea {}: no existe {} {}",
+                    caso.linea, que, ruta
+                ));
+            }
+        }
+    }
+    errores
+}
+
+/// Arma un caso por cada archivo del directorio de entradas, para empezar un manifiesto sin escribirlo a mano.
+/// Cada caso detona la primera bomba del tablero (o (0, 0) si no tiene o no se puede leer) con las reglas clasicas,
+/// y usa como salida esperada el archivo del mismo nombre en el directorio de salidas, si se paso y existe.
+///
+/// # Ejemplos
+///
+/// ```
+/// let casos = casos_de_directorio("entradas", Some("salidas"))?;
+/// // entrada = "entradas/entrada.txt", esperado = "salidas/entrada.txt"
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con los casos ordenados por nombre de archivo o un Err(String) si no se pudo leer el directorio.
+pub fn casos_de_directorio(entradas: &str, salidas: Option<&str>) -> Result<Vec<Caso>, String> {
+    let mut nombres: Vec<String> = fs::read_dir(entradas)
+        .map_err(|_| format!("Error: no se pudo leer el directorio {}", entradas))?
+        .filter_map(|entrada| entrada.ok())
+        .filter(|entrada| entrada.path().is_file())
+        .map(|entrada| entrada.file_name().to_string_lossy().into_owned())
+        .filter(|nombre| nombre != NOMBRE)
+        .collect();
+    nombres.sort();
+    let casos = nombres
+        .iter()
+        .map(|nombre| {
+            let entrada = format!("{}/{}", entradas.trim_end_matches('/'), nombre);
+            let bomba = tablero_desde_archivo(&entrada).ok().and_then(|tablero| {
+                tablero
+                    .celdas()
+                    .find(|(_, celda)| matches!(celda, Celda::Bomba(_)))
+                    .map(|(punto, _)| punto)
+            });
+            let Punto { x, y } = bomba.unwrap_or(Punto { x: 0, y: 0 });
+            let esperado = salidas
+                .map(|salidas| format!("{}/{}", salidas.trim_end_matches('/'), nombre))
+                .filter(|esperado| Path::new(esperado).is_file());
+            Caso {
+                entrada,
+                x,
+                y,
+                preset: "clasico".to_string(),
+                esperado,
+                // Todavia no se escribio en ningun manifiesto.
+                linea: 0,
+            }
+        })
+        .collect();
+    Ok(casos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escribir, parsear};
+
+    #[test]
+    fn test01_lee_los_casos() {
+        let texto = "# pruebas\n[[caso]]\nentrada = \"entradas/a.txt\"\nx = 1\ny = 2 \n\n[[caso]]\n\
+                     entrada = \"b \\\"c\\\".txt\" # con comillas\ny = 0\nx = 3\npreset = \"arcade\"\n\
+                     esperado = \"salidas/b.txt\"\n";
+        let casos = parsear(texto).unwrap();
+        assert_eq!(casos.len(), 2);
+        assert_eq!(
+            (
+                casos[0].entrada.as_str(),
+                casos[0].x,
+                casos[0].y,
+                casos[0].preset.as_str()
+            ),
+            ("entradas/a.txt", 1, 2, "clasico")
+        );
+        assert_eq!(casos[1].entrada, "b \"c\".txt");
+        assert_eq!(casos[1].esperado.as_deref(), Some("salidas/b.txt"));
+        assert_eq!(casos[1].linea, 7);
+        let releidos = parsear(&escribir(&casos)).unwrap();
+        assert_eq!(
+            (releidos[1].linea, &releidos[1].entrada),
+            (7, &casos[1].entrada)
+        );
+        assert_eq!(releidos[1].esperado, casos[1].esperado);
+    }
+
+    #[test]
+    fn test02_errores_con_su_linea() {
+        let errores = [
+            ("x = 1", "Error: linea 1: x esta fuera de un [[caso]]"),
+            ("[caso]", "Error: linea 1: tabla desconocida [caso] (se acepta [[caso]])"),
+            (
+                "[[caso]]\nentarda = \"a\"",
+                "Error: linea 2: clave desconocida entarda (se aceptan entrada, x, y, preset, esperado)",
+            ),
+            ("[[caso]]\nx = 1\nx = 2", "Error: linea 3: clave repetida x"),
+            ("[[caso]]\nx = \"1\"", "Error: linea 2: x tiene que ser un numero natural"),
+            ("[[caso]]\nentrada = 3", "Error: linea 2: entrada tiene que ser un texto entre comillas"),
+            ("[[caso]]\nentrada = \"a", "Error: linea 2: entrada: falta cerrar las comillas"),
+            (
+                "[[caso]]\nentrada = \"a\"\nx = 0\ny = 0\npreset = \"moderno\"",
+                "Error: linea 5: preset desconocido moderno (se aceptan clasico, arcade)",
+            ),
+            ("\n[[caso]]\nentrada = \"a\"\nx = 0", "Error: linea 2: al caso le falta y"),
+        ];
+        for (texto, error) in errores {
+            assert_eq!(parsear(texto), Err(error.to_string()), "{}", texto);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Component, Path};
+
+/// Primera linea de todo paquete, con la version del formato.
+const ENCABEZADO: &str = "paquete-bomberman 1";
+
+/// Un archivo dentro de un paquete: su ruta relativa y su contenido.
+#[derive(Debug, PartialEq)]
+pub struct Entrada {
+    pub ruta: String,
+    pub contenido: String,
+}
+
+/// Junta varios archivos en el texto de un unico paquete.
+///
+/// El formato es el encabezado y, por cada archivo, una linea `archivo <bytes> <ruta>` seguida del contenido tal cual
+/// y un salto de linea. Guardar la cantidad de bytes permite que los contenidos tengan cualquier texto.
+///
+/// # Ejemplos
+///
+/// ```
+/// let texto = empaquetar(&entradas);
+/// // paquete-bomberman 1
+/// // archivo 6 entradas/a.txt
+/// // B1 _ ...
+/// ```
+///
+/// # Argumentos
+///
+/// * entradas: Los archivos a empaquetar.
+///
+/// # Devuelve
+///
+/// Un String con el paquete.
+pub fn empaquetar(entradas: &[Entrada]) -> String {
+    let mut texto = format!("{}\n", ENCABEZADO);
+    for entrada in entradas {
+        texto += &format!(
+            "archivo {} {}\n{}\n",
+            entrada.contenido.len(),
+            entrada.ruta,
+            entrada.contenido
+        );
+    }
+    texto
+}
+
+/// Separa el texto de un paquete en los archivos que contiene.
+///
+/// # Argumentos
+///
+/// * texto: El contenido del paquete.
+///
+/// # Devuelve
+///
+/// Un Result con las Entradas o un Err(String) si el paquete esta truncado, no tiene el formato esperado o
+/// contiene una ruta que saldria del directorio de destino.
+pub fn desempaquetar(texto: &str) -> Result<Vec<Entrada>, String> {
+    let mut resto = texto
+        .strip_prefix(ENCABEZADO)
+        .and_then(|r| r.strip_prefix('\n'))
+        .ok_or_else(|| "Error: el archivo no es un paquete valido".to_string())?;
+    let mut entradas = Vec::new();
+    while !resto.is_empty() {
+        let (linea, siguiente) = resto
+            .split_once('\n')
+            .ok_or_else(|| "Error: paquete truncado".to_string())?;
+        let (bytes, ruta) = match linea
+            .strip_prefix("archivo ")
+            .and_then(|l| l.split_once(' '))
+        {
+            Some((bytes, ruta)) => (bytes, ruta),
+            None => return Err(format!("Error: linea invalida en el paquete: {}", linea)),
+        };
+        let bytes: usize = bytes
+            .parse()
+            .map_err(|_| format!("Error: linea invalida en el paquete: {}", linea))?;
+        if !es_ruta_segura(ruta) {
+            return Err(format!("Error: ruta no permitida en el paquete: {}", ruta));
+        }
+        let contenido = siguiente
+            .get(..bytes)
+            .ok_or_else(|| "Error: paquete truncado".to_string())?;
+        resto = siguiente[bytes..]
+            .strip_prefix('\n')
+            .ok_or_else(|| "Error: paquete truncado".to_string())?;
+        entradas.push(Entrada {
+            ruta: ruta.to_string(),
+            contenido: contenido.to_string(),
+        });
+    }
+    Ok(entradas)
+}
+
+/// Una ruta es segura si es relativa y no sube de directorio, para que desempaquetar no escriba fuera del destino.
+fn es_ruta_segura(ruta: &str) -> bool {
+    !ruta.is_empty()
+        && Path::new(ruta)
+            .components()
+            .all(|componente| matches!(componente, Component::Normal(_)))
+}
+
+/// Lee los archivos pedidos como Entradas de un paquete. Los directorios se recorren completos y en orden alfabetico.
+///
+/// # Argumentos
+///
+/// * rutas: Archivos o directorios relativos al directorio actual.
+///
+/// # Devuelve
+///
+/// Un Result con las Entradas o un Err(String) con la ruta que no se pudo leer.
+pub fn leer_entradas(rutas: &[String]) -> Result<Vec<Entrada>, String> {
+    let mut entradas = Vec::new();
+    for ruta in rutas {
+        agregar_ruta(Path::new(ruta), &mut entradas)?;
+    }
+    Ok(entradas)
+}
+
+fn agregar_ruta(ruta: &Path, entradas: &mut Vec<Entrada>) -> Result<(), String> {
+    let error = || format!("Error: no se pudo leer {}", ruta.display());
+    if ruta.is_dir() {
+        let mut hijos: Vec<_> = fs::read_dir(ruta)
+            .map_err(|_| error())?
+            .filter_map(|hijo| hijo.ok().map(|h| h.path()))
+            .collect();
+        hijos.sort();
+        for hijo in hijos {
+            agregar_ruta(&hijo, entradas)?;
+        }
+        return Ok(());
+    }
+    let texto_ruta = ruta.to_string_lossy().replace('\\', "/");
+    if !es_ruta_segura(&texto_ruta) {
+        return Err(format!(
+            "Error: ruta no permitida en el paquete: {}",
+            texto_ruta
+        ));
+    }
+    entradas.push(Entrada {
+        ruta: texto_ruta,
+        contenido: fs::read_to_string(ruta).map_err(|_| error())?,
+    });
+    Ok(())
+}
+
+/// Escribe las Entradas de un paquete dentro del directorio pedido, creando los subdirectorios que hagan falta.
+///
+/// # Devuelve
+///
+/// Un Result vacio o un Err(String) con el archivo que no se pudo escribir.
+pub fn escribir_entradas(destino: &str, entradas: &[Entrada]) -> Result<(), String> {
+    for entrada in entradas {
+        let ruta = Path::new(destino).join(&entrada.ruta);
+        let escrito = match ruta.parent() {
+            Some(padre) => fs::create_dir_all(padre),
+            None => Ok(()),
+        }
+        .and_then(|_| fs::write(&ruta, &entrada.contenido));
+        if escrito.is_err() {
+            return Err(format!("Error: no se pudo escribir {}", ruta.display()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{desempaquetar, empaquetar, Entrada};
+
+    fn entrada(ruta: &str, contenido: &str) -> Entrada {
+        Entrada {
+            ruta: ruta.to_string(),
+            contenido: contenido.to_string(),
+        }
+    }
+
+    #[test]
+    fn test01_desempaquetar_devuelve_lo_empaquetado() {
+        let entradas = vec![
+            entrada("entradas/a.txt", "B1 _\n_ F1\n"),
+            entrada("esperados/a.txt", "archivo 3 falso\n_ _\n_ _"),
+            entrada("vacio.txt", ""),
+        ];
+        assert_eq!(desempaquetar(&empaquetar(&entradas)), Ok(entradas));
+    }
+
+    #[test]
+    fn test02_rechaza_rutas_fuera_del_destino() {
+        let paquete = empaquetar(&[entrada("../fuera.txt", "x")]);
+        assert!(desempaquetar(&paquete).is_err());
+        let paquete = empaquetar(&[entrada("/tmp/fuera.txt", "x")]);
+        assert!(desempaquetar(&paquete).is_err());
+    }
+
+    #[test]
+    fn test03_rechaza_paquetes_truncados_o_invalidos() {
+        let paquete = empaquetar(&[entrada("a.txt", "B1 _ _")]);
+        assert!(desempaquetar(&paquete[..paquete.len() - 3]).is_err());
+        assert!(desempaquetar("B1 _ _\n").is_err());
+    }
+}
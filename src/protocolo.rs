@@ -0,0 +1,330 @@
+use std::io::{self, BufRead, Read, Write};
+use std::time::Duration;
+
+use tp1_bomberman::bomberman::io::{escribir_resultado_json, tablero_desde_documento_json};
+use tp1_bomberman::bomberman::json::{self, Json};
+use tp1_bomberman::bomberman::prelude::*;
+
+use crate::cache;
+
+/// El largo maximo en bytes de la linea de un pedido, sin el salto de linea. Una linea mas larga no se guarda entera:
+/// se descarta hasta el salto de linea y se responde con el codigo 413.
+pub const LARGO_MAXIMO: usize = 1 << 20;
+
+/// Las casillas que puede tener por defecto el tablero de un pedido.
+pub const CASILLAS: usize = 250_000;
+/// Las casillas que pueden recorrer por defecto las rafagas de un pedido.
+pub const PASOS: usize = 10_000_000;
+/// Lo que puede durar por defecto la simulacion de un pedido.
+pub const TIEMPO: Duration = Duration::from_secs(5);
+
+/// Los limites de cada pedido cuando la linea de comandos no da otros: los pedidos pueden venir de cualquier lado, asi
+/// que ninguno tiene que poder ocupar el proceso sin fin. Los que se dieron en la linea de comandos quedan como estan.
+///
+/// # Ejemplos
+///
+/// ```
+/// let limites = protocolo::limites(&Limites { pasos: Some(10), ..Limites::default() });
+/// assert_eq!(limites.pasos, Some(10));
+/// assert_eq!(limites.casillas, Some(protocolo::CASILLAS));
+/// ```
+pub fn limites(pedidos: &Limites) -> Limites {
+    Limites {
+        casillas: pedidos.casillas.or(Some(CASILLAS)),
+        pasos: pedidos.pasos.or(Some(PASOS)),
+        tiempo: pedidos.tiempo.or(Some(TIEMPO)),
+        ..pedidos.clone()
+    }
+}
+
+/// Atiende pedidos de simulacion de a uno por linea hasta que se termina la entrada, y responde cada uno en una linea
+/// de la salida, sin abrir archivos ni volver a arrancar el proceso entre pedidos.
+///
+/// Un pedido es un objeto JSON con el tablero y los metadatos de `--format json` (ver `filas_desde_json`), las
+/// coordenadas de la bomba en `x` e `y` y opcionalmente un `id` de texto. La respuesta es el resultado de
+/// `escribir_resultado_json` con ese id, o con el id de la jugada si el pedido no trae uno. Si el pedido no se pudo
+/// simular, la respuesta es `{"id":...,"codigo":...,"error":...}`, con un codigo al estilo de HTTP:
+///
+/// * 400: El pedido no es JSON, no es texto UTF-8, no tiene coordenadas validas o su tablero no se puede leer.
+/// * 413: La linea supera `LARGO_MAXIMO` o el tablero supera `Limites::casillas`.
+/// * 422: El pedido se leyo pero no se pudo simular: no hay una bomba en las coordenadas o se supero otro limite.
+///
+/// Las lineas vacias se saltean. Cada pedido se lee como JSON una sola vez.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ echo '{"id":"a","tablero":[["B1","F1"]],"x":0,"y":0}' | cargo run -- --protocol jsonl
+/// {"id":"a","version":"0.1.0","tablero":[["_","_"]],"resultado":{...},"bombas":[...],"advertencias":[]}
+/// $ echo '{"id":"b","tablero":[["B1","F1"]],"x":1,"y":0}' | cargo run -- --protocol jsonl
+/// {"id":"b","codigo":422,"error":"Error: ..."}
+/// ```
+///
+/// # Argumentos
+///
+/// * reglas: Las reglas con las que se validan los tableros y se arma el id de la jugada.
+/// * idioma: El idioma de los errores de las respuestas, como en `--lang`.
+/// * constructor: Arma la simulacion de cada pedido, con las reglas y limites de la linea de comandos.
+///
+/// # Devuelve
+///
+/// Un Result con la cantidad de pedidos atendidos o el io::Error de la entrada o de la salida.
+pub fn atender(
+    entrada: &mut impl BufRead,
+    salida: &mut impl Write,
+    reglas: &Reglas,
+    idioma: Idioma,
+    constructor: impl Fn(Tablero) -> Result<SimulacionBuilder, String>,
+) -> io::Result<usize> {
+    let mut atendidos = 0;
+    let mut linea = Vec::new();
+    while let Some(entera) = leer_linea(entrada, &mut linea)? {
+        let respuesta = match (entera, std::str::from_utf8(&linea)) {
+            (false, _) => rechazo(
+                None,
+                413,
+                idioma.formatear("pedido_demasiado_largo", &[&LARGO_MAXIMO]),
+            ),
+            (true, Ok(pedido)) if pedido.trim().is_empty() => continue,
+            (true, Ok(pedido)) => responder(pedido.trim(), reglas, idioma, &constructor),
+            (true, Err(_)) => rechazo(None, 400, idioma.texto("pedido_no_utf8").to_string()),
+        };
+        salida.write_all(&respuesta)?;
+        salida.flush()?;
+        atendidos += 1;
+    }
+    Ok(atendidos)
+}
+
+/// Lee la proxima linea de la entrada en `linea`, sin guardar mas de `LARGO_MAXIMO` bytes: el resto de una linea mas
+/// larga se descarta hasta el salto de linea.
+///
+/// # Devuelve
+///
+/// Un Result con None si se termino la entrada, o con si la linea entro entera.
+fn leer_linea(entrada: &mut impl BufRead, linea: &mut Vec<u8>) -> io::Result<Option<bool>> {
+    linea.clear();
+    let leidos = entrada
+        .by_ref()
+        .take(LARGO_MAXIMO as u64 + 1)
+        .read_until(b'\n', linea)?;
+    if leidos == 0 {
+        return Ok(None);
+    }
+    if linea.ends_with(b"\n") || linea.len() <= LARGO_MAXIMO {
+        return Ok(Some(true));
+    }
+    loop {
+        let pendiente = entrada.fill_buf()?;
+        match pendiente.iter().position(|&byte| byte == b'\n') {
+            Some(fin) => {
+                entrada.consume(fin + 1);
+                break;
+            }
+            None if pendiente.is_empty() => break,
+            None => {
+                let largo = pendiente.len();
+                entrada.consume(largo);
+            }
+        }
+    }
+    linea.clear();
+    Ok(Some(false))
+}
+
+/// La linea de respuesta a un pedido, con su salto de linea.
+fn responder(
+    pedido: &str,
+    reglas: &Reglas,
+    idioma: Idioma,
+    constructor: impl Fn(Tablero) -> Result<SimulacionBuilder, String>,
+) -> Vec<u8> {
+    let pedido = match json::parsear(pedido) {
+        Ok(pedido) => pedido,
+        Err(e) => return rechazo(None, 400, e.mensaje(idioma).to_string()),
+    };
+    let id = match pedido.campo("id") {
+        Some(Json::Texto(id)) => Some(id.clone()),
+        _ => None,
+    };
+    let mut respuesta = Vec::new();
+    let resultado = coordenadas(&pedido, idioma).and_then(|punto| {
+        let (tablero, metadatos) = tablero_desde_documento_json(&pedido, reglas)
+            .map_err(|e| (400, e.mensaje(idioma).to_string()))?;
+        let salida = constructor(tablero.clone())
+            .map_err(|e| (422, e))?
+            .construir()
+            .simular(punto.columna(), punto.fila())
+            .map_err(|e| (codigo(&e), e.mensaje(idioma).to_string()))?;
+        let id = id.clone().unwrap_or_else(|| {
+            cache::id_jugada(&tablero.filas(), &punto, &format!("{:?}", reglas))
+        });
+        escribir_resultado_json(
+            Some(&id),
+            &salida.tablero,
+            &salida.resultado(&tablero),
+            &salida.estadisticas,
+            &salida.advertencias,
+            metadatos.as_ref(),
+            &mut respuesta,
+        )
+        .map_err(|e| (500, e.to_string()))
+    });
+    match resultado {
+        Ok(()) => respuesta,
+        Err((codigo, error)) => rechazo(id, codigo, error),
+    }
+}
+
+/// El codigo de la respuesta a un pedido que se leyo pero no se pudo simular.
+fn codigo(error: &BombermanError) -> u16 {
+    match error {
+        BombermanError::LimiteTablero { .. } => 413,
+        BombermanError::ArchivoInvalido { .. }
+        | BombermanError::FilaIrregular { .. }
+        | BombermanError::PortalSinPareja { .. } => 400,
+        _ => 422,
+    }
+}
+
+/// La linea de respuesta `{"id":...,"codigo":...,"error":...}` a un pedido que no se pudo simular, sin el id si no
+/// se sabe.
+fn rechazo(id: Option<String>, codigo: u16, error: String) -> Vec<u8> {
+    let mut campos = vec![
+        ("codigo".to_string(), Json::Numero(codigo as f64)),
+        ("error".to_string(), Json::Texto(error)),
+    ];
+    if let Some(id) = id {
+        campos.insert(0, ("id".to_string(), Json::Texto(id)));
+    }
+    format!("{}\n", Json::Objeto(campos)).into_bytes()
+}
+
+/// Las coordenadas `x` e `y` de un pedido, que tienen que ser numeros naturales.
+fn coordenadas(pedido: &Json, idioma: Idioma) -> Result<Punto, (u16, String)> {
+    let coordenada = |clave: &str| match pedido.campo(clave) {
+        Some(Json::Numero(n)) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+        _ => Err((400, idioma.formatear("pedido_sin_coordenada", &[&clave]))),
+    };
+    Ok(Punto {
+        x: coordenada("x")?,
+        y: coordenada("y")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{atender, limites, LARGO_MAXIMO};
+    use tp1_bomberman::bomberman::json::{parsear, Json};
+    use tp1_bomberman::bomberman::prelude::*;
+
+    #[test]
+    fn test01_responde_cada_pedido_en_una_linea() {
+        let mut entrada = "{\"id\":\"a\",\"tablero\":[[\"B1\",\"F1\"]],\"x\":0,\"y\":0}\n\
+                           \n\
+                           {\"tablero\":[[\"B1\",\"R\"]],\"x\":1,\"y\":0}\n\
+                           {\"id\":\"c\",\"tablero\":[[\"B1\"]],\"x\":-1,\"y\":0}\n\
+                           {\"tablero\":[[\"B1\",\"F1\"]],\"x\":0,\"y\":0,\"metadatos\":{\"nivel\":2}}\n\
+                           no es json\n"
+            .as_bytes();
+        let mut salida = Vec::new();
+        let atendidos = atender(
+            &mut entrada,
+            &mut salida,
+            &Reglas::default(),
+            Idioma::Es,
+            |tablero| Ok(SimulacionBuilder::new(tablero)),
+        )
+        .unwrap();
+        assert_eq!(atendidos, 5);
+        let respuestas: Vec<Json> = String::from_utf8(salida)
+            .unwrap()
+            .lines()
+            .map(|linea| parsear(linea).unwrap())
+            .collect();
+        assert_eq!(respuestas.len(), 5);
+        assert_eq!(
+            respuestas[0].campo("id"),
+            Some(&Json::Texto("a".to_string()))
+        );
+        assert_eq!(
+            respuestas[0].campo("tablero").map(ToString::to_string),
+            Some("[[\"_\",\"_\"]]".to_string())
+        );
+        assert!(respuestas[1].campo("error").is_some());
+        assert_eq!(respuestas[1].campo("codigo"), Some(&Json::Numero(422.0)));
+        assert_eq!(
+            respuestas[2].campo("id"),
+            Some(&Json::Texto("c".to_string()))
+        );
+        assert_eq!(respuestas[2].campo("codigo"), Some(&Json::Numero(400.0)));
+        // Sin id en el pedido, la respuesta lleva el id de la jugada.
+        assert!(matches!(respuestas[3].campo("id"), Some(Json::Texto(_))));
+        assert_eq!(
+            respuestas[3].campo("metadatos").map(ToString::to_string),
+            Some("{\"nivel\":2}".to_string())
+        );
+        assert!(respuestas[4].campo("error").is_some());
+        assert_eq!(respuestas[4].campo("codigo"), Some(&Json::Numero(400.0)));
+    }
+
+    #[test]
+    fn test02_una_linea_demasiado_larga_se_descarta() {
+        let mut entrada = format!("{{\"id\":\"{}\"}}\n", "a".repeat(LARGO_MAXIMO)).into_bytes();
+        entrada.extend(b"\xff\n{\"id\":\"b\",\"tablero\":[[\"B1\"]],\"x\":0,\"y\":0}");
+        let mut salida = Vec::new();
+        let limites = limites(&Limites::default());
+        let atendidos = atender(
+            &mut entrada.as_slice(),
+            &mut salida,
+            &Reglas::default(),
+            Idioma::Es,
+            |t| Ok(SimulacionBuilder::new(t).limites(limites.clone())),
+        )
+        .unwrap();
+        assert_eq!(atendidos, 3);
+        let respuestas: Vec<Json> = String::from_utf8(salida)
+            .unwrap()
+            .lines()
+            .map(|linea| parsear(linea).unwrap())
+            .collect();
+        assert_eq!(respuestas[0].campo("codigo"), Some(&Json::Numero(413.0)));
+        assert_eq!(respuestas[1].campo("codigo"), Some(&Json::Numero(400.0)));
+        assert_eq!(
+            respuestas[2].campo("id"),
+            Some(&Json::Texto("b".to_string()))
+        );
+        assert!(respuestas[2].campo("error").is_none());
+    }
+
+    #[test]
+    fn test03_los_rechazos_salen_en_el_idioma_pedido() {
+        let mut entrada =
+            b"\xff\n{\"tablero\":[[\"B1\"]],\"x\":0}\n{\"tablero\":[[\"X\"]],\"x\":0,\"y\":0}\n\
+                            no es json\n"
+                .as_slice();
+        let mut salida = Vec::new();
+        atender(
+            &mut entrada,
+            &mut salida,
+            &Reglas::default(),
+            Idioma::En,
+            |t| Ok(SimulacionBuilder::new(t)),
+        )
+        .unwrap();
+        let errores: Vec<String> = String::from_utf8(salida)
+            .unwrap()
+            .lines()
+            .map(|linea| parsear(linea).unwrap().campo("error").unwrap().to_string())
+            .collect();
+        assert_eq!(
+            errores,
+            [
+                "\"Error: the request is not UTF-8 text\"",
+                "\"Error: the request needs a natural number in y\"",
+                "\"Error: invalid input file\"",
+                "\"Error: invalid input JSON\"",
+            ]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+use tp1_bomberman::bomberman::analizador::Analizador;
+use tp1_bomberman::bomberman::celda::Celda;
+use tp1_bomberman::bomberman::idioma::Idioma;
+use tp1_bomberman::bomberman::punto::Punto;
+use tp1_bomberman::bomberman::tablero::Tablero;
+
+/// Secuencias ANSI para atenuar las casillas que no se pueden elegir y resaltar las que afectaria la bomba del cursor.
+const ATENUADO: &str = "\x1b[2m";
+const RESALTADO: &str = "\x1b[7m";
+const NORMAL: &str = "\x1b[0m";
+
+/// Un cursor que se mueve sobre el tablero para elegir la bomba a detonar, en lugar de escribir sus coordenadas.
+pub struct Selector {
+    pub cursor: Punto,
+}
+
+impl Selector {
+    /// Crea un Selector con el cursor en la primera bomba del tablero, o en (0, 0) si no hay ninguna.
+    pub fn new(tablero: &Tablero) -> Selector {
+        let cursor = tablero
+            .celdas()
+            .find(|(_, celda)| matches!(celda, Celda::Bomba(_)))
+            .map(|(punto, _)| punto)
+            .unwrap_or(Punto { x: 0, y: 0 });
+        Selector { cursor }
+    }
+
+    /// Mueve el cursor una casilla con las teclas `w`, `a`, `s` y `d`, sin salirse del tablero.
+    /// Cualquier otra tecla no lo mueve.
+    pub fn mover(&mut self, tecla: char, tablero: &Tablero) {
+        let siguiente = match tecla {
+            'w' => self
+                .cursor
+                .y
+                .checked_sub(1)
+                .map(|y| Punto { y, ..self.cursor }),
+            's' => Some(Punto {
+                y: self.cursor.y + 1,
+                ..self.cursor
+            }),
+            'a' => self
+                .cursor
+                .x
+                .checked_sub(1)
+                .map(|x| Punto { x, ..self.cursor }),
+            'd' => Some(Punto {
+                x: self.cursor.x + 1,
+                ..self.cursor
+            }),
+            _ => None,
+        };
+        if let Some(punto) = siguiente.filter(|p| tablero.en_rango(*p)) {
+            self.cursor = punto;
+        }
+    }
+
+    /// Indica si la casilla bajo el cursor es una bomba, que es lo unico que se puede elegir.
+    pub fn es_elegible(&self, tablero: &Tablero) -> bool {
+        tablero
+            .get(self.cursor)
+            .is_some_and(|celda| matches!(celda, Celda::Bomba(_)))
+    }
+
+    /// Las casillas que cambiarian si se detonara la bomba bajo el cursor, segun `Analizador::afectadas`. Si el cursor
+    /// no esta sobre una bomba, o la simulacion falla (por ejemplo por un limite), no hay ninguna.
+    pub fn vista_previa(&self, analizador: &Analizador) -> HashSet<Punto> {
+        if !self.es_elegible(analizador.tablero()) {
+            return HashSet::new();
+        }
+        analizador.afectadas(self.cursor).unwrap_or_default()
+    }
+
+    /// Dibuja el tablero con el cursor entre corchetes, las casillas que afectaria la bomba del cursor resaltadas
+    /// (ver `vista_previa`) y el resto de las casillas que no son bombas atenuadas.
+    pub fn dibujar(&self, analizador: &Analizador) -> String {
+        let tablero = analizador.tablero();
+        let afectadas = self.vista_previa(analizador);
+        let mut texto = String::new();
+        for (y, fila) in tablero.filas().iter().enumerate() {
+            let celdas: Vec<String> = fila
+                .iter()
+                .enumerate()
+                .map(|(x, casilla)| {
+                    let celda = if afectadas.contains(&Punto { x, y }) {
+                        format!("{}{}{}", RESALTADO, casilla, NORMAL)
+                    } else if matches!(tablero.get(Punto { x, y }), Some(Celda::Bomba(_))) {
+                        casilla.clone()
+                    } else {
+                        format!("{}{}{}", ATENUADO, casilla, NORMAL)
+                    };
+                    if (Punto { x, y }) == self.cursor {
+                        format!("[{}]", celda)
+                    } else {
+                        format!(" {} ", celda)
+                    }
+                })
+                .collect();
+            texto += &celdas.join("");
+            texto.push('\n');
+        }
+        if !afectadas.is_empty() {
+            texto += &format!("Si se detona cambian {} casillas\n", afectadas.len());
+        }
+        texto
+    }
+}
+
+/// Muestra el tablero y deja elegir la bomba a detonar moviendo el cursor: cada linea leida puede tener varias de
+/// las teclas `w`, `a`, `s` y `d`, y una linea vacia elige la casilla del cursor si es una bomba. Mientras el cursor
+/// esta sobre una bomba se resaltan las casillas que cambiaria su detonacion, con las reglas y limites del analizador.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run entrada.txt salidas ? ?
+/// [B2] R  R  _  F1 _  _
+/// ...
+/// > ss
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con el Punto elegido o un Err(String), en el idioma dado, si se termino la entrada antes de elegir.
+pub fn elegir(
+    analizador: &Analizador,
+    entrada: &mut impl BufRead,
+    salida: &mut impl Write,
+    idioma: Idioma,
+) -> Result<Punto, String> {
+    let tablero = analizador.tablero();
+    let mut selector = Selector::new(tablero);
+    let mut linea = String::new();
+    loop {
+        let _ = write!(
+            salida,
+            "{}({}, {}) > ",
+            selector.dibujar(analizador),
+            selector.cursor.x,
+            selector.cursor.y
+        );
+        let _ = salida.flush();
+        linea.clear();
+        match entrada.read_line(&mut linea) {
+            Ok(0) | Err(_) => return Err(idioma.texto("ninguna_bomba_elegida").to_string()),
+            _ => {}
+        }
+        let teclas = linea.trim();
+        if teclas.is_empty() {
+            if selector.es_elegible(tablero) {
+                return Ok(selector.cursor);
+            }
+            let _ = writeln!(salida, "Esa casilla no es una bomba");
+        }
+        for tecla in teclas.chars() {
+            selector.mover(tecla, tablero);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{elegir, Selector};
+    use tp1_bomberman::bomberman::analizador::Analizador;
+    use tp1_bomberman::bomberman::idioma::Idioma;
+    use tp1_bomberman::bomberman::punto::Punto;
+    use tp1_bomberman::bomberman::reglas::{Limites, Reglas};
+    use tp1_bomberman::bomberman::tablero::Tablero;
+
+    fn tablero() -> Tablero {
+        Tablero::desde_filas(
+            ["_ B1 _", "W _ _", "_ _ S2"]
+                .iter()
+                .map(|fila| fila.split_whitespace().map(String::from).collect())
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test01_el_cursor_empieza_en_una_bomba_y_no_sale_del_tablero() {
+        let tablero = tablero();
+        let mut selector = Selector::new(&tablero);
+        assert_eq!(selector.cursor, Punto { x: 1, y: 0 });
+        selector.mover('w', &tablero);
+        assert_eq!(selector.cursor, Punto { x: 1, y: 0 });
+        selector.mover('s', &tablero);
+        assert!(!selector.es_elegible(&tablero));
+    }
+
+    #[test]
+    fn test02_elegir_ignora_las_casillas_que_no_son_bombas() {
+        let mut entrada = "s\n\nsd\n\n".as_bytes();
+        let mut salida = Vec::new();
+        assert_eq!(
+            elegir(
+                &Analizador::new(tablero(), Reglas::default(), Limites::default()),
+                &mut entrada,
+                &mut salida,
+                Idioma::Es
+            ),
+            Ok(Punto { x: 2, y: 2 })
+        );
+        assert!(String::from_utf8(salida)
+            .unwrap()
+            .contains("Esa casilla no es una bomba"));
+    }
+
+    #[test]
+    fn test03_la_vista_previa_muestra_lo_que_afectaria_la_bomba() {
+        let analizador = Analizador::new(tablero(), Reglas::default(), Limites::default());
+        let mut selector = Selector::new(analizador.tablero());
+        assert_eq!(
+            selector.vista_previa(&analizador),
+            [Punto { x: 1, y: 0 }].into_iter().collect()
+        );
+        assert!(selector
+            .dibujar(&analizador)
+            .contains("Si se detona cambian 1 casillas"));
+        selector.mover('s', analizador.tablero());
+        assert!(selector.vista_previa(&analizador).is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+/// Un valor del subconjunto de TOML que usan los manifiestos, los escenarios y los archivos de reglas: un texto entre
+/// comillas, un numero natural, `true` o `false`, o un arreglo de valores.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valor {
+    Texto(String),
+    Numero(usize),
+    Booleano(bool),
+    Lista(Vec<Valor>),
+}
+
+/// Una linea `clave = valor`, con la linea del archivo en la que empieza para los mensajes de error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clave {
+    pub nombre: String,
+    pub valor: Valor,
+    pub linea: usize,
+}
+
+/// Las claves que siguen a un encabezado como `[[caso]]` o `[opciones]`, hasta el proximo. Las claves anteriores
+/// al primer encabezado forman una tabla sin encabezado, en la linea 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tabla {
+    /// El encabezado tal como se escribio, o un texto vacio para las claves del principio.
+    pub encabezado: String,
+    pub linea: usize,
+    pub claves: Vec<Clave>,
+}
+
+impl Tabla {
+    /// Devuelve la clave con ese nombre, si la tabla la tiene.
+    pub fn clave(&self, nombre: &str) -> Option<&Clave> {
+        self.claves.iter().find(|clave| clave.nombre == nombre)
+    }
+}
+
+/// Lee un documento del subconjunto de TOML: comentarios con `#`, encabezados de tabla y lineas `clave = valor`,
+/// donde el valor es un texto entre comillas (con `\"` y `\\` como unicos escapes), un numero natural, `true`,
+/// `false` o un arreglo, que puede ocupar varias lineas.
+///
+/// # Ejemplos
+///
+/// ```
+/// let tablas = parsear("nombre = \"demo\"\n[[detonacion]]\nx = 0\n")?;
+/// assert_eq!(tablas[1].encabezado, "[[detonacion]]");
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con las tablas en el orden del archivo, empezando por la de las claves sin encabezado, o un Err(String)
+/// con la linea y la causa del primer error de sintaxis o de una clave repetida en la misma tabla.
+pub fn parsear(texto: &str) -> Result<Vec<Tabla>, String> {
+    let mut tablas = vec![Tabla {
+        encabezado: String::new(),
+        linea: 0,
+        claves: Vec::new(),
+    }];
+    let mut lineas = texto.lines().enumerate();
+    while let Some((indice, linea)) = lineas.next() {
+        let numero = indice + 1;
+        let error = |causa: String| format!("Error: linea {}: {}", numero, causa);
+        let linea = linea.trim();
+        if linea.is_empty() || linea.starts_with('#') {
+            continue;
+        }
+        if linea.starts_with('[') {
+            tablas.push(Tabla {
+                encabezado: linea.to_string(),
+                linea: numero,
+                claves: Vec::new(),
+            });
+            continue;
+        }
+        let (nombre, valor) = linea
+            .split_once('=')
+            .ok_or_else(|| error(format!("se esperaba `clave = valor`, no {}", linea)))?;
+        let nombre = nombre.trim();
+        // Un arreglo sigue en las lineas siguientes hasta que se cierran todos sus corchetes.
+        let mut valor = valor.trim().to_string();
+        while abiertos(&valor) > 0 {
+            match lineas.next() {
+                Some((_, siguiente)) => {
+                    valor.push('\n');
+                    valor.push_str(siguiente);
+                }
+                None => return Err(error(format!("{}: falta cerrar el arreglo", nombre))),
+            }
+        }
+        let tabla = tablas
+            .last_mut()
+            .expect("siempre esta la tabla sin encabezado");
+        if tabla.clave(nombre).is_some() {
+            return Err(error(format!("clave repetida {}", nombre)));
+        }
+        let mut lector = Lector {
+            resto: valor.as_str(),
+        };
+        let parseado = lector
+            .valor()
+            .and_then(|valor| lector.final_().map(|_| valor))
+            .map_err(|causa| error(format!("{}: {}", nombre, causa)))?;
+        tabla.claves.push(Clave {
+            nombre: nombre.to_string(),
+            valor: parseado,
+            linea: numero,
+        });
+    }
+    Ok(tablas)
+}
+
+/// Cuantos corchetes quedan abiertos en el texto, sin contar los que estan dentro de textos o comentarios.
+fn abiertos(texto: &str) -> usize {
+    let mut abiertos: usize = 0;
+    let mut en_texto = false;
+    let mut caracteres = texto.chars();
+    while let Some(c) = caracteres.next() {
+        match c {
+            '\\' if en_texto => {
+                caracteres.next();
+            }
+            '"' => en_texto = !en_texto,
+            '#' if !en_texto => {
+                // El comentario termina al final de su linea.
+                caracteres.by_ref().find(|c| *c == '\n');
+            }
+            '[' if !en_texto => abiertos += 1,
+            ']' if !en_texto => abiertos = abiertos.saturating_sub(1),
+            _ => {}
+        }
+    }
+    abiertos
+}
+
+/// Recorre el texto de un valor.
+struct Lector<'a> {
+    resto: &'a str,
+}
+
+impl Lector<'_> {
+    /// Saltea los espacios, los saltos de linea y los comentarios.
+    fn espacios(&mut self) {
+        loop {
+            self.resto = self.resto.trim_start();
+            match self.resto.strip_prefix('#') {
+                Some(comentario) => {
+                    self.resto = comentario.split_once('\n').map_or("", |(_, resto)| resto)
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Revisa que despues del valor solo haya espacios o un comentario.
+    fn final_(&mut self) -> Result<(), String> {
+        self.espacios();
+        match self.resto {
+            "" => Ok(()),
+            sobrante => Err(format!("sobra {} despues del valor", sobrante)),
+        }
+    }
+
+    fn valor(&mut self) -> Result<Valor, String> {
+        self.espacios();
+        if let Some(resto) = self.resto.strip_prefix('"') {
+            self.resto = resto;
+            return self.texto().map(Valor::Texto);
+        }
+        if let Some(resto) = self.resto.strip_prefix('[') {
+            self.resto = resto;
+            return self.lista();
+        }
+        let fin = self
+            .resto
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(self.resto.len());
+        let (palabra, resto) = self.resto.split_at(fin);
+        let valor = match palabra {
+            "true" => Some(Valor::Booleano(true)),
+            "false" => Some(Valor::Booleano(false)),
+            numero if numero.bytes().all(|b| b.is_ascii_digit()) => {
+                numero.parse().ok().map(Valor::Numero)
+            }
+            _ => None,
+        };
+        match valor {
+            Some(valor) => {
+                self.resto = resto;
+                Ok(valor)
+            }
+            None => {
+                let valor = self.resto.split_whitespace().next().unwrap_or_default();
+                Err(format!(
+                    "{} no es un texto entre comillas, un numero natural, un booleano ni un arreglo",
+                    valor
+                ))
+            }
+        }
+    }
+
+    fn lista(&mut self) -> Result<Valor, String> {
+        let mut valores = Vec::new();
+        loop {
+            self.espacios();
+            if let Some(resto) = self.resto.strip_prefix(']') {
+                self.resto = resto;
+                return Ok(Valor::Lista(valores));
+            }
+            valores.push(self.valor()?);
+            self.espacios();
+            if let Some(resto) = self.resto.strip_prefix(',') {
+                self.resto = resto;
+            } else if !self.resto.starts_with(']') {
+                return Err("se esperaba ',' o ']' en el arreglo".to_string());
+            }
+        }
+    }
+
+    fn texto(&mut self) -> Result<String, String> {
+        let mut texto = String::new();
+        let mut caracteres = self.resto.chars();
+        while let Some(c) = caracteres.next() {
+            match c {
+                '"' => {
+                    self.resto = caracteres.as_str();
+                    return Ok(texto);
+                }
+                '\\' => match caracteres.next() {
+                    Some(escapado @ ('"' | '\\')) => texto.push(escapado),
+                    _ => return Err("solo se aceptan los escapes \\\" y \\\\".to_string()),
+                },
+                '\n' => break,
+                _ => texto.push(c),
+            }
+        }
+        Err("falta cerrar las comillas".to_string())
+    }
+}
+
+/// Escribe un texto entre comillas con el formato que lee `parsear`.
+pub fn texto(texto: &str) -> String {
+    format!("\"{}\"", texto.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parsear, Valor};
+
+    #[test]
+    fn test01_tablas_claves_y_arreglos() {
+        let texto = "nombre = \"a \\\"b\\\"\" # comentario\nfilas = [\n  \"B1 _\", # primera\n  \"_ W\",\n]\n\
+                     \n[[detonacion]]\nx = 1\ny = 0\n[[detonacion]]\nx = 2\n";
+        let tablas = parsear(texto).unwrap();
+        assert_eq!(tablas.len(), 3);
+        assert_eq!(
+            tablas[0].clave("nombre").unwrap().valor,
+            Valor::Texto("a \"b\"".to_string())
+        );
+        assert_eq!(
+            tablas[0].clave("filas").unwrap().valor,
+            Valor::Lista(vec![
+                Valor::Texto("B1 _".to_string()),
+                Valor::Texto("_ W".to_string())
+            ])
+        );
+        assert_eq!(
+            (tablas[1].encabezado.as_str(), tablas[1].linea),
+            ("[[detonacion]]", 7)
+        );
+        assert_eq!(tablas[2].clave("x").unwrap().linea, 11);
+        assert_eq!(
+            parsear("a = [true, false]").unwrap()[0]
+                .clave("a")
+                .unwrap()
+                .valor,
+            Valor::Lista(vec![Valor::Booleano(true), Valor::Booleano(false)])
+        );
+    }
+
+    #[test]
+    fn test02_errores_de_sintaxis() {
+        for (texto, error) in [
+            ("a = [1, 2", "Error: linea 1: a: falta cerrar el arreglo"),
+            ("a = [1 2]", "Error: linea 1: a: se esperaba ',' o ']' en el arreglo"),
+            ("a = 1 2", "Error: linea 1: a: sobra 2 despues del valor"),
+            ("a = si", "Error: linea 1: a: si no es un texto entre comillas, un numero natural, un booleano ni un arreglo"),
+            ("a = truee", "Error: linea 1: a: truee no es un texto entre comillas, un numero natural, un booleano ni un arreglo"),
+        ] {
+            assert_eq!(parsear(texto), Err(error.to_string()), "{}", texto);
+        }
+    }
+}
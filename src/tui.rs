@@ -0,0 +1,315 @@
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use tp1_bomberman::bomberman::punto::Punto;
+use tp1_bomberman::bomberman::simulacion::SimulacionBuilder;
+use tp1_bomberman::bomberman::tablero::Tablero;
+
+use crate::selector::Selector;
+
+/// El titulo del recuadro del tablero.
+const TITULO_TABLERO: &str = "Tablero";
+/// Las teclas de la interfaz, que van debajo del mensaje.
+const TECLAS: &str = "flechas o wasd mueven el cursor, Enter detona la bomba, q sale";
+
+/// Lo que muestra la interfaz: el tablero como va quedando, el cursor, las casillas del ultimo paso, los ultimos
+/// eventos y un mensaje debajo del tablero.
+pub struct Pantalla {
+    pub tablero: Tablero,
+    pub selector: Selector,
+    cambiadas: HashSet<Punto>,
+    eventos: Vec<String>,
+    mensaje: String,
+}
+
+impl Pantalla {
+    /// Crea la Pantalla con el cursor en la primera bomba del tablero (ver `Selector::new`) y el panel vacio.
+    pub fn new(tablero: Tablero) -> Pantalla {
+        Pantalla {
+            selector: Selector::new(&tablero),
+            tablero,
+            cambiadas: HashSet::new(),
+            eventos: Vec::new(),
+            mensaje: String::new(),
+        }
+    }
+
+    /// Dibuja el tablero con el cursor entre corchetes y las casillas que cambio el ultimo paso resaltadas, y a su
+    /// derecha el panel con los ultimos eventos que entran en la altura de la terminal. Debajo van el mensaje y las
+    /// teclas.
+    pub fn dibujar(&self, frame: &mut Frame) {
+        let [arriba, abajo] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(frame.area());
+        let filas: Vec<Line> = self
+            .tablero
+            .filas()
+            .iter()
+            .enumerate()
+            .map(|(y, fila)| {
+                let casillas: Vec<Span> = fila
+                    .iter()
+                    .enumerate()
+                    .map(|(x, casilla)| {
+                        let punto = Punto { x, y };
+                        let estilo = match self.cambiadas.contains(&punto) {
+                            true => Style::default().add_modifier(Modifier::REVERSED),
+                            false => Style::default(),
+                        };
+                        match punto == self.selector.cursor {
+                            true => Span::styled(format!("[{}]", casilla), estilo),
+                            false => Span::styled(format!(" {} ", casilla), estilo),
+                        }
+                    })
+                    .collect();
+                Line::from(casillas)
+            })
+            .collect();
+        // El recuadro del tablero es por lo menos tan ancho como su titulo, mas los dos bordes.
+        let ancho_tablero = filas
+            .iter()
+            .map(Line::width)
+            .chain([TITULO_TABLERO.len()])
+            .max()
+            .unwrap_or(0) as u16;
+        let [izquierda, derecha] =
+            Layout::horizontal([Constraint::Length(ancho_tablero + 2), Constraint::Min(10)])
+                .areas(arriba);
+        frame.render_widget(
+            Paragraph::new(filas).block(Block::bordered().title(TITULO_TABLERO)),
+            izquierda,
+        );
+        let visibles = derecha.height.saturating_sub(2) as usize;
+        let desde = self.eventos.len().saturating_sub(visibles);
+        frame.render_widget(
+            List::new(self.eventos[desde..].iter().map(String::as_str))
+                .block(Block::bordered().title("Eventos")),
+            derecha,
+        );
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(self.mensaje.as_str()), Line::from(TECLAS)]),
+            abajo,
+        );
+    }
+}
+
+/// Abre la interfaz en la terminal, en modo crudo y en la pantalla alternativa, y la atiende con las teclas que se
+/// van apretando (ver `ejecutar`). Al salir, o si algo falla, deja la terminal como estaba.
+///
+/// # Argumentos
+///
+/// * tablero: El tablero inicial.
+/// * constructor: Arma la simulacion de cada detonacion, con las reglas y limites de la linea de comandos.
+/// * pausa: Cuanto se espera entre un cuadro y el siguiente.
+///
+/// # Devuelve
+///
+/// Lo mismo que `ejecutar`.
+pub fn abrir(
+    tablero: Tablero,
+    constructor: impl Fn(Tablero) -> Result<SimulacionBuilder, String>,
+    pausa: Duration,
+) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let mut teclas = std::iter::from_fn(|| loop {
+        match event::read() {
+            Ok(Event::Key(tecla)) if tecla.kind == KeyEventKind::Press => return Some(tecla),
+            Ok(_) => continue,
+            Err(_) => return None,
+        }
+    });
+    let resultado = ejecutar(
+        &mut Pantalla::new(tablero),
+        constructor,
+        &mut terminal,
+        &mut teclas,
+        pausa,
+    );
+    ratatui::restore();
+    resultado
+}
+
+/// Muestra el tablero, deja mover el cursor con las flechas o con `wasd` y, con Enter sobre una bomba, la detona y
+/// anima la reaccion en cadena de a una detonacion por vez (ver `Simulacion::paso_a_paso`), con una pausa entre
+/// cuadros. El tablero queda como termino, para seguir detonando las bombas que sobrevivieron. Termina con `q`, Esc o
+/// Ctrl+C, o cuando se terminan las teclas.
+///
+/// # Ejemplos
+///
+/// ```
+/// $ cargo run --features tui tui entrada.txt
+/// ┌Tablero──────────────┐┌Eventos─────────────────┐
+/// │[B2] R  R  _  F1 _  _││                        │
+/// ...
+/// ```
+///
+/// # Argumentos
+///
+/// * pantalla: Lo que se muestra, que queda como termino la interfaz.
+/// * constructor: Arma la simulacion de cada detonacion, con las reglas y limites de la linea de comandos.
+/// * terminal: Donde se dibuja cada cuadro.
+/// * teclas: Las teclas que se apretan, en orden.
+/// * pausa: Cuanto se espera entre un cuadro y el siguiente.
+///
+/// # Devuelve
+///
+/// Un Ok(()) o un Err(String) si el constructor falla, por ejemplo si el tablero no es valido como hexagonal, o si no
+/// se pudo dibujar. Los errores de una simulacion (por ejemplo por un limite) no la terminan: se muestran en el panel.
+pub fn ejecutar<B: Backend>(
+    pantalla: &mut Pantalla,
+    constructor: impl Fn(Tablero) -> Result<SimulacionBuilder, String>,
+    terminal: &mut Terminal<B>,
+    teclas: &mut impl Iterator<Item = KeyEvent>,
+    pausa: Duration,
+) -> Result<(), String> {
+    let dibujar = |terminal: &mut Terminal<B>, pantalla: &Pantalla| {
+        terminal
+            .draw(|frame| pantalla.dibujar(frame))
+            .map(|_| ())
+            .map_err(|_| "Error: no se pudo dibujar la interfaz".to_string())
+    };
+    loop {
+        dibujar(terminal, pantalla)?;
+        let Some(tecla) = teclas.next() else {
+            return Ok(());
+        };
+        let direccion = match tecla.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if tecla.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Up => Some('w'),
+            KeyCode::Down => Some('s'),
+            KeyCode::Left => Some('a'),
+            KeyCode::Right => Some('d'),
+            KeyCode::Char(letra) => Some(letra),
+            _ => None,
+        };
+        pantalla.mensaje.clear();
+        if let Some(direccion) = direccion {
+            pantalla.selector.mover(direccion, &pantalla.tablero);
+            continue;
+        }
+        if tecla.code != KeyCode::Enter {
+            continue;
+        }
+        if !pantalla.selector.es_elegible(&pantalla.tablero) {
+            pantalla.mensaje = "Esa casilla no es una bomba".to_string();
+            continue;
+        }
+        let cursor = pantalla.selector.cursor;
+        let simulacion = constructor(pantalla.tablero.clone())?
+            .construir()
+            .paso_a_paso(cursor.columna(), cursor.fila());
+        for paso in simulacion {
+            match paso {
+                Ok(paso) => {
+                    pantalla.mensaje = format!(
+                        "Paso {}: explota la bomba en ({}, {})",
+                        paso.numero, paso.bomba.x, paso.bomba.y
+                    );
+                    pantalla.cambiadas = paso.cambios.iter().map(|c| c.punto).collect();
+                    pantalla
+                        .eventos
+                        .extend(paso.eventos.iter().map(ToString::to_string));
+                    pantalla.tablero = paso.tablero;
+                    dibujar(terminal, pantalla)?;
+                    thread::sleep(pausa);
+                }
+                Err(e) => {
+                    pantalla.eventos.push(e.to_string());
+                    pantalla.mensaje = e.to_string();
+                }
+            }
+        }
+        pantalla.cambiadas.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::Terminal;
+
+    use super::{ejecutar, Pantalla};
+    use tp1_bomberman::bomberman::simulacion::SimulacionBuilder;
+    use tp1_bomberman::bomberman::tablero::Tablero;
+
+    /// Las lineas que quedaron dibujadas en la terminal de prueba.
+    fn lineas(terminal: &Terminal<TestBackend>) -> Vec<String> {
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn teclas(codigos: &[KeyCode]) -> impl Iterator<Item = KeyEvent> + '_ {
+        codigos.iter().map(|codigo| KeyEvent::from(*codigo))
+    }
+
+    #[test]
+    fn test01_detona_la_bomba_del_cursor_y_anima_cada_paso() {
+        let tablero = Tablero::desde_texto("B1 B1 F1\n_ W _").unwrap();
+        let mut pantalla = Pantalla::new(tablero);
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        let constructor = |tablero| Ok(SimulacionBuilder::new(tablero));
+        let mut primeras = teclas(&[KeyCode::Down, KeyCode::Enter]);
+        ejecutar(
+            &mut pantalla,
+            constructor,
+            &mut terminal,
+            &mut primeras,
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert!(lineas(&terminal).contains(&"Esa casilla no es una bomba".to_string()));
+
+        let mut segundas = teclas(&[KeyCode::Char('w'), KeyCode::Enter, KeyCode::Char('q')]);
+        ejecutar(
+            &mut pantalla,
+            constructor,
+            &mut terminal,
+            &mut segundas,
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(pantalla.tablero.to_texto(), "_ _ _\n_ W _");
+        let dibujo = lineas(&terminal);
+        // El ultimo cuadro es el tablero vacio, sin resaltar, con los ultimos eventos y el mensaje del ultimo paso.
+        assert!(dibujo[1].starts_with("│[_] _  _ ││"), "{:?}", dibujo);
+        assert!(dibujo[2].starts_with("│ _  W  _ ││"), "{:?}", dibujo);
+        assert!(
+            dibujo[3].contains("muere el enemigo en (2, 0)"),
+            "{:?}",
+            dibujo
+        );
+        assert_eq!(dibujo[6], "Paso 2: explota la bomba en (1, 0)");
+    }
+
+    #[test]
+    fn test02_el_panel_muestra_los_ultimos_eventos() {
+        let mut pantalla = Pantalla::new(Tablero::desde_texto("B1").unwrap());
+        pantalla.eventos = (0..20).map(|i| format!("evento {}", i)).collect();
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal.draw(|frame| pantalla.dibujar(frame)).unwrap();
+        let dibujo = lineas(&terminal);
+        assert_eq!(dibujo[0], "┌Tablero┐┌Eventos──────────────────────┐");
+        assert_eq!(dibujo[1], "│[B1]   ││evento 14                    │");
+        assert_eq!(dibujo[6], "│       ││evento 19                    │");
+    }
+}
@@ -0,0 +1,1068 @@
+use std::error::Error as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use clap::builder::StyledStr;
+use clap::error::{ContextKind, ContextValue, ErrorFormatter, ErrorKind};
+use clap::{
+    Arg, ArgAction, Args, Command, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum,
+};
+use tp1_bomberman::bomberman::objetivo::Objetivo;
+use tp1_bomberman::bomberman::prelude::*;
+
+/// La linea de comandos del programa. Sin subcomando se simula, asi que `tp1-bomberman entrada.txt salidas 0 0` es lo
+/// mismo que `tp1-bomberman simular entrada.txt salidas 0 0` (ver `interpretar`).
+#[derive(Parser, Debug)]
+#[command(
+    name = "tp1-bomberman",
+    about = "Detona bombas de Bomberman-R y escribe el tablero que queda",
+    after_help = "Sin subcomando se simula: `tp1-bomberman <entrada> <directorio> <x> <y>` es lo mismo que \
+                  `tp1-bomberman simular <entrada> <directorio> <x> <y>`.\n\
+                  Con `<subcomando> --help` se ve la ayuda de cada subcomando.",
+    args_conflicts_with_subcommands = true,
+    disable_help_subcommand = true,
+    arg_required_else_help = true
+)]
+pub struct Cli {
+    /// Imprime en JSON lo que soporta el programa
+    #[arg(long)]
+    pub capacidades: bool,
+    /// Lee un pedido JSON por linea de stdin (tablero, x, y) y responde cada uno en una linea de stdout, con las
+    /// opciones de reglas y limites que siguen; el unico protocolo es jsonl
+    #[arg(long, value_enum, value_name = "PROTOCOLO")]
+    pub protocol: Option<Protocolo>,
+    #[command(flatten)]
+    pub reglas: ArgsReglas,
+    #[command(flatten)]
+    pub limites: ArgsLimites,
+    #[command(subcommand)]
+    pub comando: Option<Comando>,
+}
+
+/// Los protocolos de `--protocol`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Protocolo {
+    Jsonl,
+}
+
+/// Los subcomandos, cada uno con sus argumentos.
+#[derive(Subcommand, Debug)]
+pub enum Comando {
+    /// Detona la bomba en (x, y) y escribe el resultado (por defecto)
+    ///
+    /// Detona la bomba en la columna x y la fila y (contando desde 0, o `? ?` para elegirla con el cursor) y escribe
+    /// el tablero final en <DIRECTORIO> con el mismo nombre que <ENTRADA>. Con `--tambien X,Y`, que se puede repetir,
+    /// detona otras bombas en el mismo instante.
+    ///
+    /// Si la entrada termina en `.toml` es un escenario y no lleva coordenadas: tiene nombre, autor y descripcion
+    /// opcionales, tablero = ["fila", ...] y una tabla [[detonacion]] con x = N e y = N por cada bomba a detonar, en
+    /// orden.
+    #[command(allow_negative_numbers = true)]
+    Simular(Box<ArgsSimular>),
+    /// Informa si el tablero es valido y donde esta cada error
+    ///
+    /// Lee el tablero sin simularlo. Si es invalido imprime por stderr cada casilla con problemas, con su posicion en
+    /// el archivo y por que no es valida.
+    Validar {
+        /// El archivo del tablero
+        entrada: String,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+    },
+    /// Lista las casillas en las que difieren dos tableros
+    Comparar {
+        /// El primer tablero
+        a: String,
+        /// El tablero que se compara con el primero
+        b: String,
+    },
+    /// Compara el resultado de la misma entrada con varios presets
+    CompararReglas {
+        /// Los presets a comparar, separados por comas; el primero es contra el que se comparan los demas
+        #[arg(long, value_name = "A,B,...", value_delimiter = ',', required = true)]
+        presets: Vec<String>,
+        /// El archivo del tablero
+        entrada: String,
+        /// La columna de la bomba, contando desde 0
+        x: Columna,
+        /// La fila de la bomba, contando desde 0
+        y: Fila,
+    },
+    /// Ejecuta una consulta sobre un tablero
+    Consultar {
+        /// El archivo del tablero, una entrada o un resultado
+        tablero: String,
+        /// La consulta, por ejemplo "list B where alcance>2"
+        consulta: String,
+    },
+    /// Imprime la reaccion en cadena como un grafo de Graphviz
+    ///
+    /// Imprime un grafo DOT con una flecha desde cada bomba hacia las que hizo explotar, por ejemplo para
+    /// `| dot -Tsvg -o cadena.svg`.
+    Grafo {
+        /// El archivo del tablero
+        entrada: String,
+        /// La columna de la bomba, contando desde 0
+        x: Columna,
+        /// La fila de la bomba, contando desde 0
+        y: Fila,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+    },
+    /// Busca con que detonaciones mueren todos los enemigos
+    ///
+    /// Imprime cada bomba que elimina a todos los enemigos, seguida de las bombas de su reaccion en cadena en el orden
+    /// en que explotan. Con --minimo busca la menor cantidad de detonaciones, de hasta K, que eliminan a todos los
+    /// enemigos una despues de otra, y las imprime en orden.
+    Resolver {
+        /// El archivo del tablero
+        entrada: String,
+        /// Busca la menor cantidad de detonaciones, de hasta K
+        #[arg(long, value_name = "K")]
+        minimo: Option<usize>,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+        #[command(flatten)]
+        limites: ArgsLimites,
+    },
+    /// Compara dos resultados JSON de la misma jugada en distintas versiones
+    ///
+    /// Los dos archivos son resultados de `simular ... --format json`.
+    BisectHelper {
+        /// El resultado de la version anterior
+        antes: String,
+        /// El resultado de la version nueva
+        despues: String,
+    },
+    /// Simula cada trabajo que aparece en la bandeja de entrada
+    ///
+    /// Cada trabajo es un tablero nombre.txt con sus coordenadas `x y` en nombre.coords, que se escribe al final. El
+    /// resultado queda en <SALIDA>/nombre.txt y el trabajo se anota en <SALIDA>/diario.txt.
+    Daemon {
+        /// La bandeja de entrada, donde aparecen los trabajos
+        entrada: String,
+        /// La bandeja de salida, donde quedan los resultados
+        salida: String,
+        /// Revisa la bandeja una sola vez y termina
+        #[arg(long)]
+        una_vez: bool,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+        #[command(flatten)]
+        limites: ArgsLimites,
+    },
+    /// Vuelve a simular una repeticion y revisa que den los mismos eventos
+    ///
+    /// La repeticion se graba con `simular ... --grabar-replay <ARCHIVO>`.
+    Replay {
+        /// El archivo de la repeticion
+        repeticion: String,
+        /// Imprime el tablero despues de cada detonacion
+        #[arg(long)]
+        cuadros: bool,
+    },
+    /// Pasa cada archivo del directorio por el motor
+    ///
+    /// Detona por separado cada bomba y cada mina de cada tablero del directorio, leido con las reglas pedidas.
+    ReplayCorpus {
+        /// El directorio del corpus
+        directorio: String,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+        #[command(flatten)]
+        limites: ArgsLimites,
+    },
+    /// Informa que tipos de casilla usa cada tablero del directorio
+    Censo {
+        /// El directorio de los tableros
+        directorio: String,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+    },
+    /// Escribe una copia del tablero sin datos privados para compartirla
+    ///
+    /// Quita los comentarios y los datos opacos (salvo el id de los enemigos, que se renumera).
+    Ofuscar {
+        /// El archivo del tablero
+        entrada: String,
+        /// El archivo donde se escribe la copia
+        salida: String,
+        /// Tambien rota o refleja el tablero, segun la semilla, e informa donde quedo cada bomba
+        #[arg(long, value_name = "N")]
+        semilla: Option<u64>,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+    },
+    /// Escribe un tablero al azar del tamanio pedido
+    ///
+    /// Cada densidad es la proporcion de casillas de ese tipo, entre 0 y 1; el resto queda vacio. La misma semilla da
+    /// siempre el mismo tablero.
+    Generar {
+        /// El archivo donde se escribe el tablero
+        salida: String,
+        /// La cantidad de columnas
+        ancho: usize,
+        /// La cantidad de filas
+        alto: usize,
+        /// La semilla del tablero al azar
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        semilla: u64,
+        #[command(flatten)]
+        densidades: ArgsDensidades,
+    },
+    /// Detona bombas con un cursor y muestra la reaccion paso a paso
+    ///
+    /// Con las flechas o con w, a, s y d se mueve el cursor y con Enter se detona la bomba que tiene debajo; la
+    /// reaccion en cadena se muestra de a una detonacion. q sale.
+    #[cfg(feature = "tui")]
+    Tui {
+        /// El archivo del tablero
+        entrada: String,
+        /// Milisegundos entre un cuadro y el siguiente
+        #[arg(long, value_name = "MS", default_value_t = 400)]
+        pausa: u64,
+        #[command(flatten)]
+        reglas: ArgsReglas,
+        #[command(flatten)]
+        limites: ArgsLimites,
+    },
+    /// Junta tableros y salidas esperadas en un paquete
+    Empaquetar {
+        /// El archivo del paquete
+        paquete: String,
+        /// Archivos o directorios, relativos
+        #[arg(required = true)]
+        archivos: Vec<String>,
+    },
+    /// Extrae los archivos de un paquete
+    Desempaquetar {
+        /// El archivo del paquete
+        paquete: String,
+        /// El directorio donde se extraen los archivos
+        directorio: String,
+    },
+    /// Simula cada caso del manifiesto y lo compara con su salida esperada
+    ///
+    /// El manifiesto tiene una tabla [[caso]] por caso, con entrada = "ruta", x = N, y = N y opcionalmente preset =
+    /// "clasico" y esperado = "ruta". Las rutas son relativas al directorio del manifiesto.
+    Lote {
+        /// El archivo del manifiesto
+        manifiesto: String,
+    },
+    /// Escribe o revisa un manifiesto de casos para `lote`
+    Manifiesto {
+        #[command(subcommand)]
+        accion: AccionManifiesto,
+    },
+    /// Busca un nivel con la dificultad pedida
+    OptimizarNivel {
+        /// El tablero del que se parte
+        entrada: String,
+        /// El archivo donde se escribe el nivel
+        salida: String,
+        /// La proporcion de enemigos que ninguna bomba alcanza, entre 0 y 1
+        #[arg(value_parser = parsear_dificultad)]
+        dificultad: f64,
+        /// Cuantos cambios se prueban
+        #[arg(long, value_name = "N")]
+        iteraciones: Option<usize>,
+        /// La semilla de los cambios al azar
+        #[arg(long, value_name = "N")]
+        semilla: Option<u64>,
+    },
+}
+
+/// Lo que hace `manifiesto`.
+#[derive(Subcommand, Debug)]
+pub enum AccionManifiesto {
+    /// Escribe casos.toml con un caso por archivo de entradas
+    Init {
+        /// El directorio de las entradas
+        entradas: String,
+        /// El directorio de las salidas esperadas, con el mismo nombre que cada entrada
+        salidas: Option<String>,
+    },
+    /// Informa los errores de un manifiesto
+    Validar {
+        /// El archivo del manifiesto
+        manifiesto: String,
+    },
+}
+
+/// Las opciones de reglas, que aceptan la simulacion y los subcomandos que leen tableros. Primero se aplica el
+/// `--preset`, despues el archivo de `--rules` y al final las demas opciones.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ArgsReglas {
+    /// Empieza con las reglas de un preset (clasico o arcade)
+    #[arg(long, value_name = "NOMBRE", value_parser = parsear_preset)]
+    pub preset: Option<Reglas>,
+    /// Toma las reglas y max_cadena de un archivo TOML
+    #[arg(long, value_name = "ARCHIVO")]
+    pub rules: Option<String>,
+    /// Los enemigos frenan las rafagas de las bombas normales
+    #[arg(long)]
+    pub enemigos_bloquean: bool,
+    /// Acepta ladrillos (X), que cualquier rafaga rompe y que la frenan
+    #[arg(long)]
+    pub ladrillos: bool,
+    /// Cada enemigo que muere deja una mejora de alcance (+R) en su casilla
+    #[arg(long)]
+    pub mejoras: bool,
+    /// Las rafagas que salen por un borde del tablero siguen por el opuesto
+    #[arg(long)]
+    pub toroidal: bool,
+    /// Un enemigo pierde como mucho N vidas en toda la reaccion en cadena
+    #[arg(long, value_name = "N")]
+    pub golpes_por_onda: Option<usize>,
+    /// Tope del alcance de las bombas al validar el tablero (por defecto sin tope)
+    #[arg(long, value_name = "N")]
+    pub alcance_maximo: Option<usize>,
+    /// Tope de la vida de los enemigos al validar el tablero (por defecto 255)
+    #[arg(long, value_name = "N")]
+    pub vida_maxima: Option<usize>,
+    /// Lee y escribe los tableros con los simbolos de un archivo TOML
+    #[arg(long, value_name = "ARCHIVO")]
+    pub simbolos: Option<String>,
+    /// El idioma de los errores: es (por defecto) o en
+    #[arg(long, value_name = "IDIOMA", value_parser = parsear_idioma)]
+    pub lang: Option<Idioma>,
+}
+
+/// Las opciones de limites (ver `Limites`).
+#[derive(Args, Debug, Clone, Default)]
+pub struct ArgsLimites {
+    /// Alcance maximo de las bombas; las que lo superan se recortan con una advertencia
+    #[arg(long, value_name = "N")]
+    pub limite_alcance: Option<usize>,
+    /// Corta la simulacion con un error si explotan mas de N bombas en cadena
+    #[arg(long, value_name = "N")]
+    pub max_cadena: Option<usize>,
+    /// Corta la simulacion con un error si las rafagas recorren mas de N casillas
+    #[arg(long, value_name = "N")]
+    pub max_pasos: Option<usize>,
+    /// Rechaza los tableros de mas de N casillas, antes de simular
+    #[arg(long, value_name = "N")]
+    pub max_casillas: Option<usize>,
+    /// Corta la simulacion con un error si tarda mas de MS milisegundos
+    #[arg(long, value_name = "MS")]
+    pub tiempo_maximo: Option<u64>,
+}
+
+/// Las densidades de `generar` que cambian las de `Densidades::default`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ArgsDensidades {
+    /// La proporcion de paredes
+    #[arg(long, value_name = "P")]
+    pub paredes: Option<f64>,
+    /// La proporcion de rocas
+    #[arg(long, value_name = "P")]
+    pub rocas: Option<f64>,
+    /// La proporcion de bombas
+    #[arg(long, value_name = "P")]
+    pub bombas: Option<f64>,
+    /// La proporcion de desvios
+    #[arg(long, value_name = "P")]
+    pub desvios: Option<f64>,
+    /// La proporcion de enemigos
+    #[arg(long, value_name = "P")]
+    pub enemigos: Option<f64>,
+}
+
+/// Los argumentos de `simular`.
+#[derive(Args, Debug, Clone)]
+pub struct ArgsSimular {
+    /// El archivo del tablero, o un escenario `.toml` con sus propias detonaciones
+    pub entrada: String,
+    /// El directorio donde se escribe el resultado, con el mismo nombre que la entrada
+    pub directorio: String,
+    /// La columna de la bomba, contando desde 0, o `?` para elegirla con el cursor
+    pub x: Option<Coordenada>,
+    /// La fila de la bomba, contando desde 0, o `?` para elegirla con el cursor
+    pub y: Option<Coordenada>,
+    #[command(flatten)]
+    pub opciones: ArgsSimulacion,
+    /// Los argumentos que no son posicionales, en el orden en que se escribieron, para repetirlos en la invocacion
+    /// corregida de `revisar_simulacion`.
+    #[arg(skip)]
+    pub resto: Vec<String>,
+}
+
+/// Las opciones de una simulacion.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ArgsSimulacion {
+    /// Ejecuta el comando al terminar, con {salida}, {resultado}, {bombas} y {enemigos} reemplazados
+    #[arg(long, value_name = "COMANDO")]
+    pub al_terminar: Option<String>,
+    /// Guarda los resultados en el directorio y los devuelve sin volver a simular
+    #[arg(long, value_name = "DIRECTORIO")]
+    pub cache: Option<String>,
+    /// Escribe en el archivo una repeticion para el subcomando replay
+    #[arg(long, value_name = "ARCHIVO")]
+    pub grabar_replay: Option<String>,
+    /// Escribe en el directorio un tablero por cada estado de la reaccion en cadena
+    #[arg(long, value_name = "DIRECTORIO")]
+    pub frames: Option<String>,
+    /// Escribe en el archivo el tablero inicial como SVG con el recorrido de cada rafaga
+    #[arg(long, value_name = "ARCHIVO")]
+    pub svg: Option<String>,
+    /// Escribe en el archivo una linea por casilla que cambio
+    #[arg(long, value_name = "ARCHIVO")]
+    pub diff: Option<String>,
+    #[command(flatten)]
+    pub limites: ArgsLimites,
+    #[command(flatten)]
+    pub reglas: ArgsReglas,
+    /// Imprime por stderr cada evento a medida que ocurre
+    #[arg(long)]
+    pub eventos: bool,
+    /// Imprime por stderr el recorrido de la simulacion (tambien con RUST_LOG=debug)
+    #[arg(long)]
+    pub verbose: bool,
+    /// Agrega al final del resultado las advertencias
+    #[arg(long)]
+    pub con_advertencias: bool,
+    /// Agrega al principio del resultado de donde salio
+    #[arg(long)]
+    pub con_procedencia: bool,
+    /// Reemplaza las casillas desconocidas por `_` en lugar de rechazar el tablero
+    #[arg(long)]
+    pub recuperar: bool,
+    /// Escribe el tablero final como JSON, una linea cada N filas
+    #[arg(long, value_name = "N")]
+    pub json_bloques: Option<usize>,
+    /// Lee y escribe el tablero como texto, json o hex (texto hexagonal)
+    #[arg(long, value_enum, value_name = "FORMATO")]
+    pub format: Option<Formato>,
+    /// Escribe el tablero final como texto o con emoji (texto o emoji)
+    #[arg(long, value_enum, value_name = "ESTILO")]
+    pub style: Option<Estilo>,
+    /// Informa si se cumplio el objetivo (eliminar_enemigos, destruir_rocas o alcanzar_celda:x,y)
+    #[arg(long, value_name = "OBJETIVO", value_parser = Objetivo::parsear)]
+    pub objetivo: Option<Objetivo>,
+    /// Agrega al final del resultado una explicacion de cada regla que se aplico
+    #[arg(long)]
+    pub modo_tutorial: bool,
+    /// Agrega al final del resultado una tabla con lo que hizo cada bomba
+    #[arg(long)]
+    pub estadisticas_bombas: bool,
+    /// Agrega al final del resultado los numeros de la reaccion en cadena
+    #[arg(long)]
+    pub resumen: bool,
+    /// Imprime por stderr la casilla (X, Y) despues de la detonacion PASO; se puede repetir
+    #[arg(long, value_name = "PASO:X,Y", value_parser = parsear_inspeccion)]
+    pub inspeccionar: Vec<(usize, Punto)>,
+    /// Detona tambien la bomba en (X, Y) en el mismo instante; se puede repetir
+    #[arg(long, value_name = "X,Y", value_parser = parsear_simultanea)]
+    pub tambien: Vec<Punto>,
+    /// Imprime por stderr cuantas bombas afectarian a cada casilla
+    #[arg(long)]
+    pub mapa_peligro: bool,
+    /// Termina con 0 si murieron todos los enemigos, 3 si quedo alguno y 1 si hubo un error
+    #[arg(long)]
+    pub codigo_salida: bool,
+}
+
+/// Los formatos de `--format`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Formato {
+    Texto,
+    Json,
+    Hex,
+}
+
+/// Los estilos de `--style`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Estilo {
+    Texto,
+    Emoji,
+}
+
+/// Una coordenada de `simular`: un numero natural, o `?` para elegir la bomba con el cursor (ver `selector::elegir`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coordenada {
+    Numero(usize),
+    Cursor,
+}
+
+impl std::str::FromStr for Coordenada {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Coordenada, String> {
+        match texto {
+            "?" => Ok(Coordenada::Cursor),
+            _ => texto
+                .parse()
+                .map(Coordenada::Numero)
+                .map_err(|_| "tiene que ser un numero natural (desde 0) o ?".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Coordenada {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Coordenada::Numero(numero) => write!(f, "{}", numero),
+            Coordenada::Cursor => write!(f, "?"),
+        }
+    }
+}
+
+fn parsear_preset(nombre: &str) -> Result<Reglas, String> {
+    Reglas::preset(nombre).ok_or_else(|| {
+        format!(
+            "preset desconocido (se aceptan {})",
+            Reglas::PRESETS.join(", ")
+        )
+    })
+}
+
+fn parsear_idioma(codigo: &str) -> Result<Idioma, String> {
+    Idioma::parsear(codigo).ok_or_else(|| "idioma desconocido (se aceptan es, en)".to_string())
+}
+
+fn parsear_dificultad(valor: &str) -> Result<f64, String> {
+    match valor.parse() {
+        Ok(dificultad) if (0.0..=1.0).contains(&dificultad) => Ok(dificultad),
+        _ => Err("tiene que estar entre 0 y 1".to_string()),
+    }
+}
+
+/// Lee el valor de `--inspeccionar`, con la forma `PASO:X,Y`.
+fn parsear_inspeccion(valor: &str) -> Result<(usize, Punto), String> {
+    let error = || "tiene que tener la forma PASO:X,Y".to_string();
+    let (paso, coordenadas) = valor.split_once(':').ok_or_else(error)?;
+    let (x, y) = coordenadas.split_once(',').ok_or_else(error)?;
+    match (paso.parse(), x.parse(), y.parse()) {
+        (Ok(paso), Ok(x), Ok(y)) => Ok((paso, Punto { x, y })),
+        _ => Err(error()),
+    }
+}
+
+/// Lee el valor de `--tambien`, con la forma `X,Y`.
+fn parsear_simultanea(valor: &str) -> Result<Punto, String> {
+    let error = || "tiene que tener la forma X,Y".to_string();
+    let (x, y) = valor.split_once(',').ok_or_else(error)?;
+    match (x.trim().parse(), y.trim().parse()) {
+        (Ok(x), Ok(y)) => Ok(Punto { x, y }),
+        _ => Err(error()),
+    }
+}
+
+/// Arma la linea de comandos con los textos de la ayuda en castellano (ver `en_castellano`).
+pub fn comando() -> Command {
+    let comando = en_castellano(Cli::command());
+    let mut construido = comando.clone();
+    construido.build();
+    con_usos(comando, &mut construido).override_usage(
+        "tp1-bomberman [simular] <ENTRADA> <DIRECTORIO> <X> <Y> [opciones]\n     \
+         tp1-bomberman <SUBCOMANDO> [argumentos]\n     \
+         tp1-bomberman --protocol jsonl [opciones]\n     \
+         tp1-bomberman --capacidades",
+    )
+}
+
+/// Cambia los textos que agrega clap a la ayuda de un comando y de sus subcomandos: los titulos de cada seccion y la
+/// opcion `--help`. Los argumentos quedan en el orden en que se declaran.
+fn en_castellano(comando: Command) -> Command {
+    let mut orden = 0;
+    comando
+        .help_template("{about-with-newline}\nUso: {usage}\n\n{all-args}{after-help}")
+        .subcommand_help_heading("Subcomandos")
+        .subcommand_value_name("SUBCOMANDO")
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("ayuda")
+                .short('h')
+                .long("help")
+                .action(ArgAction::Help)
+                .help("Imprime esta ayuda"),
+        )
+        .mut_args(move |arg| {
+            orden += 1;
+            let titulo = match arg.is_positional() {
+                true => "Argumentos",
+                false => "Opciones",
+            };
+            let con_valor = arg.get_action().takes_values();
+            arg.help_heading(titulo)
+                .display_order(orden)
+                .hide_possible_values(con_valor)
+        })
+        .mut_subcommands(en_castellano)
+}
+
+/// Reemplaza el uso de un comando y el de sus subcomandos por el que arma clap, con `[opciones]` en lugar de
+/// `[OPTIONS]`. El uso se toma de una copia ya construida, que tiene el nombre completo de cada subcomando.
+fn con_usos(comando: Command, construido: &mut Command) -> Command {
+    let uso = construido.render_usage().to_string();
+    let uso = uso
+        .strip_prefix("Usage: ")
+        .unwrap_or(&uso)
+        .replace("[OPTIONS]", "[opciones]");
+    comando.override_usage(uso).mut_subcommands(|subcomando| {
+        match construido.find_subcommand_mut(subcomando.get_name()) {
+            Some(construido) => con_usos(subcomando, construido),
+            None => subcomando,
+        }
+    })
+}
+
+/// La forma de usar un subcomando, como la primera linea de su ayuda.
+///
+/// # Ejemplos
+///
+/// ```text
+/// assert!(uso_de("validar").starts_with("Uso: tp1-bomberman validar"));
+/// ```
+pub fn uso_de(subcomando: &str) -> String {
+    let mut comando = comando();
+    comando.build();
+    match comando.find_subcommand_mut(subcomando) {
+        Some(subcomando) => traducir_uso(&subcomando.render_usage().to_string()),
+        None => traducir_uso(&comando.render_usage().to_string()),
+    }
+}
+
+fn traducir_uso(uso: &str) -> String {
+    uso.replacen("Usage:", "Uso:", 1)
+}
+
+/// Interpreta los argumentos del programa, empezando por su nombre. Si el primero no es un subcomando ni una opcion,
+/// es la entrada de una simulacion y se agrega `simular` antes.
+///
+/// # Ejemplos
+///
+/// ```text
+/// let cli = interpretar(vec!["tp1-bomberman".into(), "entrada.txt".into(), "salidas".into(), "0".into(), "0".into()])?;
+/// assert!(matches!(cli.comando, Some(Comando::Simular(_))));
+/// ```
+///
+/// # Devuelve
+///
+/// Un Result con la linea de comandos interpretada, o el error de uso de clap, que tambien es la forma de pedir la
+/// ayuda (ver `clap::Error::exit`).
+pub fn interpretar(mut args: Vec<String>) -> Result<Cli, clap::error::Error<Castellano>> {
+    let comando = comando();
+    // Sin la feature `tui` el subcomando no existe, pero tampoco es la entrada de una simulacion.
+    if cfg!(not(feature = "tui")) && args.get(1).is_some_and(|primero| primero == "tui") {
+        let mut error = clap::error::Error::new(ErrorKind::InvalidSubcommand).with_cmd(&comando);
+        error.insert(
+            ContextKind::InvalidSubcommand,
+            ContextValue::String("tui".to_string()),
+        );
+        error.insert(
+            ContextKind::Suggested,
+            ContextValue::StyledStrs(vec!["hay que compilar con --features tui".into()]),
+        );
+        return Err(error);
+    }
+    if let Some(primero) = args.get(1) {
+        let es_subcomando = comando
+            .get_subcommands()
+            .any(|sub| sub.get_name() == primero);
+        if !primero.starts_with('-') && !es_subcomando {
+            args.insert(1, "simular".to_string());
+        }
+    }
+    let coincidencias = comando
+        .try_get_matches_from(&args)
+        .map_err(|e| e.apply::<Castellano>())?;
+    let mut cli = Cli::from_arg_matches(&coincidencias).map_err(|e| e.apply::<Castellano>())?;
+    if let (Some(Comando::Simular(simular)), Some(("simular", sub))) =
+        (&mut cli.comando, coincidencias.subcommand())
+    {
+        // Los indices del subcomando cuentan desde `simular`, que en `args` es el 1.
+        let posicionales: Vec<usize> = ["entrada", "directorio", "x", "y"]
+            .iter()
+            .filter_map(|id| sub.index_of(id).map(|i| i + 1))
+            .collect();
+        simular.resto = (2..args.len())
+            .filter(|i| !posicionales.contains(i))
+            .map(|i| args[i].clone())
+            .collect();
+    }
+    Ok(cli)
+}
+
+/// Lee los argumentos del programa (ver `interpretar`). Con `--help` imprime la ayuda y termina, y con un error de
+/// uso lo imprime por stderr y termina con el codigo 2.
+pub fn leer_argumentos() -> Cli {
+    interpretar(std::env::args().collect()).unwrap_or_else(|e| e.exit())
+}
+
+/// Da formato en castellano a los errores de uso que informa clap, con el uso del subcomando al final.
+pub struct Castellano;
+
+impl ErrorFormatter for Castellano {
+    fn format_error(error: &clap::error::Error<Self>) -> StyledStr {
+        let texto = |clave| match error.get(clave) {
+            Some(ContextValue::String(valor)) => valor.clone(),
+            Some(ContextValue::Strings(valores)) => valores.join(", "),
+            Some(ContextValue::StyledStr(valor)) => valor.to_string(),
+            Some(ContextValue::StyledStrs(valores)) => valores
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Some(ContextValue::Number(numero)) => numero.to_string(),
+            _ => String::new(),
+        };
+        let mut mensaje = match error.kind() {
+            ErrorKind::InvalidValue => format!(
+                "valor invalido \"{}\" para {}",
+                texto(ContextKind::InvalidValue),
+                texto(ContextKind::InvalidArg)
+            ),
+            ErrorKind::ValueValidation => format!(
+                "valor invalido \"{}\" para {}: {}",
+                texto(ContextKind::InvalidValue),
+                texto(ContextKind::InvalidArg),
+                error.source().map(ToString::to_string).unwrap_or_default()
+            ),
+            ErrorKind::UnknownArgument => {
+                format!("argumento inesperado {}", texto(ContextKind::InvalidArg))
+            }
+            ErrorKind::InvalidSubcommand => format!(
+                "subcomando desconocido {}",
+                texto(ContextKind::InvalidSubcommand)
+            ),
+            ErrorKind::MissingRequiredArgument => {
+                format!("faltan argumentos: {}", texto(ContextKind::InvalidArg))
+            }
+            ErrorKind::ArgumentConflict => format!(
+                "{} no se puede usar con {}",
+                texto(ContextKind::InvalidArg),
+                texto(ContextKind::PriorArg)
+            ),
+            ErrorKind::MissingSubcommand => "falta el subcomando".to_string(),
+            otro => otro.as_str().unwrap_or("argumentos invalidos").to_string(),
+        };
+        let aceptados = texto(ContextKind::ValidValue);
+        if !aceptados.is_empty() {
+            mensaje.push_str(&format!(" (se aceptan {})", aceptados));
+        }
+        for clave in [
+            ContextKind::SuggestedArg,
+            ContextKind::SuggestedValue,
+            ContextKind::SuggestedSubcommand,
+        ] {
+            let sugerencia = texto(clave);
+            if !sugerencia.is_empty() {
+                mensaje.push_str(&format!("; quizas {}", sugerencia));
+            }
+        }
+        let consejo = texto(ContextKind::Suggested);
+        if !consejo.is_empty() {
+            mensaje.push_str(&format!("; {}", consejo));
+        }
+        let mut informe = format!("Error: {}\n", mensaje);
+        let uso = texto(ContextKind::Usage);
+        if !uso.is_empty() {
+            informe.push_str(&format!("\n{}\n", traducir_uso(&uso)));
+        }
+        informe.push_str("\nPara ver la ayuda: --help\n");
+        StyledStr::from(informe)
+    }
+}
+
+/// Revisa, antes de simular, que no se hayan pasado la entrada y el directorio de salida al reves: que la entrada no
+/// sea un directorio ni un archivo sin ninguna fila (como una salida vacia), que es lo que pasa cuando se invierten.
+///
+/// # Ejemplos
+///
+/// ```
+/// assert!(revisar_rutas("salidas", "entrada.txt").unwrap_err().contains("al reves"));
+/// ```
+///
+/// # Argumentos
+///
+/// * entrada: El primer argumento, que tendria que ser el archivo del tablero.
+/// * directorio: El segundo argumento, que tendria que ser el directorio de salida.
+///
+/// # Devuelve
+///
+/// Un Ok(()) si las rutas parecen estar en orden, o un Err con un mensaje que explica el problema y el orden correcto.
+/// Una entrada que no existe o no se puede leer no es un error aca: la simulacion la informa en la salida como siempre.
+pub fn revisar_rutas(entrada: &str, directorio: &str) -> Result<(), String> {
+    let al_reves = Path::new(entrada).is_dir() && Path::new(directorio).is_file();
+    if al_reves {
+        return Err(format!(
+            "Error: {} es un directorio y {} un archivo; parece que estan al reves.\n\
+             Primero va la entrada y despues el directorio de salida: tp1-bomberman {} {} ...",
+            entrada, directorio, directorio, entrada
+        ));
+    }
+    if Path::new(entrada).is_dir() {
+        return Err(format!(
+            "Error: la entrada {} es un directorio; tiene que ser el archivo del tablero, y el directorio de salida \
+             va despues",
+            entrada
+        ));
+    }
+    let Ok(archivo) = File::open(entrada) else {
+        return Ok(());
+    };
+    // Alcanza con encontrar la primera fila, asi no se lee entero un tablero enorme.
+    let vacia = BufReader::new(archivo)
+        .lines()
+        .map_while(Result::ok)
+        .all(|linea| linea.trim().is_empty() || linea.trim_start().starts_with('#'));
+    if vacia {
+        return Err(format!(
+            "Error: la entrada {} no tiene ningun tablero; si es una salida anterior, puede que la entrada y el \
+             directorio de salida esten al reves (el orden es <entrada> <directorio> <x> <y>)",
+            entrada
+        ));
+    }
+    Ok(())
+}
+
+/// Revisa juntos los argumentos de una simulacion que clap no puede revisar, para informar de una vez cada problema
+/// que tengan en lugar de cortar en el primero: las coordenadas que faltan, las opciones, las rutas (ver
+/// `revisar_rutas`), si existe el directorio de salida y, si hay alguno de esos problemas, tambien si las coordenadas
+/// estan dentro del tablero. Una coordenada fuera del tablero sola no es un error de uso: la simulacion la escribe en
+/// la salida como siempre.
+///
+/// # Ejemplos
+///
+/// ```text
+/// $ tp1-bomberman entrada.txt no_existe 2 9
+/// Error: hay 2 problemas con los argumentos:
+///   - el directorio de salida no_existe no existe
+///   - y = 9 esta fuera del tablero de 7x7 (tiene que ser menor que 7)
+/// Para corregirlo:
+///   mkdir -p no_existe && tp1-bomberman entrada.txt no_existe 2 <y menor que 7>
+/// ```
+///
+/// # Argumentos
+///
+/// * args: Los argumentos de `simular`.
+/// * error_opciones: El error de las opciones que no se pueden usar juntas, si lo hubo.
+/// * dimensiones: Lee el ancho y el alto del tablero de entrada, o None si no se puede leer. Solo se llama si hay que
+///   revisar las coordenadas.
+///
+/// # Devuelve
+///
+/// Un Ok(()) si no hay errores de uso, o un Err con el unico problema o la lista de todos, seguido de la invocacion
+/// corregida cuando se puede corregir algo.
+pub fn revisar_simulacion(
+    args: &ArgsSimular,
+    error_opciones: Option<String>,
+    dimensiones: impl FnOnce() -> Option<(usize, usize)>,
+) -> Result<(), String> {
+    let (entrada, directorio) = (&args.entrada, &args.directorio);
+    let es_escenario = entrada.ends_with(".toml");
+    let coordenadas = [("x", args.x), ("y", args.y)];
+    let mut escritos = vec![entrada.clone(), directorio.clone()];
+    escritos.extend(
+        coordenadas
+            .iter()
+            .flat_map(|(_, c)| c.map(|c| c.to_string())),
+    );
+    let mut corregidos = vec![entrada.clone(), directorio.clone()];
+    let mut problemas = Vec::new();
+    if es_escenario {
+        if args.x.is_some() {
+            problemas.push(
+                "Error: un escenario trae sus propias detonaciones, asi que no lleva coordenadas"
+                    .to_string(),
+            );
+        }
+    } else {
+        let faltan: Vec<String> = coordenadas
+            .iter()
+            .filter(|(_, coordenada)| coordenada.is_none())
+            .map(|(nombre, _)| format!("<{}>", nombre))
+            .collect();
+        if !faltan.is_empty() {
+            problemas.push(format!("Error: faltan argumentos: {}", faltan.join(" ")));
+        }
+        corregidos.extend(coordenadas.iter().map(|(nombre, coordenada)| {
+            coordenada.map_or(format!("<{}>", nombre), |c| c.to_string())
+        }));
+    }
+    problemas.extend(error_opciones);
+    let mut crear = None;
+    problemas.extend(revisar_rutas(entrada, directorio).err());
+    if Path::new(entrada).is_dir() && Path::new(directorio).is_file() {
+        corregidos.swap(0, 1);
+    } else if Path::new(directorio).is_file() {
+        problemas.push(format!(
+            "Error: la salida {} es un archivo; tiene que ser un directorio",
+            directorio
+        ));
+    } else if !Path::new(directorio).is_dir() {
+        problemas.push(format!(
+            "Error: el directorio de salida {} no existe",
+            directorio
+        ));
+        crear = Some(directorio);
+    }
+    if problemas.is_empty() {
+        return Ok(());
+    }
+    let numeros: Vec<(&str, usize, usize)> = coordenadas
+        .iter()
+        .enumerate()
+        .filter(|_| !es_escenario)
+        .filter_map(|(i, (nombre, coordenada))| match coordenada {
+            Some(Coordenada::Numero(numero)) => Some((*nombre, i + 2, *numero)),
+            _ => None,
+        })
+        .collect();
+    if let (false, Some((ancho, alto))) = (numeros.is_empty(), dimensiones()) {
+        for (nombre, i, numero) in numeros {
+            let limite = if nombre == "x" { ancho } else { alto };
+            if numero >= limite {
+                problemas.push(format!(
+                    "Error: {} = {} esta fuera del tablero de {}x{} (tiene que ser menor que {})",
+                    nombre, numero, ancho, alto, limite
+                ));
+                corregidos[i] = format!("<{} menor que {}>", nombre, limite);
+            }
+        }
+    }
+    let mut informe = match problemas.as_slice() {
+        [unico] => unico.clone(),
+        _ => {
+            let mut lista = format!(
+                "Error: hay {} problemas con los argumentos:",
+                problemas.len()
+            );
+            for problema in &problemas {
+                let problema = problema.strip_prefix("Error: ").unwrap_or(problema);
+                lista.push_str(&format!("\n  - {}", problema.replace('\n', "\n    ")));
+            }
+            lista
+        }
+    };
+    if crear.is_some() || corregidos != escritos {
+        let mut invocacion = crear.map_or(String::new(), |dir| format!("mkdir -p {} && ", dir));
+        invocacion.push_str("tp1-bomberman");
+        for argumento in corregidos.iter().chain(&args.resto) {
+            // Los lugares a completar, como `<x>`, van sin comillas.
+            match argumento.contains(char::is_whitespace) && !argumento.starts_with('<') {
+                true => invocacion.push_str(&format!(" \"{}\"", argumento)),
+                false => invocacion.push_str(&format!(" {}", argumento)),
+            }
+        }
+        informe.push_str(&format!("\nPara corregirlo:\n  {}", invocacion));
+    }
+    Err(informe)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{comando, interpretar, revisar_rutas, revisar_simulacion, Comando, Coordenada};
+
+    fn args(texto: &str) -> Vec<String> {
+        texto.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test01_la_linea_de_comandos_es_valida() {
+        comando().debug_assert();
+        let cli = interpretar(args("tp1 validar entrada.txt --vida-maxima 5")).unwrap();
+        assert!(matches!(cli.comando, Some(Comando::Validar { .. })));
+        assert!(interpretar(args("tp1 validar")).is_err());
+        // Sin subcomando, renderizar es la entrada de una simulacion a la que le falta el directorio.
+        let error = interpretar(args("tp1 renderizar")).unwrap_err().to_string();
+        assert!(
+            error.starts_with("Error: faltan argumentos: <DIRECTORIO>"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test02_coordenadas() {
+        assert_eq!("12".parse(), Ok(Coordenada::Numero(12)));
+        assert_eq!("?".parse(), Ok(Coordenada::Cursor));
+        assert!("dos".parse::<Coordenada>().is_err());
+        let error = interpretar(args("tp1 e.txt s 3 -1"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.starts_with(
+                "Error: valor invalido \"-1\" para [Y]: tiene que ser un numero natural"
+            ),
+            "{}",
+            error
+        );
+        let cli = interpretar(args("tp1 simular e.txt s --eventos 3 ? --tambien 1,2")).unwrap();
+        let Some(Comando::Simular(simular)) = cli.comando else {
+            panic!("{:?}", cli.comando);
+        };
+        assert_eq!(
+            (simular.x, simular.y),
+            (Some(Coordenada::Numero(3)), Some(Coordenada::Cursor))
+        );
+        assert_eq!(simular.resto, args("--eventos --tambien 1,2"));
+    }
+
+    #[test]
+    fn test03_entrada_y_directorio_al_reves() {
+        assert!(revisar_rutas("src", "Cargo.toml")
+            .unwrap_err()
+            .contains("tp1-bomberman Cargo.toml src"));
+        assert!(revisar_rutas("src", "salidas")
+            .unwrap_err()
+            .contains("es un directorio"));
+        let vacia = std::env::temp_dir().join("tp1_bomberman_uso_test03.txt");
+        fs::write(&vacia, "\n# solo un comentario\n").unwrap();
+        let vacia = vacia.to_string_lossy().into_owned();
+        assert!(revisar_rutas(&vacia, "src")
+            .unwrap_err()
+            .contains("no tiene ningun tablero"));
+        assert_eq!(revisar_rutas("Cargo.toml", "src"), Ok(()));
+        assert_eq!(revisar_rutas("no_existe.txt", "src"), Ok(()));
+    }
+
+    #[test]
+    fn test04_informa_todos_los_problemas_juntos() {
+        let simular = |texto: &str| match interpretar(args(texto)).unwrap().comando {
+            Some(Comando::Simular(simular)) => simular,
+            otro => panic!("{:?}", otro),
+        };
+        let informe = revisar_simulacion(
+            &simular("tp1 src/main.rs no_existe 2 9 --eventos"),
+            None,
+            || Some((7, 7)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            informe,
+            "Error: hay 2 problemas con los argumentos:\n\
+             \x20 - el directorio de salida no_existe no existe\n\
+             \x20 - y = 9 esta fuera del tablero de 7x7 (tiene que ser menor que 7)\n\
+             Para corregirlo:\n\
+             \x20 mkdir -p no_existe && tp1-bomberman src/main.rs no_existe 2 <y menor que 7> --eventos"
+        );
+
+        let informe = revisar_simulacion(
+            &simular("tp1 src src/main.rs --format json"),
+            Some("Error: --format json y --json-bloques no se pueden usar juntos".to_string()),
+            || None,
+        )
+        .unwrap_err();
+        assert!(informe.starts_with("Error: hay 3 problemas"), "{}", informe);
+        assert!(informe.contains("- faltan argumentos: <x> <y>"));
+        assert!(informe.ends_with("tp1-bomberman src/main.rs src <x> <y> --format json"));
+        // Sin otros problemas, las coordenadas las revisa la simulacion.
+        assert_eq!(
+            revisar_simulacion(&simular("tp1 src/main.rs src 2 9"), None, || None),
+            Ok(())
+        );
+        assert_eq!(
+            revisar_simulacion(&simular("tp1 caso.toml no_existe"), None, || None),
+            Err(
+                "Error: el directorio de salida no_existe no existe\nPara corregirlo:\n  \
+                 mkdir -p no_existe && tp1-bomberman caso.toml no_existe"
+                    .to_string()
+            )
+        );
+    }
+}